@@ -0,0 +1,183 @@
+//! Parsing of `#[presence(...)]` attributes.
+//!
+//! All derives share one parser so that a field can carry options for several derives
+//! without one of them rejecting the others' options.
+
+use syn::{
+    Attribute, Data, DeriveInput, Error, Expr, Field, Fields, GenericArgument, Ident, LitStr, Path,
+    PathArguments, Result, Type,
+};
+
+/// Options of the struct itself.
+#[derive(Default)]
+pub struct ContainerOptions {
+    /// `#[presence(env_prefix = "...")]`: the prefix of the environment variable names.
+    pub env_prefix: Option<LitStr>,
+    /// `#[presence(builder = "...")]`: the name of the derive_builder builder struct.
+    pub builder: Option<Ident>,
+    /// `#[presence(patch = "...")]`: the name of the generated patch struct.
+    pub patch: Option<Ident>,
+    /// `#[presence(patch_derive(...))]`: extra derives of the generated patch struct.
+    pub patch_derive: Vec<Path>,
+}
+
+impl ContainerOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = ContainerOptions::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("presence")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("env_prefix") {
+                    options.env_prefix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("builder") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.builder = Some(name.parse()?);
+                } else if meta.path.is_ident("patch") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.patch = Some(name.parse()?);
+                } else if meta.path.is_ident("patch_derive") {
+                    meta.parse_nested_meta(|derive| {
+                        options.patch_derive.push(derive.path);
+                        Ok(())
+                    })?;
+                } else {
+                    return Err(meta.error("unknown presence attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// Options of a struct field.
+#[derive(Default)]
+pub struct FieldOptions {
+    /// `#[presence(required)]`: the field must not be `Absent`.
+    pub required: bool,
+    /// `#[presence(non_null)]`: the field must not be `Null`.
+    pub non_null: bool,
+    /// `#[presence(skip)]`: the field is not a `Presence<T>` and is left alone.
+    pub skip: bool,
+    /// `#[presence(plain)]`: the field is not a `Presence<T>`, but `DeserializePresence`
+    /// reads it as a plain serde field.
+    pub plain: bool,
+    /// `#[presence(redact)]`: the value is hidden from change reports.
+    pub redact: bool,
+    /// `#[presence(nested)]`: the field is a patch struct itself and is merged or walked
+    /// recursively.
+    pub nested: bool,
+    /// `#[presence(merge = STRATEGY)]`: the `MergeStrategy` used for this field.
+    pub merge: Option<Expr>,
+    /// `#[presence(env = "...")]`: the environment variable the field is read from.
+    pub env: Option<LitStr>,
+    /// `#[presence(empty = null|absent|parse)]`: how an empty environment variable is read.
+    pub empty: Option<Ident>,
+}
+
+impl FieldOptions {
+    pub fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = FieldOptions::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("presence")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("required") {
+                    options.required = true;
+                } else if meta.path.is_ident("non_null") {
+                    options.non_null = true;
+                } else if meta.path.is_ident("skip") {
+                    options.skip = true;
+                } else if meta.path.is_ident("plain") {
+                    options.plain = true;
+                } else if meta.path.is_ident("redact") {
+                    options.redact = true;
+                } else if meta.path.is_ident("nested") {
+                    options.nested = true;
+                } else if meta.path.is_ident("merge") {
+                    options.merge = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("env") {
+                    options.env = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("empty") {
+                    let empty: Ident = meta.value()?.parse()?;
+                    if !["null", "absent", "parse"]
+                        .iter()
+                        .any(|value| empty == value)
+                    {
+                        return Err(Error::new_spanned(
+                            empty,
+                            "expected `null`, `absent` or `parse`",
+                        ));
+                    }
+                    options.empty = Some(empty);
+                } else {
+                    return Err(meta.error("unknown presence attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+
+    /// Returns `true` if the field is not a `Presence<T>` and not nested, i.e. `skip` or
+    /// `plain`.
+    pub fn is_skipped(&self) -> bool {
+        self.skip || self.plain
+    }
+
+    /// Returns `true` if the field is a `Presence<T>`, i.e. neither skipped nor nested.
+    pub fn is_presence(&self) -> bool {
+        !self.is_skipped() && !self.nested
+    }
+}
+
+/// Returns the named fields of a struct, or an error for enums, unions and tuple structs.
+pub fn named_fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<&'a Field>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter().collect()),
+            _ => Err(Error::new_spanned(
+                &input.ident,
+                format!(
+                    "{} can only be derived for structs with named fields",
+                    derive
+                ),
+            )),
+        },
+        _ => Err(Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for structs", derive),
+        )),
+    }
+}
+
+/// Returns the name of a field as written in the source, without a raw identifier prefix.
+pub fn field_name(field: &Field) -> String {
+    let ident = field.ident.as_ref().expect("named field");
+    let name = ident.to_string();
+    name.strip_prefix("r#").map(str::to_string).unwrap_or(name)
+}
+
+/// Returns `T` if `ty` is written as `Presence<T>`, with or without a path.
+pub fn presence_inner(ty: &Type) -> Option<&Type> {
+    wrapper_inner(ty, "Presence")
+}
+
+/// Returns `T` if `ty` is written as `Tracked<T>`, with or without a path.
+pub fn tracked_inner(ty: &Type) -> Option<&Type> {
+    wrapper_inner(ty, "Tracked")
+}
+
+fn wrapper_inner<'a>(ty: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != wrapper {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(inner)) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
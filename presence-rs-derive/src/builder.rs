@@ -0,0 +1,152 @@
+use crate::attr::{ContainerOptions, FieldOptions, field_name, named_fields, presence_inner};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Attribute, DeriveInput, Ident, LitStr, Result, parenthesized, token};
+
+/// The setter style of a derive_builder builder, from `#[builder(pattern = "...")]`.
+#[derive(Clone, Copy, Default)]
+enum Pattern {
+    #[default]
+    Mutable,
+    Owned,
+    Immutable,
+}
+
+/// The options of derive_builder's own `#[builder(...)]` attributes that shape the builder.
+#[derive(Default)]
+struct BuilderOptions {
+    name: Option<Ident>,
+    pattern: Option<Pattern>,
+}
+
+impl BuilderOptions {
+    /// Reads `name` and `pattern`, skipping the other derive_builder options.
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = BuilderOptions::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.name = Some(name.parse()?);
+                } else if meta.path.is_ident("pattern") {
+                    let pattern: LitStr = meta.value()?.parse()?;
+                    options.pattern = Some(match pattern.value().as_str() {
+                        "mutable" => Pattern::Mutable,
+                        "owned" => Pattern::Owned,
+                        "immutable" => Pattern::Immutable,
+                        _ => {
+                            return Err(syn::Error::new_spanned(
+                                pattern,
+                                "expected `mutable`, `owned` or `immutable`",
+                            ));
+                        }
+                    });
+                } else if meta.input.peek(token::Paren) {
+                    let content;
+                    parenthesized!(content in meta.input);
+                    content.parse::<TokenStream>()?;
+                } else if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let container = ContainerOptions::from_attrs(&input.attrs)?;
+    let builder_options = BuilderOptions::from_attrs(&input.attrs)?;
+    let vis = &input.vis;
+
+    let mut setters = Vec::new();
+    let mut checks = Vec::new();
+    for field in named_fields(input, "PresenceBuilder")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if options.is_skipped() || presence_inner(&field.ty).is_none() {
+            continue;
+        }
+
+        let ident = &field.ident;
+        let name = field_name(field);
+        let null = format_ident!("{}_null", name);
+        let doc = format!("Sets `{}` to `Null`.", name);
+        let pattern = BuilderOptions::from_attrs(&field.attrs)?
+            .pattern
+            .or(builder_options.pattern)
+            .unwrap_or_default();
+        let (receiver, output, new) = match pattern {
+            Pattern::Mutable => (quote!(&mut self), quote!(&mut Self), quote!(self)),
+            Pattern::Owned => (quote!(self), quote!(Self), quote!(self)),
+            Pattern::Immutable => (
+                quote!(&self),
+                quote!(Self),
+                quote!(::core::clone::Clone::clone(self)),
+            ),
+        };
+        setters.push(quote! {
+            #[doc = #doc]
+            #[allow(unused_mut)]
+            #vis fn #null(#receiver) -> #output {
+                let mut new = #new;
+                new.#ident = ::core::option::Option::Some(::presence_rs::Presence::Null);
+                new
+            }
+        });
+
+        let mut arms = Vec::new();
+        if options.required {
+            arms.push(quote! {
+                ::core::option::Option::None
+                | ::core::option::Option::Some(::presence_rs::Presence::Absent) => {
+                    ::core::option::Option::Some(::presence_rs::validate::ViolationKind::Absent)
+                }
+            });
+        }
+        if options.non_null {
+            arms.push(quote! {
+                ::core::option::Option::Some(::presence_rs::Presence::Null) => {
+                    ::core::option::Option::Some(::presence_rs::validate::ViolationKind::Null)
+                }
+            });
+        }
+        if !arms.is_empty() {
+            checks.push(quote! {
+                let kind = match &self.#ident {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                };
+                if let ::core::option::Option::Some(kind) = kind {
+                    violations.push(::presence_rs::validate::Violation { field: #name, kind }.to_string());
+                }
+            });
+        }
+    }
+
+    let builder = container
+        .builder
+        .or(builder_options.name)
+        .unwrap_or_else(|| format_ident!("{}Builder", input.ident));
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #builder #ty_generics
+        #where_clause
+        {
+            #(#setters)*
+
+            /// Checks the `#[presence(required)]` and `#[presence(non_null)]` policies of the
+            /// fields, treating unset fields as `Absent`, for use as the `build_fn` validator.
+            #vis fn check_presence(&self) -> ::core::result::Result<(), ::std::string::String> {
+                #[allow(unused_mut)]
+                let mut violations: ::std::vec::Vec<::std::string::String> = ::std::vec::Vec::new();
+                #(#checks)*
+                if violations.is_empty() {
+                    ::core::result::Result::Ok(())
+                } else {
+                    ::core::result::Result::Err(violations.join("; "))
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+use crate::attr::{FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut writes = Vec::new();
+    for field in named_fields(input, "DescribePatch")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if !options.is_presence() {
+            continue;
+        }
+        let ident = &field.ident;
+        let name = field_name(field);
+        let first = writes.is_empty();
+        // Redacted values are never formatted, so they don't need to implement `Debug`.
+        let value = if options.redact {
+            quote!(&::presence_rs::Presence::as_ref(&self.#ident).map(|_| ()))
+        } else {
+            quote!(&self.#ident)
+        };
+        let redact = options.redact;
+        writes.push(quote! {
+            ::presence_rs::reflect::__describe_field(f, #first, #name, #value, #redact)?;
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::reflect::DescribePatch for #ident #ty_generics
+        #where_clause
+        {
+            fn describe_to(&self, f: &mut dyn ::core::fmt::Write) -> ::core::fmt::Result {
+                #(#writes)*
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
@@ -0,0 +1,293 @@
+use crate::attr::{FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Attribute, DeriveInput, Error, GenericParam, Lifetime, LifetimeParam, LitStr, Result,
+    meta::ParseNestedMeta, parse_quote,
+};
+
+/// The `#[serde(...)]` options of the struct that `DeserializePresence` honors.
+#[derive(Default)]
+struct SerdeContainer {
+    rename: Option<LitStr>,
+    rename_all: Option<LitStr>,
+    deny_unknown_fields: bool,
+}
+
+impl SerdeContainer {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = SerdeContainer::default();
+        for attr in serde_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename_all") {
+                    let rule: LitStr = meta.value()?.parse()?;
+                    if rename(&rule.value(), "a_b").is_none() {
+                        return Err(Error::new_spanned(rule, "unknown rename_all rule"));
+                    }
+                    options.rename_all = Some(rule);
+                } else if meta.path.is_ident("deny_unknown_fields") {
+                    options.deny_unknown_fields = true;
+                } else if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(unsupported(&meta));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+/// The `#[serde(...)]` options of a field that `DeserializePresence` honors.
+#[derive(Default)]
+struct SerdeField {
+    rename: Option<LitStr>,
+    aliases: Vec<LitStr>,
+    default: bool,
+}
+
+impl SerdeField {
+    fn from_attrs(attrs: &[Attribute]) -> Result<Self> {
+        let mut options = SerdeField::default();
+        for attr in serde_attrs(attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    options.rename = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("alias") {
+                    options.aliases.push(meta.value()?.parse()?);
+                } else if meta.path.is_ident("default") {
+                    options.default = true;
+                } else if meta.path.is_ident("skip_serializing_if")
+                    || meta.path.is_ident("serialize_with")
+                {
+                    // Serialization only.
+                    meta.value()?.parse::<LitStr>()?;
+                } else {
+                    return Err(unsupported(&meta));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(options)
+    }
+}
+
+fn serde_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("serde"))
+}
+
+fn unsupported(meta: &ParseNestedMeta) -> Error {
+    meta.error("serde attribute not supported by DeserializePresence")
+}
+
+/// Applies a serde `rename_all` rule to a snake_case field name.
+fn rename(rule: &str, field: &str) -> Option<String> {
+    let pascal = || {
+        field
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    Some(match rule {
+        "lowercase" | "snake_case" => field.to_string(),
+        "UPPERCASE" | "SCREAMING_SNAKE_CASE" => field.to_ascii_uppercase(),
+        "PascalCase" => pascal(),
+        "camelCase" => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|first| first.to_lowercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+        "kebab-case" => field.replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => field.to_ascii_uppercase().replace('_', "-"),
+        _ => return None,
+    })
+}
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let container = SerdeContainer::from_attrs(&input.attrs)?;
+    let ident = &input.ident;
+    let struct_name = container
+        .rename
+        .as_ref()
+        .map_or_else(|| ident.to_string(), LitStr::value);
+
+    let mut keys = Vec::new();
+    let mut slots = Vec::new();
+    let mut arms = Vec::new();
+    let mut elements = Vec::new();
+    let mut values = Vec::new();
+    let mut field_idents = Vec::new();
+    let mut seq_slots = Vec::new();
+    let mut index = 0usize;
+    for field in named_fields(input, "DeserializePresence")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        let serde = SerdeField::from_attrs(&field.attrs)?;
+        let field_ident = &field.ident;
+        if options.skip {
+            if options.plain {
+                return Err(Error::new_spanned(
+                    field_ident,
+                    "a field cannot be both `#[presence(skip)]` and `#[presence(plain)]`",
+                ));
+            }
+            values.push(quote!(#field_ident: ::core::default::Default::default()));
+            field_idents.push(field_ident);
+            seq_slots.push(quote!(::core::default::Default::default()));
+            continue;
+        }
+        let name = field_name(field);
+        let key = match (&serde.rename, &container.rename_all) {
+            (Some(rename), _) => rename.value(),
+            (None, Some(rule)) => rename(&rule.value(), &name).expect("validated rule"),
+            (None, None) => name,
+        };
+        let aliases = &serde.aliases;
+
+        let slot = format_ident!("__field{}", index);
+        let ty = &field.ty;
+        slots.push(
+            quote!(let mut #slot: ::core::option::Option<#ty> = ::core::option::Option::None;),
+        );
+        arms.push(quote! {
+            #key #(| #aliases)* => {
+                if #slot.is_some() {
+                    return ::core::result::Result::Err(
+                        <__A::Error as __serde::de::Error>::duplicate_field(#key),
+                    );
+                }
+                #slot = ::core::option::Option::Some(__serde::de::MapAccess::next_value(&mut map)?);
+            }
+        });
+        elements.push(quote! {
+            let #slot: #ty = match __serde::de::SeqAccess::next_element(&mut seq)? {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => {
+                    return ::core::result::Result::Err(
+                        <__A::Error as __serde::de::Error>::invalid_length(#index, &self),
+                    );
+                }
+            };
+        });
+
+        let missing = if options.is_presence() {
+            quote!(::presence_rs::Presence::Absent)
+        } else if options.nested || serde.default {
+            quote!(::core::default::Default::default())
+        } else {
+            quote! {
+                return ::core::result::Result::Err(
+                    <__A::Error as __serde::de::Error>::missing_field(#key),
+                )
+            }
+        };
+        values.push(quote! {
+            #field_ident: match #slot {
+                ::core::option::Option::Some(value) => value,
+                ::core::option::Option::None => #missing,
+            }
+        });
+        keys.push(key);
+        field_idents.push(field_ident);
+        seq_slots.push(quote!(#slot));
+        index += 1;
+    }
+
+    let unknown = if container.deny_unknown_fields {
+        quote! {
+            return ::core::result::Result::Err(
+                <__A::Error as __serde::de::Error>::unknown_field(&key, FIELDS),
+            )
+        }
+    } else {
+        quote! {
+            __serde::de::MapAccess::next_value::<__serde::de::IgnoredAny>(&mut map)?;
+        }
+    };
+
+    let de: Lifetime = parse_quote!('de);
+    let mut generics = input.generics.clone();
+    let type_params: Vec<_> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    generics
+        .params
+        .insert(0, GenericParam::Lifetime(LifetimeParam::new(de.clone())));
+    let where_clause = generics.make_where_clause();
+    for param in &type_params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: __serde::Deserialize<#de>));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+    let expecting = format!("struct {}", struct_name);
+
+    Ok(quote! {
+        const _: () = {
+            use ::presence_rs::__serde;
+
+            const FIELDS: &[&str] = &[#(#keys),*];
+
+            struct __Visitor<__T>(::core::marker::PhantomData<__T>);
+
+            impl #impl_generics __serde::de::Visitor<'de> for __Visitor<#ident #ty_generics>
+            #where_clause
+            {
+                type Value = #ident #ty_generics;
+
+                fn expecting(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#expecting)
+                }
+
+                fn visit_seq<__A: __serde::de::SeqAccess<'de>>(
+                    self,
+                    mut seq: __A,
+                ) -> ::core::result::Result<Self::Value, __A::Error> {
+                    #(#elements)*
+                    ::core::result::Result::Ok(#ident { #(#field_idents: #seq_slots),* })
+                }
+
+                fn visit_map<__A: __serde::de::MapAccess<'de>>(
+                    self,
+                    mut map: __A,
+                ) -> ::core::result::Result<Self::Value, __A::Error> {
+                    #(#slots)*
+                    while let ::core::option::Option::Some(key) =
+                        __serde::de::MapAccess::next_key::<::std::string::String>(&mut map)?
+                    {
+                        match key.as_str() {
+                            #(#arms)*
+                            _ => { #unknown }
+                        }
+                    }
+                    ::core::result::Result::Ok(#ident { #(#values),* })
+                }
+            }
+
+            impl #impl_generics __serde::Deserialize<'de> for #ident #ty_generics
+            #where_clause
+            {
+                fn deserialize<__D: __serde::Deserializer<'de>>(
+                    deserializer: __D,
+                ) -> ::core::result::Result<Self, __D::Error> {
+                    deserializer.deserialize_struct(
+                        #struct_name,
+                        FIELDS,
+                        __Visitor(::core::marker::PhantomData),
+                    )
+                }
+            }
+        };
+    })
+}
@@ -0,0 +1,60 @@
+use crate::attr::{ContainerOptions, FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let container = ContainerOptions::from_attrs(&input.attrs)?;
+    let prefix = container
+        .env_prefix
+        .map(|prefix| prefix.value())
+        .unwrap_or_default();
+
+    let mut fields = Vec::new();
+    for field in named_fields(input, "FromEnv")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        let ident = &field.ident;
+        let ty = &field.ty;
+        let value = if options.is_skipped() {
+            quote!(::core::default::Default::default())
+        } else if options.nested {
+            quote!(<#ty as ::presence_rs::env::FromEnv>::from_env_lookup(&lookup)?)
+        } else {
+            let var = match options.env {
+                Some(var) => var.value(),
+                None => format!("{}{}", prefix, field_name(field).to_uppercase()),
+            };
+            let tokens = match options.empty {
+                Some(empty) if empty == "absent" => quote!(.absent("")),
+                Some(empty) if empty == "parse" => quote!(),
+                _ => quote!(.null("")),
+            };
+            quote! {
+                ::presence_rs::Presence::parse_env(
+                    #var,
+                    lookup(#var),
+                    &::presence_rs::text::Tokens::none()#tokens,
+                )?
+            }
+        };
+        fields.push(quote!(#ident: #value));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::env::FromEnv for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn from_env_lookup<F>(
+                lookup: F,
+            ) -> ::core::result::Result<Self, ::presence_rs::env::EnvError>
+            where
+                F: ::core::ops::Fn(&str) -> ::core::option::Option<::std::ffi::OsString>,
+            {
+                ::core::result::Result::Ok(Self { #(#fields),* })
+            }
+        }
+    })
+}
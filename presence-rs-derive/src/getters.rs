@@ -0,0 +1,57 @@
+use crate::attr::{FieldOptions, field_name, named_fields, presence_inner};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut methods = Vec::new();
+    for field in named_fields(input, "PresenceGetters")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if options.is_skipped() {
+            continue;
+        }
+        let Some(inner) = presence_inner(&field.ty) else {
+            if options.nested {
+                continue;
+            }
+            return Err(Error::new_spanned(
+                &field.ty,
+                "PresenceGetters fields must be `Presence<T>`; mark other fields with `#[presence(skip)]`",
+            ));
+        };
+
+        let vis = &field.vis;
+        let ident = &field.ident;
+        let name = field_name(field);
+        let doc = format!(
+            "Returns the value of `{}`, or a `Violation` naming the field if it is `Absent` or `Null`.",
+            name
+        );
+        methods.push(quote! {
+            #[doc = #doc]
+            #vis fn #ident(&self) -> ::core::result::Result<&#inner, ::presence_rs::validate::Violation> {
+                match &self.#ident {
+                    ::presence_rs::Presence::Some(value) => ::core::result::Result::Ok(value),
+                    ::presence_rs::Presence::Null => ::core::result::Result::Err(::presence_rs::validate::Violation {
+                        field: #name,
+                        kind: ::presence_rs::validate::ViolationKind::Null,
+                    }),
+                    ::presence_rs::Presence::Absent => ::core::result::Result::Err(::presence_rs::validate::Violation {
+                        field: #name,
+                        kind: ::presence_rs::validate::ViolationKind::Absent,
+                    }),
+                }
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics
+        #where_clause
+        {
+            #(#methods)*
+        }
+    })
+}
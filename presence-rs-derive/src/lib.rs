@@ -0,0 +1,490 @@
+//! Derive macros for [`presence-rs`](https://docs.rs/presence-rs).
+//!
+//! Use them through the `derive` feature of `presence-rs`, which re-exports each derive
+//! next to the trait it implements (for example `presence_rs::validate::ValidatePresence`).
+
+mod attr;
+mod builder;
+mod describe;
+mod deserialize;
+mod env;
+mod getters;
+mod merge;
+mod napi;
+mod reflect;
+mod setters;
+mod tracked;
+mod validate;
+mod visit;
+
+use proc_macro::TokenStream;
+use syn::{DeriveInput, parse_macro_input};
+
+/// Derives `ValidatePresence` from `#[presence(...)]` field attributes.
+///
+/// - `#[presence(required)]`: an `Absent` field is a violation
+/// - `#[presence(non_null)]`: a `Null` field is a violation
+///
+/// Both can be combined to require a value. Fields without attributes are not checked.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::validate::{ValidatePresence, Violation, ViolationKind};
+///
+/// #[derive(ValidatePresence)]
+/// struct UserPatch {
+///     #[presence(non_null)]
+///     name: Presence<String>,
+///     #[presence(required, non_null)]
+///     version: Presence<u64>,
+///     avatar: Presence<String>,
+/// }
+///
+/// let patch = UserPatch {
+///     name: Presence::Null,
+///     version: Presence::Absent,
+///     avatar: Presence::Null,
+/// };
+///
+/// assert_eq!(
+///     patch.validate_presence().unwrap_err(),
+///     [
+///         Violation { field: "name", kind: ViolationKind::Null },
+///         Violation { field: "version", kind: ViolationKind::Absent },
+///     ]
+/// );
+/// ```
+#[proc_macro_derive(ValidatePresence, attributes(presence))]
+pub fn derive_validate_presence(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    validate::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `PresenceFields`, reporting the state of every field.
+///
+/// Every field must be a `Presence<T>`; mark other fields with `#[presence(skip)]`.
+/// `#[presence(nested)]` fields are not reported either.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, PresenceState};
+/// use presence_rs::reflect::PresenceFields;
+///
+/// #[derive(PresenceFields)]
+/// struct PostPatch {
+///     title: Presence<String>,
+///     summary: Presence<String>,
+///     #[presence(skip)]
+///     revision: u32,
+/// }
+///
+/// let patch = PostPatch { title: Presence::Absent, summary: Presence::Null, revision: 3 };
+/// assert_eq!(patch.defined_fields(), ["summary"]);
+/// assert_eq!(patch.field_states()[0], ("title", PresenceState::Absent));
+/// ```
+#[proc_macro_derive(PresenceFields, attributes(presence))]
+pub fn derive_presence_fields(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    reflect::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `DescribePatch`, summarizing each field as `name: <change>`.
+///
+/// A field is reported as `set to <value>` (formatted with `Debug`), `cleared` or
+/// `unchanged`. `#[presence(redact)]` replaces the value with `<redacted>`, and
+/// `#[presence(skip)]` and `#[presence(nested)]` leave the field out of the summary.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::reflect::DescribePatch;
+///
+/// #[derive(DescribePatch)]
+/// struct UserPatch {
+///     name: Presence<String>,
+///     avatar: Presence<String>,
+///     email: Presence<String>,
+/// }
+///
+/// let patch = UserPatch {
+///     name: Presence::Some("Bob".into()),
+///     avatar: Presence::Null,
+///     email: Presence::Absent,
+/// };
+/// assert_eq!(
+///     patch.describe(),
+///     r#"name: set to "Bob", avatar: cleared, email: unchanged"#
+/// );
+/// ```
+#[proc_macro_derive(DescribePatch, attributes(presence))]
+pub fn derive_describe_patch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    describe::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `MergeFrom`, merging two patches field by field.
+///
+/// By default the later patch wins wherever it is defined (`PreferOtherIfDefined`).
+///
+/// - `#[presence(merge = STRATEGY)]` uses another `MergeStrategy` for the field
+/// - `#[presence(nested)]` merges a field that is itself a `MergeFrom` struct
+/// - `#[presence(skip)]` keeps the current value of a field
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::merge::{MergeFrom, PreferOtherIfPresent};
+///
+/// #[derive(MergeFrom)]
+/// struct UserPatch {
+///     name: Presence<String>,
+///     #[presence(merge = PreferOtherIfPresent)]
+///     email: Presence<String>,
+/// }
+///
+/// let mut patch = UserPatch { name: Presence::Some("Al".into()), email: Presence::Some("a@x.io".into()) };
+/// patch.merge_from(UserPatch { name: Presence::Null, email: Presence::Null });
+///
+/// assert_eq!(patch.name, Presence::Null);
+/// assert_eq!(patch.email, Presence::Some("a@x.io".into()));
+/// ```
+#[proc_macro_derive(MergeFrom, attributes(presence))]
+pub fn derive_merge_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    merge::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `FromEnv`, reading each field from an environment variable.
+///
+/// A `Presence<T>` field is read from its name in upper case, prefixed with the container's
+/// `#[presence(env_prefix = "...")]`. An unset variable is `Absent`, an empty one `Null`,
+/// and anything else is parsed with `FromStr`.
+///
+/// - `#[presence(env = "NAME")]` reads the field from `NAME`, without the prefix
+/// - `#[presence(empty = absent)]` (or `null`, `parse`) changes how an empty value is read
+/// - `#[presence(nested)]` reads a field that is itself `FromEnv`
+/// - `#[presence(skip)]` leaves a field at its `Default`
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::env::FromEnv;
+/// use std::ffi::OsString;
+///
+/// #[derive(FromEnv)]
+/// #[presence(env_prefix = "DB_")]
+/// struct Database {
+///     url: Presence<String>,
+///     #[presence(empty = absent)]
+///     pool_size: Presence<u32>,
+/// }
+///
+/// let db = Database::from_env_lookup(|name| match name {
+///     "DB_URL" => Some(OsString::from("postgres://localhost")),
+///     "DB_POOL_SIZE" => Some(OsString::new()),
+///     _ => None,
+/// })
+/// .unwrap();
+///
+/// assert_eq!(db.url, Presence::Some("postgres://localhost".to_string()));
+/// assert_eq!(db.pool_size, Presence::Absent);
+/// ```
+#[proc_macro_derive(FromEnv, attributes(presence))]
+pub fn derive_from_env(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    env::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `VisitPresence`, walking every field with a `PresenceVisitor`.
+///
+/// - `#[presence(nested)]` walks a field that is itself `VisitPresence`, including a
+///   `Presence<P>` of one
+/// - `#[presence(redact)]` hides the value of a field from the visitor
+/// - `#[presence(skip)]` leaves a field out
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::reflect::VisitPresence;
+///
+/// #[derive(VisitPresence)]
+/// struct LimitsPatch {
+///     max: Presence<u32>,
+/// }
+///
+/// #[derive(VisitPresence)]
+/// struct PlanPatch {
+///     name: Presence<String>,
+///     #[presence(nested)]
+///     limits: Presence<LimitsPatch>,
+/// }
+///
+/// let patch = PlanPatch {
+///     name: Presence::Absent,
+///     limits: Presence::Some(LimitsPatch { max: Presence::Some(5) }),
+/// };
+///
+/// let mut defined = Vec::new();
+/// patch.visit_presence(&mut |path: &[&'static str], value: Presence<&dyn std::fmt::Debug>| {
+///     if value.is_defined() {
+///         defined.push(path.join("."));
+///     }
+/// });
+/// assert_eq!(defined, ["limits.max"]);
+/// ```
+#[proc_macro_derive(VisitPresence, attributes(presence))]
+pub fn derive_visit_presence(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    visit::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives serde's `Deserialize` for a patch struct, reading every `Presence<T>` field
+/// the input leaves out as `Absent` without a `#[serde(default)]` on each field.
+///
+/// Requires the `serde` feature of `presence-rs`. Do not combine it with
+/// `#[derive(Deserialize)]`, which implements the same trait.
+///
+/// - `#[presence(nested)]` fields that are missing take their `Default` value
+/// - `#[presence(plain)]` fields are read like any serde field: they are required unless
+///   marked `#[serde(default)]`. The other derives leave them alone, as with `skip`.
+/// - `#[presence(skip)]` fields are not read and take their `Default` value
+///
+/// The serde attributes `rename`, `alias` and `default` are supported on fields, and
+/// `rename`, `rename_all` and `deny_unknown_fields` on the struct. Serialization-only attributes
+/// are ignored; other serde attributes are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{DeserializePresence, Presence};
+///
+/// #[derive(Debug, DeserializePresence)]
+/// #[serde(rename_all = "camelCase")]
+/// struct UserPatch {
+///     display_name: Presence<String>,
+///     email: Presence<String>,
+///     #[presence(plain)]
+///     id: u64,
+/// }
+///
+/// let patch: UserPatch = serde_json::from_str(r#"{"id":7,"displayName":null}"#).unwrap();
+/// assert_eq!(patch.display_name, Presence::Null);
+/// assert_eq!(patch.email, Presence::Absent);
+/// assert_eq!(patch.id, 7);
+/// ```
+#[proc_macro_derive(DeserializePresence, attributes(presence, serde))]
+pub fn derive_deserialize_presence(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    deserialize::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives fluent `set_x`, `null_x` and `unset_x` methods for every `Presence<T>` field.
+///
+/// - `set_x(value)` sets the field to `Some(value.into())`
+/// - `null_x()` sets the field to `Null`
+/// - `unset_x()` sets the field to `Absent`
+///
+/// Each method takes `&mut self`, returns `&mut Self` for chaining and has the visibility
+/// of its field. `#[presence(skip)]` fields, and `#[presence(nested)]` fields that are not
+/// a `Presence<T>`, get no methods.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, PresenceSetters};
+///
+/// #[derive(Default, PresenceSetters)]
+/// struct ProfilePatch {
+///     name: Presence<String>,
+///     avatar: Presence<String>,
+///     bio: Presence<String>,
+/// }
+///
+/// let mut patch = ProfilePatch::default();
+/// patch.set_name("Ada").set_avatar("ada.png").null_bio().unset_avatar();
+///
+/// assert_eq!(patch.name, Presence::Some("Ada".to_string()));
+/// assert_eq!(patch.avatar, Presence::Absent);
+/// assert_eq!(patch.bio, Presence::Null);
+/// ```
+#[proc_macro_derive(PresenceSetters, attributes(presence))]
+pub fn derive_presence_setters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    setters::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives an accessor for every `Presence<T>` field that returns the value or a
+/// `Violation` naming the field and its empty state.
+///
+/// `fn x(&self) -> Result<&T, Violation>` is named after its field and has the field's
+/// visibility. `#[presence(skip)]` fields, and `#[presence(nested)]` fields that are not
+/// a `Presence<T>`, get no accessor.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, PresenceGetters};
+/// use presence_rs::validate::{Violation, ViolationKind};
+///
+/// #[derive(PresenceGetters)]
+/// struct Order {
+///     id: Presence<u64>,
+///     coupon: Presence<String>,
+/// }
+///
+/// let order = Order { id: Presence::Some(7), coupon: Presence::Null };
+/// assert_eq!(order.id(), Ok(&7));
+/// assert_eq!(
+///     order.coupon(),
+///     Err(Violation { field: "coupon", kind: ViolationKind::Null }),
+/// );
+/// assert_eq!(order.coupon().unwrap_err().to_string(), "`coupon` must not be null");
+/// ```
+#[proc_macro_derive(PresenceGetters, attributes(presence))]
+pub fn derive_presence_getters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    getters::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `Null` setters and presence checks for the derive_builder builder of a patch
+/// struct.
+///
+/// derive_builder stores every field of `Name` as an `Option<_>` in `NameBuilder`. For
+/// each `Presence<T>` field, this derive adds to the builder:
+///
+/// - `x_null()`, setting the field to `Null`
+/// - a check of `#[presence(required)]` and `#[presence(non_null)]` in `check_presence()`,
+///   where an unset field is `Absent`
+///
+/// `check_presence()` returns `Result<(), String>`, to be passed as
+/// `#[builder(build_fn(validate = "Self::check_presence"))]`.
+///
+/// The derive reads derive_builder's `#[builder(name = "...")]` for the builder's name,
+/// which `#[presence(builder = "...")]` overrides, and `#[builder(pattern = "...")]` on the
+/// struct or a field: `x_null()` takes and returns `&mut Self` for the default `mutable`
+/// pattern, `Self` for `owned`, and `&self` returning a clone for `immutable`.
+///
+/// # Examples
+///
+/// The builder below is written by hand as derive_builder would generate it.
+///
+/// ```
+/// use presence_rs::{Presence, PresenceBuilder};
+///
+/// #[derive(PresenceBuilder)]
+/// struct UserPatch {
+///     #[presence(required)]
+///     name: Presence<String>,
+///     bio: Presence<String>,
+/// }
+///
+/// #[derive(Default)]
+/// struct UserPatchBuilder {
+///     name: Option<Presence<String>>,
+///     bio: Option<Presence<String>>,
+/// }
+///
+/// let mut builder = UserPatchBuilder::default();
+/// builder.bio_null();
+/// assert_eq!(builder.bio, Some(Presence::Null));
+/// assert_eq!(builder.check_presence(), Err("`name` is required".to_string()));
+/// ```
+#[proc_macro_derive(PresenceBuilder, attributes(presence))]
+pub fn derive_presence_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    builder::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `IntoPatch` for a struct of `Tracked<T>` fields, generating its patch struct.
+///
+/// The patch struct is named after the struct with a `Patch` suffix, or as given by
+/// `#[presence(patch = "...")]`, and has the struct's visibility and generics. Each
+/// `Tracked<T>` field becomes a `Presence<T>` field with the same name and visibility, and
+/// any other field must itself be `IntoPatch` and becomes its patch.
+///
+/// - `#[presence(patch_derive(...))]` adds derives to the patch struct, which always
+///   derives `Clone`, `Debug`, `Default` and `PartialEq`. With serde's `Serialize`,
+///   `Absent` fields are skipped; with `Deserialize`, missing fields are `Absent`.
+/// - `#[presence(skip)]` leaves a field out of the patch
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::tracked::{IntoPatch, Tracked};
+///
+/// #[derive(IntoPatch)]
+/// struct Profile {
+///     name: Tracked<String>,
+///     bio: Tracked<String>,
+///     #[presence(skip)]
+///     id: u64,
+/// }
+///
+/// let mut profile = Profile {
+///     name: Tracked::new("Ann".into()),
+///     bio: Tracked::new("Hi".into()),
+///     id: 7,
+/// };
+/// profile.bio.clear();
+///
+/// assert!(profile.is_changed());
+/// assert_eq!(
+///     profile.into_patch(),
+///     ProfilePatch { name: Presence::Absent, bio: Presence::Null }
+/// );
+/// ```
+#[proc_macro_derive(IntoPatch, attributes(presence))]
+pub fn derive_into_patch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    tracked::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives napi-rs conversions for a patch struct, reading a missing key as `Absent`.
+///
+/// Implements `TypeName`, `ValidateNapiValue`, `FromNapiValue` and `ToNapiValue`, so the
+/// struct can be passed to and returned from `#[napi]` functions like a `#[napi(object)]`.
+/// Properties are named after the fields in camelCase.
+///
+/// - a `Presence<T>` field reads a missing key or `undefined` as `Absent`, and is left
+///   out of the object when `Absent`
+/// - `#[presence(plain)]` marks a required field, which rejects a missing key
+/// - `#[presence(skip)]` leaves a field out, reading it as `Default::default()`
+///
+/// Requires the `napi` feature of `presence-rs`; see `presence_rs::napi` for an example.
+#[proc_macro_derive(NapiObject, attributes(presence))]
+pub fn derive_napi_object(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    napi::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
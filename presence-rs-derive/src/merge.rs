@@ -0,0 +1,47 @@
+use crate::attr::{FieldOptions, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Error, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut merges = Vec::new();
+    for field in named_fields(input, "MergeFrom")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        let ident = &field.ident;
+        if options.nested && options.merge.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "`nested` and `merge` cannot be combined",
+            ));
+        }
+        if options.is_skipped() {
+            continue;
+        }
+        if options.nested {
+            merges.push(quote! {
+                ::presence_rs::merge::MergeFrom::merge_from(&mut self.#ident, other.#ident);
+            });
+        } else {
+            let strategy = options
+                .merge
+                .map(|strategy| quote!(#strategy))
+                .unwrap_or_else(|| quote!(::presence_rs::merge::PreferOtherIfDefined));
+            merges.push(quote! {
+                ::presence_rs::Presence::merge_with(&mut self.#ident, other.#ident, #strategy);
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::merge::MergeFrom for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn merge_from(&mut self, other: Self) {
+                #(#merges)*
+            }
+        }
+    })
+}
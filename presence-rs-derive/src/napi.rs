@@ -0,0 +1,100 @@
+use crate::attr::{FieldOptions, field_name, named_fields, presence_inner};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut reads = Vec::new();
+    let mut writes = Vec::new();
+    for field in named_fields(input, "NapiObject")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        let ident = &field.ident;
+        if options.skip {
+            reads.push(quote!(#ident: ::core::default::Default::default()));
+            continue;
+        }
+        let key = camel_case(&field_name(field));
+        if presence_inner(&field.ty).is_some() && !options.plain {
+            reads.push(quote! {
+                #ident: ::presence_rs::napi::__private::get_presence(env, napi_val, #key)?
+            });
+            writes.push(quote! {
+                ::presence_rs::napi::__private::set_presence(env, object, #key, val.#ident)?;
+            });
+        } else {
+            reads.push(quote! {
+                #ident: ::presence_rs::napi::__private::get_field(env, napi_val, #key)?
+            });
+            writes.push(quote! {
+                ::presence_rs::napi::__private::set_field(env, object, #key, val.#ident)?;
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::napi::__private::TypeName for #ident #ty_generics
+        #where_clause
+        {
+            fn type_name() -> &'static str {
+                #name
+            }
+
+            fn value_type() -> ::presence_rs::napi::__private::ValueType {
+                ::presence_rs::napi::__private::ValueType::Object
+            }
+        }
+
+        impl #impl_generics ::presence_rs::napi::__private::ValidateNapiValue
+            for #ident #ty_generics
+        #where_clause
+        {
+        }
+
+        impl #impl_generics ::presence_rs::napi::__private::FromNapiValue for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables, unused_unsafe)]
+            unsafe fn from_napi_value(
+                env: ::presence_rs::napi::__private::sys::napi_env,
+                napi_val: ::presence_rs::napi::__private::sys::napi_value,
+            ) -> ::presence_rs::napi::__private::Result<Self> {
+                unsafe { ::core::result::Result::Ok(Self { #(#reads),* }) }
+            }
+        }
+
+        impl #impl_generics ::presence_rs::napi::__private::ToNapiValue for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables, unused_unsafe)]
+            unsafe fn to_napi_value(
+                env: ::presence_rs::napi::__private::sys::napi_env,
+                val: Self,
+            ) -> ::presence_rs::napi::__private::Result<
+                ::presence_rs::napi::__private::sys::napi_value,
+            > {
+                unsafe {
+                    let object = ::presence_rs::napi::__private::create_object(env)?;
+                    #(#writes)*
+                    ::core::result::Result::Ok(object)
+                }
+            }
+        }
+    })
+}
+
+/// Converts a snake_case field name to the camelCase property name napi-rs uses.
+fn camel_case(name: &str) -> String {
+    let mut words = name.split('_').filter(|word| !word.is_empty());
+    let mut result = words.next().unwrap_or_default().to_string();
+    for word in words {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
@@ -0,0 +1,32 @@
+use crate::attr::{FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut states = Vec::new();
+    for field in named_fields(input, "PresenceFields")? {
+        if !FieldOptions::from_attrs(&field.attrs)?.is_presence() {
+            continue;
+        }
+        let ident = &field.ident;
+        let name = field_name(field);
+        states.push(quote! {
+            (#name, ::presence_rs::Presence::state(&self.#ident))
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::reflect::PresenceFields for #ident #ty_generics
+        #where_clause
+        {
+            fn field_states(
+                &self,
+            ) -> ::std::vec::Vec<(&'static str, ::presence_rs::PresenceState)> {
+                ::std::vec![#(#states),*]
+            }
+        }
+    })
+}
@@ -0,0 +1,62 @@
+use crate::attr::{FieldOptions, field_name, named_fields, presence_inner};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Error, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut methods = Vec::new();
+    for field in named_fields(input, "PresenceSetters")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if options.is_skipped() {
+            continue;
+        }
+        let Some(inner) = presence_inner(&field.ty) else {
+            if options.nested {
+                continue;
+            }
+            return Err(Error::new_spanned(
+                &field.ty,
+                "PresenceSetters fields must be `Presence<T>`; mark other fields with `#[presence(skip)]`",
+            ));
+        };
+
+        let vis = &field.vis;
+        let ident = &field.ident;
+        let name = field_name(field);
+        let set = format_ident!("set_{}", name);
+        let null = format_ident!("null_{}", name);
+        let unset = format_ident!("unset_{}", name);
+        let set_doc = format!("Sets `{}` to `Some(value)`.", name);
+        let null_doc = format!("Sets `{}` to `Null`.", name);
+        let unset_doc = format!("Sets `{}` to `Absent`.", name);
+        methods.push(quote! {
+            #[doc = #set_doc]
+            #vis fn #set(&mut self, value: impl ::core::convert::Into<#inner>) -> &mut Self {
+                self.#ident = ::presence_rs::Presence::Some(value.into());
+                self
+            }
+
+            #[doc = #null_doc]
+            #vis fn #null(&mut self) -> &mut Self {
+                self.#ident = ::presence_rs::Presence::Null;
+                self
+            }
+
+            #[doc = #unset_doc]
+            #vis fn #unset(&mut self) -> &mut Self {
+                self.#ident = ::presence_rs::Presence::Absent;
+                self
+            }
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #ident #ty_generics
+        #where_clause
+        {
+            #(#methods)*
+        }
+    })
+}
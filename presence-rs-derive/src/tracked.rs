@@ -0,0 +1,83 @@
+use crate::attr::{ContainerOptions, FieldOptions, named_fields, tracked_inner};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let container = ContainerOptions::from_attrs(&input.attrs)?;
+    let ident = &input.ident;
+    let patch = container
+        .patch
+        .unwrap_or_else(|| format_ident!("{}Patch", ident));
+    let serialize = container
+        .patch_derive
+        .iter()
+        .any(|path| path.segments.last().is_some_and(|s| s.ident == "Serialize"));
+    let deserialize = container.patch_derive.iter().any(|path| {
+        path.segments
+            .last()
+            .is_some_and(|s| s.ident == "Deserialize")
+    });
+
+    let mut fields = Vec::new();
+    let mut conversions = Vec::new();
+    let mut changes = Vec::new();
+    for field in named_fields(input, "IntoPatch")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if options.is_skipped() {
+            continue;
+        }
+        let vis = &field.vis;
+        let name = &field.ident;
+        let ty = &field.ty;
+        let field = match tracked_inner(ty) {
+            Some(inner) => {
+                let serde = match (serialize, deserialize) {
+                    (true, true) => quote! {
+                        #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+                    },
+                    (true, false) => quote! {
+                        #[serde(skip_serializing_if = "::presence_rs::Presence::is_absent")]
+                    },
+                    (false, true) => quote!(#[serde(default)]),
+                    (false, false) => quote!(),
+                };
+                quote!(#serde #vis #name: ::presence_rs::Presence<#inner>)
+            }
+            None => quote!(#vis #name: <#ty as ::presence_rs::tracked::IntoPatch>::Patch),
+        };
+        fields.push(field);
+        conversions.push(quote!(#name: ::presence_rs::tracked::IntoPatch::into_patch(self.#name)));
+        changes.push(quote!(::presence_rs::tracked::IntoPatch::is_changed(&self.#name)));
+    }
+
+    let vis = &input.vis;
+    let doc = format!(
+        "The patch of [`{}`], created by `IntoPatch::into_patch`.",
+        ident
+    );
+    let derives = &container.patch_derive;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Debug, Default, PartialEq #(, #derives)*)]
+        #vis struct #patch #generics #where_clause {
+            #(#fields,)*
+        }
+
+        impl #impl_generics ::presence_rs::tracked::IntoPatch for #ident #ty_generics
+        #where_clause
+        {
+            type Patch = #patch #ty_generics;
+
+            fn into_patch(self) -> Self::Patch {
+                #patch { #(#conversions,)* }
+            }
+
+            fn is_changed(&self) -> bool {
+                false #(|| #changes)*
+            }
+        }
+    })
+}
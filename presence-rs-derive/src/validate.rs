@@ -0,0 +1,53 @@
+use crate::attr::{FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut checks = Vec::new();
+    for field in named_fields(input, "ValidatePresence")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        let ident = &field.ident;
+        let name = field_name(field);
+        if options.required {
+            checks.push(quote! {
+                if ::presence_rs::Presence::is_absent(&self.#ident) {
+                    violations.push(::presence_rs::validate::Violation {
+                        field: #name,
+                        kind: ::presence_rs::validate::ViolationKind::Absent,
+                    });
+                }
+            });
+        }
+        if options.non_null {
+            checks.push(quote! {
+                if ::presence_rs::Presence::is_null(&self.#ident) {
+                    violations.push(::presence_rs::validate::Violation {
+                        field: #name,
+                        kind: ::presence_rs::validate::ViolationKind::Null,
+                    });
+                }
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::validate::ValidatePresence for #ident #ty_generics
+        #where_clause
+        {
+            fn validate_presence(
+                &self,
+            ) -> ::core::result::Result<(), ::std::vec::Vec<::presence_rs::validate::Violation>> {
+                let mut violations = ::std::vec::Vec::new();
+                #(#checks)*
+                if violations.is_empty() {
+                    ::core::result::Result::Ok(())
+                } else {
+                    ::core::result::Result::Err(violations)
+                }
+            }
+        }
+    })
+}
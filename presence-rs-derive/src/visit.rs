@@ -0,0 +1,49 @@
+use crate::attr::{FieldOptions, field_name, named_fields};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let mut visits = Vec::new();
+    for field in named_fields(input, "VisitPresence")? {
+        let options = FieldOptions::from_attrs(&field.attrs)?;
+        if options.is_skipped() {
+            continue;
+        }
+        let ident = &field.ident;
+        let name = field_name(field);
+        if options.nested {
+            visits.push(quote! {
+                ::presence_rs::reflect::__visit_nested(path, #name, &self.#ident, visitor);
+            });
+        } else {
+            // Redacted values are never formatted, so they don't need to implement `Debug`.
+            let value = if options.redact {
+                quote!(&::presence_rs::Presence::as_ref(&self.#ident).map(|_| ()))
+            } else {
+                quote!(&self.#ident)
+            };
+            let redact = options.redact;
+            visits.push(quote! {
+                ::presence_rs::reflect::__visit_field(path, #name, #value, #redact, visitor);
+            });
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::presence_rs::reflect::VisitPresence for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables)]
+            fn visit_presence_at(
+                &self,
+                path: &mut ::std::vec::Vec<&'static str>,
+                visitor: &mut dyn ::presence_rs::reflect::PresenceVisitor,
+            ) {
+                #(#visits)*
+            }
+        }
+    })
+}
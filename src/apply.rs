@@ -0,0 +1,540 @@
+//! Applying [`Presence<T>`] patches onto existing values.
+//!
+//! A patch field describes what to do with the matching field of a target:
+//!
+//! - `Absent` → leave the target untouched
+//! - `Null` → clear the target (`None`, `Null`, or the type's default value)
+//! - `Some(value)` → overwrite the target with `value`
+//!
+//! [`ApplyPresence`] encodes this for a single field and [`apply_patch!`] applies a
+//! whole patch struct field by field. [`DeepApply`] extends this to nested patch structs,
+//! where a `Presence<InnerPatch>` field descends into the matching subtree, and
+//! [`ApplyPresenceInverse`] and [`DeepApplyInverse`] return the patch that undoes a change.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::{Presence, apply_patch};
+//!
+//! struct User {
+//!     name: String,
+//!     nickname: Option<String>,
+//!     age: u32,
+//! }
+//!
+//! struct UserPatch {
+//!     name: Presence<String>,
+//!     nickname: Presence<String>,
+//!     age: Presence<u32>,
+//! }
+//!
+//! let mut user = User { name: "Alice".into(), nickname: Some("Al".into()), age: 30 };
+//! let patch = UserPatch {
+//!     name: Presence::Absent,
+//!     nickname: Presence::Null,
+//!     age: Presence::Some(31),
+//! };
+//!
+//! apply_patch!(user, patch; name, nickname, age);
+//!
+//! assert_eq!(user.name, "Alice");
+//! assert_eq!(user.nickname, None);
+//! assert_eq!(user.age, 31);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`apply_patch!`]: crate::apply_patch
+
+use crate::presence::Presence;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{BuildHasher, Hash};
+
+/// A field that a `Presence<T>` patch value can be applied to.
+///
+/// `Absent` never changes the field and `Some(value)` always overwrites it. What
+/// `Null` does depends on the field type:
+///
+/// - `Option<T>` becomes `None`
+/// - `Presence<T>` becomes `Null`
+/// - Non-nullable values (numbers, `bool`, `String`, collections) are reset to their
+///   [`Default`], matching proto3's "cleared field" semantics
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::apply::ApplyPresence;
+///
+/// let mut email = Some("a@example.com".to_string());
+/// email.apply_presence(Presence::Absent);
+/// assert_eq!(email.as_deref(), Some("a@example.com"));
+///
+/// email.apply_presence(Presence::Null);
+/// assert_eq!(email, None);
+///
+/// let mut count = 5u32;
+/// count.apply_presence(Presence::Null);
+/// assert_eq!(count, 0);
+/// ```
+pub trait ApplyPresence<T> {
+    /// Applies `value` to `self`.
+    fn apply_presence(&mut self, value: Presence<T>);
+}
+
+impl<T> ApplyPresence<T> for Option<T> {
+    #[inline]
+    fn apply_presence(&mut self, value: Presence<T>) {
+        match value {
+            Presence::Some(value) => *self = Some(value),
+            Presence::Null => *self = None,
+            Presence::Absent => {}
+        }
+    }
+}
+
+impl<T> ApplyPresence<T> for Presence<T> {
+    #[inline]
+    fn apply_presence(&mut self, value: Presence<T>) {
+        if value.is_defined() {
+            *self = value;
+        }
+    }
+}
+
+macro_rules! apply_presence_or_default {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ApplyPresence<$ty> for $ty {
+                #[inline]
+                fn apply_presence(&mut self, value: Presence<$ty>) {
+                    match value {
+                        Presence::Some(value) => *self = value,
+                        Presence::Null => *self = Default::default(),
+                        Presence::Absent => {}
+                    }
+                }
+            }
+        )*
+    };
+}
+
+apply_presence_or_default!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, String,
+);
+
+impl<T> ApplyPresence<Vec<T>> for Vec<T> {
+    #[inline]
+    fn apply_presence(&mut self, value: Presence<Vec<T>>) {
+        match value {
+            Presence::Some(value) => *self = value,
+            Presence::Null => self.clear(),
+            Presence::Absent => {}
+        }
+    }
+}
+
+impl<K, V, S> ApplyPresence<HashMap<K, V, S>> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher,
+{
+    #[inline]
+    fn apply_presence(&mut self, value: Presence<HashMap<K, V, S>>) {
+        match value {
+            Presence::Some(value) => *self = value,
+            Presence::Null => self.clear(),
+            Presence::Absent => {}
+        }
+    }
+}
+
+impl<K: Ord, V> ApplyPresence<BTreeMap<K, V>> for BTreeMap<K, V> {
+    #[inline]
+    fn apply_presence(&mut self, value: Presence<BTreeMap<K, V>>) {
+        match value {
+            Presence::Some(value) => *self = value,
+            Presence::Null => self.clear(),
+            Presence::Absent => {}
+        }
+    }
+}
+
+/// A field that can return the inverse of an applied `Presence<T>` patch value.
+///
+/// The inverse restores the field when applied afterwards: it is the prior value as `Some`,
+/// or `Null` if the field was empty (`None`, a `Presence` that was `Null` or `Absent`, or
+/// the `Default` of a plain field). It is `Absent` if `value` was `Absent` or left the field
+/// unchanged, for every kind of field, so an undo patch only names the fields that changed.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::apply::{ApplyPresence, ApplyPresenceInverse};
+///
+/// let mut email = Some("a@example.com".to_string());
+/// let undo = email.apply_presence_inverse(Presence::Null);
+/// assert_eq!(email, None);
+/// assert_eq!(undo, Presence::Some("a@example.com".to_string()));
+///
+/// email.apply_presence(undo);
+/// assert_eq!(email.as_deref(), Some("a@example.com"));
+/// ```
+pub trait ApplyPresenceInverse<T>: ApplyPresence<T> {
+    /// Applies `value` to `self`, returning the patch value that undoes it.
+    fn apply_presence_inverse(&mut self, value: Presence<T>) -> Presence<T>;
+}
+
+impl<T: PartialEq> ApplyPresenceInverse<T> for Option<T> {
+    fn apply_presence_inverse(&mut self, value: Presence<T>) -> Presence<T> {
+        if value.is_absent() {
+            return Presence::Absent;
+        }
+        let prior = self.take();
+        self.apply_presence(value);
+        match prior {
+            prior if *self == prior => Presence::Absent,
+            Some(prior) => Presence::Some(prior),
+            None => Presence::Null,
+        }
+    }
+}
+
+impl<T: PartialEq> ApplyPresenceInverse<T> for Presence<T> {
+    /// A target that was `Absent` is restored as `Null`, since no patch value moves a
+    /// field back to `Absent`.
+    fn apply_presence_inverse(&mut self, value: Presence<T>) -> Presence<T> {
+        if value.is_absent() {
+            return Presence::Absent;
+        }
+        match std::mem::replace(self, value) {
+            prior if *self == prior => Presence::Absent,
+            Presence::Absent => Presence::Null,
+            prior => prior,
+        }
+    }
+}
+
+/// Applies `value` to a field whose empty state is its `Default`, returning the prior value,
+/// or `Absent` if the field is unchanged.
+fn apply_inverse_by_take<T>(target: &mut T, value: Presence<T>) -> Presence<T>
+where
+    T: ApplyPresence<T> + Default + PartialEq,
+{
+    if value.is_absent() {
+        return Presence::Absent;
+    }
+    let prior = std::mem::take(target);
+    target.apply_presence(value);
+    if *target == prior {
+        Presence::Absent
+    } else {
+        Presence::Some(prior)
+    }
+}
+
+macro_rules! apply_presence_inverse_by_take {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ApplyPresenceInverse<$ty> for $ty {
+                #[inline]
+                fn apply_presence_inverse(&mut self, value: Presence<$ty>) -> Presence<$ty> {
+                    apply_inverse_by_take(self, value)
+                }
+            }
+        )*
+    };
+}
+
+apply_presence_inverse_by_take!(
+    bool, char, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, String,
+);
+
+impl<T: PartialEq> ApplyPresenceInverse<Vec<T>> for Vec<T> {
+    #[inline]
+    fn apply_presence_inverse(&mut self, value: Presence<Vec<T>>) -> Presence<Vec<T>> {
+        apply_inverse_by_take(self, value)
+    }
+}
+
+impl<K, V, S> ApplyPresenceInverse<HashMap<K, V, S>> for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    V: PartialEq,
+    S: BuildHasher + Default,
+{
+    #[inline]
+    fn apply_presence_inverse(
+        &mut self,
+        value: Presence<HashMap<K, V, S>>,
+    ) -> Presence<HashMap<K, V, S>> {
+        apply_inverse_by_take(self, value)
+    }
+}
+
+impl<K: Ord, V: PartialEq> ApplyPresenceInverse<BTreeMap<K, V>> for BTreeMap<K, V> {
+    #[inline]
+    fn apply_presence_inverse(
+        &mut self,
+        value: Presence<BTreeMap<K, V>>,
+    ) -> Presence<BTreeMap<K, V>> {
+        apply_inverse_by_take(self, value)
+    }
+}
+
+/// A patch that can be applied to a whole target value, descending into nested patches.
+///
+/// Implement it for a patch struct to make `Presence<Patch>` fields applicable
+/// recursively: `Presence<P>` implements `DeepApply<T>` for every `P: DeepApply<T>`, where
+///
+/// - `Absent` leaves the target subtree untouched
+/// - `Null` clears it to `T::default()`, which is `None` for `Option<T>` targets
+/// - `Some(patch)` applies `patch` to the existing subtree
+///
+/// The nested section of [`apply_patch!`] applies fields through this trait.
+///
+/// [`apply_patch!`]: crate::apply_patch
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::apply::DeepApply;
+/// use presence_rs::{Presence, apply_patch};
+///
+/// #[derive(Default)]
+/// struct Address { street: String, city: String }
+/// struct AddressPatch { street: Presence<String>, city: Presence<String> }
+///
+/// impl DeepApply<Address> for AddressPatch {
+///     fn deep_apply(self, address: &mut Address) {
+///         apply_patch!(address, self; street, city);
+///     }
+/// }
+///
+/// struct User { name: String, address: Address }
+/// struct UserPatch { name: Presence<String>, address: Presence<AddressPatch> }
+///
+/// impl DeepApply<User> for UserPatch {
+///     fn deep_apply(self, user: &mut User) {
+///         apply_patch!(user, self; name; nested address);
+///     }
+/// }
+///
+/// let mut user = User {
+///     name: "Alice".into(),
+///     address: Address { street: "Main St".into(), city: "Kyiv".into() },
+/// };
+/// let patch = UserPatch {
+///     name: Presence::Absent,
+///     address: Presence::Some(AddressPatch {
+///         street: Presence::Absent,
+///         city: Presence::Some("Lviv".into()),
+///     }),
+/// };
+/// patch.deep_apply(&mut user);
+/// assert_eq!(user.address.street, "Main St");
+/// assert_eq!(user.address.city, "Lviv");
+///
+/// let clear = UserPatch { name: Presence::Absent, address: Presence::Null };
+/// clear.deep_apply(&mut user);
+/// assert_eq!(user.address.city, "");
+/// ```
+pub trait DeepApply<Target> {
+    /// Applies `self` to `target`.
+    fn deep_apply(self, target: &mut Target);
+}
+
+impl<T: Default, P: DeepApply<T>> DeepApply<T> for Presence<P> {
+    #[inline]
+    fn deep_apply(self, target: &mut T) {
+        match self {
+            Presence::Some(patch) => patch.deep_apply(target),
+            Presence::Null => *target = T::default(),
+            Presence::Absent => {}
+        }
+    }
+}
+
+/// A patch that can be applied to a whole target value, returning the patch that undoes it.
+///
+/// This is [`DeepApply`] for undo: implement it for a patch struct with the inverse form of
+/// [`apply_patch!`]. `Presence<P>` implements it for every `P: DeepApplyInverse<T>`, and
+/// its inverse is
+///
+/// - `Absent` if the patch was `Absent` or left the subtree unchanged
+/// - `Some(inverse)` of the nested patch when it was `Some(patch)`
+/// - `Some(P::from(prior))` when `Null` cleared the subtree, so `From<T>` must build a
+///   patch that sets every field of the prior value
+///
+/// [`apply_patch!`]: crate::apply_patch
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::apply::{DeepApply, DeepApplyInverse};
+/// use presence_rs::{Presence, apply_patch};
+///
+/// #[derive(Default, PartialEq)]
+/// struct Address { city: String }
+/// #[derive(Default, PartialEq)]
+/// struct AddressPatch { city: Presence<String> }
+///
+/// impl From<Address> for AddressPatch {
+///     fn from(address: Address) -> Self {
+///         AddressPatch { city: Presence::Some(address.city) }
+///     }
+/// }
+///
+/// impl DeepApply<Address> for AddressPatch {
+///     fn deep_apply(self, address: &mut Address) {
+///         apply_patch!(address, self; city);
+///     }
+/// }
+///
+/// impl DeepApplyInverse<Address> for AddressPatch {
+///     fn deep_apply_inverse(self, address: &mut Address) -> Self {
+///         apply_patch!(address, self => AddressPatch; city)
+///     }
+/// }
+///
+/// let mut address = Address { city: "Kyiv".into() };
+/// let undo = Presence::<AddressPatch>::Null.deep_apply_inverse(&mut address);
+/// assert_eq!(address.city, "");
+///
+/// undo.deep_apply(&mut address);
+/// assert_eq!(address.city, "Kyiv");
+/// ```
+pub trait DeepApplyInverse<Target>: DeepApply<Target> {
+    /// Applies `self` to `target`, returning the patch that undoes it.
+    fn deep_apply_inverse(self, target: &mut Target) -> Self;
+}
+
+impl<T, P> DeepApplyInverse<T> for Presence<P>
+where
+    T: Default + PartialEq,
+    P: DeepApplyInverse<T> + Default + PartialEq + From<T>,
+{
+    fn deep_apply_inverse(self, target: &mut T) -> Self {
+        match self {
+            Presence::Some(patch) => match patch.deep_apply_inverse(target) {
+                inverse if inverse == P::default() => Presence::Absent,
+                inverse => Presence::Some(inverse),
+            },
+            Presence::Null => match std::mem::take(target) {
+                prior if prior == T::default() => Presence::Absent,
+                prior => Presence::Some(P::from(prior)),
+            },
+            Presence::Absent => Presence::Absent,
+        }
+    }
+}
+
+/// Applies the listed fields of a patch struct onto a target value.
+///
+/// Each listed field of `$patch` must be a `Presence<T>`, and the same-named field of
+/// `$target` must implement [`ApplyPresence<T>`]. Fields listed after `; nested` are
+/// applied with [`DeepApply`] instead, so a `Presence<InnerPatch>` field descends into the
+/// matching target field. The patch fields are moved out of `$patch`.
+///
+/// With `$patch => PatchType`, the macro returns the inverse patch, which undoes the
+/// change when applied afterwards. Its listed fields come from
+/// [`ApplyPresenceInverse<T>`], its nested fields from [`DeepApplyInverse`], and the others
+/// are left at `PatchType::default()`.
+///
+/// [`ApplyPresence<T>`]: crate::apply::ApplyPresence
+/// [`ApplyPresenceInverse<T>`]: crate::apply::ApplyPresenceInverse
+/// [`DeepApply`]: crate::apply::DeepApply
+/// [`DeepApplyInverse`]: crate::apply::DeepApplyInverse
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, apply_patch};
+///
+/// struct Settings { volume: u8, theme: Option<String> }
+/// struct SettingsPatch { volume: Presence<u8>, theme: Presence<String> }
+///
+/// let mut settings = Settings { volume: 7, theme: None };
+/// let patch = SettingsPatch { volume: Presence::Absent, theme: Presence::Some("dark".into()) };
+///
+/// apply_patch!(settings, patch; volume, theme);
+/// assert_eq!(settings.volume, 7);
+/// assert_eq!(settings.theme.as_deref(), Some("dark"));
+/// ```
+///
+/// Capturing the inverse for undo:
+///
+/// ```
+/// use presence_rs::{Presence, apply_patch};
+///
+/// struct Settings { volume: u8, theme: Option<String> }
+/// #[derive(Default)]
+/// struct SettingsPatch { volume: Presence<u8>, theme: Presence<String> }
+///
+/// let mut settings = Settings { volume: 7, theme: Some("light".into()) };
+/// let patch = SettingsPatch { volume: Presence::Some(9), theme: Presence::Null };
+///
+/// let undo = apply_patch!(settings, patch => SettingsPatch; volume, theme);
+/// assert_eq!(settings.volume, 9);
+/// assert_eq!(undo.volume, Presence::Some(7));
+/// assert_eq!(undo.theme, Presence::Some("light".into()));
+///
+/// apply_patch!(settings, undo; volume, theme);
+/// assert_eq!(settings.volume, 7);
+/// assert_eq!(settings.theme.as_deref(), Some("light"));
+/// ```
+#[macro_export]
+macro_rules! apply_patch {
+    ($target:expr, $patch:expr => $($inverse:ident)::+; $($field:ident),+ $(,)?) => {{
+        let patch = $patch;
+        #[allow(clippy::needless_update)]
+        let inverse = $($inverse)::+ {
+            $(
+                $field: $crate::apply::ApplyPresenceInverse::apply_presence_inverse(
+                    &mut $target.$field,
+                    patch.$field,
+                ),
+            )+
+            ..::core::default::Default::default()
+        };
+        inverse
+    }};
+    (
+        $target:expr, $patch:expr => $($inverse:ident)::+;
+        $($field:ident),* ; nested $($nested:ident),+ $(,)?
+    ) => {{
+        let patch = $patch;
+        #[allow(clippy::needless_update)]
+        let inverse = $($inverse)::+ {
+            $(
+                $field: $crate::apply::ApplyPresenceInverse::apply_presence_inverse(
+                    &mut $target.$field,
+                    patch.$field,
+                ),
+            )*
+            $(
+                $nested: $crate::apply::DeepApplyInverse::deep_apply_inverse(
+                    patch.$nested,
+                    &mut $target.$nested,
+                ),
+            )+
+            ..::core::default::Default::default()
+        };
+        inverse
+    }};
+    ($target:expr, $patch:expr; $($field:ident),+ $(,)?) => {{
+        let patch = $patch;
+        $(
+            $crate::apply::ApplyPresence::apply_presence(&mut $target.$field, patch.$field);
+        )+
+    }};
+    ($target:expr, $patch:expr; $($field:ident),* ; nested $($nested:ident),+ $(,)?) => {{
+        let patch = $patch;
+        $(
+            $crate::apply::ApplyPresence::apply_presence(&mut $target.$field, patch.$field);
+        )*
+        $(
+            $crate::apply::DeepApply::deep_apply(patch.$nested, &mut $target.$nested);
+        )+
+    }};
+}
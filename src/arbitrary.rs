@@ -0,0 +1,45 @@
+//! Fuzzing support for [`Presence<T>`] via the arbitrary crate.
+//!
+//! A generated `Presence<T>` is `Some` half of the time and `Absent` or `Null` a quarter
+//! of the time each, so fuzz targets reach all three branches of patch-handling code. Once
+//! the input is exhausted, values are `Absent`.
+//!
+//! # Examples
+//!
+//! ```
+//! use arbitrary::{Arbitrary, Unstructured};
+//! use presence_rs::Presence;
+//!
+//! let mut u = Unstructured::new(&[0, 1, 2, 7]);
+//! assert_eq!(Presence::<u8>::arbitrary(&mut u).unwrap(), Presence::Absent);
+//! assert_eq!(Presence::<u8>::arbitrary(&mut u).unwrap(), Presence::Null);
+//! assert_eq!(Presence::<u8>::arbitrary(&mut u).unwrap(), Presence::Some(7));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use arbitrary::{Arbitrary, MaxRecursionReached, Result, Unstructured, size_hint};
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Presence<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0u8..=3)? {
+            0 => Presence::Absent,
+            1 => Presence::Null,
+            _ => Presence::Some(T::arbitrary(u)?),
+        })
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    #[inline]
+    fn try_size_hint(depth: usize) -> Result<(usize, Option<usize>), MaxRecursionReached> {
+        Ok(size_hint::and(
+            <u8 as Arbitrary>::try_size_hint(depth)?,
+            size_hint::or((0, Some(0)), T::try_size_hint(depth)?),
+        ))
+    }
+}
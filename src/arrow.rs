@@ -0,0 +1,118 @@
+//! Arrow arrays for sequences of [`Presence<T>`] values.
+//!
+//! An Arrow array's validity bitmap can only tell null from non-null, so each column is
+//! exported as two arrays: the data array, whose validity bitmap marks `Null` and `Absent`
+//! slots, and a companion [`BooleanArray`] that is `false` for `Absent` slots. This is the
+//! layout of [`PresenceColumn`], and keeps `Absent` from collapsing into null.
+//!
+//! # Examples
+//!
+//! ```
+//! use arrow::array::Int32Array;
+//! use presence_rs::Presence;
+//! use presence_rs::arrow::{from_primitive, to_primitive};
+//!
+//! let ages = [Presence::Some(30), Presence::Null, Presence::Absent];
+//! let (values, defined) = to_primitive::<arrow::datatypes::Int32Type>(&ages);
+//!
+//! assert_eq!(values, Int32Array::from(vec![Some(30), None, None]));
+//! assert_eq!(defined.values().iter().collect::<Vec<_>>(), [true, true, false]);
+//! assert_eq!(from_primitive(&values, &defined).unwrap(), ages);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`PresenceColumn`]: crate::columnar::PresenceColumn
+
+use crate::columnar::PresenceColumn;
+use crate::presence::Presence;
+use arrow::array::{Array, ArrowPrimitiveType, BooleanArray, PrimitiveArray, StringArray};
+use arrow::buffer::NullBuffer;
+use arrow::error::ArrowError;
+
+/// Builds a primitive array and its `defined` array from presence values.
+pub fn to_primitive<T: ArrowPrimitiveType>(
+    presences: &[Presence<T::Native>],
+) -> (PrimitiveArray<T>, BooleanArray) {
+    let (values, validity, defined) = PresenceColumn::from(presences).into_parts();
+    let values = PrimitiveArray::new(values.into(), Some(NullBuffer::from(validity)));
+    (values, BooleanArray::from(defined))
+}
+
+/// Reads presence values back from a primitive array and its `defined` array.
+///
+/// # Errors
+///
+/// Returns an error if the arrays have different lengths or `defined` has null slots.
+pub fn from_primitive<T: ArrowPrimitiveType>(
+    values: &PrimitiveArray<T>,
+    defined: &BooleanArray,
+) -> Result<Vec<Presence<T::Native>>, ArrowError> {
+    let defined = defined_flags(values, defined)?;
+    let validity = (0..values.len())
+        .map(|index| values.is_valid(index))
+        .collect();
+    Ok(PresenceColumn::from_parts(values.values().to_vec(), validity, defined).into_presences())
+}
+
+/// Builds a string array and its `defined` array from presence values.
+///
+/// # Examples
+///
+/// ```
+/// use arrow::array::StringArray;
+/// use presence_rs::Presence;
+/// use presence_rs::arrow::{from_strings, to_strings};
+///
+/// let names = [Presence::Absent, Presence::Some("Ann".to_string())];
+/// let (values, defined) = to_strings(&names);
+/// assert_eq!(values, StringArray::from(vec![None, Some("Ann")]));
+/// assert_eq!(from_strings(&values, &defined).unwrap(), names);
+/// ```
+pub fn to_strings<S: AsRef<str>>(presences: &[Presence<S>]) -> (StringArray, BooleanArray) {
+    let values = presences
+        .iter()
+        .map(|presence| presence.as_ref().map(AsRef::as_ref).to_optional())
+        .collect();
+    let defined = presences
+        .iter()
+        .map(|presence| Some(presence.is_defined()))
+        .collect();
+    (values, defined)
+}
+
+/// Reads presence values back from a string array and its `defined` array.
+///
+/// # Errors
+///
+/// Returns an error if the arrays have different lengths or `defined` has null slots.
+pub fn from_strings(
+    values: &StringArray,
+    defined: &BooleanArray,
+) -> Result<Vec<Presence<String>>, ArrowError> {
+    let defined = defined_flags(values, defined)?;
+    Ok(values
+        .iter()
+        .zip(defined)
+        .map(|(value, defined)| match (defined, value) {
+            (false, _) => Presence::Absent,
+            (true, None) => Presence::Null,
+            (true, Some(value)) => Presence::Some(value.to_string()),
+        })
+        .collect())
+}
+
+fn defined_flags(values: &dyn Array, defined: &BooleanArray) -> Result<Vec<bool>, ArrowError> {
+    if values.len() != defined.len() {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "expected {} defined flags, got {}",
+            values.len(),
+            defined.len()
+        )));
+    }
+    if defined.null_count() > 0 {
+        return Err(ArrowError::InvalidArgumentError(
+            "defined flags must not be null".to_string(),
+        ));
+    }
+    Ok(defined.values().iter().collect())
+}
@@ -0,0 +1,179 @@
+//! async-graphql support for [`Presence<T>`].
+//!
+//! GraphQL input values have the same three states as `Presence`, so a `Presence<T>`
+//! can be used directly as an argument or input object field:
+//!
+//! - Argument not provided → `Absent`
+//! - Explicit `null` → `Null`
+//! - A value → `Some(value)`
+//!
+//! As an output type, `Presence<T>` resolves like `Option<T>`: both `Null` and
+//! `Absent` resolve to `null`, and the field is nullable in the schema.
+//!
+//! Code already using async-graphql's [`MaybeUndefined<T>`] can convert to and from
+//! `Presence<T>` losslessly with `From`/`Into`.
+//!
+//! # Examples
+//!
+//! ```
+//! use async_graphql::{InputObject, InputType, Value};
+//! use presence_rs::Presence;
+//!
+//! #[derive(InputObject)]
+//! struct UserPatch {
+//!     name: Presence<String>,
+//!     avatar: Presence<String>,
+//! }
+//!
+//! assert_eq!(Presence::<i32>::parse(None).unwrap(), Presence::Absent);
+//! assert_eq!(Presence::<i32>::parse(Some(Value::Null)).unwrap(), Presence::Null);
+//! assert_eq!(Presence::<i32>::parse(Some(Value::from(7))).unwrap(), Presence::Some(7));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`MaybeUndefined<T>`]: async_graphql::MaybeUndefined
+
+use crate::presence::Presence;
+use async_graphql::parser::types::Field;
+use async_graphql::{
+    ContextSelectionSet, InputType, InputValueError, InputValueResult, MaybeUndefined, OutputType,
+    Positioned, ServerResult, Value, registry,
+};
+use std::borrow::Cow;
+
+impl<T: InputType> InputType for Presence<T> {
+    type RawValueType = T::RawValueType;
+
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::type_name().to_string()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        T::type_name().to_string()
+    }
+
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        match value {
+            None => Ok(Presence::Absent),
+            Some(Value::Null) => Ok(Presence::Null),
+            Some(value) => Ok(Presence::Some(
+                T::parse(Some(value)).map_err(InputValueError::propagate)?,
+            )),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Presence::Some(value) => value.to_value(),
+            Presence::Null | Presence::Absent => Value::Null,
+        }
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        match self {
+            Presence::Some(value) => value.as_raw_value(),
+            Presence::Null | Presence::Absent => None,
+        }
+    }
+}
+
+impl<T: OutputType + Sync> OutputType for Presence<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::type_name().to_string()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        T::type_name().to_string()
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ContextSelectionSet<'_>,
+        field: &Positioned<Field>,
+    ) -> ServerResult<Value> {
+        match self {
+            Presence::Some(inner) => match OutputType::resolve(inner, ctx, field).await {
+                Ok(value) => Ok(value),
+                Err(err) => {
+                    ctx.add_error(err);
+                    Ok(Value::Null)
+                }
+            },
+            Presence::Null | Presence::Absent => Ok(Value::Null),
+        }
+    }
+}
+
+impl<T> From<MaybeUndefined<T>> for Presence<T> {
+    /// Converts a `MaybeUndefined<T>` into `Presence<T>`.
+    ///
+    /// - `Undefined` → `Absent`
+    /// - `Null` → `Null`
+    /// - `Value(v)` → `Some(v)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::MaybeUndefined;
+    /// use presence_rs::Presence;
+    ///
+    /// let p: Presence<i32> = MaybeUndefined::Value(42).into();
+    /// assert_eq!(p, Presence::Some(42));
+    ///
+    /// let p: Presence<i32> = MaybeUndefined::Null.into();
+    /// assert_eq!(p, Presence::Null);
+    ///
+    /// let p: Presence<i32> = MaybeUndefined::Undefined.into();
+    /// assert_eq!(p, Presence::Absent);
+    /// ```
+    #[inline]
+    fn from(value: MaybeUndefined<T>) -> Self {
+        match value {
+            MaybeUndefined::Undefined => Presence::Absent,
+            MaybeUndefined::Null => Presence::Null,
+            MaybeUndefined::Value(value) => Presence::Some(value),
+        }
+    }
+}
+
+impl<T> From<Presence<T>> for MaybeUndefined<T> {
+    /// Converts a `Presence<T>` into `MaybeUndefined<T>`.
+    ///
+    /// - `Absent` → `Undefined`
+    /// - `Null` → `Null`
+    /// - `Some(v)` → `Value(v)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_graphql::MaybeUndefined;
+    /// use presence_rs::Presence;
+    ///
+    /// let m: MaybeUndefined<i32> = Presence::Some(42).into();
+    /// assert_eq!(m, MaybeUndefined::Value(42));
+    ///
+    /// let m: MaybeUndefined<i32> = Presence::Null.into();
+    /// assert_eq!(m, MaybeUndefined::Null);
+    ///
+    /// let m: MaybeUndefined<i32> = Presence::Absent.into();
+    /// assert_eq!(m, MaybeUndefined::Undefined);
+    /// ```
+    #[inline]
+    fn from(presence: Presence<T>) -> Self {
+        match presence {
+            Presence::Absent => MaybeUndefined::Undefined,
+            Presence::Null => MaybeUndefined::Null,
+            Presence::Some(value) => MaybeUndefined::Value(value),
+        }
+    }
+}
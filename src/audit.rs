@@ -0,0 +1,259 @@
+//! Structured audit records for patches.
+//!
+//! [`audit_entries`] walks a [`VisitPresence`] patch and returns one [`AuditEntry`] per
+//! defined field, with its path, the [`Operation`] and the new value. Unchanged (`Absent`)
+//! fields are left out and `#[presence(redact)]` fields keep their value hidden.
+//!
+//! Values are taken from the patch serialized to JSON, so field names must match their
+//! serialized names. When the prior state is known, for example the inverse patch
+//! returned by `apply_patch!`, [`audit_entries_with_prior`] fills in the old values as
+//! well. [`AuditEntry::to_json`] and [`audit_json`] render the records for audit logs.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::audit::{AuditEntry, Operation, audit_entries};
+//! use presence_rs::reflect::VisitPresence;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! #[derive(Serialize, VisitPresence)]
+//! struct UserPatch {
+//!     name: Presence<String>,
+//!     avatar: Presence<String>,
+//!     email: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch {
+//!     name: Presence::Some("Bob".into()),
+//!     avatar: Presence::Null,
+//!     email: Presence::Absent,
+//! };
+//!
+//! assert_eq!(
+//!     audit_entries(&patch).unwrap(),
+//!     [
+//!         AuditEntry {
+//!             path: "name".into(),
+//!             operation: Operation::Set,
+//!             old: None,
+//!             new: Some(json!("Bob")),
+//!         },
+//!         AuditEntry { path: "avatar".into(), operation: Operation::Clear, old: None, new: None },
+//!     ]
+//! );
+//! # }
+//! ```
+//!
+//! [`VisitPresence`]: crate::reflect::VisitPresence
+
+use crate::presence::Presence;
+use crate::reflect::{PresenceVisitor, VisitPresence};
+use serde::Serialize;
+use serde::ser::Error as _;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::{self, Debug};
+
+/// What a patch does to a field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// The field is set to a value (`Some`).
+    Set,
+    /// The field is cleared (`Null`).
+    Clear,
+}
+
+impl Operation {
+    /// Returns the name of the operation: `"set"` or `"clear"`.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Operation::Set => "set",
+            Operation::Clear => "clear",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One changed field of a patch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// The path of the field, with field names separated by `/`.
+    pub path: String,
+    /// What the patch does to the field.
+    pub operation: Operation,
+    /// The prior value, if known and not empty.
+    pub old: Option<Value>,
+    /// The new value, for [`Operation::Set`]. A redacted value is the string
+    /// `"<redacted>"`.
+    pub new: Option<Value>,
+}
+
+/// Returns an entry for every defined field of `patch`, in visiting order.
+///
+/// # Errors
+///
+/// Returns an error if `patch` fails to serialize, or if a defined field is missing from
+/// the serialized patch, for example because it is renamed.
+pub fn audit_entries<P: VisitPresence + Serialize + ?Sized>(
+    patch: &P,
+) -> Result<Vec<AuditEntry>, serde_json::Error> {
+    let mut visitor = ValueVisitor::new(serde_json::to_value(patch)?);
+    patch.visit_presence(&mut visitor);
+    visitor.finish()
+}
+
+/// Returns an entry for every defined field of `patch`, with old values taken from the
+/// same fields of `prior`.
+///
+/// `prior` is typically the inverse patch returned by `apply_patch!(target, patch => ...)`.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`audit_entries`], for either patch.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use presence_rs::audit::{Operation, audit_entries_with_prior};
+/// use presence_rs::reflect::VisitPresence;
+/// use presence_rs::{Presence, apply_patch};
+/// use serde::Serialize;
+/// use serde_json::json;
+///
+/// struct Settings { volume: u8 }
+///
+/// #[derive(Clone, Default, Serialize, VisitPresence)]
+/// struct SettingsPatch { volume: Presence<u8> }
+///
+/// let mut settings = Settings { volume: 3 };
+/// let patch = SettingsPatch { volume: Presence::Some(7) };
+/// let undo = apply_patch!(settings, patch.clone() => SettingsPatch; volume);
+///
+/// let entries = audit_entries_with_prior(&patch, &undo).unwrap();
+/// assert_eq!(entries[0].operation, Operation::Set);
+/// assert_eq!(entries[0].old, Some(json!(3)));
+/// assert_eq!(entries[0].new, Some(json!(7)));
+/// # }
+/// ```
+pub fn audit_entries_with_prior<P: VisitPresence + Serialize + ?Sized>(
+    patch: &P,
+    prior: &P,
+) -> Result<Vec<AuditEntry>, serde_json::Error> {
+    let mut old_values: HashMap<String, Value> = audit_entries(prior)?
+        .into_iter()
+        .filter_map(|entry| Some((entry.path, entry.new?)))
+        .collect();
+    let mut entries = audit_entries(patch)?;
+    for entry in &mut entries {
+        entry.old = old_values.remove(&entry.path);
+    }
+    Ok(entries)
+}
+
+impl AuditEntry {
+    /// Renders the entry as a JSON object with `path`, `op`, `old` and `new` keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::audit::{AuditEntry, Operation};
+    /// use serde_json::json;
+    ///
+    /// let entry = AuditEntry {
+    ///     path: "avatar".into(),
+    ///     operation: Operation::Clear,
+    ///     old: Some(json!("a.png")),
+    ///     new: None,
+    /// };
+    /// assert_eq!(
+    ///     entry.to_json(),
+    ///     json!({ "path": "avatar", "op": "clear", "old": "a.png", "new": null })
+    /// );
+    /// ```
+    pub fn to_json(&self) -> Value {
+        serde_json::json!({
+            "path": self.path,
+            "op": self.operation.as_str(),
+            "old": self.old,
+            "new": self.new,
+        })
+    }
+}
+
+/// Renders entries as a JSON array of [`AuditEntry::to_json`] objects.
+pub fn audit_json(entries: &[AuditEntry]) -> Value {
+    entries.iter().map(AuditEntry::to_json).collect()
+}
+
+/// Collects an entry per visited field, looking its value up in the serialized patch.
+struct ValueVisitor {
+    patch: Value,
+    entries: Vec<AuditEntry>,
+    error: Option<serde_json::Error>,
+}
+
+impl ValueVisitor {
+    fn new(patch: Value) -> Self {
+        Self {
+            patch,
+            entries: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn push(&mut self, path: &[&'static str], operation: Operation, new: Option<Value>) {
+        self.entries.push(AuditEntry {
+            path: path.join("/"),
+            operation,
+            old: None,
+            new,
+        });
+    }
+
+    fn finish(self) -> Result<Vec<AuditEntry>, serde_json::Error> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.entries),
+        }
+    }
+}
+
+impl PresenceVisitor for ValueVisitor {
+    fn visit(&mut self, path: &[&'static str], value: Presence<&dyn Debug>) {
+        match value {
+            Presence::Some(_) => {
+                let found = path
+                    .iter()
+                    .try_fold(&self.patch, |value, name| value.get(name));
+                match found {
+                    Some(value) => {
+                        let value = value.clone();
+                        self.push(path, Operation::Set, Some(value));
+                    }
+                    None if self.error.is_none() => {
+                        self.error = Some(serde_json::Error::custom(format_args!(
+                            "field `{}` is not in the serialized patch",
+                            path.join("/")
+                        )));
+                    }
+                    None => {}
+                }
+            }
+            Presence::Null => self.push(path, Operation::Clear, None),
+            Presence::Absent => {}
+        }
+    }
+
+    fn visit_redacted(&mut self, path: &[&'static str]) {
+        self.push(path, Operation::Set, Some(Value::from("<redacted>")));
+    }
+}
@@ -0,0 +1,180 @@
+//! An axum extractor for `PATCH` bodies with [`Presence<T>`] fields.
+//!
+//! [`PresenceJson<T>`] works like axum's `Json<T>`, but it also checks the deserialized
+//! patch against the field rules of its [`ValidatePresence`] implementation:
+//!
+//! - A `#[presence(required)]` field must not be missing (`Absent`)
+//! - A `#[presence(non_null)]` field must not be `null`
+//!
+//! Violations and deserialization errors are rejected with `422 Unprocessable Entity` and
+//! a JSON body naming each offending field:
+//!
+//! ```json
+//! { "errors": [{ "field": "name", "message": "must not be null" }] }
+//! ```
+//!
+//! Violations name the Rust field, as reported by [`Violation`]. Bodies that are not JSON
+//! at all (wrong content type, syntax errors) are rejected like `Json<T>` does.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use axum::Router;
+//! use axum::routing::patch;
+//! use presence_rs::Presence;
+//! use presence_rs::axum::PresenceJson;
+//! use presence_rs::validate::ValidatePresence;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, ValidatePresence)]
+//! struct UserPatch {
+//!     // Users can remove their avatar, but not their name.
+//!     #[serde(default)]
+//!     #[presence(non_null)]
+//!     name: Presence<String>,
+//!     #[serde(default)]
+//!     avatar: Presence<String>,
+//! }
+//!
+//! async fn update_user(PresenceJson(patch): PresenceJson<UserPatch>) {
+//!     // `patch.name` is never `Null` here.
+//!     # let _ = patch;
+//! }
+//!
+//! let app: Router = Router::new().route("/users/{id}", patch(update_user));
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`ValidatePresence`]: crate::validate::ValidatePresence
+//! [`Violation`]: crate::validate::Violation
+
+use crate::validate::{ValidatePresence, Violation, ViolationKind};
+use axum::Json;
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fmt;
+
+/// Extracts a JSON patch body and validates it with the [`ValidatePresence`] rules of `T`.
+///
+/// See the [module documentation](self) for the rejection format.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PresenceJson<T>(pub T);
+
+impl<T> std::ops::Deref for PresenceJson<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for PresenceJson<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequest<S> for PresenceJson<T>
+where
+    T: DeserializeOwned + ValidatePresence,
+    S: Send + Sync,
+{
+    type Rejection = PresenceJsonRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(body) = Json::<Value>::from_request(req, state).await?;
+        let value: T = serde_path_to_error::deserialize(body).map_err(|err| {
+            let path = err.path().to_string();
+            let field = (path != ".").then_some(path);
+            let message = err.into_inner().to_string();
+            PresenceJsonRejection::Invalid(vec![FieldError { field, message }])
+        })?;
+        value.validate_presence().map_err(|violations| {
+            PresenceJsonRejection::Invalid(violations.into_iter().map(FieldError::from).collect())
+        })?;
+        Ok(PresenceJson(value))
+    }
+}
+
+/// A field that broke its validation rules or could not be deserialized.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// The path of the offending field, or `None` if the error concerns the whole body.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl From<Violation> for FieldError {
+    fn from(violation: Violation) -> Self {
+        let message = match violation.kind {
+            ViolationKind::Absent => "is required",
+            ViolationKind::Null => "must not be null",
+        };
+        Self {
+            field: Some(violation.field.to_string()),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Rejection used for [`PresenceJson`].
+#[derive(Debug)]
+pub enum PresenceJsonRejection {
+    /// The body is not a JSON document, as rejected by axum's `Json`.
+    Json(JsonRejection),
+    /// The body does not match `T` or breaks its rules (`422 Unprocessable Entity`).
+    Invalid(Vec<FieldError>),
+}
+
+impl From<JsonRejection> for PresenceJsonRejection {
+    fn from(rejection: JsonRejection) -> Self {
+        PresenceJsonRejection::Json(rejection)
+    }
+}
+
+impl fmt::Display for PresenceJsonRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PresenceJsonRejection::Json(rejection) => rejection.fmt(f),
+            PresenceJsonRejection::Invalid(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("; ")?;
+                    }
+                    match &error.field {
+                        Some(field) => write!(f, "{}: {}", field, error.message)?,
+                        None => f.write_str(&error.message)?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for PresenceJsonRejection {}
+
+impl IntoResponse for PresenceJsonRejection {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct Body {
+            errors: Vec<FieldError>,
+        }
+
+        match self {
+            PresenceJsonRejection::Json(rejection) => rejection.into_response(),
+            PresenceJsonRejection::Invalid(errors) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, Json(Body { errors })).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! MongoDB update documents from patch structs of [`Presence<T>`] fields.
+//!
+//! A patch struct is serialized to BSON and every field is mapped to an update operator:
+//!
+//! - `Some(value)` → `$set: { field: value }`
+//! - `Null` → `$set: { field: null }`, or `$unset: { field: "" }` with [`NullHandling::Unset`]
+//! - `Absent` → omitted
+//!
+//! Presence fields must be annotated with `#[serde(skip_serializing_if = "Presence::is_absent")]`,
+//! otherwise `Absent` serializes as `null` and becomes a `Null` update.
+//!
+//! # Examples
+//!
+//! ```
+//! use bson::doc;
+//! use presence_rs::Presence;
+//! use presence_rs::bson::{NullHandling, update_document, update_document_with};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct UserPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     avatar: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     age: Presence<i32>,
+//! }
+//!
+//! let patch = UserPatch {
+//!     name: Presence::Some("Bob".into()),
+//!     avatar: Presence::Null,
+//!     age: Presence::Absent,
+//! };
+//!
+//! assert_eq!(
+//!     update_document(&patch).unwrap(),
+//!     doc! { "$set": { "name": "Bob", "avatar": null } },
+//! );
+//! assert_eq!(
+//!     update_document_with(&patch, NullHandling::Unset).unwrap(),
+//!     doc! { "$set": { "name": "Bob" }, "$unset": { "avatar": "" } },
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use bson::{Bson, Document};
+use serde::Serialize;
+
+/// How [`Null`] fields are written to the update document.
+///
+/// [`Null`]: crate::Presence::Null
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NullHandling {
+    /// Store an explicit `null` with `$set`.
+    #[default]
+    SetNull,
+    /// Remove the field from the stored document with `$unset`.
+    Unset,
+}
+
+/// Builds a MongoDB update document from a patch struct, storing nulls with `$set`.
+///
+/// See [`update_document_with`] for the mapping rules.
+///
+/// # Errors
+///
+/// Returns an error if `patch` does not serialize to a BSON document.
+pub fn update_document<T: Serialize + ?Sized>(patch: &T) -> bson::error::Result<Document> {
+    update_document_with(patch, NullHandling::default())
+}
+
+/// Builds a MongoDB update document from a patch struct.
+///
+/// Defined fields go into `$set`, except `null` fields when `null` is
+/// [`NullHandling::Unset`], which go into `$unset`. Operators with no fields are
+/// left out, so a patch with only `Absent` fields yields an empty document; MongoDB
+/// rejects empty updates, so check [`Document::is_empty`] before sending.
+///
+/// # Errors
+///
+/// Returns an error if `patch` does not serialize to a BSON document.
+///
+/// # Examples
+///
+/// ```
+/// use bson::doc;
+/// use presence_rs::Presence;
+/// use presence_rs::bson::{NullHandling, update_document_with};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Patch {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     nickname: Presence<String>,
+/// }
+///
+/// let patch = Patch { nickname: Presence::Absent };
+/// assert!(update_document_with(&patch, NullHandling::Unset).unwrap().is_empty());
+///
+/// let patch = Patch { nickname: Presence::Null };
+/// assert_eq!(
+///     update_document_with(&patch, NullHandling::Unset).unwrap(),
+///     doc! { "$unset": { "nickname": "" } },
+/// );
+/// ```
+pub fn update_document_with<T: Serialize + ?Sized>(
+    patch: &T,
+    null: NullHandling,
+) -> bson::error::Result<Document> {
+    let fields = bson::serialize_to_document(patch)?;
+
+    let mut set = Document::new();
+    let mut unset = Document::new();
+    for (key, value) in fields {
+        match (value, null) {
+            (Bson::Null, NullHandling::Unset) => {
+                unset.insert(key, "");
+            }
+            (value, _) => {
+                set.insert(key, value);
+            }
+        }
+    }
+
+    let mut update = Document::new();
+    if !set.is_empty() {
+        update.insert("$set", set);
+    }
+    if !unset.is_empty() {
+        update.insert("$unset", unset);
+    }
+    Ok(update)
+}
@@ -0,0 +1,121 @@
+//! Builder setters for [`Presence<T>`] fields.
+//!
+//! Builder crates generate one setter per field, taking the field type or whatever
+//! converts into it. For a `Presence<T>` field that means wrapping every argument by hand.
+//! [`BuilderValue`] is the conversion a setter needs instead:
+//!
+//! - a `T` → `Some(value)`
+//! - an `Option<T>` → `Some(value)`, or `Null` for `None`
+//! - no call at all → `Absent`, the field's default
+//!
+//! # typed-builder
+//!
+//! Mark the field `default` and turn its setter into a `BuilderValue` conversion:
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::builder::BuilderValue;
+//! use typed_builder::TypedBuilder;
+//!
+//! #[derive(TypedBuilder)]
+//! struct UserPatch {
+//!     #[builder(default, setter(transform = |value: impl BuilderValue<String>| value.into_field()))]
+//!     name: Presence<String>,
+//!     #[builder(default, setter(transform = |value: impl BuilderValue<u32>| value.into_field()))]
+//!     age: Presence<u32>,
+//!     #[builder(default, setter(transform = |value: impl BuilderValue<String>| value.into_field()))]
+//!     bio: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch::builder().name("Ann".to_string()).age(None).build();
+//! assert_eq!(patch.name, Presence::Some("Ann".to_string()));
+//! assert_eq!(patch.age, Presence::Null);
+//! assert_eq!(patch.bio, Presence::Absent);
+//! ```
+//!
+//! typed-builder turns the closure parameter into the setter's parameter, so `name` accepts
+//! both `String` and `Option<String>`.
+//!
+//! # derive_builder
+//!
+//! derive_builder keeps an `Option<Presence<T>>` per field and needs `#[builder(default)]`
+//! for a field to start `Absent`. Its `setter(into)` takes a `T`, through
+//! `From<T> for Presence<T>`. With the `derive` feature, `#[derive(PresenceBuilder)]` adds
+//! to the builder an `x_null()` setter for each field and a `check_presence()` validator
+//! enforcing `#[presence(required)]` and `#[presence(non_null)]`. The `x_null()` setters
+//! follow the builder's `pattern`:
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use derive_builder::Builder;
+//! use presence_rs::{Presence, PresenceBuilder};
+//!
+//! #[derive(Builder, Default, PresenceBuilder)]
+//! #[builder(default, setter(into), build_fn(validate = "Self::check_presence"))]
+//! struct UserPatch {
+//!     #[presence(required)]
+//!     name: Presence<String>,
+//!     bio: Presence<String>,
+//! }
+//!
+//! let patch = UserPatchBuilder::default().name("Ann".to_string()).bio_null().build().unwrap();
+//! assert_eq!(patch.bio, Presence::Null);
+//!
+//! assert!(UserPatchBuilder::default().bio("Hi".to_string()).build().is_err());
+//! # }
+//! ```
+//!
+//! # Examples
+//!
+//! A hand-written setter works the same way:
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::builder::BuilderValue;
+//!
+//! #[derive(Default)]
+//! struct UserPatchBuilder {
+//!     age: Presence<u32>,
+//! }
+//!
+//! impl UserPatchBuilder {
+//!     fn age(mut self, value: impl BuilderValue<u32>) -> Self {
+//!         self.age = value.into_field();
+//!         self
+//!     }
+//! }
+//!
+//! assert_eq!(UserPatchBuilder::default().age(30).age, Presence::Some(30));
+//! assert_eq!(UserPatchBuilder::default().age(None).age, Presence::Null);
+//! assert_eq!(UserPatchBuilder::default().age, Presence::Absent);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+
+/// A setter argument for a `Presence<T>` field.
+///
+/// Unlike [`Presence::from_optional`], an `Option<T>` argument reads `None` as `Null`: a
+/// setter that is called always defines its field.
+pub trait BuilderValue<T> {
+    /// Converts the argument into the field value.
+    fn into_field(self) -> Presence<T>;
+}
+
+impl<T> BuilderValue<T> for T {
+    #[inline]
+    fn into_field(self) -> Presence<T> {
+        Presence::Some(self)
+    }
+}
+
+impl<T> BuilderValue<T> for Option<T> {
+    #[inline]
+    fn into_field(self) -> Presence<T> {
+        match self {
+            Some(value) => Presence::Some(value),
+            None => Presence::Null,
+        }
+    }
+}
@@ -0,0 +1,302 @@
+//! Diffing any serializable value into a presence patch.
+//!
+//! [`ChangeSet::diff`] serializes two values to [`serde_json::Value`] and compares them,
+//! so it works for third-party types that cannot carry a derive. The result is both:
+//!
+//! - a patch: `Absent` if nothing changed, otherwise a JSON Merge Patch with the changed
+//!   keys set and the removed or nulled ones as `null`
+//! - a list of [`Change`]s, one per changed leaf, with its path and old and new states
+//!
+//! Objects are compared key by key; arrays and scalars are replaced as a whole, as in
+//! [`json::merge`]. A key that becomes `null` and a key that disappears are both a
+//! [`Clear`](Operation::Clear), since a merge patch cannot tell them apart.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::audit::Operation;
+//! use presence_rs::changeset::ChangeSet;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! #[derive(Serialize)]
+//! struct User {
+//!     name: String,
+//!     avatar: Option<String>,
+//!     langs: Vec<String>,
+//! }
+//!
+//! let old = User { name: "Ann".into(), avatar: Some("a.png".into()), langs: vec!["en".into()] };
+//! let new = User { name: "Ann".into(), avatar: None, langs: vec!["en".into(), "uk".into()] };
+//!
+//! let changes = ChangeSet::diff(&old, &new).unwrap();
+//! assert_eq!(
+//!     changes.patch(),
+//!     &Presence::Some(json!({ "avatar": null, "langs": ["en", "uk"] }))
+//! );
+//! assert_eq!(changes.changes()[0].path, "avatar");
+//! assert_eq!(changes.changes()[0].operation, Operation::Clear);
+//! assert_eq!(changes.changes()[0].old, Presence::Some(json!("a.png")));
+//! ```
+//!
+//! [`json::merge`]: crate::json::merge
+
+use crate::audit::Operation;
+use crate::presence::Presence;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// One changed leaf of a [`ChangeSet`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// The [JSON Pointer] of the value without the leading `/`, such as `address/city`,
+    /// or an empty string when the whole value is replaced.
+    ///
+    /// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+    pub path: String,
+    /// Whether the value is set or cleared.
+    pub operation: Operation,
+    /// The value before the change, `Absent` if the key did not exist and `Null` if it was
+    /// `null`.
+    pub old: Presence<Value>,
+    /// The value after the change, `Absent` if the key was removed and `Null` if it became
+    /// `null`.
+    pub new: Presence<Value>,
+}
+
+/// The difference between two values of type `T`, computed through serde.
+///
+/// `T` only ties the change set to the type it was computed from, so that
+/// [`apply`](ChangeSet::apply) can deserialize the patched value.
+pub struct ChangeSet<T: ?Sized> {
+    patch: Presence<Value>,
+    changes: Vec<Change>,
+    marker: PhantomData<fn(&T)>,
+}
+
+impl<T: Serialize + ?Sized> ChangeSet<T> {
+    /// Computes the changes that turn `old` into `new`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either value fails to serialize to JSON.
+    pub fn diff(old: &T, new: &T) -> Result<Self, serde_json::Error> {
+        let old = serde_json::to_value(old)?;
+        let new = serde_json::to_value(new)?;
+        Ok(Self::from_values(&old, &new))
+    }
+}
+
+impl<T: ?Sized> ChangeSet<T> {
+    /// Computes the changes that turn the JSON value `old` into `new`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::changeset::ChangeSet;
+    /// use serde_json::{Value, json};
+    ///
+    /// let old = json!({ "prefs": { "theme": "dark", "lang": "en" } });
+    /// let new = json!({ "prefs": { "theme": "dark", "lang": "uk" } });
+    ///
+    /// let changes = ChangeSet::<Value>::from_values(&old, &new);
+    /// assert_eq!(changes.patch(), &Presence::Some(json!({ "prefs": { "lang": "uk" } })));
+    /// assert_eq!(changes.paths().collect::<Vec<_>>(), ["prefs/lang"]);
+    ///
+    /// assert!(ChangeSet::<Value>::from_values(&old, &old).is_empty());
+    /// ```
+    pub fn from_values(old: &Value, new: &Value) -> Self {
+        let mut changes = Vec::new();
+        let patch = match (old, new) {
+            _ if old == new => Presence::Absent,
+            (Value::Object(old), Value::Object(new)) => {
+                let patch = diff_objects(old, new, &mut String::new(), &mut changes);
+                if patch.is_empty() {
+                    Presence::Absent
+                } else {
+                    Presence::Some(Value::Object(patch))
+                }
+            }
+            _ => {
+                changes.push(Change {
+                    path: String::new(),
+                    operation: operation(new),
+                    old: presence(Some(old)),
+                    new: presence(Some(new)),
+                });
+                if new.is_null() {
+                    Presence::Null
+                } else {
+                    Presence::Some(new.clone())
+                }
+            }
+        };
+        Self {
+            patch,
+            changes,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the patch: `Absent` if nothing changed, `Null` if the new value is `null`,
+    /// and otherwise a JSON Merge Patch or the replacing value.
+    ///
+    /// The patch can be applied with [`Presence::merge_into`].
+    pub fn patch(&self) -> &Presence<Value> {
+        &self.patch
+    }
+
+    /// Consumes the change set, returning its patch.
+    pub fn into_patch(self) -> Presence<Value> {
+        self.patch
+    }
+
+    /// Returns the changed leaves in the order of the new value's keys, followed by the
+    /// removed keys.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns an iterator over the paths of the changed leaves.
+    pub fn paths(&self) -> impl Iterator<Item = &str> {
+        self.changes.iter().map(|change| change.path.as_str())
+    }
+
+    /// Returns `true` if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> ChangeSet<T> {
+    /// Applies the patch to `value`, returning the patched copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` fails to serialize or the patched JSON does not
+    /// deserialize back into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::changeset::ChangeSet;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let old = BTreeMap::from([('a', 1), ('b', 2)]);
+    /// let new = BTreeMap::from([('a', 1), ('c', 3)]);
+    ///
+    /// let changes = ChangeSet::diff(&old, &new).unwrap();
+    /// let other = BTreeMap::from([('a', 0), ('b', 2)]);
+    /// assert_eq!(changes.apply(&other).unwrap(), BTreeMap::from([('a', 0), ('c', 3)]));
+    /// ```
+    pub fn apply(&self, value: &T) -> Result<T, serde_json::Error> {
+        let mut value = serde_json::to_value(value)?;
+        self.patch.merge_into(&mut value);
+        serde_json::from_value(value)
+    }
+}
+
+impl<T: ?Sized> Clone for ChangeSet<T> {
+    fn clone(&self) -> Self {
+        Self {
+            patch: self.patch.clone(),
+            changes: self.changes.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for ChangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChangeSet")
+            .field("patch", &self.patch)
+            .field("changes", &self.changes)
+            .finish()
+    }
+}
+
+impl<T: ?Sized> PartialEq for ChangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.patch == other.patch && self.changes == other.changes
+    }
+}
+
+fn operation(value: &Value) -> Operation {
+    if value.is_null() {
+        Operation::Clear
+    } else {
+        Operation::Set
+    }
+}
+
+fn presence(value: Option<&Value>) -> Presence<Value> {
+    match value {
+        Some(Value::Null) => Presence::Null,
+        Some(value) => Presence::Some(value.clone()),
+        None => Presence::Absent,
+    }
+}
+
+fn diff_objects(
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    path: &mut String,
+    changes: &mut Vec<Change>,
+) -> Map<String, Value> {
+    let mut patch = Map::new();
+    for (key, new_value) in new {
+        let old_value = old.get(key);
+        if old_value == Some(new_value) {
+            continue;
+        }
+        let len = path.len();
+        push_segment(path, key);
+        match (old_value, new_value) {
+            (Some(Value::Object(old)), Value::Object(new)) => {
+                let nested = diff_objects(old, new, path, changes);
+                if !nested.is_empty() {
+                    patch.insert(key.clone(), Value::Object(nested));
+                }
+            }
+            // Clearing a key that was not there changes nothing.
+            (None | Some(Value::Null), Value::Null) => {}
+            _ => {
+                changes.push(Change {
+                    path: path.clone(),
+                    operation: operation(new_value),
+                    old: presence(old_value),
+                    new: presence(Some(new_value)),
+                });
+                patch.insert(key.clone(), new_value.clone());
+            }
+        }
+        path.truncate(len);
+    }
+    for (key, old_value) in old {
+        if new.contains_key(key) || old_value.is_null() {
+            continue;
+        }
+        let len = path.len();
+        push_segment(path, key);
+        changes.push(Change {
+            path: path.clone(),
+            operation: Operation::Clear,
+            old: presence(Some(old_value)),
+            new: Presence::Absent,
+        });
+        patch.insert(key.clone(), Value::Null);
+        path.truncate(len);
+    }
+    patch
+}
+
+fn push_segment(path: &mut String, key: &str) {
+    if !path.is_empty() {
+        path.push('/');
+    }
+    path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+}
@@ -0,0 +1,163 @@
+//! Tri-state command-line options with clap.
+//!
+//! A CLI that edits remote resources needs to say "clear this field" as well as "set it"
+//! and "leave it alone". With [`PresenceValueParser`], an option maps to all three states:
+//!
+//! - Option omitted → `Absent`
+//! - `--field null` (the null token is configurable) → `Null`
+//! - `--field value` → `Some(value)`
+//!
+//! clap's derive requires a default for non-`Option` fields, so declare the field as
+//! `Option<Presence<T>>` and call `unwrap_or_default()` (an omitted option is `None`,
+//! which becomes `Absent`). For a separate `--no-field` flag, combine both arguments with
+//! [`with_clear_flag`]. Builder users can read the option with [`get_presence`].
+//!
+//! # Examples
+//!
+//! ```
+//! use clap::Parser;
+//! use presence_rs::Presence;
+//! use presence_rs::clap::{presence_parser, with_clear_flag};
+//!
+//! #[derive(Parser)]
+//! struct UpdateUser {
+//!     #[arg(long, value_parser = presence_parser::<String>())]
+//!     name: Option<Presence<String>>,
+//!     #[arg(long, value_parser = presence_parser::<u32>())]
+//!     age: Option<Presence<u32>>,
+//!     #[arg(long, value_parser = presence_parser::<String>())]
+//!     avatar: Option<Presence<String>>,
+//!     /// Remove the avatar
+//!     #[arg(long, conflicts_with = "avatar")]
+//!     no_avatar: bool,
+//! }
+//!
+//! let args = UpdateUser::parse_from(["update-user", "--age", "null", "--no-avatar"]);
+//!
+//! assert_eq!(args.name.unwrap_or_default(), Presence::Absent);
+//! assert_eq!(args.age.unwrap_or_default(), Presence::Null);
+//! assert_eq!(with_clear_flag(args.avatar, args.no_avatar), Presence::Null);
+//! ```
+
+use crate::presence::Presence;
+use clap::builder::{PossibleValue, TypedValueParser};
+use clap::{ArgMatches, Command};
+use std::error::Error;
+use std::ffi::OsStr;
+use std::str::FromStr;
+
+/// Parses `null` as `Presence::Null` and anything else with an inner parser as
+/// `Presence::Some`.
+#[derive(Clone, Debug)]
+pub struct PresenceValueParser<P> {
+    inner: P,
+    null_token: &'static str,
+}
+
+impl<P: TypedValueParser> PresenceValueParser<P> {
+    /// Wraps a typed value parser, using `null` as the null token.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use clap::{Arg, Command, value_parser};
+    /// use presence_rs::Presence;
+    /// use presence_rs::clap::{PresenceValueParser, get_presence};
+    ///
+    /// let cmd = Command::new("app").arg(
+    ///     Arg::new("port")
+    ///         .long("port")
+    ///         .value_parser(PresenceValueParser::new(value_parser!(u16)).null_token("none")),
+    /// );
+    ///
+    /// let matches = cmd.clone().get_matches_from(["app", "--port", "none"]);
+    /// assert_eq!(get_presence::<u16>(&matches, "port"), Presence::Null);
+    ///
+    /// let matches = cmd.get_matches_from(["app"]);
+    /// assert_eq!(get_presence::<u16>(&matches, "port"), Presence::Absent);
+    /// ```
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            null_token: "null",
+        }
+    }
+
+    /// Sets the value that is parsed as `Null`.
+    pub fn null_token(mut self, token: &'static str) -> Self {
+        self.null_token = token;
+        self
+    }
+}
+
+impl<P> TypedValueParser for PresenceValueParser<P>
+where
+    P: TypedValueParser,
+{
+    type Value = Presence<P::Value>;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, clap::Error> {
+        if value == self.null_token {
+            return Ok(Presence::Null);
+        }
+        self.inner.parse_ref(cmd, arg, value).map(Presence::Some)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        let values = self.inner.possible_values()?;
+        Some(Box::new(
+            values.chain(std::iter::once(PossibleValue::new(self.null_token))),
+        ))
+    }
+}
+
+/// Returns a [`PresenceValueParser`] for any [`FromStr`] type.
+pub fn presence_parser<T>() -> PresenceValueParser<impl TypedValueParser<Value = T>>
+where
+    T: FromStr + Clone + Send + Sync + 'static,
+    T::Err: Into<Box<dyn Error + Send + Sync + 'static>>,
+{
+    PresenceValueParser::new(|value: &str| value.parse::<T>())
+}
+
+/// Reads an option parsed with [`PresenceValueParser`], returning `Absent` if it was not
+/// given.
+///
+/// # Panics
+///
+/// Panics if `id` is not an argument or was parsed into another type, like
+/// [`ArgMatches::get_one`].
+pub fn get_presence<T>(matches: &ArgMatches, id: &str) -> Presence<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    matches
+        .get_one::<Presence<T>>(id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Combines an option with a `--no-<option>` flag: the flag makes the result `Null`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::clap::with_clear_flag;
+///
+/// assert_eq!(with_clear_flag::<i32>(None, false), Presence::Absent);
+/// assert_eq!(with_clear_flag::<i32>(None, true), Presence::Null);
+/// assert_eq!(with_clear_flag(Some(Presence::Some(3)), false), Presence::Some(3));
+/// ```
+pub fn with_clear_flag<T>(value: Option<Presence<T>>, clear: bool) -> Presence<T> {
+    if clear {
+        Presence::Null
+    } else {
+        value.unwrap_or_default()
+    }
+}
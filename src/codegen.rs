@@ -0,0 +1,502 @@
+//! Code generation for protobuf update requests with [`Presence<T>`] fields.
+//!
+//! prost maps proto3 `optional` fields to `Option<T>`, which cannot tell a field the
+//! client left out from one it cleared. [`PresencePatches`] hooks into a
+//! [`prost_build::Config`] and generates, next to prost's output, a companion patch
+//! struct for each selected message with a `Presence<T>` per field, together with the
+//! FieldMask and apply helpers that connect it to the prost message:
+//!
+//! - `field_mask()` returns a `prost_types::FieldMask` with the defined fields, using
+//!   their proto names
+//! - `apply_to()` writes the defined fields onto the prost message, like `apply_patch!`
+//!
+//! The patches of a package are written to `<package>.patch.rs`, to be included as a
+//! `patch` module inside the package's module. They refer to the prost messages through
+//! `super`, so packages must be mounted in modules following their package names, as with
+//! `tonic::include_proto!`. The generated code refers to `presence_rs`, `serde` and
+//! `prost_types`, which must be dependencies of the crate including it.
+//!
+//! [`PatchStruct`] renders a single patch struct from hand-listed fields, for messages
+//! that do not come from a descriptor set.
+//!
+//! # Examples
+//!
+//! In `build.rs`:
+//!
+//! ```no_run
+//! use presence_rs::codegen::PresencePatches;
+//!
+//! let mut config = prost_build::Config::new();
+//! PresencePatches::new()
+//!     .message(".shop.User")
+//!     .compile_protos(&mut config, &["proto/shop.proto"], &["proto"])
+//!     .unwrap();
+//! ```
+//!
+//! and in the crate:
+//!
+//! ```ignore
+//! pub mod shop {
+//!     include!(concat!(env!("OUT_DIR"), "/shop.rs"));
+//!
+//!     pub mod patch {
+//!         include!(concat!(env!("OUT_DIR"), "/shop.patch.rs"));
+//!     }
+//! }
+//!
+//! let patch = shop::patch::UserPatch::default();
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+use prost_build::{Config, Module};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorSet};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+/// Builder for the source code of a patch struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatchStruct {
+    name: String,
+    message: Option<String>,
+    fields: Vec<PatchField>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PatchField {
+    ident: String,
+    path: String,
+    ty: String,
+}
+
+impl PatchStruct {
+    /// Starts a patch struct with the given type name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            message: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Sets the path of the prost message the patch updates, which enables the
+    /// `field_mask()` and `apply_to()` methods.
+    pub fn message(mut self, path: impl Into<String>) -> Self {
+        self.message = Some(path.into());
+        self
+    }
+
+    /// Adds a field of the message, with the Rust type of its value.
+    ///
+    /// `name` is the Rust identifier of the field. A raw identifier such as `r#type`
+    /// appears in the FieldMask as `type`. For an `optional` proto3 field the type is the
+    /// `T` of prost's `Option<T>`.
+    pub fn field(self, name: impl Into<String>, ty: impl Into<String>) -> Self {
+        let ident = name.into();
+        let path = ident.trim_start_matches("r#").to_string();
+        self.push_field(ident, path, ty.into())
+    }
+
+    fn push_field(mut self, ident: String, path: String, ty: String) -> Self {
+        self.fields.push(PatchField { ident, path, ty });
+        self
+    }
+
+    /// Renders the source code of the struct and its methods.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::codegen::PatchStruct;
+    ///
+    /// let code = PatchStruct::new("TagPatch").field("label", "String").render();
+    /// assert_eq!(
+    ///     code,
+    ///     r#"#[derive(Clone, Debug, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    /// pub struct TagPatch {
+    ///     #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    ///     pub label: ::presence_rs::Presence<String>,
+    /// }
+    /// "#
+    /// );
+    /// ```
+    pub fn render(&self) -> String {
+        let mut code = String::new();
+        code.push_str(
+            "#[derive(Clone, Debug, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]\n",
+        );
+        writeln!(code, "pub struct {} {{", self.name).unwrap();
+        for field in &self.fields {
+            code.push_str(
+                "    #[serde(default, skip_serializing_if = \"::presence_rs::Presence::is_absent\")]\n",
+            );
+            writeln!(
+                code,
+                "    pub {}: ::presence_rs::Presence<{}>,",
+                field.ident, field.ty
+            )
+            .unwrap();
+        }
+        code.push_str("}\n");
+
+        let Some(message) = &self.message else {
+            return code;
+        };
+
+        writeln!(code, "\nimpl {} {{", self.name).unwrap();
+        code.push_str("    /// Returns a `FieldMask` with the defined fields of the patch.\n");
+        code.push_str("    pub fn field_mask(&self) -> ::prost_types::FieldMask {\n");
+        code.push_str("        let mut paths = ::std::vec::Vec::new();\n");
+        for field in &self.fields {
+            writeln!(code, "        if self.{}.is_defined() {{", field.ident).unwrap();
+            writeln!(
+                code,
+                "            paths.push(\"{}\".to_string());",
+                field.path
+            )
+            .unwrap();
+            code.push_str("        }\n");
+        }
+        code.push_str("        ::prost_types::FieldMask { paths }\n");
+        code.push_str("    }\n\n");
+        code.push_str("    /// Writes the defined fields of the patch onto `message`.\n");
+        writeln!(
+            code,
+            "    pub fn apply_to(self, message: &mut {}) {{",
+            message
+        )
+        .unwrap();
+        for field in &self.fields {
+            writeln!(
+                code,
+                "        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.{0}, self.{0});",
+                field.ident
+            )
+            .unwrap();
+        }
+        code.push_str("    }\n");
+        code.push_str("}\n");
+        code
+    }
+}
+
+/// A [`prost_build::Config`] hook generating [`PatchStruct`]s for selected messages.
+///
+/// Each message `.pkg.Name` gets a `NamePatch` struct in the `patch` module of its
+/// package, and prost's `Name` gets a doc attribute pointing at it. Field types follow
+/// prost's defaults: `Vec<u8>` for `bytes`, `i32` for enums, `HashMap` for maps and
+/// `prost_types` for well-known types. Messages with `oneof` fields are rejected.
+///
+/// The patch structs derive serde, so the prost types of message fields need serde
+/// derives too, for example added with [`Config::type_attribute`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PresencePatches {
+    messages: Vec<String>,
+    out_dir: Option<PathBuf>,
+}
+
+impl PresencePatches {
+    /// Starts a hook without any messages.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a message by its fully qualified proto name, such as `.shop.User`.
+    pub fn message(mut self, name: impl Into<String>) -> Self {
+        self.messages.push(name.into());
+        self
+    }
+
+    /// Sets the directory the patch files are written to, which defaults to `OUT_DIR`.
+    ///
+    /// This should match the `out_dir` of the prost config.
+    pub fn out_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(path.into());
+        self
+    }
+
+    /// Compiles `protos` with `config` and writes the patch files next to its output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if protoc or prost fail, or as for [`compile_fds`].
+    ///
+    /// [`compile_fds`]: PresencePatches::compile_fds
+    pub fn compile_protos(
+        &self,
+        config: &mut Config,
+        protos: &[impl AsRef<Path>],
+        includes: &[impl AsRef<Path>],
+    ) -> Result<()> {
+        let fds = config.load_fds(protos, includes)?;
+        self.compile_fds(config, fds)
+    }
+
+    /// Compiles a loaded descriptor set with `config` and writes the patch files next to
+    /// its output.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if prost fails, if a patch cannot be generated as for
+    /// [`generate`], or if no output directory is set and `OUT_DIR` is not either.
+    ///
+    /// [`generate`]: PresencePatches::generate
+    pub fn compile_fds(&self, config: &mut Config, fds: FileDescriptorSet) -> Result<()> {
+        let files = self.generate(&fds)?;
+        let out_dir = match &self.out_dir {
+            Some(path) => path.clone(),
+            None => env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or_else(|| Error::other("OUT_DIR environment variable is not set"))?,
+        };
+
+        for message in &self.messages {
+            let patch = patch_name(message.rsplit('.').next().unwrap_or_default());
+            config.type_attribute(
+                message,
+                format!("#[doc = \" Updated by `patch::{}`.\"]", patch),
+            );
+        }
+        config.compile_fds(fds)?;
+
+        for (file_name, code) in files {
+            fs::write(out_dir.join(file_name), code)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the file names and source code of the patch modules, one per package with
+    /// selected messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`ErrorKind::InvalidInput`] if a selected message is not
+    /// in `fds`, or has a `oneof`, group or unresolvable message field.
+    pub fn generate(&self, fds: &FileDescriptorSet) -> Result<Vec<(String, String)>> {
+        let index = MessageIndex::new(fds);
+        let mut files = BTreeMap::<String, String>::new();
+        for name in &self.messages {
+            let entry = index.messages.get(name.as_str()).ok_or_else(|| {
+                invalid(format!("message `{}` is not in the descriptor set", name))
+            })?;
+            let code = files
+                .entry(entry.package.to_file_name_or("_"))
+                .or_insert_with(|| {
+                    "// This file is @generated by presence_rs::codegen.\n".to_string()
+                });
+            code.push('\n');
+            code.push_str(&index.patch_struct(name, entry)?.render());
+        }
+        Ok(files
+            .into_iter()
+            .map(|(file_name, code)| {
+                let stem = file_name.trim_end_matches(".rs");
+                (format!("{}.patch.rs", stem), code)
+            })
+            .collect())
+    }
+}
+
+struct MessageEntry<'a> {
+    package: Module,
+    /// The enclosing messages of nested messages, outermost first.
+    parents: Vec<String>,
+    descriptor: &'a DescriptorProto,
+}
+
+struct MessageIndex<'a> {
+    messages: HashMap<String, MessageEntry<'a>>,
+}
+
+impl<'a> MessageIndex<'a> {
+    fn new(fds: &'a FileDescriptorSet) -> Self {
+        let mut messages = HashMap::new();
+        for file in &fds.file {
+            let package = Module::from_protobuf_package_name(file.package());
+            let prefix = match file.package() {
+                "" => String::new(),
+                package => format!(".{}", package),
+            };
+            for descriptor in &file.message_type {
+                index_message(&mut messages, &package, &prefix, Vec::new(), descriptor);
+            }
+        }
+        Self { messages }
+    }
+
+    fn patch_struct(&self, name: &str, entry: &MessageEntry<'_>) -> Result<PatchStruct> {
+        let message = entry.descriptor;
+        let mut patch = PatchStruct::new(patch_name(message.name()))
+            .message(self.rust_path(&entry.package, entry));
+        for field in &message.field {
+            if field.oneof_index.is_some() && !field.proto3_optional() {
+                return Err(invalid(format!(
+                    "`{}.{}` is part of a oneof, which patches do not support",
+                    name,
+                    field.name()
+                )));
+            }
+            let ty = self.field_type(&entry.package, name, field)?;
+            patch = patch.push_field(
+                sanitize(field.name().to_snake_case()),
+                field.name().to_string(),
+                ty,
+            );
+        }
+        Ok(patch)
+    }
+
+    fn field_type(
+        &self,
+        from: &Module,
+        name: &str,
+        field: &FieldDescriptorProto,
+    ) -> Result<String> {
+        if field.label() == Label::Repeated && field.r#type() == Type::Message {
+            let entry = self.lookup(field.type_name())?;
+            if entry
+                .descriptor
+                .options
+                .as_ref()
+                .is_some_and(|o| o.map_entry())
+            {
+                let key = self.value_type(from, name, &entry.descriptor.field[0])?;
+                let value = self.value_type(from, name, &entry.descriptor.field[1])?;
+                return Ok(format!("::std::collections::HashMap<{}, {}>", key, value));
+            }
+        }
+        let ty = self.value_type(from, name, field)?;
+        if field.label() == Label::Repeated {
+            Ok(format!("::std::vec::Vec<{}>", ty))
+        } else {
+            Ok(ty)
+        }
+    }
+
+    fn value_type(
+        &self,
+        from: &Module,
+        name: &str,
+        field: &FieldDescriptorProto,
+    ) -> Result<String> {
+        let ty = match field.r#type() {
+            Type::Double => "f64",
+            Type::Float => "f32",
+            Type::Int64 | Type::Sint64 | Type::Sfixed64 => "i64",
+            Type::Uint64 | Type::Fixed64 => "u64",
+            Type::Int32 | Type::Sint32 | Type::Sfixed32 | Type::Enum => "i32",
+            Type::Uint32 | Type::Fixed32 => "u32",
+            Type::Bool => "bool",
+            Type::String => "::std::string::String",
+            Type::Bytes => "::std::vec::Vec<u8>",
+            Type::Message => return self.message_type(from, field.type_name()),
+            Type::Group => {
+                return Err(invalid(format!(
+                    "`{}.{}` is a group, which patches do not support",
+                    name,
+                    field.name()
+                )));
+            }
+        };
+        Ok(ty.to_string())
+    }
+
+    fn message_type(&self, from: &Module, type_name: &str) -> Result<String> {
+        if let Some(name) = type_name.strip_prefix(".google.protobuf.") {
+            let ty = match name {
+                "BoolValue" => "bool".to_string(),
+                "BytesValue" => "::std::vec::Vec<u8>".to_string(),
+                "DoubleValue" => "f64".to_string(),
+                "FloatValue" => "f32".to_string(),
+                "Int32Value" => "i32".to_string(),
+                "Int64Value" => "i64".to_string(),
+                "UInt32Value" => "u32".to_string(),
+                "UInt64Value" => "u64".to_string(),
+                "StringValue" => "::std::string::String".to_string(),
+                "Empty" => "()".to_string(),
+                name => format!("::prost_types::{}", name),
+            };
+            return Ok(ty);
+        }
+        let entry = self.lookup(type_name)?;
+        Ok(self.rust_path(from, entry))
+    }
+
+    /// Returns the path of a prost type from the patch module of the `from` package.
+    fn rust_path(&self, from: &Module, entry: &MessageEntry<'_>) -> String {
+        let mut path = String::from("super::");
+        if entry.package != *from {
+            for _ in 0..from.len() {
+                path.push_str("super::");
+            }
+            for part in entry.package.parts() {
+                write!(path, "{}::", part).unwrap();
+            }
+        }
+        for parent in &entry.parents {
+            write!(path, "{}::", sanitize(parent.to_snake_case())).unwrap();
+        }
+        path.push_str(&sanitize(entry.descriptor.name().to_upper_camel_case()));
+        path
+    }
+
+    fn lookup(&self, type_name: &str) -> Result<&MessageEntry<'a>> {
+        self.messages.get(type_name).ok_or_else(|| {
+            invalid(format!(
+                "message `{}` is not in the descriptor set",
+                type_name
+            ))
+        })
+    }
+}
+
+fn index_message<'a>(
+    messages: &mut HashMap<String, MessageEntry<'a>>,
+    package: &Module,
+    prefix: &str,
+    parents: Vec<String>,
+    descriptor: &'a DescriptorProto,
+) {
+    let name = format!("{}.{}", prefix, descriptor.name());
+    let mut nested_parents = parents.clone();
+    nested_parents.push(descriptor.name().to_string());
+    for nested in &descriptor.nested_type {
+        index_message(messages, package, &name, nested_parents.clone(), nested);
+    }
+    messages.insert(
+        name,
+        MessageEntry {
+            package: package.clone(),
+            parents,
+            descriptor,
+        },
+    );
+}
+
+fn patch_name(message: &str) -> String {
+    format!("{}Patch", message.to_upper_camel_case())
+}
+
+/// Turns a Rust keyword into an identifier the way prost does.
+fn sanitize(ident: String) -> String {
+    match ident.as_str() {
+        "as" | "break" | "const" | "continue" | "else" | "enum" | "false" | "fn" | "for" | "if"
+        | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut" | "pub" | "ref"
+        | "return" | "static" | "struct" | "trait" | "true" | "type" | "unsafe" | "use"
+        | "where" | "while" | "dyn" | "abstract" | "become" | "box" | "do" | "final" | "macro"
+        | "override" | "priv" | "typeof" | "unsized" | "virtual" | "yield" | "async" | "await"
+        | "try" | "gen" => format!("r#{}", ident),
+        "_" | "super" | "self" | "Self" | "extern" | "crate" => format!("{}_", ident),
+        _ if ident.starts_with(|c: char| c.is_numeric()) => format!("_{}", ident),
+        _ => ident,
+    }
+}
+
+fn invalid(message: String) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
@@ -0,0 +1,293 @@
+//! Columnar layout for sequences of [`Presence<T>`] values.
+//!
+//! Columnar formats such as Arrow store a column as a buffer of values plus a validity
+//! bitmap, which can only tell null from non-null. [`PresenceColumn`] keeps a second
+//! bitmap for definedness, so `Absent` survives the export instead of collapsing into
+//! null:
+//!
+//! | State | `validity` | `defined` | `values` slot |
+//! |-------|------------|-----------|---------------|
+//! | `Some(value)` | `true` | `true` | `value` |
+//! | `Null` | `false` | `true` | `T::default()` |
+//! | `Absent` | `false` | `false` | `T::default()` |
+//!
+//! The parts map directly onto Arrow arrays: `values` and `validity` build the data array
+//! and `defined` a companion boolean array. The `arrow` feature builds them in the
+//! `presence_rs::arrow` module.
+//!
+//! # Definition Levels
+//!
+//! Parquet encodes nullability with definition levels rather than bitmaps. The `parquet`
+//! feature stores presence columns that way in the `presence_rs::parquet` module.
+//!
+//! # Nullable Columns
+//!
+//! Dataframe libraries such as polars build a column from `Vec<Option<T>>`. Such a column
+//! can only hold `Absent` through a policy ([`PresenceColumn::to_options`]) or as a struct
+//! column with a `value` field and a boolean `defined` field
+//! ([`PresenceColumn::to_struct_fields`]). The `polars` feature builds both kinds of
+//! `Series` in the `presence_rs::polars` module.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::columnar::PresenceColumn;
+//!
+//! let ages = [Presence::Some(30), Presence::Null, Presence::Absent];
+//! let column = PresenceColumn::from(&ages[..]);
+//!
+//! assert_eq!(column.values(), [30, 0, 0]);
+//! assert_eq!(column.validity(), [true, false, false]);
+//! assert_eq!(column.defined(), [true, true, false]);
+//! assert_eq!(column.into_presences(), ages);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use std::fmt;
+
+/// A column of [`Presence<T>`] values split into a value buffer and two bitmaps.
+///
+/// See the [module documentation](self) for the layout.
+///
+/// [`Presence<T>`]: crate::Presence
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PresenceColumn<T> {
+    values: Vec<T>,
+    validity: Vec<bool>,
+    defined: Vec<bool>,
+}
+
+impl<T> PresenceColumn<T> {
+    /// Creates an empty column.
+    pub const fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            validity: Vec::new(),
+            defined: Vec::new(),
+        }
+    }
+
+    /// Creates a column from its parts, as read back from a columnar format.
+    ///
+    /// A slot that is not `defined` is `Absent` whatever its validity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parts have different lengths.
+    pub fn from_parts(values: Vec<T>, validity: Vec<bool>, defined: Vec<bool>) -> Self {
+        assert!(
+            values.len() == validity.len() && values.len() == defined.len(),
+            "column parts must have the same length"
+        );
+        Self {
+            values,
+            validity,
+            defined,
+        }
+    }
+
+    /// Returns the number of slots.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the column has no slots.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the value buffer, with `T::default()` in `Null` and `Absent` slots.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Returns the validity bitmap, `true` for `Some` slots.
+    pub fn validity(&self) -> &[bool] {
+        &self.validity
+    }
+
+    /// Returns the definedness bitmap, `false` for `Absent` slots.
+    pub fn defined(&self) -> &[bool] {
+        &self.defined
+    }
+
+    /// Returns the state of the slot at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Presence<&T>> {
+        let value = self.values.get(index)?;
+        Some(match (self.defined[index], self.validity[index]) {
+            (false, _) => Presence::Absent,
+            (true, false) => Presence::Null,
+            (true, true) => Presence::Some(value),
+        })
+    }
+
+    /// Appends a slot.
+    pub fn push(&mut self, value: Presence<T>)
+    where
+        T: Default,
+    {
+        self.validity.push(value.is_present());
+        self.defined.push(value.is_defined());
+        self.values.push(value.unwrap_or_default());
+    }
+
+    /// Iterates over the slots.
+    pub fn iter(&self) -> impl Iterator<Item = Presence<&T>> + '_ {
+        (0..self.len()).map(|index| self.get(index).unwrap())
+    }
+
+    /// Consumes the column, returning the value buffer, the validity bitmap and the
+    /// definedness bitmap.
+    pub fn into_parts(self) -> (Vec<T>, Vec<bool>, Vec<bool>) {
+        (self.values, self.validity, self.defined)
+    }
+
+    /// Returns the slots as nullable values, handling `Absent` slots by `policy`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first `Absent` row with [`AbsentPolicy::Reject`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::columnar::{AbsentPolicy, PresenceColumn};
+    ///
+    /// let column = PresenceColumn::from(vec![Presence::Some(1), Presence::Absent]);
+    /// assert_eq!(column.to_options(AbsentPolicy::Null), Ok(vec![Some(1), None]));
+    /// assert!(column.to_options(AbsentPolicy::Reject).is_err());
+    /// ```
+    pub fn to_options(&self, policy: AbsentPolicy) -> Result<Vec<Option<T>>, AbsentError>
+    where
+        T: Clone,
+    {
+        self.iter()
+            .enumerate()
+            .map(|(row, value)| match (value, policy) {
+                (Presence::Absent, AbsentPolicy::Reject) => Err(AbsentError { row }),
+                (value, _) => Ok(value.cloned().to_optional()),
+            })
+            .collect()
+    }
+
+    /// Returns the fields of a struct column: the nullable `value` and the `defined` flag.
+    pub fn to_struct_fields(&self) -> (Vec<Option<T>>, Vec<bool>)
+    where
+        T: Clone,
+    {
+        let values = self
+            .iter()
+            .map(|value| value.cloned().to_optional())
+            .collect();
+        (values, self.defined.clone())
+    }
+
+    /// Creates a column from nullable values, with `None` as `Null`.
+    pub fn from_options(values: Vec<Option<T>>) -> Self
+    where
+        T: Default,
+    {
+        values
+            .into_iter()
+            .map(|value| Presence::from_nullable(Some(value)))
+            .collect()
+    }
+
+    /// Creates a column from the fields of a struct column, as returned by
+    /// [`to_struct_fields`](Self::to_struct_fields).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the fields have different lengths.
+    pub fn from_struct_fields(values: Vec<Option<T>>, defined: Vec<bool>) -> Self
+    where
+        T: Default,
+    {
+        assert_eq!(
+            values.len(),
+            defined.len(),
+            "column parts must have the same length"
+        );
+        values
+            .into_iter()
+            .zip(defined)
+            .map(|(value, defined)| match (defined, value) {
+                (false, _) => Presence::Absent,
+                (true, None) => Presence::Null,
+                (true, Some(value)) => Presence::Some(value),
+            })
+            .collect()
+    }
+
+    /// Converts the column back into presence values.
+    pub fn into_presences(self) -> Vec<Presence<T>> {
+        self.values
+            .into_iter()
+            .zip(self.validity)
+            .zip(self.defined)
+            .map(|((value, valid), defined)| match (defined, valid) {
+                (false, _) => Presence::Absent,
+                (true, false) => Presence::Null,
+                (true, true) => Presence::Some(value),
+            })
+            .collect()
+    }
+}
+
+impl<T: Clone + Default> From<&[Presence<T>]> for PresenceColumn<T> {
+    fn from(presences: &[Presence<T>]) -> Self {
+        presences.iter().cloned().collect()
+    }
+}
+
+impl<T: Default> From<Vec<Presence<T>>> for PresenceColumn<T> {
+    fn from(presences: Vec<Presence<T>>) -> Self {
+        presences.into_iter().collect()
+    }
+}
+
+impl<T: Default> FromIterator<Presence<T>> for PresenceColumn<T> {
+    fn from_iter<I: IntoIterator<Item = Presence<T>>>(iter: I) -> Self {
+        let mut column = Self::new();
+        column.extend(iter);
+        column
+    }
+}
+
+impl<T: Default> Extend<Presence<T>> for PresenceColumn<T> {
+    fn extend<I: IntoIterator<Item = Presence<T>>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// How [`PresenceColumn::to_options`] handles `Absent` slots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AbsentPolicy {
+    /// Store `Absent` as null, like `Null`.
+    #[default]
+    Null,
+    /// Fail on `Absent`, for columns that must be fully defined.
+    Reject,
+}
+
+/// Error returned by [`PresenceColumn::to_options`] for an `Absent` slot with
+/// [`AbsentPolicy::Reject`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AbsentError {
+    /// The index of the `Absent` slot.
+    pub row: usize,
+}
+
+impl fmt::Display for AbsentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "absent value at row {}", self.row)
+    }
+}
+
+impl std::error::Error for AbsentError {}
@@ -0,0 +1,149 @@
+//! Layered configuration with [`Presence<T>`] settings.
+//!
+//! A settings struct with `Presence<T>` fields can express three things per layer:
+//!
+//! - `Absent` → inherit the value of the layers below
+//! - `Null` → explicitly unset the value, overriding the layers below
+//! - `Some(value)` → override the value
+//!
+//! [`Layers`] stacks instances of such a struct (defaults, files, environment, flags) and
+//! resolves them with [`MergeFrom`], so later layers win wherever they are defined. It
+//! also remembers which layer each field came from, for diagnostics.
+//!
+//! With the `figment` and `config-rs` features, layers can be read directly from a
+//! `figment::Figment` or a `config::Config`. Fields read from these sources need
+//! `#[serde(default)]` so that missing keys become `Absent`.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::config::Layers;
+//! use presence_rs::merge::MergeFrom;
+//! use presence_rs::reflect::PresenceFields;
+//!
+//! #[derive(Default, MergeFrom, PresenceFields)]
+//! struct Settings {
+//!     log_level: Presence<String>,
+//!     proxy: Presence<String>,
+//! }
+//!
+//! let defaults = Settings {
+//!     log_level: Presence::Some("info".into()),
+//!     proxy: Presence::Some("http://proxy:3128".into()),
+//! };
+//! let file = Settings { log_level: Presence::Some("debug".into()), ..Default::default() };
+//! let flags = Settings { proxy: Presence::Null, ..Default::default() };
+//!
+//! let layers = Layers::new("defaults", defaults)
+//!     .with("file", file)
+//!     .with("flags", flags);
+//!
+//! assert_eq!(layers.origin("log_level"), Some("file"));
+//! assert_eq!(layers.origin("proxy"), Some("flags"));
+//!
+//! let settings = layers.resolve();
+//! assert_eq!(settings.log_level, Presence::Some("debug".into()));
+//! assert_eq!(settings.proxy, Presence::Null);
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::merge::MergeFrom;
+use crate::reflect::PresenceFields;
+
+/// A stack of named configuration layers, from lowest to highest priority.
+#[derive(Clone, Debug)]
+pub struct Layers<T> {
+    layers: Vec<(String, T)>,
+}
+
+impl<T> Layers<T> {
+    /// Starts a stack with its lowest layer, usually the defaults.
+    pub fn new(name: impl Into<String>, base: T) -> Self {
+        Self {
+            layers: vec![(name.into(), base)],
+        }
+    }
+
+    /// Adds a layer on top of the stack.
+    pub fn with(mut self, name: impl Into<String>, layer: T) -> Self {
+        self.push(name, layer);
+        self
+    }
+
+    /// Adds a layer on top of the stack.
+    pub fn push(&mut self, name: impl Into<String>, layer: T) {
+        self.layers.push((name.into(), layer));
+    }
+
+    /// Returns the layer names, from lowest to highest priority.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Returns the number of layers, which is at least one for the base layer.
+    #[allow(clippy::len_without_is_empty)] // a stack always has its base layer
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+}
+
+impl<T: MergeFrom> Layers<T> {
+    /// Merges all layers, higher layers winning wherever they are defined.
+    pub fn resolve(self) -> T {
+        self.layers
+            .into_iter()
+            .map(|(_, layer)| layer)
+            .reduce(MergeFrom::merged)
+            .expect("a stack has at least its base layer")
+    }
+}
+
+impl<T: PresenceFields> Layers<T> {
+    /// Returns the name of the highest layer that defines `field`, if any.
+    ///
+    /// This follows the default merge semantics; fields merged with another strategy may
+    /// resolve to a different layer.
+    pub fn origin(&self, field: &str) -> Option<&str> {
+        self.layers
+            .iter()
+            .rev()
+            .find(|(_, layer)| layer.defined_fields().contains(&field))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[cfg(feature = "figment")]
+impl<T: serde::de::DeserializeOwned> Layers<T> {
+    /// Extracts a layer from a figment and adds it on top of the stack.
+    ///
+    /// Keys that are missing from the figment are `Absent`. Figment has no `null`: values
+    /// that a provider serialized from `None` or `Null` are read as `Null`.
+    #[allow(clippy::result_large_err)] // figment::Error is what figment itself returns
+    pub fn with_figment(
+        self,
+        name: impl Into<String>,
+        figment: &figment::Figment,
+    ) -> Result<Self, figment::Error> {
+        let layer = figment.extract()?;
+        Ok(self.with(name, layer))
+    }
+}
+
+#[cfg(feature = "config-rs")]
+impl<T: serde::de::DeserializeOwned> Layers<T> {
+    /// Deserializes a layer from a config-rs `Config` and adds it on top of the stack.
+    ///
+    /// Keys that are missing from the config are `Absent` and `nil` values are `Null`.
+    pub fn with_config(
+        self,
+        name: impl Into<String>,
+        config: ::config::Config,
+    ) -> Result<Self, ::config::ConfigError> {
+        let layer = config.try_deserialize()?;
+        Ok(self.with(name, layer))
+    }
+}
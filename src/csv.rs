@@ -0,0 +1,259 @@
+//! CSV support for [`Presence<T>`].
+//!
+//! Bulk-import pipelines need to tell "column not provided" apart from "explicitly blank".
+//! This module maps CSV cells onto the three states:
+//!
+//! - Missing column → `Absent`
+//! - Cell matching a `Null` token → `Null`
+//! - Cell matching an `Absent` token → `Absent`
+//! - Any other cell → `Some(value)`
+//!
+//! The tokens are a [`Tokens`] set, the same type that formats and parses presence text.
+//! [`Tokens::csv`] reads the empty cell, `NULL` and `\N` as `Null`. Two entry points are
+//! provided, and both classify cells with [`Tokens::state`], so they agree on every cell:
+//!
+//! - [`Tokens::deserialize`] deserializes a whole record into a struct through serde.
+//!   Presence fields need `#[serde(default)]` so that missing columns become `Absent`.
+//! - [`Tokens::field`] reads a single column by header name and parses it with [`FromStr`].
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::text::Tokens;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! struct Row {
+//!     id: u32,
+//!     #[serde(default)]
+//!     age: Presence<u32>,
+//!     #[serde(default)]
+//!     email: Presence<String>,
+//! }
+//!
+//! let data = "id,age\n1,42\n2,\n3,NULL\n";
+//! let mut reader = csv::Reader::from_reader(data.as_bytes());
+//! let headers = reader.headers().unwrap().clone();
+//! let tokens = Tokens::csv();
+//!
+//! let rows: Vec<Row> = reader
+//!     .records()
+//!     .map(|record| tokens.deserialize(&record.unwrap(), &headers).unwrap())
+//!     .collect();
+//!
+//! assert_eq!(rows[0].age, Presence::Some(42));
+//! assert_eq!(rows[1].age, Presence::Null);
+//! assert_eq!(rows[2].age, Presence::Null);
+//! assert_eq!(rows[0].email, Presence::Absent); // no `email` column at all
+//! ```
+
+use crate::presence::{Presence, PresenceState};
+use crate::text::Tokens;
+use csv::StringRecord;
+use serde::de::value::{Error, MapDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Unexpected, Visitor};
+use serde::forward_to_deserialize_any;
+use std::str::FromStr;
+
+impl Tokens {
+    /// Returns the tokens of SQL dumps: the empty cell, `NULL` and `\N` (the
+    /// MySQL/PostgreSQL convention) are read as [`Null`], and there is no `Absent` token.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::PresenceState;
+    /// use presence_rs::text::Tokens;
+    ///
+    /// let tokens = Tokens::csv();
+    /// assert_eq!(tokens.state(""), Some(PresenceState::Null));
+    /// assert_eq!(tokens.state("NULL"), Some(PresenceState::Null));
+    /// assert_eq!(tokens.state("\\N"), Some(PresenceState::Null));
+    /// assert_eq!(tokens.state("null"), None);
+    /// ```
+    pub fn csv() -> Self {
+        Tokens::none().null("").or_null("NULL").or_null("\\N")
+    }
+
+    /// Reads the column named `column` from `record` and parses it with
+    /// [`Presence::parse_with`].
+    ///
+    /// Returns `Absent` when `headers` has no such column, or `record` ends before it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csv::StringRecord;
+    /// use presence_rs::Presence;
+    /// use presence_rs::text::Tokens;
+    ///
+    /// let headers = StringRecord::from(vec!["id", "score"]);
+    /// let record = StringRecord::from(vec!["1", "NULL"]);
+    /// let tokens = Tokens::csv();
+    ///
+    /// assert_eq!(tokens.field::<u32>(&record, &headers, "id"), Ok(Presence::Some(1)));
+    /// assert_eq!(tokens.field::<u32>(&record, &headers, "score"), Ok(Presence::Null));
+    /// assert_eq!(tokens.field::<u32>(&record, &headers, "rank"), Ok(Presence::Absent));
+    /// ```
+    pub fn field<T: FromStr>(
+        &self,
+        record: &StringRecord,
+        headers: &StringRecord,
+        column: &str,
+    ) -> Result<Presence<T>, T::Err> {
+        headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|index| record.get(index))
+            .map_or(Ok(Presence::Absent), |cell| {
+                Presence::parse_with(cell, self)
+            })
+    }
+
+    /// Returns a copy of `record` where every token cell is replaced by the empty cell.
+    ///
+    /// The `csv` deserializer reads an empty cell as `None`, which [`Presence`] maps
+    /// to `Null`, so a normalized record deserializes every token as `Null`.
+    pub fn normalize(&self, record: &StringRecord) -> StringRecord {
+        record
+            .iter()
+            .map(|cell| if self.state(cell).is_some() { "" } else { cell })
+            .collect()
+    }
+
+    /// Deserializes `record` into `D`, reading token cells as `Null` or `Absent`.
+    ///
+    /// Presence fields must be annotated with `#[serde(default)]` so that columns
+    /// missing from `headers` or past the end of `record`, and cells matching an `Absent`
+    /// token, deserialize as `Absent`. Cells are classified by [`state`] like in
+    /// [`field`], so without an empty `Null` token an empty cell is a value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a cell does not parse as its field type, or a field without a
+    /// default is missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use csv::StringRecord;
+    /// use presence_rs::Presence;
+    /// use presence_rs::text::Tokens;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Row {
+    ///     #[serde(default)]
+    ///     name: Presence<String>,
+    /// }
+    ///
+    /// let headers = StringRecord::from(vec!["name"]);
+    /// let record = StringRecord::from(vec![""]);
+    /// let tokens = Tokens::none().null("NULL");
+    /// let row: Row = tokens.deserialize(&record, &headers).unwrap();
+    /// assert_eq!(row.name, Presence::Some(String::new()));
+    /// ```
+    ///
+    /// [`state`]: Tokens::state
+    /// [`field`]: Tokens::field
+    pub fn deserialize<D: DeserializeOwned>(
+        &self,
+        record: &StringRecord,
+        headers: &StringRecord,
+    ) -> Result<D, Error> {
+        let cells = headers
+            .iter()
+            .zip(record.iter())
+            .filter(|(_, cell)| self.state(cell) != Some(PresenceState::Absent))
+            .map(|(header, cell)| (header, Cell { cell, tokens: self }));
+        D::deserialize(MapDeserializer::new(cells))
+    }
+}
+
+/// A deserializer for one cell, which parses scalars from the text and reads null cells
+/// as `None`.
+struct Cell<'de> {
+    cell: &'de str,
+    tokens: &'de Tokens,
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Cell<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                match self.cell.parse() {
+                    Ok(value) => visitor.$visit(value),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.cell), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for Cell<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_borrowed_str(self.cell)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.tokens.state(self.cell).is_some() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.cell
+            .into_deserializer()
+            .deserialize_enum(name, variants, visitor)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
@@ -0,0 +1,409 @@
+//! diesel integration for [`Presence<T>`] patches.
+//!
+//! [`assign`] turns a `Presence<T>` into an optional `column.eq(value)` assignment, which
+//! diesel accepts as a changeset:
+//!
+//! - `Some(value)` → `column = value`
+//! - `Null` → `column = NULL`
+//! - `Absent` → left out of the `SET` clause
+//!
+//! [`presence_changeset!`] builds a changeset from the listed fields of a patch struct,
+//! using each field name as the column name, ready to pass to `diesel::update(..).set(..)`.
+//!
+//! Assignments bind `Option<T>`, so the target columns must be `Nullable`. For `NOT NULL`
+//! columns, where `Null` has no meaning, keep using diesel's `Option<T>` fields.
+//!
+//! `Presence<T>` can also be used directly wherever diesel accepts `Option<T>` for a
+//! `Nullable<ST>` column: as a bound value (`ToSql`/`AsExpression`) and in query results
+//! (`FromSql`/`Queryable`/`QueryableByName`). SQL `NULL` loads as `Null`, and
+//! [`get_presence`] reads a column missing from the result set as `Absent`. Binding
+//! `Absent` is an error rather than `NULL`, since it would clear the column; use
+//! [`assign`] to leave a column out of an update instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use diesel::debug_query;
+//! use diesel::prelude::*;
+//! use diesel::sqlite::Sqlite;
+//! use presence_rs::{Presence, presence_changeset};
+//!
+//! diesel::table! {
+//!     users {
+//!         id -> Integer,
+//!         nickname -> Nullable<Text>,
+//!         bio -> Nullable<Text>,
+//!         age -> Nullable<Integer>,
+//!     }
+//! }
+//!
+//! struct UserPatch {
+//!     nickname: Presence<String>,
+//!     bio: Presence<String>,
+//!     age: Presence<i32>,
+//! }
+//!
+//! let patch = UserPatch {
+//!     nickname: Presence::Some("bob".into()),
+//!     bio: Presence::Null,
+//!     age: Presence::Absent,
+//! };
+//!
+//! let query = diesel::update(users::table.find(1))
+//!     .set(presence_changeset!(users, patch; nickname, bio, age));
+//! assert_eq!(
+//!     debug_query::<Sqlite, _>(&query).to_string(),
+//!     r#"UPDATE `users` SET `nickname` = ?, `bio` = ? WHERE (`users`.`id` = ?) -- binds: [Some("bob"), None, 1]"#,
+//! );
+//! ```
+//!
+//! For `UPDATE` statements written as SQL text, [`sql_update`] binds a driver-agnostic
+//! [`SetClause`] to a [`sql_query`](diesel::sql_query).
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`presence_changeset!`]: crate::presence_changeset
+//! [`SetClause`]: crate::sql::SetClause
+
+use crate::presence::Presence;
+use crate::sql::SetClause;
+use diesel::backend::Backend;
+use diesel::deserialize::{self, FromSql, Queryable, QueryableByName};
+use diesel::dsl;
+use diesel::expression::{
+    AppearsOnTable, AsExpression, Expression, SelectableExpression, TypedExpressionType,
+    ValidGrouping, is_aggregate,
+};
+use diesel::prelude::*;
+use diesel::query_builder::{AstPass, BoxedSqlQuery, QueryFragment, QueryId, SqlQuery};
+use diesel::result::UnexpectedNullError;
+use diesel::row::{Field, NamedRow, Row};
+use diesel::serialize::{self, IsNull, Output, ToSql};
+use diesel::sql_types::{HasSqlType, Nullable, SingleValue, SqlType, is_nullable};
+use std::marker::PhantomData;
+
+/// Builds the assignment `column = value` for a defined `value`.
+///
+/// Returns `None` for `Absent`, which diesel skips when building the `SET` clause.
+///
+/// # Examples
+///
+/// ```
+/// use diesel::debug_query;
+/// use diesel::prelude::*;
+/// use diesel::sqlite::Sqlite;
+/// use presence_rs::Presence;
+/// use presence_rs::diesel::assign;
+///
+/// diesel::table! {
+///     posts {
+///         id -> Integer,
+///         title -> Nullable<Text>,
+///         summary -> Nullable<Text>,
+///     }
+/// }
+///
+/// let query = diesel::update(posts::table).set((
+///     assign(posts::title, Presence::<String>::Absent),
+///     assign(posts::summary, Presence::<String>::Null),
+/// ));
+/// assert_eq!(
+///     debug_query::<Sqlite, _>(&query).to_string(),
+///     "UPDATE `posts` SET `summary` = ? -- binds: [None]",
+/// );
+/// ```
+pub fn assign<C, T>(column: C, value: Presence<T>) -> Option<dsl::Eq<C, Option<T>>>
+where
+    C: Column + ExpressionMethods,
+    C::SqlType: SqlType,
+    Option<T>: AsExpression<C::SqlType>,
+{
+    match value {
+        Presence::Absent => None,
+        Presence::Null => Some(column.eq(None)),
+        Presence::Some(value) => Some(column.eq(Some(value))),
+    }
+}
+
+/// Starts an `UPDATE table SET ...` query from `set`, binding each of its values as
+/// `Nullable<ST>`.
+///
+/// Returns `None` if `set` is empty, since an `UPDATE` without columns is invalid SQL.
+///
+/// The `WHERE` clause and its binds are appended with [`BoxedSqlQuery::sql`] and
+/// [`BoxedSqlQuery::bind`]. The placeholders of `set` must match the backend, and the
+/// next one is given by [`SetClause::next_placeholder`].
+///
+/// # Examples
+///
+/// ```
+/// use diesel::debug_query;
+/// use diesel::sql_types::{Integer, Text};
+/// use diesel::sqlite::Sqlite;
+/// use presence_rs::Presence;
+/// use presence_rs::diesel::sql_update;
+/// use presence_rs::sql::{Placeholder, SetClause};
+///
+/// let mut set = SetClause::<String>::new(Placeholder::Question);
+/// set.set("nickname", Presence::Some("bob".to_string()))
+///     .set("bio", Presence::<String>::Null)
+///     .set("email", Presence::<String>::Absent);
+///
+/// let query = sql_update::<Sqlite, Text, _>("users", set)
+///     .unwrap()
+///     .sql(" WHERE id = ?")
+///     .bind::<Integer, _>(1);
+/// assert_eq!(
+///     debug_query(&query).to_string(),
+///     "UPDATE users SET nickname = ?, bio = ? WHERE id = ? -- binds: [Some(\"bob\"), None, 1]",
+/// );
+/// ```
+pub fn sql_update<'f, DB, ST, V>(
+    table: &str,
+    set: SetClause<V>,
+) -> Option<BoxedSqlQuery<'f, DB, SqlQuery>>
+where
+    DB: Backend + HasSqlType<Nullable<ST>>,
+    ST: Send + 'f,
+    Option<V>: ToSql<Nullable<ST>, DB> + Send + 'f,
+{
+    if set.is_empty() {
+        return None;
+    }
+    let query = diesel::sql_query(format!("UPDATE {} {}", table, set.sql())).into_boxed();
+    Some(
+        set.into_binds()
+            .into_iter()
+            .fold(query, |query, value| query.bind::<Nullable<ST>, _>(value)),
+    )
+}
+
+impl<T, ST, DB> FromSql<Nullable<ST>, DB> for Presence<T>
+where
+    T: FromSql<ST, DB>,
+    DB: Backend,
+    ST: SqlType<IsNull = is_nullable::NotNull>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+        T::from_sql(bytes).map(Presence::Some)
+    }
+
+    fn from_nullable_sql(bytes: Option<DB::RawValue<'_>>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(bytes) => T::from_sql(bytes).map(Presence::Some),
+            None => Ok(Presence::Null),
+        }
+    }
+}
+
+impl<T, ST, DB> ToSql<Nullable<ST>, DB> for Presence<T>
+where
+    T: ToSql<ST, DB>,
+    DB: Backend,
+    ST: SqlType<IsNull = is_nullable::NotNull>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+        match self {
+            Presence::Some(value) => value.to_sql(out),
+            Presence::Null => Ok(IsNull::Yes),
+            Presence::Absent => {
+                Err("cannot bind an Absent presence; use `assign` to leave the column out".into())
+            }
+        }
+    }
+}
+
+/// The expression of a bound `Presence<T>` value, which writes it through its `ToSql`
+/// impl.
+#[derive(Clone, Copy, Debug)]
+pub struct Bind<ST, U> {
+    value: U,
+    sql_type: PhantomData<ST>,
+}
+
+impl<ST, U> Bind<ST, U> {
+    fn new(value: U) -> Self {
+        Self {
+            value,
+            sql_type: PhantomData,
+        }
+    }
+}
+
+impl<ST, U> Expression for Bind<ST, U>
+where
+    ST: SqlType + TypedExpressionType,
+{
+    type SqlType = ST;
+}
+
+impl<ST, U, DB> QueryFragment<DB> for Bind<ST, U>
+where
+    DB: Backend + HasSqlType<ST>,
+    U: ToSql<ST, DB>,
+{
+    fn walk_ast<'b>(&'b self, mut pass: AstPass<'_, 'b, DB>) -> QueryResult<()> {
+        pass.push_bind_param(&self.value)
+    }
+}
+
+impl<ST: QueryId, U> QueryId for Bind<ST, U> {
+    type QueryId = Bind<ST::QueryId, ()>;
+
+    const HAS_STATIC_QUERY_ID: bool = ST::HAS_STATIC_QUERY_ID;
+}
+
+impl<ST, U, QS> SelectableExpression<QS> for Bind<ST, U> where Self: AppearsOnTable<QS> {}
+
+impl<ST, U, QS> AppearsOnTable<QS> for Bind<ST, U> where Self: Expression {}
+
+impl<ST, U, GB> ValidGrouping<GB> for Bind<ST, U> {
+    type IsAggregate = is_aggregate::Never;
+}
+
+impl<T, ST> AsExpression<Nullable<ST>> for Presence<T>
+where
+    ST: SqlType<IsNull = is_nullable::NotNull>,
+    Nullable<ST>: TypedExpressionType,
+{
+    type Expression = Bind<Nullable<ST>, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bind::new(self)
+    }
+}
+
+impl<T, ST> AsExpression<Nullable<ST>> for &Presence<T>
+where
+    ST: SqlType<IsNull = is_nullable::NotNull>,
+    Nullable<ST>: TypedExpressionType,
+{
+    type Expression = Bind<Nullable<ST>, Self>;
+
+    fn as_expression(self) -> Self::Expression {
+        Bind::new(self)
+    }
+}
+
+impl<ST, T, DB> Queryable<ST, DB> for Presence<T>
+where
+    ST: SingleValue<IsNull = is_nullable::IsNullable>,
+    DB: Backend,
+    Self: FromSql<ST, DB>,
+{
+    type Row = Self;
+
+    fn build(row: Self::Row) -> deserialize::Result<Self> {
+        Ok(row)
+    }
+}
+
+impl<T, DB> QueryableByName<DB> for Presence<T>
+where
+    DB: Backend,
+    T: QueryableByName<DB>,
+{
+    fn build<'a>(row: &impl NamedRow<'a, DB>) -> deserialize::Result<Self> {
+        match T::build(row) {
+            Ok(value) => Ok(Presence::Some(value)),
+            Err(err) if err.is::<UnexpectedNullError>() => Ok(Presence::Null),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Reads the column `column_name` of a row, or `Absent` if the query did not select it.
+///
+/// `ST` is the SQL type of the column, as for [`NamedRow::get`]. Use it in a hand-written
+/// `QueryableByName` impl to load the same struct from queries selecting different
+/// columns.
+///
+/// # Errors
+///
+/// Returns an error if the selected value fails to deserialize.
+///
+/// # Examples
+///
+/// ```
+/// use diesel::deserialize::{self, QueryableByName};
+/// use diesel::prelude::*;
+/// use diesel::row::NamedRow;
+/// use diesel::sql_types::{Nullable, Text};
+/// use diesel::sqlite::Sqlite;
+/// use presence_rs::Presence;
+/// use presence_rs::diesel::get_presence;
+///
+/// struct Profile {
+///     bio: Presence<String>,
+/// }
+///
+/// impl QueryableByName<Sqlite> for Profile {
+///     fn build<'a>(row: &impl NamedRow<'a, Sqlite>) -> deserialize::Result<Self> {
+///         Ok(Profile { bio: get_presence::<Nullable<Text>, _, _, _>(row, "bio")? })
+///     }
+/// }
+///
+/// let mut conn = SqliteConnection::establish(":memory:").unwrap();
+/// let profiles: Vec<Profile> = diesel::sql_query("SELECT 1 AS id").load(&mut conn).unwrap();
+/// assert_eq!(profiles[0].bio, Presence::Absent);
+/// ```
+pub fn get_presence<'a, ST, T, DB, R>(
+    row: &R,
+    column_name: &str,
+) -> deserialize::Result<Presence<T>>
+where
+    DB: Backend,
+    R: NamedRow<'a, DB>,
+    Presence<T>: FromSql<ST, DB>,
+{
+    match Row::get(row, column_name) {
+        Some(field) => Presence::from_nullable_sql(field.value()),
+        None => Ok(Presence::Absent),
+    }
+}
+
+/// Builds a diesel changeset from the listed fields of a patch struct.
+///
+/// `$table` is the module generated by `diesel::table!`, and each listed field is
+/// assigned to the column of the same name with [`assign`]. The result is a tuple that
+/// can be passed to `set`. The patch fields are moved out of `$patch`.
+///
+/// If every field is `Absent`, executing the update fails with diesel's
+/// `QueryBuilderError("There are no changes to save...")`; check the patch first if that
+/// can happen.
+///
+/// [`assign`]: crate::diesel::assign
+///
+/// # Examples
+///
+/// ```
+/// use diesel::debug_query;
+/// use diesel::prelude::*;
+/// use diesel::sqlite::Sqlite;
+/// use presence_rs::{Presence, presence_changeset};
+///
+/// diesel::table! {
+///     settings {
+///         id -> Integer,
+///         theme -> Nullable<Text>,
+///     }
+/// }
+///
+/// struct SettingsPatch { theme: Presence<String> }
+///
+/// let patch = SettingsPatch { theme: Presence::Some("dark".into()) };
+/// let query =
+///     diesel::update(settings::table).set(presence_changeset!(settings, patch; theme));
+/// assert_eq!(
+///     debug_query::<Sqlite, _>(&query).to_string(),
+///     r#"UPDATE `settings` SET `theme` = ? -- binds: [Some("dark")]"#,
+/// );
+/// ```
+#[macro_export]
+macro_rules! presence_changeset {
+    ($table:ident, $patch:expr; $($field:ident),+ $(,)?) => {{
+        let patch = $patch;
+        ($(
+            $crate::diesel::assign($table::$field, patch.$field),
+        )+)
+    }};
+}
@@ -0,0 +1,100 @@
+//! DynamoDB items from and to structs of [`Presence<T>`] fields, through [`serde_dynamo`].
+//!
+//! Presence fields map onto attribute presence:
+//!
+//! - `Some(value)` → the attribute holds the value
+//! - `Null` → a `NULL` attribute
+//! - `Absent` → no attribute
+//!
+//! As with other formats, presence fields need
+//! `#[serde(default, skip_serializing_if = "Presence::is_absent")]` to keep `Absent` out of
+//! the item and to read a missing attribute back as `Absent`. [`to_item`] serializes under
+//! [`Strict`], so a field missing the attribute is an error instead of a `NULL` attribute
+//! that would clear the stored value.
+//!
+//! Items are generic over the item type like in [`serde_dynamo`], so enabling one of its
+//! SDK features (such as `aws-sdk-dynamodb+1`) lets [`to_item`] and [`from_item`] work with
+//! the SDK's own item maps.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::dynamodb::{AttributeValue, Item, from_item, to_item};
+//! use serde::{Deserialize, Serialize};
+//! use std::collections::HashMap;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct User {
+//!     id: String,
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     age: Presence<u32>,
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     avatar: Presence<String>,
+//! }
+//!
+//! let user = User { id: "u1".into(), age: Presence::Some(30), avatar: Presence::Null };
+//! let item: Item = to_item(&user).unwrap();
+//! assert_eq!(item.get("age"), Some(&AttributeValue::N("30".into())));
+//! assert_eq!(item.get("avatar"), Some(&AttributeValue::Null(true)));
+//! assert_eq!(from_item::<_, User>(item).unwrap(), user);
+//!
+//! let item = HashMap::from([("id".to_string(), AttributeValue::S("u2".into()))]);
+//! let user: User = from_item(item).unwrap();
+//! assert_eq!(user.age, Presence::Absent);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`Strict`]: crate::strict::Strict
+
+use crate::strict::Strict;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+pub use serde_dynamo::{AttributeValue, Error, Item};
+
+/// Converts a struct into a DynamoDB item, leaving out `Absent` attributes.
+///
+/// # Errors
+///
+/// Returns an error if `value` fails to serialize, does not serialize as a struct or map,
+/// or serializes an `Absent` presence.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::dynamodb::{Item, to_item};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Forgetful {
+///     name: Presence<String>,
+/// }
+///
+/// let result: Result<Item, _> = to_item(&Forgetful { name: Presence::Absent });
+/// assert!(result.is_err());
+/// ```
+pub fn to_item<T, I>(value: &T) -> Result<I, Error>
+where
+    T: Serialize + ?Sized,
+    I: From<Item>,
+{
+    serde_dynamo::to_item(Strict(value))
+}
+
+/// Reads a struct from a DynamoDB item.
+///
+/// Missing attributes are left to serde, so `#[serde(default)]` presence fields become
+/// `Absent` and `NULL` attributes become `Null`.
+///
+/// # Errors
+///
+/// Returns an error if the item does not deserialize into `T`.
+pub fn from_item<I, T>(item: I) -> Result<T, Error>
+where
+    I: Into<Item>,
+    T: DeserializeOwned,
+{
+    serde_dynamo::from_item(item)
+}
@@ -0,0 +1,146 @@
+//! Elasticsearch `_update` request bodies from patch structs of [`Presence<T>`] fields.
+//!
+//! A patch struct is serialized to JSON and turned into a partial document update:
+//!
+//! - `Some(value)` → `doc: { field: value }`
+//! - `Null` → `doc: { field: null }`, or removed from `_source` with [`NullHandling::Remove`]
+//! - `Absent` → omitted
+//!
+//! The patch is serialized with [`json::patch_object`], so a Presence field without
+//! `skip_serializing_if` fails rather than clearing the field.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::elasticsearch::update_body;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! #[derive(Serialize)]
+//! struct ArticlePatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     title: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     summary: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     views: Presence<u64>,
+//! }
+//!
+//! let patch = ArticlePatch {
+//!     title: Presence::Some("Presence".into()),
+//!     summary: Presence::Null,
+//!     views: Presence::Absent,
+//! };
+//!
+//! assert_eq!(
+//!     update_body(&patch).unwrap(),
+//!     json!({ "doc": { "title": "Presence", "summary": null } }),
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`json::patch_object`]: crate::json::patch_object
+
+use crate::json::patch_object;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// The painless script used for updates that remove fields.
+///
+/// It copies `params.set` into `_source` and removes every field named in
+/// `params.remove`. The source never changes, so Elasticsearch compiles it once.
+pub const UPDATE_SCRIPT: &str = "for (entry in params.set.entrySet()) { ctx._source[entry.getKey()] = entry.getValue() } for (field in params.remove) { ctx._source.remove(field) }";
+
+/// How [`Null`] fields are written to the update body.
+///
+/// [`Null`]: crate::Presence::Null
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NullHandling {
+    /// Store an explicit `null` in the partial document.
+    #[default]
+    SetNull,
+    /// Remove the field from `_source` with a scripted update.
+    Remove,
+}
+
+/// Builds an `_update` body from a patch struct, storing nulls in the partial document.
+///
+/// See [`update_body_with`] for the mapping rules.
+///
+/// # Errors
+///
+/// Returns an error if [`patch_object`] fails.
+pub fn update_body<T: Serialize + ?Sized>(patch: &T) -> Result<Value, serde_json::Error> {
+    update_body_with(patch, NullHandling::default())
+}
+
+/// Builds an `_update` body from a patch struct.
+///
+/// The body is a partial `doc` update with the defined fields, unless `null` is
+/// [`NullHandling::Remove`] and the patch has `Null` fields. Elasticsearch cannot remove a
+/// field with a partial document, so the body is then a scripted update running
+/// [`UPDATE_SCRIPT`], with the values in `params.set` and the `Null` fields in
+/// `params.remove`. A patch with only `Absent` fields yields `{"doc": {}}`, which
+/// Elasticsearch treats as a no-op.
+///
+/// # Errors
+///
+/// Returns an error if [`patch_object`] fails.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::elasticsearch::{NullHandling, UPDATE_SCRIPT, update_body_with};
+/// use serde::Serialize;
+/// use serde_json::json;
+///
+/// #[derive(Serialize)]
+/// struct Patch {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     title: Presence<String>,
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     summary: Presence<String>,
+/// }
+///
+/// let patch = Patch { title: Presence::Some("Draft".into()), summary: Presence::Null };
+/// assert_eq!(
+///     update_body_with(&patch, NullHandling::Remove).unwrap(),
+///     json!({
+///         "script": {
+///             "lang": "painless",
+///             "source": UPDATE_SCRIPT,
+///             "params": { "set": { "title": "Draft" }, "remove": ["summary"] },
+///         }
+///     }),
+/// );
+/// ```
+pub fn update_body_with<T: Serialize + ?Sized>(
+    patch: &T,
+    null: NullHandling,
+) -> Result<Value, serde_json::Error> {
+    let fields = patch_object(patch)?;
+
+    if null == NullHandling::SetNull || !fields.values().any(Value::is_null) {
+        return Ok(json!({ "doc": fields }));
+    }
+
+    let mut set = Map::new();
+    let mut remove = Vec::new();
+    for (key, value) in fields {
+        match value {
+            Value::Null => remove.push(Value::String(key)),
+            value => {
+                set.insert(key, value);
+            }
+        }
+    }
+    Ok(json!({
+        "script": {
+            "lang": "painless",
+            "source": UPDATE_SCRIPT,
+            "params": { "set": set, "remove": remove },
+        }
+    }))
+}
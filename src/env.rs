@@ -0,0 +1,206 @@
+//! Reading [`Presence<T>`] values from environment variables.
+//!
+//! `std::env::var` flattens three different situations into `Result<String, _>`. This
+//! module keeps them apart:
+//!
+//! - An unset variable → `Absent`
+//! - An empty variable → `Null`
+//! - Any other value → parsed with [`FromStr`] into `Some`
+//!
+//! [`Presence::from_env_with`] and [`Presence::parse_env`] take the [`Tokens`] to read
+//! instead, the same type that formats and parses presence text.
+//!
+//! With the `derive` feature, `#[derive(FromEnv)]` reads a whole settings struct. Each
+//! `Presence<T>` field is read from the variable named after the field in upper case,
+//! prefixed with the container's `#[presence(env_prefix = "...")]`:
+//!
+//! - `#[presence(env = "NAME")]` reads the field from `NAME` instead (no prefix)
+//! - `#[presence(empty = absent)]` (or `null`, `parse`) reads an empty value as `Absent`
+//!   (or `Null`, or parses it)
+//! - `#[presence(nested)]` reads a field that is itself `FromEnv`
+//! - `#[presence(skip)]` leaves a field at its `Default`
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::text::Tokens;
+//! use std::ffi::OsString;
+//!
+//! let tokens = Tokens::none().null("").absent("unset");
+//! let read = |value: Option<&str>| {
+//!     Presence::<u16>::parse_env("PORT", value.map(OsString::from), &tokens)
+//! };
+//!
+//! assert_eq!(read(None).unwrap(), Presence::Absent);
+//! assert_eq!(read(Some("")).unwrap(), Presence::Null);
+//! assert_eq!(read(Some("unset")).unwrap(), Presence::Absent);
+//! assert_eq!(read(Some("8080")).unwrap(), Presence::Some(8080));
+//! assert!(read(Some("http")).is_err());
+//! ```
+//!
+//! Reading a struct, here from a fixed set of variables instead of the process
+//! environment:
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::env::FromEnv;
+//! use std::ffi::OsString;
+//!
+//! #[derive(FromEnv)]
+//! #[presence(env_prefix = "APP_")]
+//! struct Settings {
+//!     port: Presence<u16>,
+//!     #[presence(env = "HTTPS_PROXY")]
+//!     proxy: Presence<String>,
+//! }
+//!
+//! let settings = Settings::from_env_lookup(|name| match name {
+//!     "APP_PORT" => Some(OsString::from("8080")),
+//!     "HTTPS_PROXY" => Some(OsString::new()),
+//!     _ => None,
+//! })
+//! .unwrap();
+//!
+//! assert_eq!(settings.port, Presence::Some(8080));
+//! assert_eq!(settings.proxy, Presence::Null);
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::{Presence, PresenceState};
+use crate::text::Tokens;
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::FromEnv;
+
+/// An environment variable that could not be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnvError {
+    /// The variable is not valid Unicode.
+    NotUnicode {
+        /// The name of the variable.
+        var: String,
+    },
+    /// The value could not be parsed.
+    Invalid {
+        /// The name of the variable.
+        var: String,
+        /// The value of the variable.
+        value: String,
+        /// The parse error.
+        message: String,
+    },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnvError::NotUnicode { var } => {
+                write!(f, "environment variable `{}` is not valid unicode", var)
+            }
+            EnvError::Invalid {
+                var,
+                value,
+                message,
+            } => write!(
+                f,
+                "invalid value `{}` for environment variable `{}`: {}",
+                value, var, message
+            ),
+        }
+    }
+}
+
+impl Error for EnvError {}
+
+impl<T: FromStr> Presence<T>
+where
+    T::Err: fmt::Display,
+{
+    /// Reads the environment variable `var`; an empty value is `Null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let value = Presence::<u32>::from_env("PRESENCE_RS_DOC_UNSET_VARIABLE").unwrap();
+    /// assert_eq!(value, Presence::Absent);
+    /// ```
+    pub fn from_env(var: &str) -> Result<Self, EnvError> {
+        Self::from_env_with(var, &Tokens::none().null(""))
+    }
+
+    /// Reads the environment variable `var`, reading values matching `tokens` as `Null`
+    /// or `Absent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::text::Tokens;
+    ///
+    /// let empty_is_absent = Tokens::none().absent("");
+    /// let value = Presence::<u32>::from_env_with("PRESENCE_RS_DOC_UNSET_VARIABLE", &empty_is_absent);
+    /// assert_eq!(value.unwrap(), Presence::Absent);
+    /// ```
+    pub fn from_env_with(var: &str, tokens: &Tokens) -> Result<Self, EnvError> {
+        Self::parse_env(var, std::env::var_os(var), tokens)
+    }
+
+    /// Reads an already looked-up value of the variable `var`.
+    ///
+    /// `var` is only used in errors. This is the building block of [`from_env`] and of
+    /// `FromEnv` lookups that do not use the process environment.
+    ///
+    /// [`from_env`]: Presence::from_env
+    pub fn parse_env(
+        var: &str,
+        value: Option<OsString>,
+        tokens: &Tokens,
+    ) -> Result<Self, EnvError> {
+        let Some(value) = value else {
+            return Ok(Presence::Absent);
+        };
+        let value = value.into_string().map_err(|_| EnvError::NotUnicode {
+            var: var.to_string(),
+        })?;
+
+        match tokens.state(&value) {
+            Some(PresenceState::Null) => return Ok(Presence::Null),
+            Some(_) => return Ok(Presence::Absent),
+            None => {}
+        }
+
+        value
+            .parse()
+            .map(Presence::Some)
+            .map_err(|err: T::Err| EnvError::Invalid {
+                var: var.to_string(),
+                message: err.to_string(),
+                value,
+            })
+    }
+}
+
+/// A settings struct that can be read from environment variables.
+///
+/// Usually derived; see the [module documentation](self).
+pub trait FromEnv: Sized {
+    /// Reads the struct, looking variables up with `lookup`.
+    fn from_env_lookup<F>(lookup: F) -> Result<Self, EnvError>
+    where
+        F: Fn(&str) -> Option<OsString>;
+
+    /// Reads the struct from the process environment.
+    fn from_env() -> Result<Self, EnvError> {
+        Self::from_env_lookup(|var| std::env::var_os(var))
+    }
+}
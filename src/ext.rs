@@ -0,0 +1,102 @@
+//! Extension traits for converting `Option`s into [`Presence<T>`] in method position.
+//!
+//! [`Presence::from_optional`] and [`Presence::from_nullable`] read poorly at the end of a
+//! combinator chain. With these traits in scope, the conversion is one more method call:
+//!
+//! - [`NestedOptionExt::into_presence`]: `Option<Option<T>>`, preserving all three states
+//! - [`OptionExt::into_presence_absent`]: `Option<T>`, with `None` as `Absent`
+//! - [`OptionExt::into_presence_null`]: `Option<T>`, with `None` as `Null`
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::ext::{NestedOptionExt, OptionExt};
+//! use std::collections::HashMap;
+//!
+//! let query: HashMap<&str, Option<&str>> = [("name", Some("bob")), ("bio", None)].into();
+//!
+//! let name = query.get("name").copied().into_presence().map(str::to_uppercase);
+//! assert_eq!(name, Presence::Some("BOB".to_string()));
+//! assert_eq!(query.get("bio").copied().into_presence(), Presence::Null);
+//! assert_eq!(query.get("age").copied().into_presence(), Presence::Absent);
+//!
+//! let age = "31".parse::<u32>().ok().into_presence_null();
+//! assert_eq!(age, Presence::Some(31));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+
+/// Method-position conversions from `Option<T>` into `Presence<T>`.
+pub trait OptionExt<T> {
+    /// Converts into a `Presence`, treating `None` as `Absent`.
+    ///
+    /// Equivalent to [`Presence::from_optional`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::ext::OptionExt;
+    ///
+    /// assert_eq!(Some(1).into_presence_absent(), Presence::Some(1));
+    /// assert_eq!(None::<i32>.into_presence_absent(), Presence::Absent);
+    /// ```
+    fn into_presence_absent(self) -> Presence<T>;
+
+    /// Converts into a `Presence`, treating `None` as `Null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::ext::OptionExt;
+    ///
+    /// assert_eq!(Some(1).into_presence_null(), Presence::Some(1));
+    /// assert_eq!(None::<i32>.into_presence_null(), Presence::Null);
+    /// ```
+    fn into_presence_null(self) -> Presence<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[inline]
+    fn into_presence_absent(self) -> Presence<T> {
+        Presence::from_optional(self)
+    }
+
+    #[inline]
+    fn into_presence_null(self) -> Presence<T> {
+        match self {
+            Some(value) => Presence::Some(value),
+            None => Presence::Null,
+        }
+    }
+}
+
+/// Method-position conversion from `Option<Option<T>>` into `Presence<T>`.
+pub trait NestedOptionExt<T> {
+    /// Converts into a `Presence`, preserving all three states.
+    ///
+    /// Equivalent to [`Presence::from_nullable`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::ext::NestedOptionExt;
+    ///
+    /// assert_eq!(Some(Some(1)).into_presence(), Presence::Some(1));
+    /// assert_eq!(Some(None::<i32>).into_presence(), Presence::Null);
+    /// assert_eq!(None::<Option<i32>>.into_presence(), Presence::Absent);
+    /// ```
+    fn into_presence(self) -> Presence<T>;
+}
+
+impl<T> NestedOptionExt<T> for Option<Option<T>> {
+    #[inline]
+    fn into_presence(self) -> Presence<T> {
+        Presence::from_nullable(self)
+    }
+}
@@ -0,0 +1,101 @@
+//! Test data generation for [`Presence<T>`] via the fake crate.
+//!
+//! `Presence<T>` implements `Dummy<Faker>`, generating `Some` half of the time and `Absent`
+//! or `Null` a quarter of the time each. [`PresenceFaker`] wraps another faker for the
+//! value and sets the share of each state, which also works in `#[dummy(faker = ...)]`
+//! attributes of derived structs.
+//!
+//! # Examples
+//!
+//! ```
+//! use fake::faker::name::en::Name;
+//! use fake::{Dummy, Fake, Faker};
+//! use presence_rs::Presence;
+//! use presence_rs::fake::PresenceFaker;
+//!
+//! #[derive(Debug, Dummy)]
+//! struct UserPatch {
+//!     #[dummy(faker = "PresenceFaker::new(Name(), 10, 0)")]
+//!     name: Presence<String>,
+//!     age: Presence<u8>,
+//! }
+//!
+//! let patch: UserPatch = Faker.fake();
+//! assert!(!patch.name.is_absent());
+//!
+//! let always_null: Presence<u32> = PresenceFaker::new(Faker, 100, 0).fake();
+//! assert_eq!(always_null, Presence::Null);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use fake::{Dummy, Faker, Rng};
+
+impl<T: Dummy<Faker>> Dummy<Faker> for Presence<T> {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &Faker, rng: &mut R) -> Self {
+        PresenceFaker::default().value_with_rng(config, rng)
+    }
+}
+
+/// Custom fake `Presence<T>` generator.
+///
+/// The rates are percentages; the remainder after `null_rate` and `absent_rate` is `Some`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PresenceFaker<U> {
+    inner: U,
+    null_rate: u8,
+    absent_rate: u8,
+}
+
+impl Default for PresenceFaker<Faker> {
+    fn default() -> Self {
+        Self::some(Faker)
+    }
+}
+
+impl<U> PresenceFaker<U> {
+    /// Generates values with `inner`, with a 25% chance each of `Null` and `Absent`.
+    pub fn some(inner: U) -> Self {
+        Self::new(inner, 25, 25)
+    }
+
+    /// Generates values with `inner`, `Null` in `null_rate` percent of cases and `Absent`
+    /// in `absent_rate` percent of cases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the rates add up to more than 100.
+    pub fn new(inner: U, null_rate: u8, absent_rate: u8) -> Self {
+        assert!(
+            u16::from(null_rate) + u16::from(absent_rate) <= 100,
+            "null and absent rates must not exceed 100 percent"
+        );
+        Self {
+            inner,
+            null_rate,
+            absent_rate,
+        }
+    }
+
+    fn value_with_rng<T, C, R>(&self, config: &C, rng: &mut R) -> Presence<T>
+    where
+        T: Dummy<C>,
+        R: Rng + ?Sized,
+    {
+        let roll = rng.random_range(0..100u8);
+        if roll < self.null_rate {
+            Presence::Null
+        } else if roll < self.null_rate + self.absent_rate {
+            Presence::Absent
+        } else {
+            Presence::Some(T::dummy_with_rng(config, rng))
+        }
+    }
+}
+
+impl<T: Dummy<U>, U> Dummy<PresenceFaker<U>> for Presence<T> {
+    fn dummy_with_rng<R: Rng + ?Sized>(config: &PresenceFaker<U>, rng: &mut R) -> Self {
+        config.value_with_rng(&config.inner, rng)
+    }
+}
@@ -0,0 +1,179 @@
+//! A C-compatible representation of [`Presence<T>`].
+//!
+//! `Presence<T>` itself has no stable layout. [`PresenceFfi<T>`] is a `#[repr(C)]` struct
+//! with a one-byte [`PresenceTag`] followed by the payload, which is only initialized when
+//! the tag is `PRESENCE_TAG_SOME`. cbindgen turns a concrete instantiation such as
+//! `PresenceFfi<int32_t>` into:
+//!
+//! ```c
+//! typedef enum PresenceTag {
+//!   PRESENCE_TAG_ABSENT = 0,
+//!   PRESENCE_TAG_NULL = 1,
+//!   PRESENCE_TAG_SOME = 2,
+//! } PresenceTag;
+//!
+//! typedef struct PresenceFfi_i32 {
+//!   PresenceTag tag;
+//!   int32_t value;
+//! } PresenceFfi_i32;
+//! ```
+//!
+//! Convert at the boundary with [`PresenceFfi::into_presence`] and `PresenceFfi::from`, and
+//! work with `Presence<T>` everywhere else.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::ffi::{PresenceFfi, PresenceTag};
+//!
+//! #[unsafe(no_mangle)]
+//! pub extern "C" fn plugin_set_timeout(timeout: PresenceFfi<u32>) -> bool {
+//!     match timeout.into_presence() {
+//!         Presence::Absent | Presence::Null => true,
+//!         Presence::Some(secs) => secs <= 300,
+//!     }
+//! }
+//!
+//! assert!(plugin_set_timeout(PresenceFfi::null()));
+//! assert!(plugin_set_timeout(Presence::Some(30).into()));
+//! assert!(!plugin_set_timeout(PresenceFfi::some(600)));
+//! assert_eq!(PresenceFfi::<u32>::absent().tag(), PresenceTag::Absent);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use std::fmt;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+/// The state stored in a [`PresenceFfi`].
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PresenceTag {
+    /// No value; the payload is uninitialized.
+    Absent = 0,
+    /// An explicit null; the payload is uninitialized.
+    Null = 1,
+    /// A value; the payload is initialized.
+    Some = 2,
+}
+
+/// A `#[repr(C)]` tagged union of a [`PresenceTag`] and a payload.
+///
+/// The fields are private so that safe Rust code can only build values whose payload
+/// matches the tag. Values coming from C must uphold the same invariant: the tag is one of
+/// the three [`PresenceTag`] values, and the payload is a valid `T` when it is `Some`.
+///
+/// An owned `Some` payload is dropped with the wrapper.
+#[repr(C)]
+pub struct PresenceFfi<T> {
+    tag: PresenceTag,
+    value: MaybeUninit<T>,
+}
+
+impl<T> PresenceFfi<T> {
+    /// Returns an `Absent` wrapper.
+    pub const fn absent() -> Self {
+        Self {
+            tag: PresenceTag::Absent,
+            value: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Returns a `Null` wrapper.
+    pub const fn null() -> Self {
+        Self {
+            tag: PresenceTag::Null,
+            value: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Returns a wrapper holding `value`.
+    pub const fn some(value: T) -> Self {
+        Self {
+            tag: PresenceTag::Some,
+            value: MaybeUninit::new(value),
+        }
+    }
+
+    /// Returns the tag.
+    pub const fn tag(&self) -> PresenceTag {
+        self.tag
+    }
+
+    /// Borrows the wrapper as a `Presence<&T>`.
+    pub fn as_presence(&self) -> Presence<&T> {
+        match self.tag {
+            PresenceTag::Absent => Presence::Absent,
+            PresenceTag::Null => Presence::Null,
+            // SAFETY: the payload is initialized whenever the tag is `Some`.
+            PresenceTag::Some => Presence::Some(unsafe { self.value.assume_init_ref() }),
+        }
+    }
+
+    /// Converts the wrapper into a `Presence<T>`.
+    pub fn into_presence(self) -> Presence<T> {
+        let this = ManuallyDrop::new(self);
+        match this.tag {
+            PresenceTag::Absent => Presence::Absent,
+            PresenceTag::Null => Presence::Null,
+            // SAFETY: the payload is initialized whenever the tag is `Some`, and `this` is
+            // not dropped, so the value is moved out exactly once.
+            PresenceTag::Some => Presence::Some(unsafe { this.value.assume_init_read() }),
+        }
+    }
+}
+
+impl<T> Drop for PresenceFfi<T> {
+    fn drop(&mut self) {
+        if self.tag == PresenceTag::Some {
+            // SAFETY: the payload is initialized whenever the tag is `Some`.
+            unsafe { self.value.assume_init_drop() }
+        }
+    }
+}
+
+impl<T> From<Presence<T>> for PresenceFfi<T> {
+    fn from(value: Presence<T>) -> Self {
+        match value {
+            Presence::Absent => Self::absent(),
+            Presence::Null => Self::null(),
+            Presence::Some(value) => Self::some(value),
+        }
+    }
+}
+
+impl<T> From<PresenceFfi<T>> for Presence<T> {
+    fn from(value: PresenceFfi<T>) -> Self {
+        value.into_presence()
+    }
+}
+
+impl<T> Default for PresenceFfi<T> {
+    fn default() -> Self {
+        Self::absent()
+    }
+}
+
+impl<T: Clone> Clone for PresenceFfi<T> {
+    fn clone(&self) -> Self {
+        self.as_presence().cloned().into()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PresenceFfi<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PresenceFfi")
+            .field(&self.as_presence())
+            .finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for PresenceFfi<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_presence() == other.as_presence()
+    }
+}
+
+impl<T: Eq> Eq for PresenceFfi<T> {}
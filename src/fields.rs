@@ -0,0 +1,436 @@
+//! Field reflection for patch structs through serde.
+//!
+//! Integrations such as protobuf field masks need to know which fields of a patch
+//! struct are defined without knowing the concrete type. This module answers that
+//! question for any `Serialize` struct by observing how it serializes:
+//!
+//! - A field skipped with `skip_serializing_if = "Presence::is_absent"` is `Absent`
+//! - A field that serializes as `null` is `Null`
+//! - Any other field is `Some`
+//!
+//! Only the top level of the value is inspected; field values are never serialized
+//! beyond the first call needed to tell `null` apart from a value. Maps with string
+//! keys (including structs using `#[serde(flatten)]`) are treated like structs.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::fields::{defined_fields, null_fields};
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct UserPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     avatar: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     email: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch {
+//!     name: Presence::Some("Bob".into()),
+//!     avatar: Presence::Null,
+//!     email: Presence::Absent,
+//! };
+//!
+//! assert_eq!(defined_fields(&patch).unwrap(), ["name", "avatar"]);
+//! assert_eq!(null_fields(&patch).unwrap(), ["avatar"]);
+//! ```
+
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+use std::fmt;
+
+/// Error returned when a value cannot be inspected as a patch struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The value does not serialize as a struct or a map with string keys.
+    NotAStruct,
+    /// The value's `Serialize` implementation reported an error.
+    Custom(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotAStruct => write!(f, "value does not serialize as a struct"),
+            Error::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error::Custom(msg.to_string())
+    }
+}
+
+/// Returns the names of all defined (`Null` or `Some`) fields, in serialization order.
+///
+/// # Errors
+///
+/// Returns [`Error::NotAStruct`] if `value` does not serialize as a struct or map.
+pub fn defined_fields<T: Serialize + ?Sized>(value: &T) -> Result<Vec<String>, Error> {
+    Ok(inspect(value)?.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Returns the names of all fields that serialize as `null`, in serialization order.
+///
+/// # Errors
+///
+/// Returns [`Error::NotAStruct`] if `value` does not serialize as a struct or map.
+pub fn null_fields<T: Serialize + ?Sized>(value: &T) -> Result<Vec<String>, Error> {
+    Ok(inspect(value)?
+        .into_iter()
+        .filter(|(_, is_null)| *is_null)
+        .map(|(name, _)| name)
+        .collect())
+}
+
+/// Returns every serialized field name paired with whether it serialized as `null`.
+pub(crate) fn inspect<T: Serialize + ?Sized>(value: &T) -> Result<Vec<(String, bool)>, Error> {
+    value.serialize(FieldSerializer)
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Struct serializer
+/////////////////////////////////////////////////////////////////////////////
+
+struct FieldSerializer;
+
+struct FieldCollector {
+    fields: Vec<(String, bool)>,
+    pending_key: Option<String>,
+}
+
+impl FieldCollector {
+    fn new() -> Self {
+        FieldCollector {
+            fields: Vec::new(),
+            pending_key: None,
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, name: String, value: &T) -> Result<(), Error> {
+        let is_null = match value.serialize(NullProbe) {
+            Ok(()) => true,
+            Err(Probe::Value) => false,
+            Err(Probe::Custom(msg)) => return Err(Error::Custom(msg)),
+        };
+        self.fields.push((name, is_null));
+        Ok(())
+    }
+}
+
+macro_rules! not_a_struct {
+    ($($method:ident($($ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ret, Self::Error> {
+                Err(Error::NotAStruct)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for FieldSerializer {
+    type Ok = Vec<(String, bool)>;
+    type Error = Error;
+    type SerializeSeq = Impossible<Self::Ok, Error>;
+    type SerializeTuple = Impossible<Self::Ok, Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Error>;
+    type SerializeMap = FieldCollector;
+    type SerializeStruct = FieldCollector;
+    type SerializeStructVariant = Impossible<Self::Ok, Error>;
+
+    not_a_struct! {
+        serialize_bool(bool) -> Self::Ok;
+        serialize_i8(i8) -> Self::Ok;
+        serialize_i16(i16) -> Self::Ok;
+        serialize_i32(i32) -> Self::Ok;
+        serialize_i64(i64) -> Self::Ok;
+        serialize_u8(u8) -> Self::Ok;
+        serialize_u16(u16) -> Self::Ok;
+        serialize_u32(u32) -> Self::Ok;
+        serialize_u64(u64) -> Self::Ok;
+        serialize_f32(f32) -> Self::Ok;
+        serialize_f64(f64) -> Self::Ok;
+        serialize_char(char) -> Self::Ok;
+        serialize_str(&str) -> Self::Ok;
+        serialize_bytes(&[u8]) -> Self::Ok;
+        serialize_none() -> Self::Ok;
+        serialize_unit() -> Self::Ok;
+        serialize_unit_struct(&'static str) -> Self::Ok;
+        serialize_unit_variant(&'static str, u32, &'static str) -> Self::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<Self::Ok, Error> {
+        Err(Error::NotAStruct)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, Error> {
+        Err(Error::NotAStruct)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+        Ok(FieldCollector::new())
+    }
+
+    fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+        Ok(FieldCollector::new())
+    }
+}
+
+impl ser::SerializeStruct for FieldCollector {
+    type Ok = Vec<(String, bool)>;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(self.fields)
+    }
+}
+
+impl ser::SerializeMap for FieldCollector {
+    type Ok = Vec<(String, bool)>;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.pending_key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| Error::Custom("map value serialized before its key".to_string()))?;
+        self.push(key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Error> {
+        Ok(self.fields)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Map key serializer
+/////////////////////////////////////////////////////////////////////////////
+
+struct KeySerializer;
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = Error;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    not_a_struct! {
+        serialize_bool(bool) -> Self::Ok;
+        serialize_i8(i8) -> Self::Ok;
+        serialize_i16(i16) -> Self::Ok;
+        serialize_i32(i32) -> Self::Ok;
+        serialize_i64(i64) -> Self::Ok;
+        serialize_u8(u8) -> Self::Ok;
+        serialize_u16(u16) -> Self::Ok;
+        serialize_u32(u32) -> Self::Ok;
+        serialize_u64(u64) -> Self::Ok;
+        serialize_f32(f32) -> Self::Ok;
+        serialize_f64(f64) -> Self::Ok;
+        serialize_bytes(&[u8]) -> Self::Ok;
+        serialize_none() -> Self::Ok;
+        serialize_unit() -> Self::Ok;
+        serialize_unit_struct(&'static str) -> Self::Ok;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, Error> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<String, Error> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, _: &T) -> Result<String, Error> {
+        Err(Error::NotAStruct)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<String, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<String, Error> {
+        Err(Error::NotAStruct)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Null probe
+/////////////////////////////////////////////////////////////////////////////
+
+/// Outcome of probing a field value: `Ok(())` means the value is `null`.
+#[derive(Debug)]
+enum Probe {
+    /// The value is not `null`; serialization stops at the first call.
+    Value,
+    /// The value's `Serialize` implementation reported an error.
+    Custom(String),
+}
+
+impl fmt::Display for Probe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Probe::Value => write!(f, "value is not null"),
+            Probe::Custom(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Probe {}
+
+impl ser::Error for Probe {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Probe::Custom(msg.to_string())
+    }
+}
+
+struct NullProbe;
+
+macro_rules! not_null {
+    ($($method:ident($($ty:ty),*) -> $ret:ty;)*) => {
+        $(
+            fn $method(self, $(_: $ty),*) -> Result<$ret, Self::Error> {
+                Err(Probe::Value)
+            }
+        )*
+    };
+}
+
+impl ser::Serializer for NullProbe {
+    type Ok = ();
+    type Error = Probe;
+    type SerializeSeq = Impossible<(), Probe>;
+    type SerializeTuple = Impossible<(), Probe>;
+    type SerializeTupleStruct = Impossible<(), Probe>;
+    type SerializeTupleVariant = Impossible<(), Probe>;
+    type SerializeMap = Impossible<(), Probe>;
+    type SerializeStruct = Impossible<(), Probe>;
+    type SerializeStructVariant = Impossible<(), Probe>;
+
+    not_null! {
+        serialize_bool(bool) -> ();
+        serialize_i8(i8) -> ();
+        serialize_i16(i16) -> ();
+        serialize_i32(i32) -> ();
+        serialize_i64(i64) -> ();
+        serialize_u8(u8) -> ();
+        serialize_u16(u16) -> ();
+        serialize_u32(u32) -> ();
+        serialize_u64(u64) -> ();
+        serialize_f32(f32) -> ();
+        serialize_f64(f64) -> ();
+        serialize_char(char) -> ();
+        serialize_str(&str) -> ();
+        serialize_bytes(&[u8]) -> ();
+        serialize_unit_struct(&'static str) -> ();
+        serialize_unit_variant(&'static str, u32, &'static str) -> ();
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_none(self) -> Result<(), Probe> {
+        Ok(())
+    }
+
+    fn serialize_unit(self) -> Result<(), Probe> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), Probe> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<(), Probe> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<(), Probe> {
+        Err(Probe::Value)
+    }
+}
@@ -0,0 +1,157 @@
+//! Firestore document updates from patch structs of [`Presence<T>`] fields.
+//!
+//! A Firestore `PATCH` only writes the fields listed in `updateMask.fieldPaths`, and a
+//! field in the mask that is missing from the document is deleted. [`update`] builds both
+//! from a patch struct:
+//!
+//! - `Some(value)` → in the mask, written with the value
+//! - `Null` → in the mask, written as an explicit `nullValue`
+//! - `Absent` → left out of the mask and the document
+//!
+//! Patches go through [`json::patch_object`], which rejects an `Absent` field that is not
+//! skipped instead of turning it into a `Null` write.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::firestore::update;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! #[derive(Serialize)]
+//! struct CityPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     mayor: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     population: Presence<u64>,
+//! }
+//!
+//! let patch = CityPatch {
+//!     name: Presence::Some("Kyiv".into()),
+//!     mayor: Presence::Null,
+//!     population: Presence::Absent,
+//! };
+//!
+//! let update = update(&patch).unwrap();
+//! assert_eq!(update.field_paths, ["mayor", "name"]);
+//! assert_eq!(
+//!     update.document,
+//!     json!({ "fields": { "name": { "stringValue": "Kyiv" }, "mayor": { "nullValue": null } } }),
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`json::patch_object`]: crate::json::patch_object
+
+use crate::json::patch_object;
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+
+/// The update mask and document fragment of a Firestore `PATCH` request.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocumentUpdate {
+    /// The paths for `updateMask.fieldPaths`, one per defined leaf field.
+    pub field_paths: Vec<String>,
+    /// The request body: a `Document` with the defined fields as Firestore values.
+    pub document: Value,
+}
+
+impl DocumentUpdate {
+    /// Returns `true` if the patch changes nothing, in which case the request can be skipped.
+    ///
+    /// Sending an empty mask would make Firestore overwrite the whole document.
+    pub fn is_empty(&self) -> bool {
+        self.field_paths.is_empty()
+    }
+}
+
+/// Builds the update mask and document fragment for a patch struct.
+///
+/// Nested objects, such as nested patch structs, are masked leaf by leaf with dotted paths
+/// like `address.city`, so a map field only has its defined keys written and keeps the
+/// rest. An empty object is masked as a whole. Field names that are not simple
+/// identifiers are quoted with backticks, as Firestore field paths require.
+///
+/// # Errors
+///
+/// Returns an error if [`patch_object`] fails.
+pub fn update<T: Serialize + ?Sized>(patch: &T) -> Result<DocumentUpdate, serde_json::Error> {
+    let fields = patch_object(patch)?;
+
+    let mut field_paths = Vec::new();
+    push_field_paths(&mut field_paths, "", &fields);
+    let fields: Map<String, Value> = fields
+        .into_iter()
+        .map(|(key, value)| (key, to_firestore_value(value)))
+        .collect();
+    Ok(DocumentUpdate {
+        field_paths,
+        document: json!({ "fields": fields }),
+    })
+}
+
+/// Converts a JSON value into a Firestore `Value`.
+///
+/// Integers become `integerValue` (as a string), other numbers `doubleValue`, arrays
+/// `arrayValue` and objects `mapValue`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::firestore::to_firestore_value;
+/// use serde_json::json;
+///
+/// assert_eq!(to_firestore_value(json!(3)), json!({ "integerValue": "3" }));
+/// assert_eq!(
+///     to_firestore_value(json!({ "tags": [true] })),
+///     json!({ "mapValue": { "fields": { "tags": { "arrayValue": { "values": [{ "booleanValue": true }] } } } } }),
+/// );
+/// ```
+pub fn to_firestore_value(value: Value) -> Value {
+    match value {
+        Value::Null => json!({ "nullValue": null }),
+        Value::Bool(value) => json!({ "booleanValue": value }),
+        Value::Number(value) if value.is_f64() => json!({ "doubleValue": value }),
+        Value::Number(value) => json!({ "integerValue": value.to_string() }),
+        Value::String(value) => json!({ "stringValue": value }),
+        Value::Array(values) => {
+            let values: Vec<Value> = values.into_iter().map(to_firestore_value).collect();
+            json!({ "arrayValue": { "values": values } })
+        }
+        Value::Object(fields) => {
+            let fields: Map<String, Value> = fields
+                .into_iter()
+                .map(|(key, value)| (key, to_firestore_value(value)))
+                .collect();
+            json!({ "mapValue": { "fields": fields } })
+        }
+    }
+}
+
+fn push_field_paths(paths: &mut Vec<String>, prefix: &str, fields: &Map<String, Value>) {
+    for (name, value) in fields {
+        let path = format!("{}{}", prefix, field_path(name));
+        match value {
+            Value::Object(nested) if !nested.is_empty() => {
+                push_field_paths(paths, &format!("{}.", path), nested)
+            }
+            _ => paths.push(path),
+        }
+    }
+}
+
+fn field_path(name: &str) -> String {
+    let mut chars = name.chars();
+    let simple = chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric());
+    if simple {
+        name.to_string()
+    } else {
+        format!("`{}`", name.replace('\\', "\\\\").replace('`', "\\`"))
+    }
+}
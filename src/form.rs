@@ -0,0 +1,520 @@
+//! HTML form decoding with [`Presence<T>`] semantics.
+//!
+//! An "edit profile" form has three kinds of fields:
+//!
+//! - An input that was not submitted (an unchecked checkbox, a disabled or missing input)
+//!   → `Absent`
+//! - An input submitted empty, or with the clear marker → `Null`
+//! - An input with a value → `Some(value)`
+//!
+//! [`Form`] reads `application/x-www-form-urlencoded` and `multipart/form-data` bodies,
+//! and deserializes patch structs from them with [`Form::deserialize`]. Values are parsed
+//! from text, so numbers and booleans (`on`, `true`, `1`, `off`, `false`, `0`) work as
+//! field types. When a key repeats, the last value wins, which supports the usual hidden
+//! input + checkbox pattern.
+//!
+//! File inputs are read with [`Form::file`]: a file input left empty is `Absent`, and a
+//! field of the same name carrying the clear marker (for example a "remove" checkbox) is
+//! `Null`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::form::Form;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct ProfilePatch {
+//!     #[serde(default)]
+//!     name: Presence<String>,
+//!     #[serde(default)]
+//!     bio: Presence<String>,
+//!     #[serde(default)]
+//!     age: Presence<u32>,
+//!     #[serde(default)]
+//!     newsletter: Presence<bool>,
+//! }
+//!
+//! let form = Form::from_urlencoded(b"name=Bob&bio=&age=31");
+//! let patch: ProfilePatch = form.deserialize().unwrap();
+//!
+//! assert_eq!(patch.name, Presence::Some("Bob".to_string()));
+//! assert_eq!(patch.bio, Presence::Null);
+//! assert_eq!(patch.age, Presence::Some(31));
+//! assert_eq!(patch.newsletter, Presence::Absent);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use serde::de::value::{Error as ValueError, MapDeserializer, StrDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A file submitted in a `multipart/form-data` body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilePart {
+    /// The name of the form field.
+    pub name: String,
+    /// The file name sent by the browser.
+    pub filename: String,
+    /// The `Content-Type` of the part, if any.
+    pub content_type: Option<String>,
+    /// The content of the file.
+    pub data: Vec<u8>,
+}
+
+/// A decoded HTML form.
+#[derive(Clone, Debug, Default)]
+pub struct Form {
+    fields: Vec<(String, String)>,
+    files: Vec<FilePart>,
+    clear_marker: Option<String>,
+}
+
+impl Form {
+    /// Decodes an `application/x-www-form-urlencoded` body.
+    pub fn from_urlencoded(body: &[u8]) -> Self {
+        Self {
+            fields: form_urlencoded::parse(body).into_owned().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Decodes a `multipart/form-data` body with the given boundary (the `boundary`
+    /// parameter of the request's `Content-Type`).
+    ///
+    /// Parts with a `filename` become files; all other parts become text fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::form::Form;
+    ///
+    /// let body = concat!(
+    ///     "--XyZ\r\n",
+    ///     "Content-Disposition: form-data; name=\"name\"\r\n\r\n",
+    ///     "Bob\r\n",
+    ///     "--XyZ\r\n",
+    ///     "Content-Disposition: form-data; name=\"avatar\"; filename=\"\"\r\n",
+    ///     "Content-Type: application/octet-stream\r\n\r\n",
+    ///     "\r\n",
+    ///     "--XyZ--\r\n",
+    /// );
+    ///
+    /// let form = Form::from_multipart(body.as_bytes(), "XyZ").unwrap();
+    /// assert_eq!(form.value("name"), Presence::Some("Bob"));
+    /// assert!(form.file("avatar").is_absent());
+    /// ```
+    pub fn from_multipart(body: &[u8], boundary: &str) -> Result<Self, FormError> {
+        let mut form = Self::default();
+        for part in multipart::parse(body, boundary)? {
+            match part.filename {
+                Some(filename) => form.files.push(FilePart {
+                    name: part.name,
+                    filename,
+                    content_type: part.content_type,
+                    data: part.data,
+                }),
+                None => {
+                    let value = String::from_utf8(part.data).map_err(|_| {
+                        FormError::Malformed(format!("field `{}` is not valid UTF-8", part.name))
+                    })?;
+                    form.fields.push((part.name, value));
+                }
+            }
+        }
+        Ok(form)
+    }
+
+    /// Sets a value that means `Null` in addition to the empty value, such as `__clear`.
+    pub fn with_clear_marker(mut self, marker: impl Into<String>) -> Self {
+        self.clear_marker = Some(marker.into());
+        self
+    }
+
+    fn is_null(&self, value: &str) -> bool {
+        value.is_empty() || self.clear_marker.as_deref() == Some(value)
+    }
+
+    /// Returns the last value submitted for `name`.
+    pub fn value(&self, name: &str) -> Presence<&str> {
+        match self.fields.iter().rev().find(|(key, _)| key == name) {
+            None => Presence::Absent,
+            Some((_, value)) if self.is_null(value) => Presence::Null,
+            Some((_, value)) => Presence::Some(value),
+        }
+    }
+
+    /// Parses the last value submitted for `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::form::Form;
+    ///
+    /// let form = Form::from_urlencoded(b"age=x&score=").with_clear_marker("-");
+    /// assert!(form.parse::<u8>("age").is_err());
+    /// assert_eq!(form.parse::<u8>("score").unwrap(), Presence::Null);
+    /// assert_eq!(form.parse::<u8>("rank").unwrap(), Presence::Absent);
+    /// ```
+    pub fn parse<T>(&self, name: &str) -> Result<Presence<T>, FormError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.value(name)
+            .map(|value| {
+                value.parse().map_err(|err: T::Err| FormError::Invalid {
+                    field: name.to_string(),
+                    message: err.to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    /// Returns the file submitted for `name`.
+    ///
+    /// - No file part, or one without a file name and content (an empty file input) →
+    ///   `Absent`
+    /// - A text field `name` with an empty value or the clear marker → `Null`
+    /// - Otherwise → `Some(file)`
+    pub fn file(&self, name: &str) -> Presence<&FilePart> {
+        let file =
+            self.files.iter().rev().find(|file| {
+                file.name == name && !(file.filename.is_empty() && file.data.is_empty())
+            });
+        match file {
+            Some(file) => Presence::Some(file),
+            None if self.value(name).is_null() => Presence::Null,
+            None => Presence::Absent,
+        }
+    }
+
+    /// Returns all text fields, in submission order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns all non-empty files, in submission order.
+    pub fn files(&self) -> impl Iterator<Item = &FilePart> {
+        self.files
+            .iter()
+            .filter(|file| !(file.filename.is_empty() && file.data.is_empty()))
+    }
+
+    /// Deserializes the text fields into `T`.
+    ///
+    /// Missing fields are `Absent` (with `#[serde(default)]`), and empty or clear-marker
+    /// values are `Null`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<T, FormError> {
+        let mut last: Vec<(&str, &str)> = Vec::new();
+        for (key, value) in &self.fields {
+            match last.iter_mut().find(|(k, _)| k == key) {
+                Some(entry) => entry.1 = value,
+                None => last.push((key, value)),
+            }
+        }
+
+        let entries = last.into_iter().map(|(key, value)| {
+            (
+                key,
+                FieldDeserializer {
+                    value,
+                    null: self.is_null(value),
+                },
+            )
+        });
+        T::deserialize(MapDeserializer::new(entries))
+            .map_err(|err: ValueError| FormError::Custom(err.to_string()))
+    }
+}
+
+/// An error from decoding a form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FormError {
+    /// The multipart body is malformed.
+    Malformed(String),
+    /// A field value could not be parsed.
+    Invalid {
+        /// The name of the field.
+        field: String,
+        /// The parse error.
+        message: String,
+    },
+    /// The fields do not match the deserialized type.
+    Custom(String),
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormError::Malformed(msg) => write!(f, "malformed multipart body: {}", msg),
+            FormError::Invalid { field, message } => {
+                write!(f, "invalid value for field `{}`: {}", field, message)
+            }
+            FormError::Custom(msg) => f.write_str(msg),
+        }
+    }
+}
+
+impl Error for FormError {}
+
+/// Deserializes a single form value, parsing primitives from text.
+struct FieldDeserializer<'a> {
+    value: &'a str,
+    null: bool,
+}
+
+impl<'de> IntoDeserializer<'de, ValueError> for FieldDeserializer<'de> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+            match self.value.parse() {
+                Ok(value) => visitor.$visit(value),
+                Err(err) => Err(de::Error::custom(format_args!(
+                    "invalid value `{}`: {}",
+                    self.value, err
+                ))),
+            }
+        }
+    )*};
+}
+
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
+    type Error = ValueError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        if self.null {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        match self.value {
+            "on" | "true" | "1" => visitor.visit_bool(true),
+            "off" | "false" | "0" => visitor.visit_bool(false),
+            value => Err(de::Error::invalid_value(
+                de::Unexpected::Str(value),
+                &"a checkbox value",
+            )),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ValueError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ValueError> {
+        StrDeserializer::<ValueError>::new(self.value).deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+mod multipart {
+    use super::FormError;
+
+    pub(super) struct Part {
+        pub name: String,
+        pub filename: Option<String>,
+        pub content_type: Option<String>,
+        pub data: Vec<u8>,
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    fn malformed(msg: &str) -> FormError {
+        FormError::Malformed(msg.to_string())
+    }
+
+    pub(super) fn parse(body: &[u8], boundary: &str) -> Result<Vec<Part>, FormError> {
+        let delimiter = format!("--{}", boundary);
+        let start = find(body, delimiter.as_bytes()).ok_or_else(|| malformed("no boundary"))?;
+        let mut rest = &body[start + delimiter.len()..];
+        let delimiter = format!("\r\n--{}", boundary);
+
+        let mut parts = Vec::new();
+        loop {
+            if rest.starts_with(b"--") {
+                return Ok(parts);
+            }
+            rest = rest
+                .strip_prefix(b"\r\n")
+                .ok_or_else(|| malformed("expected a line break after the boundary"))?;
+
+            let headers_end =
+                find(rest, b"\r\n\r\n").ok_or_else(|| malformed("unterminated part headers"))?;
+            let headers = std::str::from_utf8(&rest[..headers_end])
+                .map_err(|_| malformed("part headers are not valid UTF-8"))?;
+            let content = &rest[headers_end + 4..];
+            let data_end = find(content, delimiter.as_bytes())
+                .ok_or_else(|| malformed("missing closing boundary"))?;
+
+            parts.push(parse_part(headers, content[..data_end].to_vec())?);
+            rest = &content[data_end + delimiter.len()..];
+        }
+    }
+
+    fn parse_part(headers: &str, data: Vec<u8>) -> Result<Part, FormError> {
+        let mut name = None;
+        let mut filename = None;
+        let mut content_type = None;
+        for line in headers.split("\r\n") {
+            let Some((header, value)) = line.split_once(':') else {
+                continue;
+            };
+            if header.eq_ignore_ascii_case("content-disposition") {
+                let mut ext_filename = None;
+                for (key, value) in disposition_params(value)? {
+                    match key.as_str() {
+                        "name" => name = Some(value),
+                        "filename" => filename = Some(value),
+                        "filename*" => ext_filename = Some(ext_value(&value)?),
+                        _ => {}
+                    }
+                }
+                filename = ext_filename.or(filename);
+            } else if header.eq_ignore_ascii_case("content-type") {
+                content_type = Some(value.trim().to_string());
+            }
+        }
+
+        Ok(Part {
+            name: name.ok_or_else(|| malformed("part without a name"))?,
+            filename,
+            content_type,
+            data,
+        })
+    }
+
+    /// Returns the parameters of a `Content-Disposition` value, with lowercase names and
+    /// unquoted values.
+    fn disposition_params(value: &str) -> Result<Vec<(String, String)>, FormError> {
+        let mut params = Vec::new();
+        let mut rest = value.split_once(';').map_or("", |(_, params)| params);
+        loop {
+            rest = rest.trim_start_matches([' ', '\t', ';']);
+            if rest.is_empty() {
+                return Ok(params);
+            }
+            let (key, value) = rest
+                .split_once('=')
+                .ok_or_else(|| malformed("Content-Disposition parameter without a value"))?;
+            let value = value.trim_start();
+            let (value, after) = match value.strip_prefix('"') {
+                Some(quoted) => quoted_string(quoted)?,
+                None => {
+                    let end = value.find(';').unwrap_or(value.len());
+                    (value[..end].trim_end().to_string(), &value[end..])
+                }
+            };
+            rest = after.trim_start();
+            if !rest.is_empty() && !rest.starts_with(';') {
+                return Err(malformed(
+                    "unexpected text after a Content-Disposition parameter",
+                ));
+            }
+            params.push((key.trim().to_ascii_lowercase(), value));
+        }
+    }
+
+    /// Reads a quoted string up to its closing quote, returning its content and the rest.
+    fn quoted_string(s: &str) -> Result<(String, &str), FormError> {
+        let mut value = String::new();
+        let mut chars = s.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => return Ok((value, &s[index + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                c => value.push(c),
+            }
+        }
+        Err(malformed(
+            "unterminated quoted string in Content-Disposition",
+        ))
+    }
+
+    /// Decodes an RFC 8187 extended value such as `UTF-8''na%C3%AFve.txt`.
+    fn ext_value(value: &str) -> Result<String, FormError> {
+        let invalid = || malformed("invalid extended Content-Disposition parameter");
+        let (charset, rest) = value.split_once('\'').ok_or_else(invalid)?;
+        let (_language, encoded) = rest.split_once('\'').ok_or_else(invalid)?;
+        let bytes = percent_decode(encoded).ok_or_else(invalid)?;
+        if charset.eq_ignore_ascii_case("utf-8") {
+            String::from_utf8(bytes).map_err(|_| invalid())
+        } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+            Ok(bytes.into_iter().map(char::from).collect())
+        } else {
+            Err(malformed("unsupported charset in Content-Disposition"))
+        }
+    }
+
+    fn percent_decode(s: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(s.len());
+        let mut iter = s.bytes();
+        while let Some(byte) = iter.next() {
+            if byte == b'%' {
+                let high = char::from(iter.next()?).to_digit(16)?;
+                let low = char::from(iter.next()?).to_digit(16)?;
+                bytes.push((high * 16 + low) as u8);
+            } else {
+                bytes.push(byte);
+            }
+        }
+        Some(bytes)
+    }
+}
@@ -0,0 +1,170 @@
+//! googletest matchers for [`Presence<T>`].
+//!
+//! [`is_present`] matches a `Some` value with an inner matcher, and [`is_null`] and
+//! [`is_absent`] match the two empty states. They work on `Presence<T>` values and on
+//! references to them, and compose with the other googletest matchers.
+//!
+//! # Examples
+//!
+//! ```
+//! use googletest::prelude::*;
+//! use presence_rs::Presence;
+//! use presence_rs::googletest::{is_absent, is_null, is_present};
+//!
+//! # fn main() -> googletest::Result<()> {
+//! verify_that!(Presence::Some(42), is_present(gt(40)))?;
+//! verify_that!(Presence::<i32>::Null, is_null())?;
+//! verify_that!(Presence::<i32>::Absent, is_absent())?;
+//! verify_that!(Presence::Some("Bob".to_string()), is_present(starts_with("B")))?;
+//! verify_that!(Presence::Some(1), not(is_null()))?;
+//!
+//! assert!(verify_that!(Presence::<i32>::Null, is_present(anything())).is_err());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::{Presence, PresenceState};
+use googletest::description::Description;
+use googletest::matcher::{Matcher, MatcherBase, MatcherResult};
+use std::fmt::Debug;
+
+/// Matches a `Presence` holding a value matched by `inner`.
+pub fn is_present<Inner>(inner: Inner) -> IsPresentMatcher<Inner> {
+    IsPresentMatcher { inner }
+}
+
+/// Matches a `Presence` that is `Null`.
+pub fn is_null() -> StateMatcher {
+    StateMatcher(PresenceState::Null)
+}
+
+/// Matches a `Presence` that is `Absent`.
+pub fn is_absent() -> StateMatcher {
+    StateMatcher(PresenceState::Absent)
+}
+
+/// The matcher returned by [`is_present`].
+pub struct IsPresentMatcher<Inner> {
+    inner: Inner,
+}
+
+impl<Inner> MatcherBase for IsPresentMatcher<Inner> {}
+
+fn describe_empty(state: PresenceState) -> Description {
+    match state {
+        PresenceState::Null => "which is null".into(),
+        _ => "which is absent".into(),
+    }
+}
+
+impl<Inner> IsPresentMatcher<Inner> {
+    fn describe_with<T: Debug + Copy>(&self, matcher_result: MatcherResult) -> Description
+    where
+        Inner: Matcher<T>,
+    {
+        match matcher_result {
+            MatcherResult::Match => format!(
+                "is present with a value which {}",
+                self.inner.describe(MatcherResult::Match)
+            )
+            .into(),
+            MatcherResult::NoMatch => format!(
+                "is null, absent, or present with a value which {}",
+                self.inner.describe(MatcherResult::NoMatch)
+            )
+            .into(),
+        }
+    }
+}
+
+impl<T, Inner> Matcher<Presence<T>> for IsPresentMatcher<Inner>
+where
+    T: Debug + Copy,
+    Inner: Matcher<T>,
+{
+    fn matches(&self, actual: Presence<T>) -> MatcherResult {
+        match actual {
+            Presence::Some(value) => self.inner.matches(value),
+            Presence::Null | Presence::Absent => MatcherResult::NoMatch,
+        }
+    }
+
+    fn explain_match(&self, actual: Presence<T>) -> Description {
+        match actual {
+            Presence::Some(value) => Description::new()
+                .text("which is present")
+                .nested(self.inner.explain_match(value)),
+            empty => describe_empty(empty.state()),
+        }
+    }
+
+    fn describe(&self, matcher_result: MatcherResult) -> Description {
+        self.describe_with::<T>(matcher_result)
+    }
+}
+
+impl<'a, T, Inner> Matcher<&'a Presence<T>> for IsPresentMatcher<Inner>
+where
+    T: Debug,
+    Inner: Matcher<&'a T>,
+{
+    fn matches(&self, actual: &'a Presence<T>) -> MatcherResult {
+        match actual {
+            Presence::Some(value) => self.inner.matches(value),
+            Presence::Null | Presence::Absent => MatcherResult::NoMatch,
+        }
+    }
+
+    fn explain_match(&self, actual: &'a Presence<T>) -> Description {
+        match actual {
+            Presence::Some(value) => Description::new()
+                .text("which is present")
+                .nested(self.inner.explain_match(value)),
+            empty => describe_empty(empty.state()),
+        }
+    }
+
+    fn describe(&self, matcher_result: MatcherResult) -> Description {
+        self.describe_with::<&'a T>(matcher_result)
+    }
+}
+
+/// The matcher returned by [`is_null`] and [`is_absent`].
+pub struct StateMatcher(PresenceState);
+
+impl MatcherBase for StateMatcher {}
+
+impl StateMatcher {
+    fn describe_state(&self, matcher_result: MatcherResult) -> Description {
+        let state = match self.0 {
+            PresenceState::Null => "null",
+            _ => "absent",
+        };
+        match matcher_result {
+            MatcherResult::Match => format!("is {}", state).into(),
+            MatcherResult::NoMatch => format!("isn't {}", state).into(),
+        }
+    }
+}
+
+impl<T: Debug + Copy> Matcher<Presence<T>> for StateMatcher {
+    fn matches(&self, actual: Presence<T>) -> MatcherResult {
+        (actual.state() == self.0).into()
+    }
+
+    fn describe(&self, matcher_result: MatcherResult) -> Description {
+        self.describe_state(matcher_result)
+    }
+}
+
+impl<'a, T: Debug> Matcher<&'a Presence<T>> for StateMatcher {
+    fn matches(&self, actual: &'a Presence<T>) -> MatcherResult {
+        (actual.state() == self.0).into()
+    }
+
+    fn describe(&self, matcher_result: MatcherResult) -> Description {
+        self.describe_state(matcher_result)
+    }
+}
@@ -0,0 +1,96 @@
+//! GraphQL variables from patch structs of [`Presence<T>`] fields.
+//!
+//! GraphQL distinguishes an input field that is not provided from one provided as `null`,
+//! and servers commonly apply only the provided fields of an update input. Patch structs
+//! map onto this directly:
+//!
+//! - `Some(value)` → the field with its value
+//! - `Null` → the field as an explicit `null`
+//! - `Absent` → the field omitted
+//!
+//! The resulting JSON object can be sent as the `variables` of a hand-written query, or
+//! passed to a `graphql_client`-style client.
+//!
+//! Patches are serialized with [`json::patch_object`], so every Presence field needs
+//! `skip_serializing_if`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::graphql::input_variables;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! const QUERY: &str = "mutation UpdateUser($input: UpdateUserInput!) { updateUser(input: $input) { id } }";
+//!
+//! #[derive(Serialize)]
+//! #[serde(rename_all = "camelCase")]
+//! struct UpdateUserInput {
+//!     id: String,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     display_name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     avatar_url: Presence<String>,
+//! }
+//!
+//! let input = UpdateUserInput {
+//!     id: "42".into(),
+//!     display_name: Presence::Absent,
+//!     avatar_url: Presence::Null,
+//! };
+//!
+//! let body = json!({ "query": QUERY, "variables": input_variables("input", &input).unwrap() });
+//! assert_eq!(body["variables"], json!({ "input": { "id": "42", "avatarUrl": null } }));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`json::patch_object`]: crate::json::patch_object
+
+use crate::json::patch_object;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Converts a patch struct into a GraphQL variables object, one variable per field.
+///
+/// # Errors
+///
+/// Returns an error if [`patch_object`] fails.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::graphql::variables;
+/// use serde::Serialize;
+/// use serde_json::json;
+///
+/// #[derive(Serialize)]
+/// struct Vars {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     first: Presence<u32>,
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     after: Presence<String>,
+/// }
+///
+/// let vars = Vars { first: Presence::Some(10), after: Presence::Absent };
+/// assert_eq!(variables(&vars).unwrap(), json!({ "first": 10 }));
+/// ```
+pub fn variables<T: Serialize + ?Sized>(patch: &T) -> Result<Value, serde_json::Error> {
+    patch_object(patch).map(Value::Object)
+}
+
+/// Converts a patch struct into a variables object with the patch as the single input
+/// object variable `name`, as in `mutation($input: UpdateUserInput!)`.
+///
+/// # Errors
+///
+/// Returns an error under the same conditions as [`variables`].
+pub fn input_variables<T: Serialize + ?Sized>(
+    name: &str,
+    patch: &T,
+) -> Result<Value, serde_json::Error> {
+    let mut variables = Map::new();
+    variables.insert(name.to_string(), self::variables(patch)?);
+    Ok(Value::Object(variables))
+}
@@ -0,0 +1,674 @@
+//! Conversions between [`Presence<T>`] and [`serde_json::Value`].
+//!
+//! Code that manipulates dynamic JSON can lift individual fields into `Presence`
+//! without declaring a struct for every shape:
+//!
+//! - A missing key (`None` from [`Map::get`]) → `Absent`
+//! - `Value::Null` → `Null`
+//! - Any other value → `Some(value)`, deserialized into `T`
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use serde_json::json;
+//!
+//! let body = json!({ "name": "Alice", "avatar": null });
+//!
+//! let name = Presence::<String>::from_json_lookup(body.get("name")).unwrap();
+//! let avatar = Presence::<String>::from_json_lookup(body.get("avatar")).unwrap();
+//! let email = Presence::<String>::from_json_lookup(body.get("email")).unwrap();
+//!
+//! assert_eq!(name, Presence::Some("Alice".to_string()));
+//! assert_eq!(avatar, Presence::Null);
+//! assert_eq!(email, Presence::Absent);
+//! ```
+//!
+//! [`presence_at`] does the same for a nested value addressed by a path such as
+//! `"address/city"`.
+//!
+//! # Deep Merge
+//!
+//! [`merge`] applies a patch document to a base document with the same semantics,
+//! recursively (this is [RFC 7396] JSON Merge Patch):
+//!
+//! ```
+//! use presence_rs::json::merge;
+//! use serde_json::json;
+//!
+//! let mut user = json!({ "name": "Alice", "avatar": "a.png", "prefs": { "theme": "dark", "lang": "en" } });
+//! let patch = json!({ "avatar": null, "prefs": { "lang": "uk" } });
+//!
+//! merge(&mut user, &patch);
+//! assert_eq!(user, json!({ "name": "Alice", "prefs": { "theme": "dark", "lang": "uk" } }));
+//! ```
+//!
+//! # PATCH Requests
+//!
+//! [`PatchBody`] turns a patch struct into the body of an HTTP `PATCH` request, as a JSON
+//! Merge Patch or an [RFC 6902] JSON Patch, together with its `Content-Type`. Only the
+//! defined fields are sent, so fields the client did not touch are never overwritten:
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::json::PatchBody;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! #[derive(Serialize)]
+//! struct UserPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     avatar: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch { name: Presence::Absent, avatar: Presence::Null };
+//! let body = PatchBody::merge_patch(&patch).unwrap();
+//!
+//! assert_eq!(body.content_type(), "application/merge-patch+json");
+//! assert_eq!(body.to_vec(), br#"{"avatar":null}"#);
+//! ```
+//!
+//! [`Map::get`]: serde_json::Map::get
+//! [RFC 7396]: https://www.rfc-editor.org/rfc/rfc7396
+//! [RFC 6902]: https://www.rfc-editor.org/rfc/rfc6902
+
+use crate::presence::Presence;
+use crate::strict::Strict;
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::{Map, Value};
+
+/// Applies `patch` to `base` using presence semantics.
+///
+/// - Keys missing from a patch object leave the base untouched (`Absent`)
+/// - Keys set to `null` are removed from the base (`Null`)
+/// - Objects are merged recursively
+/// - Any other value replaces the base value (`Some`)
+///
+/// A patch that is not an object replaces `base` entirely. If `base` is not an object
+/// but `patch` is, `base` is first replaced with an empty object.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::json::merge;
+/// use serde_json::json;
+///
+/// let mut doc = json!({ "a": 1, "b": { "c": 2, "d": 3 } });
+/// merge(&mut doc, &json!({ "a": [1, 2], "b": { "c": null, "e": 4 } }));
+/// assert_eq!(doc, json!({ "a": [1, 2], "b": { "d": 3, "e": 4 } }));
+///
+/// let mut scalar = json!("old");
+/// merge(&mut scalar, &json!({ "x": null, "y": 1 }));
+/// assert_eq!(scalar, json!({ "y": 1 }));
+/// ```
+pub fn merge(base: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *base = patch.clone();
+        return;
+    };
+    if !base.is_object() {
+        *base = Value::Object(Map::new());
+    }
+    let Value::Object(base) = base else {
+        unreachable!()
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            base.remove(key);
+        } else {
+            merge(base.entry(key.as_str()).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Serializes a patch struct into a JSON object in [strict mode](crate::strict).
+///
+/// This is how every patch-building integration serializes its input. Presence fields
+/// must be annotated with `#[serde(skip_serializing_if = "Presence::is_absent")]`; a field
+/// without it fails instead of being sent as `null`, which would clear it.
+///
+/// # Errors
+///
+/// Returns an error if `patch` fails to serialize, serializes an `Absent` value, or does
+/// not serialize to a JSON object.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::json::patch_object;
+/// use serde::Serialize;
+/// use serde_json::{Value, json};
+///
+/// #[derive(Serialize)]
+/// struct UserPatch {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     name: Presence<String>,
+///     email: Presence<String>,
+/// }
+///
+/// let patch = UserPatch { name: Presence::Absent, email: Presence::Null };
+/// assert_eq!(Value::Object(patch_object(&patch).unwrap()), json!({ "email": null }));
+///
+/// let patch = UserPatch { name: Presence::Absent, email: Presence::Absent };
+/// assert!(patch_object(&patch).is_err());
+/// assert!(patch_object(&[1, 2]).is_err());
+/// ```
+pub fn patch_object<T: Serialize + ?Sized>(
+    patch: &T,
+) -> Result<Map<String, Value>, serde_json::Error> {
+    match serde_json::to_value(Strict(patch))? {
+        Value::Object(object) => Ok(object),
+        _ => Err(serde::ser::Error::custom(
+            "a patch must serialize to a JSON object",
+        )),
+    }
+}
+
+/// Returns the presence of the value at `path` in `value`.
+///
+/// `path` is a [JSON Pointer] with an optional leading `/`, such as `"a/b/0"`. A path that
+/// does not resolve is `Absent`, an explicit `null` is `Null`, and any other value is
+/// `Some`.
+///
+/// [JSON Pointer]: https://www.rfc-editor.org/rfc/rfc6901
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::json::presence_at;
+/// use serde_json::json;
+///
+/// let body = json!({ "address": { "city": "Lviv", "zip": null }, "tags": ["a"] });
+///
+/// assert_eq!(presence_at(&body, "address/city"), Presence::Some(&json!("Lviv")));
+/// assert_eq!(presence_at(&body, "address/zip"), Presence::Null);
+/// assert_eq!(presence_at(&body, "/address/street"), Presence::Absent);
+/// assert_eq!(presence_at(&body, "tags/0"), Presence::Some(&json!("a")));
+///
+/// // If `address.zip` is defined, `address.city` must be present.
+/// let valid = !presence_at(&body, "address/zip").is_defined()
+///     || presence_at(&body, "address/city").is_present();
+/// assert!(valid);
+/// ```
+pub fn presence_at<'a>(value: &'a Value, path: &str) -> Presence<&'a Value> {
+    let found = if path.is_empty() || path.starts_with('/') {
+        value.pointer(path)
+    } else {
+        value.pointer(&format!("/{}", path))
+    };
+    match found {
+        Some(Value::Null) => Presence::Null,
+        Some(value) => Presence::Some(value),
+        None => Presence::Absent,
+    }
+}
+
+impl Presence<Value> {
+    /// Applies this presence as a patch to `base`.
+    ///
+    /// - [`Absent`] leaves `base` untouched
+    /// - [`Null`] sets `base` to `Value::Null`
+    /// - [`Some`] is deep-merged into `base` with [`merge`]
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    /// [`merge`]: crate::json::merge
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::{Value, json};
+    ///
+    /// let mut settings = json!({ "volume": 3, "muted": false });
+    ///
+    /// Presence::Absent.merge_into(&mut settings);
+    /// assert_eq!(settings, json!({ "volume": 3, "muted": false }));
+    ///
+    /// Presence::Some(json!({ "muted": true })).merge_into(&mut settings);
+    /// assert_eq!(settings, json!({ "volume": 3, "muted": true }));
+    ///
+    /// Presence::Null.merge_into(&mut settings);
+    /// assert_eq!(settings, Value::Null);
+    /// ```
+    pub fn merge_into(&self, base: &mut Value) {
+        match self {
+            Presence::Some(patch) => merge(base, patch),
+            Presence::Null => *base = Value::Null,
+            Presence::Absent => {}
+        }
+    }
+}
+
+impl<T> Presence<T> {
+    /// Creates a presence from a JSON value, deserializing non-null values into `T`.
+    ///
+    /// `Value::Null` becomes [`Null`]; every other value becomes [`Some`].
+    /// A standalone value is never [`Absent`]; use [`from_json_lookup`] to
+    /// lift a map lookup.
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    /// [`from_json_lookup`]: Presence::from_json_lookup
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a non-null value cannot be deserialized into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(Presence::<u32>::from_json_value(json!(42)).unwrap(), Presence::Some(42));
+    /// assert_eq!(Presence::<u32>::from_json_value(json!(null)).unwrap(), Presence::Null);
+    /// assert!(Presence::<u32>::from_json_value(json!("42")).is_err());
+    /// ```
+    pub fn from_json_value(value: Value) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match value {
+            Value::Null => Ok(Presence::Null),
+            value => serde_json::from_value(value).map(Presence::Some),
+        }
+    }
+
+    /// Creates a presence from a borrowed JSON value, mapping `null` to [`Null`].
+    ///
+    /// This is the borrowing counterpart of [`from_json_value`]. It is also available as
+    /// `TryFrom<&Value>` for `Value`, `String`, `bool` and the primitive numbers; a
+    /// blanket impl would overlap with `From<T> for Presence<T>` when `T` is `&Value`.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`from_json_value`]: Presence::from_json_value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a non-null value cannot be deserialized into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::json;
+    ///
+    /// let value = json!([1, 2, 3]);
+    /// let p = Presence::<Vec<u8>>::from_json_ref(&value).unwrap();
+    /// assert_eq!(p, Presence::Some(vec![1, 2, 3]));
+    /// ```
+    pub fn from_json_ref(value: &Value) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match value {
+            Value::Null => Ok(Presence::Null),
+            value => T::deserialize(value).map(Presence::Some),
+        }
+    }
+
+    /// Creates a presence from the result of a JSON lookup, mapping a missing key
+    /// to [`Absent`].
+    ///
+    /// Accepts the `Option<&Value>` returned by [`Value::get`] and [`Map::get`].
+    ///
+    /// [`Absent`]: Presence::Absent
+    /// [`Map::get`]: serde_json::Map::get
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a non-null value cannot be deserialized into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::json;
+    ///
+    /// let value = json!({ "a": 1, "b": null });
+    /// assert_eq!(Presence::<i32>::from_json_lookup(value.get("a")).unwrap(), Presence::Some(1));
+    /// assert_eq!(Presence::<i32>::from_json_lookup(value.get("b")).unwrap(), Presence::Null);
+    /// assert_eq!(Presence::<i32>::from_json_lookup(value.get("c")).unwrap(), Presence::Absent);
+    /// ```
+    pub fn from_json_lookup(value: Option<&Value>) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        match value {
+            Some(value) => Presence::from_json_ref(value),
+            None => Ok(Presence::Absent),
+        }
+    }
+
+    /// Converts the presence into a JSON value, returning `None` for [`Absent`].
+    ///
+    /// [`Null`] becomes `Some(Value::Null)`, so the result can be inserted into a
+    /// JSON object only when it is `Some`, preserving all three states.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` fails to serialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::{Value, json};
+    ///
+    /// assert_eq!(Presence::Some(42).to_json_value().unwrap(), Some(json!(42)));
+    /// assert_eq!(Presence::<i32>::Null.to_json_value().unwrap(), Some(Value::Null));
+    /// assert_eq!(Presence::<i32>::Absent.to_json_value().unwrap(), None);
+    /// ```
+    pub fn to_json_value(&self) -> Result<Option<Value>, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        match self {
+            Presence::Some(value) => serde_json::to_value(value).map(Some),
+            Presence::Null => Ok(Some(Value::Null)),
+            Presence::Absent => Ok(None),
+        }
+    }
+
+    /// Parses a presence from a JSON document.
+    ///
+    /// `null` becomes [`Null`], any other value is deserialized into [`Some`], and an empty
+    /// or whitespace-only document is [`Absent`], the inverse of [`to_json_string`].
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    /// [`to_json_string`]: Presence::to_json_string
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document is not valid JSON or cannot be deserialized
+    /// into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::<u32>::from_json_str("42").unwrap(), Presence::Some(42));
+    /// assert_eq!(Presence::<u32>::from_json_str(" null ").unwrap(), Presence::Null);
+    /// assert_eq!(Presence::<u32>::from_json_str("").unwrap(), Presence::Absent);
+    /// assert!(Presence::<u32>::from_json_str("{").is_err());
+    /// ```
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        if s.trim().is_empty() {
+            return Ok(Presence::Absent);
+        }
+        serde_json::from_str::<Value>(s).and_then(Presence::from_json_value)
+    }
+
+    /// Serializes the presence as a JSON document, with [`Absent`] as an empty string.
+    ///
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` fails to serialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(vec![1, 2]).to_json_string().unwrap(), "[1,2]");
+    /// assert_eq!(Presence::<i32>::Null.to_json_string().unwrap(), "null");
+    /// assert_eq!(Presence::<i32>::Absent.to_json_string().unwrap(), "");
+    /// ```
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        match self {
+            Presence::Some(value) => serde_json::to_string(value),
+            Presence::Null => Ok("null".to_string()),
+            Presence::Absent => Ok(String::new()),
+        }
+    }
+
+    /// Serializes the presence as a pretty-printed JSON document, with [`Absent`] as an
+    /// empty string.
+    ///
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` fails to serialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use serde_json::json;
+    ///
+    /// let config = Presence::Some(json!({ "debug": true }));
+    /// assert_eq!(config.to_json_string_pretty().unwrap(), "{\n  \"debug\": true\n}");
+    /// ```
+    pub fn to_json_string_pretty(&self) -> Result<String, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        match self {
+            Presence::Some(value) => serde_json::to_string_pretty(value),
+            Presence::Null => Ok("null".to_string()),
+            Presence::Absent => Ok(String::new()),
+        }
+    }
+}
+
+macro_rules! impl_try_from_ref {
+    ($($ty:ty),*) => {$(
+        impl TryFrom<&Value> for Presence<$ty> {
+            type Error = serde_json::Error;
+
+            /// Converts a borrowed JSON value with [`Presence::from_json_ref`].
+            fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                Presence::from_json_ref(value)
+            }
+        }
+    )*};
+}
+
+impl_try_from_ref!(
+    Value, String, bool, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64
+);
+
+macro_rules! impl_value_from {
+    ($($ty:ty),*) => {$(
+        impl From<Presence<$ty>> for Value {
+            /// Converts a presence into a JSON value, with both [`Null`] and [`Absent`] as
+            /// `Value::Null`. Use [`Presence::to_json_value`] to keep `Absent` distinct.
+            ///
+            /// [`Null`]: Presence::Null
+            /// [`Absent`]: Presence::Absent
+            fn from(presence: Presence<$ty>) -> Self {
+                match presence {
+                    Presence::Some(value) => value.into(),
+                    Presence::Null | Presence::Absent => Value::Null,
+                }
+            }
+        }
+    )*};
+}
+
+impl_value_from!(
+    Value, String, &str, bool, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64
+);
+
+/// The document format of a [`PatchBody`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PatchFormat {
+    /// [RFC 7396] JSON Merge Patch: the defined fields as an object, `Null` as `null`.
+    ///
+    /// [RFC 7396]: https://www.rfc-editor.org/rfc/rfc7396
+    MergePatch,
+    /// [RFC 6902] JSON Patch: an `add` operation per value and a `remove` per `Null`.
+    ///
+    /// A `remove` fails if the member does not exist in the target document.
+    ///
+    /// [RFC 6902]: https://www.rfc-editor.org/rfc/rfc6902
+    JsonPatch,
+}
+
+impl PatchFormat {
+    /// Returns the media type to send in the `Content-Type` header.
+    pub fn content_type(self) -> &'static str {
+        match self {
+            PatchFormat::MergePatch => "application/merge-patch+json",
+            PatchFormat::JsonPatch => "application/json-patch+json",
+        }
+    }
+}
+
+/// The body of an HTTP `PATCH` request built from a patch struct.
+///
+/// The patch is serialized with [`patch_object`], so its `Absent` fields must be skipped.
+///
+/// Nested objects are merged like [`merge`] does, so a JSON Patch contains an operation
+/// for each changed leaf, and the containing objects must already exist on the server.
+///
+/// The formats differ for a `Null` field whose member does not exist on the server: a
+/// merge patch ignores it, while the `remove` operation of a JSON Patch requires an
+/// existing member, so the server rejects the whole patch. Use a merge patch unless the
+/// current document is known.
+///
+/// The body is independent of any HTTP client; with reqwest, for example:
+///
+/// ```ignore
+/// let body = PatchBody::merge_patch(&patch)?;
+/// if !body.is_empty() {
+///     client
+///         .patch(url)
+///         .header(CONTENT_TYPE, body.content_type())
+///         .body(body.to_vec())
+///         .send()
+///         .await?;
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatchBody {
+    format: PatchFormat,
+    value: Value,
+}
+
+impl PatchBody {
+    /// Serializes `patch` into a body of the given format.
+    ///
+    /// Fails if [`patch_object`] fails.
+    pub fn new<T: Serialize + ?Sized>(
+        patch: &T,
+        format: PatchFormat,
+    ) -> Result<Self, serde_json::Error> {
+        let object = patch_object(patch)?;
+
+        let value = match format {
+            PatchFormat::MergePatch => Value::Object(object),
+            PatchFormat::JsonPatch => {
+                let mut operations = Vec::new();
+                push_operations(&mut operations, &mut String::new(), object);
+                Value::Array(operations)
+            }
+        };
+        Ok(Self { format, value })
+    }
+
+    /// Serializes `patch` into a JSON Merge Patch body.
+    pub fn merge_patch<T: Serialize + ?Sized>(patch: &T) -> Result<Self, serde_json::Error> {
+        Self::new(patch, PatchFormat::MergePatch)
+    }
+
+    /// Serializes `patch` into a JSON Patch body.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::json::PatchBody;
+    /// use serde_json::json;
+    ///
+    /// let patch = json!({ "name": "Bob", "prefs": { "theme": null } });
+    /// let body = PatchBody::json_patch(&patch).unwrap();
+    ///
+    /// assert_eq!(body.content_type(), "application/json-patch+json");
+    /// assert_eq!(
+    ///     body.value(),
+    ///     &json!([
+    ///         { "op": "add", "path": "/name", "value": "Bob" },
+    ///         { "op": "remove", "path": "/prefs/theme" },
+    ///     ])
+    /// );
+    /// ```
+    pub fn json_patch<T: Serialize + ?Sized>(patch: &T) -> Result<Self, serde_json::Error> {
+        Self::new(patch, PatchFormat::JsonPatch)
+    }
+
+    /// Returns the format of the body.
+    pub fn format(&self) -> PatchFormat {
+        self.format
+    }
+
+    /// Returns the media type to send in the `Content-Type` header.
+    pub fn content_type(&self) -> &'static str {
+        self.format.content_type()
+    }
+
+    /// Returns `true` if the patch changes nothing, in which case the request can be skipped.
+    pub fn is_empty(&self) -> bool {
+        match &self.value {
+            Value::Object(object) => object.is_empty(),
+            Value::Array(operations) => operations.is_empty(),
+            // `new` only builds objects and arrays.
+            _ => false,
+        }
+    }
+
+    /// Returns the JSON document of the body.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Consumes the body, returning its JSON document.
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+
+    /// Serializes the body into bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        serde_json::to_vec(&self.value).expect("a JSON value always serializes")
+    }
+}
+
+fn push_operations(operations: &mut Vec<Value>, path: &mut String, object: Map<String, Value>) {
+    for (key, value) in object {
+        let len = path.len();
+        path.push('/');
+        path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+        match value {
+            Value::Null => operations.push(serde_json::json!({ "op": "remove", "path": path })),
+            // An empty nested patch is a value of its own, not an absence of operations.
+            Value::Object(object) if !object.is_empty() => {
+                push_operations(operations, path, object)
+            }
+            value => {
+                operations.push(serde_json::json!({ "op": "add", "path": path, "value": value }))
+            }
+        }
+        path.truncate(len);
+    }
+}
@@ -0,0 +1,141 @@
+//! JSON:API resource documents around patch structs of [`Presence<T>`] fields.
+//!
+//! A JSON:API `PATCH` request carries a resource object whose `attributes` hold only the
+//! attributes to change, which is exactly a patch struct:
+//!
+//! - `Some(value)` → the attribute with its value
+//! - `Null` → the attribute set to `null`
+//! - `Absent` → the attribute omitted, leaving it unchanged
+//!
+//! [`Resource`] wraps a patch struct in the `{"data": {"type", "id", "attributes"}}`
+//! envelope with [`Resource::to_document`] and unwraps it with [`Resource::from_document`].
+//! Presence fields need `#[serde(default)]` so omitted attributes read back as `Absent`,
+//! and `skip_serializing_if` as required by [`json::patch_object`].
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::jsonapi::Resource;
+//! use serde::{Deserialize, Serialize};
+//! use serde_json::json;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct ArticlePatch {
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     title: Presence<String>,
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     summary: Presence<String>,
+//! }
+//!
+//! let request = json!({
+//!     "data": { "type": "articles", "id": "1", "attributes": { "summary": null } }
+//! });
+//!
+//! let resource = Resource::<ArticlePatch>::from_document(request.clone()).unwrap();
+//! assert_eq!(resource.kind, "articles");
+//! assert_eq!(resource.attributes.title, Presence::Absent);
+//! assert_eq!(resource.attributes.summary, Presence::Null);
+//!
+//! assert_eq!(resource.to_document().unwrap(), request);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`json::patch_object`]: crate::json::patch_object
+
+use crate::json::patch_object;
+use serde::Serialize;
+use serde::de::{DeserializeOwned, Error as _};
+use serde_json::{Map, Value, json};
+
+/// A JSON:API resource object with the attributes of a patch struct.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Resource<T> {
+    /// The resource `type`.
+    pub kind: String,
+    /// The resource `id`, which is `None` for resources created without a client id.
+    pub id: Option<String>,
+    /// The attributes of the resource.
+    pub attributes: T,
+}
+
+impl<T> Resource<T> {
+    /// Creates a resource of the given type and id.
+    pub fn new(kind: impl Into<String>, id: impl Into<String>, attributes: T) -> Self {
+        Self {
+            kind: kind.into(),
+            id: Some(id.into()),
+            attributes,
+        }
+    }
+}
+
+impl<T: Serialize> Resource<T> {
+    /// Wraps the resource in a JSON:API document, as `{"data": {...}}`.
+    ///
+    /// The `id` member is left out when it is `None`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`patch_object`] fails for the attributes.
+    pub fn to_document(&self) -> Result<Value, serde_json::Error> {
+        let attributes = patch_object(&self.attributes)?;
+
+        let mut data = Map::new();
+        data.insert("type".to_string(), Value::String(self.kind.clone()));
+        if let Some(id) = &self.id {
+            data.insert("id".to_string(), Value::String(id.clone()));
+        }
+        data.insert("attributes".to_string(), Value::Object(attributes));
+        Ok(json!({ "data": data }))
+    }
+}
+
+impl<T: DeserializeOwned> Resource<T> {
+    /// Unwraps the resource of a JSON:API document.
+    ///
+    /// A resource without `attributes` is read as an empty attributes object, so every
+    /// Presence field is `Absent`. Other members of the document, such as
+    /// `relationships` or `meta`, are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document has no resource object in `data`, the resource has
+    /// no string `type` or a non-string `id`, or the attributes fail to deserialize.
+    pub fn from_document(document: Value) -> Result<Self, serde_json::Error> {
+        let Value::Object(mut document) = document else {
+            return Err(serde_json::Error::custom(
+                "a JSON:API document must be an object",
+            ));
+        };
+        let Some(Value::Object(mut data)) = document.remove("data") else {
+            return Err(serde_json::Error::custom(
+                "a JSON:API document must have a resource object in `data`",
+            ));
+        };
+
+        let Some(Value::String(kind)) = data.remove("type") else {
+            return Err(serde_json::Error::custom(
+                "a JSON:API resource must have a string `type`",
+            ));
+        };
+        let id = match data.remove("id") {
+            None => None,
+            Some(Value::String(id)) => Some(id),
+            Some(_) => {
+                return Err(serde_json::Error::custom(
+                    "a JSON:API resource `id` must be a string",
+                ));
+            }
+        };
+        let attributes = data
+            .remove("attributes")
+            .unwrap_or_else(|| Value::Object(Map::new()));
+
+        Ok(Self {
+            kind,
+            id,
+            attributes: serde_json::from_value(attributes)?,
+        })
+    }
+}
@@ -39,12 +39,161 @@
 //!
 //! [`Presence<T>`]: presence::Presence
 
+pub mod apply;
+pub mod builder;
+pub mod columnar;
+pub mod config;
+pub mod env;
+pub mod ext;
+pub mod ffi;
+pub mod map;
+pub mod merge;
+pub mod optics;
 pub mod presence;
-pub use presence::Presence;
+pub mod reflect;
+pub mod sql;
+pub mod text;
+pub mod tracked;
+pub mod tri;
+pub mod validate;
+pub use presence::{Presence, PresenceState};
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::{PresenceBuilder, PresenceGetters, PresenceSetters};
 
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "serde")]
+pub use serde::PresenceSeed;
+
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use ::serde as __serde;
+
+#[cfg(all(feature = "derive", feature = "serde"))]
+pub use presence_rs_derive::DeserializePresence;
+
+#[cfg(feature = "serde")]
+pub mod fields;
+
+#[cfg(feature = "serde")]
+pub mod strict;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+
+#[cfg(feature = "json")]
+pub mod json;
+
+#[cfg(feature = "json")]
+pub mod schema;
+
+#[cfg(feature = "json")]
+pub mod audit;
+
+#[cfg(feature = "json")]
+pub mod changeset;
+
+#[cfg(feature = "bson")]
+pub mod bson;
+
+#[cfg(feature = "dynamodb")]
+pub mod dynamodb;
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+
+#[cfg(feature = "parquet")]
+pub mod parquet;
+
+#[cfg(feature = "polars")]
+pub mod polars;
+
+#[cfg(feature = "elasticsearch")]
+pub mod elasticsearch;
+
+#[cfg(feature = "firestore")]
+pub mod firestore;
+
+#[cfg(feature = "codegen")]
+pub mod codegen;
+
+#[cfg(feature = "jsonapi")]
+pub mod jsonapi;
+
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
+#[cfg(feature = "tokio")]
+pub mod tokio;
+
+#[cfg(feature = "prost")]
+pub mod prost;
+
+#[cfg(feature = "async-graphql")]
+mod async_graphql;
+
+#[cfg(feature = "schemars")]
+mod schemars;
+
+#[cfg(feature = "utoipa")]
+mod utoipa;
+
+#[cfg(feature = "ts-rs")]
+mod ts_rs;
+
+#[cfg(feature = "specta")]
+mod specta;
+
+#[cfg(feature = "sqlx")]
+pub mod sqlx;
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+
+#[cfg(feature = "sea-orm")]
+mod sea_orm;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+
+#[cfg(feature = "sea-query")]
+pub mod sea_query;
+
+#[cfg(feature = "redis")]
+pub mod redis;
+
+#[cfg(feature = "axum")]
+pub mod axum;
+
+#[cfg(feature = "clap")]
+pub mod clap;
+
+#[cfg(feature = "form")]
+pub mod form;
+
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "napi")]
+pub mod napi;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "fake")]
+pub mod fake;
+
+#[cfg(feature = "googletest")]
+pub mod googletest;
+
 /// Convenience macro for creating [`Presence`] values.
 ///
 /// This macro provides a concise syntax for constructing `Presence` values,
@@ -57,6 +206,8 @@ mod serde;
 /// - `presence!()` - Creates `Presence::Absent`
 /// - `presence!(null)` - Creates `Presence::Null`
 /// - `presence!(value)` - Creates `Presence::Some(value)`
+/// - `presence!(? option)` - Converts an `Option<T>`, with `None` as `Presence::Absent`
+/// - `presence!(?? nested)` - Converts an `Option<Option<T>>`, preserving all three states
 ///
 /// # Examples
 ///
@@ -78,6 +229,14 @@ mod serde;
 ///
 /// let owned = presence!("hello".to_string());
 /// assert_eq!(owned, presence::Presence::Some("hello".to_string()));
+///
+/// // Converting options
+/// let nickname: Option<&str> = None;
+/// assert_eq!(presence!(? nickname), presence::Presence::Absent);
+/// assert_eq!(presence!(? Some(1)), presence::Presence::Some(1));
+///
+/// let avatar: Option<Option<&str>> = Some(None);
+/// assert_eq!(presence!(?? avatar), presence::Presence::Null);
 /// ```
 #[macro_export]
 macro_rules! presence {
@@ -87,7 +246,285 @@ macro_rules! presence {
     (null) => {
         $crate::presence::Presence::Null
     };
+    (?? $nested:expr) => {
+        $crate::presence::Presence::from_nullable($nested)
+    };
+    (? $option:expr) => {
+        $crate::presence::Presence::from_optional($option)
+    };
     ($value:expr) => {
         $crate::presence::Presence::Some($value)
     };
 }
+
+#[doc(hidden)]
+pub mod __private {
+    /// The value conversion of `presence_patch!`: the value itself, or a `String` from a
+    /// `&str`. Unlike `Into`, this keeps integer literals inferable.
+    pub trait PatchValue<T> {
+        fn into_patch_value(self) -> T;
+    }
+
+    impl<T> PatchValue<T> for T {
+        fn into_patch_value(self) -> T {
+            self
+        }
+    }
+
+    impl PatchValue<String> for &str {
+        fn into_patch_value(self) -> String {
+            self.to_string()
+        }
+    }
+}
+
+/// Builds a patch struct literal with `Presence` fields.
+///
+/// Each field is written as `field: value`:
+///
+/// - `field: @null` → `Presence::Null`
+/// - `field: @absent` → `Presence::Absent`
+/// - `field: expr` → `Presence::Some(expr)`, with string literals converted to `String`
+///
+/// The `@` markers cannot start an expression, so locals named `null` or `absent` are
+/// still read as values.
+///
+/// Ending the field list with `..` leaves every other field at its `Default`, which is
+/// `Absent` for `Presence` fields, so the struct must implement `Default`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, presence_patch};
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct UserPatch {
+///     name: Presence<String>,
+///     avatar: Presence<String>,
+///     age: Presence<u32>,
+/// }
+///
+/// let patch = presence_patch!(UserPatch { name: "Bob", avatar: @null, .. });
+///
+/// assert_eq!(patch, UserPatch {
+///     name: Presence::Some("Bob".to_string()),
+///     avatar: Presence::Null,
+///     age: Presence::Absent,
+/// });
+/// ```
+#[macro_export]
+macro_rules! presence_patch {
+    ($($ty:ident)::+ { $($fields:tt)* }) => {
+        $crate::__presence_patch!(@fields [$($ty)::+]; []; $($fields)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __presence_patch {
+    (@fields [$($ty:ident)::+]; [$($done:tt)*]; ) => {
+        $($ty)::+ { $($done)* }
+    };
+    (@fields [$($ty:ident)::+]; [$($done:tt)*]; ..) => {
+        $($ty)::+ { $($done)* ..::core::default::Default::default() }
+    };
+    (@fields [$($ty:ident)::+]; [$($done:tt)*]; $field:ident : @null $(, $($rest:tt)*)?) => {
+        $crate::__presence_patch!(
+            @fields [$($ty)::+]; [$($done)* $field: $crate::presence::Presence::Null,]; $($($rest)*)?
+        )
+    };
+    (@fields [$($ty:ident)::+]; [$($done:tt)*]; $field:ident : @absent $(, $($rest:tt)*)?) => {
+        $crate::__presence_patch!(
+            @fields [$($ty)::+]; [$($done)* $field: $crate::presence::Presence::Absent,]; $($($rest)*)?
+        )
+    };
+    (@fields [$($ty:ident)::+]; [$($done:tt)*]; $field:ident : $value:expr $(, $($rest:tt)*)?) => {
+        $crate::__presence_patch!(
+            @fields [$($ty)::+];
+            [$($done)* $field: $crate::presence::Presence::Some(
+                $crate::__private::PatchValue::into_patch_value($value)
+            ),];
+            $($($rest)*)?
+        )
+    };
+}
+
+/// Matches on a [`Presence`] with short arm names.
+///
+/// The arms are `some(pattern)`, `null` and `absent`, in that order. The two empty states
+/// can share an arm with `null | absent`.
+///
+/// [`Presence`]: presence::Presence
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, match_presence};
+///
+/// fn describe(age: Presence<u32>) -> String {
+///     match_presence!(age,
+///         some(years) => format!("set to {}", years),
+///         null => "cleared".to_string(),
+///         absent => "unchanged".to_string(),
+///     )
+/// }
+/// assert_eq!(describe(Presence::Some(31)), "set to 31");
+/// assert_eq!(describe(Presence::Null), "cleared");
+///
+/// let name = Presence::Some(("Ada", "Lovelace"));
+/// let first = match_presence!(name, some((first, _)) => first, null | absent => "anonymous");
+/// assert_eq!(first, "Ada");
+/// ```
+#[macro_export]
+macro_rules! match_presence {
+    ($presence:expr, some($value:pat) => $some:expr, null => $null:expr, absent => $absent:expr $(,)?) => {
+        match $presence {
+            $crate::presence::Presence::Some($value) => $some,
+            $crate::presence::Presence::Null => $null,
+            $crate::presence::Presence::Absent => $absent,
+        }
+    };
+    ($presence:expr, some($value:pat) => $some:expr, null | absent => $empty:expr $(,)?) => {
+        match $presence {
+            $crate::presence::Presence::Some($value) => $some,
+            $crate::presence::Presence::Null | $crate::presence::Presence::Absent => $empty,
+        }
+    };
+}
+
+/// Asserts that a [`Presence`] is `Some` and evaluates to its value.
+///
+/// On failure, the panic message names the expression and its actual state. Passing a
+/// reference (`assert_present!(&p)`) returns a reference to the value. A custom message can
+/// follow the expression, as with [`assert!`].
+///
+/// [`Presence`]: presence::Presence
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, assert_present};
+///
+/// let name = Presence::Some("Bob".to_string());
+/// assert_eq!(assert_present!(&name), "Bob");
+/// assert_eq!(assert_present!(name, "name should be set"), "Bob");
+/// ```
+///
+/// ```should_panic
+/// use presence_rs::{Presence, assert_present};
+///
+/// // panics with: assertion failed: `Presence::<i32>::Null` is present
+/// //                actual: Null
+/// assert_present!(Presence::<i32>::Null);
+/// ```
+#[macro_export]
+macro_rules! assert_present {
+    ($presence:expr $(,)?) => {
+        match $presence {
+            $crate::presence::Presence::Some(value) => value,
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is present\n  actual: {:?}",
+                ::core::stringify!($presence),
+                other.state(),
+            ),
+        }
+    };
+    ($presence:expr, $($arg:tt)+) => {
+        match $presence {
+            $crate::presence::Presence::Some(value) => value,
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is present: {}\n  actual: {:?}",
+                ::core::stringify!($presence),
+                ::core::format_args!($($arg)+),
+                other.state(),
+            ),
+        }
+    };
+}
+
+/// Asserts that a [`Presence`] is `Null`.
+///
+/// On failure, the panic message names the expression and shows its actual value, so the
+/// value type must implement `Debug`. A custom message can follow the expression.
+///
+/// [`Presence`]: presence::Presence
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, assert_null};
+///
+/// assert_null!(Presence::<i32>::Null);
+/// ```
+///
+/// ```should_panic
+/// use presence_rs::{Presence, assert_null};
+///
+/// // panics with: assertion failed: `Presence::Some(3)` is null
+/// //                actual: Some(3)
+/// assert_null!(Presence::Some(3));
+/// ```
+#[macro_export]
+macro_rules! assert_null {
+    ($presence:expr $(,)?) => {
+        match $presence {
+            $crate::presence::Presence::Null => {}
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is null\n  actual: {:?}",
+                ::core::stringify!($presence),
+                other,
+            ),
+        }
+    };
+    ($presence:expr, $($arg:tt)+) => {
+        match $presence {
+            $crate::presence::Presence::Null => {}
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is null: {}\n  actual: {:?}",
+                ::core::stringify!($presence),
+                ::core::format_args!($($arg)+),
+                other,
+            ),
+        }
+    };
+}
+
+/// Asserts that a [`Presence`] is `Absent`.
+///
+/// On failure, the panic message names the expression and shows its actual value, so the
+/// value type must implement `Debug`. A custom message can follow the expression.
+///
+/// [`Presence`]: presence::Presence
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, assert_absent};
+///
+/// let patch: Presence<String> = Presence::Absent;
+/// assert_absent!(patch, "untouched fields must not be sent");
+/// ```
+#[macro_export]
+macro_rules! assert_absent {
+    ($presence:expr $(,)?) => {
+        match $presence {
+            $crate::presence::Presence::Absent => {}
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is absent\n  actual: {:?}",
+                ::core::stringify!($presence),
+                other,
+            ),
+        }
+    };
+    ($presence:expr, $($arg:tt)+) => {
+        match $presence {
+            $crate::presence::Presence::Absent => {}
+            ref other => ::core::panic!(
+                "assertion failed: `{}` is absent: {}\n  actual: {:?}",
+                ::core::stringify!($presence),
+                ::core::format_args!($($arg)+),
+                other,
+            ),
+        }
+    };
+}
@@ -0,0 +1,595 @@
+//! Maps of [`Presence<T>`] values keyed by field name.
+//!
+//! A patch document is often kept as a map from field name to `Presence<V>`: a missing key
+//! leaves the field alone, a `Null` value clears it, and a `Some` value sets it.
+//! [`PresenceEntryExt::presence_entry`] gives `HashMap` and `BTreeMap` an entry API that
+//! reads a missing key as `Absent`, so all three states are handled in one place.
+//! [`PresenceMap`] is a map type built around the same idea, with a missing key as
+//! `Absent` and a tombstoned key as `Null`.
+//!
+//! With the `serde` feature, `KnownKeys` deserializes a patch map against a known field
+//! list, filling in the fields that were not sent as `Absent`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::map::PresenceEntryExt;
+//! use std::collections::HashMap;
+//!
+//! let mut patch: HashMap<&str, Presence<String>> = HashMap::new();
+//! patch.insert("bio", Presence::Null);
+//!
+//! assert_eq!(patch.presence_entry("name").get(), Presence::Absent);
+//! assert_eq!(patch.presence_entry("bio").get(), Presence::Null);
+//!
+//! // Fill in a default for fields that are missing or cleared.
+//! patch.presence_entry("bio").or_insert_with(|| "n/a".to_string());
+//! assert_eq!(patch["bio"], Presence::Some("n/a".to_string()));
+//!
+//! // Clear a field unless the patch already says something about it.
+//! patch.presence_entry("avatar").or_insert_null();
+//! assert_eq!(patch["avatar"], Presence::Null);
+//!
+//! // Setting `Absent` removes the key.
+//! let previous = patch.presence_entry("avatar").set(Presence::Absent);
+//! assert_eq!(previous, Presence::Null);
+//! assert!(!patch.contains_key("avatar"));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap, btree_map, hash_map};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+mod private {
+    use crate::presence::Presence;
+
+    /// A map entry whose value is a `Presence`.
+    pub trait Entry<'a>: Sized {
+        type Key;
+        type Value: 'a;
+
+        fn key(&self) -> &Self::Key;
+        fn slot(&self) -> Option<&Presence<Self::Value>>;
+        fn slot_mut(&mut self) -> Option<&mut Presence<Self::Value>>;
+        fn or_insert_with<F>(self, f: F) -> &'a mut Presence<Self::Value>
+        where
+            F: FnOnce() -> Presence<Self::Value>;
+        fn remove(self) -> Option<Presence<Self::Value>>;
+    }
+}
+
+use private::Entry;
+
+impl<'a, K, V> Entry<'a> for hash_map::Entry<'a, K, Presence<V>> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        self.key()
+    }
+
+    fn slot(&self) -> Option<&Presence<V>> {
+        match self {
+            hash_map::Entry::Occupied(entry) => Some(entry.get()),
+            hash_map::Entry::Vacant(_) => None,
+        }
+    }
+
+    fn slot_mut(&mut self) -> Option<&mut Presence<V>> {
+        match self {
+            hash_map::Entry::Occupied(entry) => Some(entry.get_mut()),
+            hash_map::Entry::Vacant(_) => None,
+        }
+    }
+
+    fn or_insert_with<F>(self, f: F) -> &'a mut Presence<V>
+    where
+        F: FnOnce() -> Presence<V>,
+    {
+        self.or_insert_with(f)
+    }
+
+    fn remove(self) -> Option<Presence<V>> {
+        match self {
+            hash_map::Entry::Occupied(entry) => Some(entry.remove()),
+            hash_map::Entry::Vacant(_) => None,
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Entry<'a> for btree_map::Entry<'a, K, Presence<V>> {
+    type Key = K;
+    type Value = V;
+
+    fn key(&self) -> &K {
+        self.key()
+    }
+
+    fn slot(&self) -> Option<&Presence<V>> {
+        match self {
+            btree_map::Entry::Occupied(entry) => Some(entry.get()),
+            btree_map::Entry::Vacant(_) => None,
+        }
+    }
+
+    fn slot_mut(&mut self) -> Option<&mut Presence<V>> {
+        match self {
+            btree_map::Entry::Occupied(entry) => Some(entry.get_mut()),
+            btree_map::Entry::Vacant(_) => None,
+        }
+    }
+
+    fn or_insert_with<F>(self, f: F) -> &'a mut Presence<V>
+    where
+        F: FnOnce() -> Presence<V>,
+    {
+        self.or_insert_with(f)
+    }
+
+    fn remove(self) -> Option<Presence<V>> {
+        match self {
+            btree_map::Entry::Occupied(entry) => Some(entry.remove()),
+            btree_map::Entry::Vacant(_) => None,
+        }
+    }
+}
+
+/// Entry API for maps of `Presence` values.
+pub trait PresenceEntryExt<K, V> {
+    /// The underlying entry type of the map.
+    type Entry<'a>
+    where
+        Self: 'a;
+
+    /// Gets the entry for `key`, reading a missing key as `Absent`.
+    fn presence_entry(&mut self, key: K) -> PresenceEntry<Self::Entry<'_>>;
+}
+
+impl<K: Eq + Hash, V, S: BuildHasher> PresenceEntryExt<K, V> for HashMap<K, Presence<V>, S> {
+    type Entry<'a>
+        = hash_map::Entry<'a, K, Presence<V>>
+    where
+        Self: 'a;
+
+    fn presence_entry(&mut self, key: K) -> PresenceEntry<Self::Entry<'_>> {
+        PresenceEntry {
+            inner: self.entry(key),
+        }
+    }
+}
+
+impl<K: Ord, V> PresenceEntryExt<K, V> for BTreeMap<K, Presence<V>> {
+    type Entry<'a>
+        = btree_map::Entry<'a, K, Presence<V>>
+    where
+        Self: 'a;
+
+    fn presence_entry(&mut self, key: K) -> PresenceEntry<Self::Entry<'_>> {
+        PresenceEntry {
+            inner: self.entry(key),
+        }
+    }
+}
+
+/// A view into a single key of a map of `Presence` values.
+///
+/// Created by [`PresenceEntryExt::presence_entry`]. A missing key reads as `Absent`.
+#[derive(Debug)]
+pub struct PresenceEntry<E> {
+    inner: E,
+}
+
+impl<'a, E: Entry<'a>> PresenceEntry<E> {
+    /// Returns the key of this entry.
+    pub fn key(&self) -> &E::Key {
+        self.inner.key()
+    }
+
+    /// Returns the state of this entry: `Absent` if the key is missing, and the stored
+    /// presence otherwise.
+    pub fn get(&self) -> Presence<&E::Value> {
+        match self.inner.slot() {
+            Some(slot) => slot.as_ref(),
+            None => Presence::Absent,
+        }
+    }
+
+    /// Returns the state of this entry with a mutable reference to a stored value.
+    pub fn get_mut(&mut self) -> Presence<&mut E::Value> {
+        match self.inner.slot_mut() {
+            Some(slot) => slot.as_mut(),
+            None => Presence::Absent,
+        }
+    }
+
+    /// Calls `f` with the stored value if the entry is `Some`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::map::PresenceEntryExt;
+    /// use std::collections::BTreeMap;
+    ///
+    /// let mut counters = BTreeMap::from([("hits", Presence::Some(1)), ("misses", Presence::Null)]);
+    /// counters.presence_entry("hits").and_modify(|n| *n += 1);
+    /// counters.presence_entry("misses").and_modify(|n| *n += 1);
+    /// assert_eq!(counters["hits"], Presence::Some(2));
+    /// assert_eq!(counters["misses"], Presence::Null);
+    /// ```
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut E::Value),
+    {
+        if let Presence::Some(value) = self.get_mut() {
+            f(value);
+        }
+        self
+    }
+
+    /// Stores `Some(value)` if the entry is `Absent` or `Null`, then returns a mutable
+    /// reference to the stored value.
+    pub fn or_insert(self, value: E::Value) -> &'a mut E::Value {
+        self.or_insert_with(|| value)
+    }
+
+    /// Stores `Some` of the result of `f` if the entry is `Absent` or `Null`, then returns
+    /// a mutable reference to the stored value.
+    pub fn or_insert_with<F>(self, f: F) -> &'a mut E::Value
+    where
+        F: FnOnce() -> E::Value,
+    {
+        self.inner
+            .or_insert_with(|| Presence::Absent)
+            .get_or_insert_with(f)
+    }
+
+    /// Stores `Some` of the default value if the entry is `Absent` or `Null`, then returns
+    /// a mutable reference to the stored value.
+    pub fn or_default(self) -> &'a mut E::Value
+    where
+        E::Value: Default,
+    {
+        self.or_insert_with(Default::default)
+    }
+
+    /// Stores `Null` if the key is missing, then returns a mutable reference to the stored
+    /// presence.
+    pub fn or_insert_null(self) -> &'a mut Presence<E::Value> {
+        self.inner.or_insert_with(|| Presence::Null)
+    }
+
+    /// Sets the entry to `value`, returning the previous state.
+    ///
+    /// Setting `Absent` removes the key, so the map never stores `Absent`.
+    pub fn set(self, value: Presence<E::Value>) -> Presence<E::Value> {
+        match value {
+            Presence::Absent => self.remove(),
+            value => std::mem::replace(self.inner.or_insert_with(|| Presence::Absent), value),
+        }
+    }
+
+    /// Removes the key, returning the previous state.
+    pub fn remove(self) -> Presence<E::Value> {
+        self.inner.remove().unwrap_or(Presence::Absent)
+    }
+}
+
+/// A map whose keys are either set to a value or tombstoned.
+///
+/// [`get`](PresenceMap::get) returns `Absent` for a missing key, `Null` for a tombstoned
+/// key, and `Some` for a key with a value, which makes the map a dynamic patch document.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::map::PresenceMap;
+///
+/// let mut patch = PresenceMap::new();
+/// let _ = patch.insert("name", "Alice");
+/// let _ = patch.tombstone("nickname");
+///
+/// assert_eq!(patch.get("name"), Presence::Some(&"Alice"));
+/// assert_eq!(patch.get("nickname"), Presence::Null);
+/// assert_eq!(patch.get("email"), Presence::Absent);
+///
+/// assert_eq!(patch.entries().collect::<Vec<_>>(), [(&"name", &"Alice")]);
+/// assert_eq!(patch.tombstones().collect::<Vec<_>>(), [&"nickname"]);
+/// ```
+#[derive(Clone)]
+pub struct PresenceMap<K, V> {
+    entries: HashMap<K, Option<V>>,
+}
+
+impl<K, V> PresenceMap<K, V> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Returns the number of keys, including tombstoned keys.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all keys.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Iterates over all keys with their state, which is `Null` or `Some`.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, Presence<&V>)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key, Presence::from_nullable(Some(value.as_ref()))))
+    }
+
+    /// Iterates over the keys with a value, paired with their value.
+    pub fn entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.entries
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| (key, value)))
+    }
+
+    /// Iterates over the tombstoned keys.
+    pub fn tombstones(&self) -> impl Iterator<Item = &K> {
+        self.entries
+            .iter()
+            .filter_map(|(key, value)| value.is_none().then_some(key))
+    }
+}
+
+impl<K: Eq + Hash, V> PresenceMap<K, V> {
+    /// Returns the state of `key`: `Absent` if missing, `Null` if tombstoned, and `Some`
+    /// with its value otherwise.
+    pub fn get<Q>(&self, key: &Q) -> Presence<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.entries.get(key) {
+            Some(value) => Presence::from_nullable(Some(value.as_ref())),
+            None => Presence::Absent,
+        }
+    }
+
+    /// Returns the state of `key` with a mutable reference to its value.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Presence<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.entries.get_mut(key) {
+            Some(value) => Presence::from_nullable(Some(value.as_mut())),
+            None => Presence::Absent,
+        }
+    }
+
+    /// Returns `true` if `key` has a value or is tombstoned.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries.contains_key(key)
+    }
+
+    /// Sets `key` to `value`, returning the previous state.
+    pub fn insert(&mut self, key: K, value: V) -> Presence<V> {
+        Presence::from_nullable(self.entries.insert(key, Some(value)))
+    }
+
+    /// Tombstones `key`, returning the previous state.
+    pub fn tombstone(&mut self, key: K) -> Presence<V> {
+        Presence::from_nullable(self.entries.insert(key, None))
+    }
+
+    /// Sets the state of `key`, returning the previous state. Setting `Absent` removes the
+    /// key.
+    pub fn set(&mut self, key: K, value: Presence<V>) -> Presence<V> {
+        match value {
+            Presence::Some(value) => self.insert(key, value),
+            Presence::Null => self.tombstone(key),
+            Presence::Absent => self.remove(&key),
+        }
+    }
+
+    /// Removes `key`, returning its previous state.
+    pub fn remove<Q>(&mut self, key: &Q) -> Presence<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        Presence::from_nullable(self.entries.remove(key))
+    }
+}
+
+impl<K: Eq + Hash, V: PartialEq> PartialEq for PresenceMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries == other.entries
+    }
+}
+
+impl<K: Eq + Hash, V: Eq> Eq for PresenceMap<K, V> {}
+
+impl<K, V> Default for PresenceMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for PresenceMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Eq + Hash, V> FromIterator<(K, Presence<V>)> for PresenceMap<K, V> {
+    /// Collects key states into a map, skipping `Absent` ones.
+    fn from_iter<I: IntoIterator<Item = (K, Presence<V>)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Eq + Hash, V> Extend<(K, Presence<V>)> for PresenceMap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, Presence<V>)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            let _ = self.set(key, value);
+        }
+    }
+}
+
+impl<K, V> IntoIterator for PresenceMap<K, V> {
+    type Item = (K, Presence<V>);
+    type IntoIter =
+        std::iter::Map<hash_map::IntoIter<K, Option<V>>, fn((K, Option<V>)) -> (K, Presence<V>)>;
+
+    /// Iterates over all keys with their state, which is `Null` or `Some`.
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries
+            .into_iter()
+            .map(|(key, value)| (key, Presence::from_nullable(Some(value))))
+    }
+}
+
+/// Deserializes a map against a known key set.
+///
+/// A map deserialized with `HashMap<String, Presence<T>>`'s own `Deserialize` can only hold
+/// the keys that were sent. This seed fills every key of the set that is missing from the
+/// input as `Absent`, keeps explicit nulls as `Null`, and rejects keys outside the set.
+/// The keys can be known at compile time or loaded at runtime, for example from a schema.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::map::KnownKeys;
+/// use serde::de::DeserializeSeed;
+///
+/// let fields = KnownKeys::<serde_json::Value>::new(["name", "bio", "age"]);
+///
+/// let mut input = serde_json::Deserializer::from_str(r#"{ "name": "Alice", "bio": null }"#);
+/// let patch = fields.clone().deserialize(&mut input).unwrap();
+///
+/// assert_eq!(patch["name"], Presence::Some("Alice".into()));
+/// assert_eq!(patch["bio"], Presence::Null);
+/// assert_eq!(patch["age"], Presence::Absent);
+///
+/// let mut input = serde_json::Deserializer::from_str(r#"{ "admin": true }"#);
+/// let err = fields.deserialize(&mut input).unwrap_err();
+/// assert!(err.to_string().starts_with("unknown field `admin`"));
+/// ```
+#[cfg(feature = "serde")]
+pub struct KnownKeys<T> {
+    keys: Vec<String>,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> KnownKeys<T> {
+    /// Creates a seed that deserializes maps with the given keys.
+    pub fn new(keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            keys: keys.into_iter().map(Into::into).collect(),
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Returns the known keys.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    fn expected(&self) -> String {
+        match self.keys.as_slice() {
+            [] => "there are no fields".to_string(),
+            [key] => format!("expected `{}`", key),
+            keys => {
+                let keys: Vec<String> = keys.iter().map(|key| format!("`{}`", key)).collect();
+                format!("expected one of {}", keys.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> Clone for KnownKeys<T> {
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> fmt::Debug for KnownKeys<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KnownKeys").field(&self.keys).finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::DeserializeSeed<'de> for KnownKeys<T> {
+    type Value = HashMap<String, Presence<T>>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::de::Visitor<'de> for KnownKeys<T> {
+    type Value = HashMap<String, Presence<T>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a map with keys {:?}", self.keys)
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(
+        self,
+        mut access: A,
+    ) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        // The keys are not `'static`, so serde's `unknown_field` and `duplicate_field`
+        // cannot be used; the messages are the same.
+        let mut map = HashMap::with_capacity(self.keys.len());
+        while let Some(key) = access.next_key::<String>()? {
+            if !self.keys.contains(&key) {
+                return Err(A::Error::custom(format_args!(
+                    "unknown field `{}`, {}",
+                    key,
+                    self.expected()
+                )));
+            }
+            if map.contains_key(&key) {
+                return Err(A::Error::custom(format_args!("duplicate field `{}`", key)));
+            }
+            let value = access.next_value::<Presence<T>>()?;
+            map.insert(key, value);
+        }
+        for key in self.keys {
+            map.entry(key).or_insert(Presence::Absent);
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,183 @@
+//! Merging [`Presence<T>`] values from several sources.
+//!
+//! Layered configuration (defaults ← file ← environment ← command line) and combined
+//! partial updates both need to decide, field by field, which of two values wins. A
+//! [`MergeStrategy`] encodes that decision and [`Presence::merge`] applies it:
+//!
+//! - [`PreferOtherIfDefined`] → `other` unless it is `Absent`
+//! - [`PreferOtherIfPresent`] → `other` only if it is `Some`
+//! - [`PreferSelf`] → `self` unless it is `Absent`
+//!
+//! Any `Fn(Presence<T>, Presence<T>) -> Presence<T>` closure is a strategy too.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::merge::{PreferOtherIfDefined, PreferOtherIfPresent};
+//!
+//! let defaults = Presence::Some("info");
+//! let file = Presence::Absent;
+//! let env = Presence::Null;
+//!
+//! // An explicit `null` resets the value...
+//! let level = defaults.merge(file, PreferOtherIfDefined).merge(env, PreferOtherIfDefined);
+//! assert_eq!(level, Presence::Null);
+//!
+//! // ...unless only real values may override lower layers.
+//! let level = defaults.merge(file, PreferOtherIfPresent).merge(env, PreferOtherIfPresent);
+//! assert_eq!(level, Presence::Some("info"));
+//! ```
+//!
+//! # Merging Patch Structs
+//!
+//! [`MergeFrom`] merges two instances of a patch struct field by field, for example to
+//! combine several partial updates into one effective patch. With the `derive` feature,
+//! `#[derive(MergeFrom)]` lets the later patch win wherever it is defined; see the derive
+//! for per-field strategies and nested structs.
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::merge::MergeFrom;
+//!
+//! #[derive(MergeFrom)]
+//! struct UserPatch {
+//!     name: Presence<String>,
+//!     avatar: Presence<String>,
+//! }
+//!
+//! let first = UserPatch { name: Presence::Some("Bob".into()), avatar: Presence::Some("a.png".into()) };
+//! let second = UserPatch { name: Presence::Absent, avatar: Presence::Null };
+//!
+//! let patch = first.merged(second);
+//! assert_eq!(patch.name, Presence::Some("Bob".into()));
+//! assert_eq!(patch.avatar, Presence::Null);
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::MergeFrom;
+
+/// Decides the result of merging two presences.
+pub trait MergeStrategy<T> {
+    /// Merges `other` into `current`.
+    fn merge(&self, current: Presence<T>, other: Presence<T>) -> Presence<T>;
+}
+
+/// `other` wins whenever it is defined, including an explicit `Null`.
+///
+/// These are patch semantics: later sources override earlier ones and `null` clears.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PreferOtherIfDefined;
+
+impl<T> MergeStrategy<T> for PreferOtherIfDefined {
+    fn merge(&self, current: Presence<T>, other: Presence<T>) -> Presence<T> {
+        match other {
+            Presence::Absent => current,
+            other => other,
+        }
+    }
+}
+
+/// `other` wins only if it holds a value; its `Null` is ignored like `Absent`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PreferOtherIfPresent;
+
+impl<T> MergeStrategy<T> for PreferOtherIfPresent {
+    fn merge(&self, current: Presence<T>, other: Presence<T>) -> Presence<T> {
+        match other {
+            Presence::Some(value) => Presence::Some(value),
+            Presence::Null | Presence::Absent => current,
+        }
+    }
+}
+
+/// `self` wins whenever it is defined; `other` only fills in `Absent`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PreferSelf;
+
+impl<T> MergeStrategy<T> for PreferSelf {
+    fn merge(&self, current: Presence<T>, other: Presence<T>) -> Presence<T> {
+        match current {
+            Presence::Absent => other,
+            current => current,
+        }
+    }
+}
+
+impl<T, F> MergeStrategy<T> for F
+where
+    F: Fn(Presence<T>, Presence<T>) -> Presence<T>,
+{
+    fn merge(&self, current: Presence<T>, other: Presence<T>) -> Presence<T> {
+        self(current, other)
+    }
+}
+
+/// A patch struct that can absorb a later patch of the same type.
+///
+/// Usually derived; see the [module documentation](self#merging-patch-structs).
+pub trait MergeFrom {
+    /// Merges `other` into `self`; `other` is the later of the two.
+    fn merge_from(&mut self, other: Self);
+
+    /// Returns `self` merged with `other`.
+    fn merged(mut self, other: Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.merge_from(other);
+        self
+    }
+}
+
+impl<T> Presence<T> {
+    /// Merges `other` into `self` using `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::merge::{PreferOtherIfDefined, PreferSelf};
+    ///
+    /// assert_eq!(Presence::Some(1).merge(Presence::Null, PreferOtherIfDefined), Presence::Null);
+    /// assert_eq!(Presence::Some(1).merge(Presence::Absent, PreferOtherIfDefined), Presence::Some(1));
+    /// assert_eq!(Presence::Null.merge(Presence::Some(2), PreferSelf), Presence::Null);
+    ///
+    /// // A custom strategy: sum the values when both are present.
+    /// let sum = |a: Presence<i32>, b: Presence<i32>| match (a, b) {
+    ///     (Presence::Some(a), Presence::Some(b)) => Presence::Some(a + b),
+    ///     (a, b) => a.or(b),
+    /// };
+    /// assert_eq!(Presence::Some(1).merge(Presence::Some(2), sum), Presence::Some(3));
+    /// ```
+    #[inline]
+    #[must_use = "Returns the merged presence"]
+    pub fn merge<S: MergeStrategy<T>>(self, other: Presence<T>, strategy: S) -> Presence<T> {
+        strategy.merge(self, other)
+    }
+
+    /// Merges `other` into `self` in place using `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::merge::PreferOtherIfPresent;
+    ///
+    /// let mut port = Presence::Some(8080);
+    /// port.merge_with(Presence::Null, PreferOtherIfPresent);
+    /// assert_eq!(port, Presence::Some(8080));
+    /// port.merge_with(Presence::Some(9000), PreferOtherIfPresent);
+    /// assert_eq!(port, Presence::Some(9000));
+    /// ```
+    #[inline]
+    pub fn merge_with<S: MergeStrategy<T>>(&mut self, other: Presence<T>, strategy: S) {
+        *self = strategy.merge(self.take(), other);
+    }
+}
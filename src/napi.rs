@@ -0,0 +1,216 @@
+//! Node.js addon conversions for [`Presence<T>`] via napi-rs.
+//!
+//! `Presence<T>` implements `ToNapiValue` and `FromNapiValue`, mapping the three states to
+//! their JavaScript counterparts:
+//!
+//! | JavaScript  | `Presence<T>`  |
+//! |-------------|----------------|
+//! | `undefined` | `Absent`       |
+//! | `null`      | `Null`         |
+//! | any value   | `Some(value)`  |
+//!
+//! `#[napi(object)]` reads a missing property as `undefined` only for fields declared as
+//! `Option<_>`, and rejects it for any other field type. Derive [`NapiObject`] on patch
+//! structs instead: a `Presence<T>` field reads a missing key as `Absent` and `null` as
+//! `Null`, and an `Absent` field is left out of the object when converting back to
+//! JavaScript. Fields marked `#[presence(plain)]` are required, as with `#[napi(object)]`,
+//! and `#[presence(skip)]` fields are never read or written. Property names are the
+//! camelCase form of the field names.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::napi::NapiObject;
+//!
+//! #[derive(NapiObject)]
+//! pub struct UserPatch {
+//!     pub display_name: Presence<String>,
+//!     pub age: Presence<u32>,
+//!     #[presence(plain)]
+//!     pub id: u32,
+//! }
+//! ```
+//!
+//! `UserPatch` can then be taken by a `#[napi]` function: `updateUser({ id: 1,
+//! displayName: null })` clears the name and leaves `age` as `Absent`.
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use napi::bindgen_prelude::{
+    FromNapiValue, Null, ToNapiValue, TypeName, Undefined, ValidateNapiValue,
+};
+use napi::{Error, Result, Status, ValueType, sys};
+use std::ffi::CString;
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::NapiObject;
+
+impl<T: TypeName> TypeName for Presence<T> {
+    fn type_name() -> &'static str {
+        T::type_name()
+    }
+
+    fn value_type() -> ValueType {
+        T::value_type()
+    }
+}
+
+impl<T: ValidateNapiValue> ValidateNapiValue for Presence<T> {
+    unsafe fn validate(env: sys::napi_env, napi_val: sys::napi_value) -> Result<sys::napi_value> {
+        match unsafe { value_type(env, napi_val) }? {
+            ValueType::Null | ValueType::Undefined => Ok(std::ptr::null_mut()),
+            received => unsafe { T::validate(env, napi_val) }.map_err(|_| {
+                Error::new(
+                    Status::InvalidArg,
+                    format!(
+                        "Expect value to be Presence<{}>, but received {}",
+                        T::value_type(),
+                        received
+                    ),
+                )
+            }),
+        }
+    }
+}
+
+impl<T: FromNapiValue> FromNapiValue for Presence<T> {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> Result<Self> {
+        match unsafe { value_type(env, napi_val) }? {
+            ValueType::Undefined => Ok(Presence::Absent),
+            ValueType::Null => Ok(Presence::Null),
+            _ => unsafe { T::from_napi_value(env, napi_val) }.map(Presence::Some),
+        }
+    }
+}
+
+impl<T: ToNapiValue> ToNapiValue for Presence<T> {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> Result<sys::napi_value> {
+        match val {
+            Presence::Absent => unsafe { Undefined::to_napi_value(env, ()) },
+            Presence::Null => unsafe { Null::to_napi_value(env, Null) },
+            Presence::Some(value) => unsafe { T::to_napi_value(env, value) },
+        }
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use napi::bindgen_prelude::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+    pub use napi::{Result, ValueType, sys};
+
+    use super::{CString, Error, Presence, Status, check, value_type};
+
+    /// Creates an empty JavaScript object.
+    ///
+    /// # Safety
+    ///
+    /// `env` must be a valid handle.
+    pub unsafe fn create_object(env: sys::napi_env) -> Result<sys::napi_value> {
+        let mut object = std::ptr::null_mut();
+        let status = unsafe { sys::napi_create_object(env, &mut object) };
+        check(status, "Failed to create object")?;
+        Ok(object)
+    }
+
+    /// Reads the property `key` of `object`; a missing key converts to `Absent`.
+    ///
+    /// # Safety
+    ///
+    /// `env` and `object` must be valid handles, and `object` must be an object.
+    pub unsafe fn get_presence<T: FromNapiValue>(
+        env: sys::napi_env,
+        object: sys::napi_value,
+        key: &str,
+    ) -> Result<Presence<T>> {
+        let value = unsafe { get_property(env, object, key) }?;
+        unsafe { Presence::from_napi_value(env, value) }
+    }
+
+    /// Reads the property `key` of `object`, rejecting a missing key.
+    ///
+    /// # Safety
+    ///
+    /// `env` and `object` must be valid handles, and `object` must be an object.
+    pub unsafe fn get_field<T: FromNapiValue>(
+        env: sys::napi_env,
+        object: sys::napi_value,
+        key: &str,
+    ) -> Result<T> {
+        let value = unsafe { get_property(env, object, key) }?;
+        if unsafe { value_type(env, value) }? == ValueType::Undefined {
+            return Err(Error::new(
+                Status::InvalidArg,
+                format!("Missing field `{key}`"),
+            ));
+        }
+        unsafe { T::from_napi_value(env, value) }
+    }
+
+    /// Sets the property `key` of `object`, leaving it out for `Absent`.
+    ///
+    /// # Safety
+    ///
+    /// `env` and `object` must be valid handles, and `object` must be an object.
+    pub unsafe fn set_presence<T: ToNapiValue>(
+        env: sys::napi_env,
+        object: sys::napi_value,
+        key: &str,
+        value: Presence<T>,
+    ) -> Result<()> {
+        match value {
+            Presence::Absent => Ok(()),
+            value => unsafe { set_field(env, object, key, value) },
+        }
+    }
+
+    /// Sets the property `key` of `object`.
+    ///
+    /// # Safety
+    ///
+    /// `env` and `object` must be valid handles, and `object` must be an object.
+    pub unsafe fn set_field<T: ToNapiValue>(
+        env: sys::napi_env,
+        object: sys::napi_value,
+        key: &str,
+        value: T,
+    ) -> Result<()> {
+        let key = CString::new(key)?;
+        let value = unsafe { T::to_napi_value(env, value) }?;
+        let status = unsafe { sys::napi_set_named_property(env, object, key.as_ptr(), value) };
+        check(status, "Failed to set object property")
+    }
+
+    unsafe fn get_property(
+        env: sys::napi_env,
+        object: sys::napi_value,
+        key: &str,
+    ) -> Result<sys::napi_value> {
+        let key = CString::new(key)?;
+        let mut value = std::ptr::null_mut();
+        let status = unsafe { sys::napi_get_named_property(env, object, key.as_ptr(), &mut value) };
+        check(status, "Failed to get object property")?;
+        Ok(value)
+    }
+}
+
+/// Returns the JavaScript type of `napi_val`.
+///
+/// # Safety
+///
+/// `env` and `napi_val` must be valid handles, as for `FromNapiValue::from_napi_value`.
+unsafe fn value_type(env: sys::napi_env, napi_val: sys::napi_value) -> Result<ValueType> {
+    let mut result = -1;
+    let status = unsafe { sys::napi_typeof(env, napi_val, &mut result) };
+    check(status, "Failed to detect napi value type")?;
+    Ok(ValueType::from(result))
+}
+
+/// Turns a failed napi `status` into an error with `message`.
+fn check(status: sys::napi_status, message: &str) -> Result<()> {
+    if status != sys::Status::napi_ok {
+        return Err(Error::new(Status::from(status), message.to_owned()));
+    }
+    Ok(())
+}
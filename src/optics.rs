@@ -0,0 +1,406 @@
+//! Composable optics for reading and updating nested [`Presence<T>`] fields.
+//!
+//! An [`Optic`] focuses on at most one part of a value: it previews the part if it is
+//! there and updates it in place or on an owned copy. Optics chain with [`Optic::then`],
+//! so a path through several partial documents is built once and reused:
+//!
+//! - [`lens`] → a field that is always there, from a pair of accessor closures
+//! - [`affine`] → a part that may be missing, from a pair of `Option`-returning closures
+//! - [`some`] → the prism focusing the `Some` case of a `Presence<T>`
+//! - [`presence_field`] → a `Presence<T>` field followed by [`some`]
+//!
+//! A focus that is `Null` or `Absent` is missing, so updates through it leave the value
+//! unchanged. [`SomePrism::review`] goes the other way and wraps a value in `Some`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::optics::{Optic, presence_field};
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct Address { city: Presence<String> }
+//!
+//! #[derive(Debug, PartialEq)]
+//! struct User { address: Presence<Address> }
+//!
+//! let city = presence_field(|user: &User| &user.address, |user: &mut User| &mut user.address)
+//!     .then(presence_field(|address: &Address| &address.city, |address: &mut Address| &mut address.city));
+//!
+//! let user = User { address: Presence::Some(Address { city: Presence::Some("Lviv".into()) }) };
+//! assert_eq!(city.preview(&user).map(String::as_str), Some("Lviv"));
+//!
+//! let user = city.modify(user, |city| city.make_ascii_uppercase());
+//! assert_eq!(city.preview(&user).map(String::as_str), Some("LVIV"));
+//!
+//! let nobody = User { address: Presence::Null };
+//! assert_eq!(city.set(nobody, "Kyiv".into()), User { address: Presence::Null });
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Focuses on at most one part of a value of type `S`.
+///
+/// This is an affine traversal: lenses focus on exactly one part and prisms on one case
+/// of an enum, and both are optics.
+pub trait Optic<S: ?Sized> {
+    /// The type of the focused part.
+    type Focus: ?Sized;
+
+    /// Returns the focused part, or `None` if it is missing.
+    fn preview<'s>(&self, source: &'s S) -> Option<&'s Self::Focus>
+    where
+        Self: 's;
+
+    /// Returns the focused part mutably, or `None` if it is missing.
+    fn preview_mut<'s>(&self, source: &'s mut S) -> Option<&'s mut Self::Focus>
+    where
+        Self: 's;
+
+    /// Returns `true` if the focused part is there.
+    fn is_focused(&self, source: &S) -> bool {
+        self.preview(source).is_some()
+    }
+
+    /// Replaces the focused part in place, returning the previous one, or gives `value`
+    /// back if the part is missing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `value` if the focused part is missing.
+    fn replace(&self, source: &mut S, value: Self::Focus) -> Result<Self::Focus, Self::Focus>
+    where
+        Self::Focus: Sized,
+    {
+        match self.preview_mut(source) {
+            Some(focus) => Ok(std::mem::replace(focus, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Returns `source` with the focused part set to `value`, or unchanged if the part is
+    /// missing.
+    fn set(&self, mut source: S, value: Self::Focus) -> S
+    where
+        S: Sized,
+        Self::Focus: Sized,
+    {
+        if let Some(focus) = self.preview_mut(&mut source) {
+            *focus = value;
+        }
+        source
+    }
+
+    /// Returns `source` with `f` applied to the focused part, or unchanged if the part is
+    /// missing.
+    fn modify<F>(&self, mut source: S, f: F) -> S
+    where
+        S: Sized,
+        F: FnOnce(&mut Self::Focus),
+    {
+        if let Some(focus) = self.preview_mut(&mut source) {
+            f(focus);
+        }
+        source
+    }
+
+    /// Chains `next` after this optic, focusing on a part of the focused part.
+    fn then<O>(self, next: O) -> Then<Self, O>
+    where
+        Self: Sized,
+        O: Optic<Self::Focus>,
+    {
+        Then { first: self, next }
+    }
+}
+
+/// An optic focusing on a part that is always there, created by [`lens`].
+pub struct Lens<S: ?Sized, A: ?Sized, G, M> {
+    get: G,
+    get_mut: M,
+    marker: PhantomData<fn(&S) -> &A>,
+}
+
+/// Creates a lens from accessor closures for a part of `S`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::optics::{Optic, lens};
+///
+/// let first = lens(|pair: &(u8, u8)| &pair.0, |pair: &mut (u8, u8)| &mut pair.0);
+///
+/// assert_eq!(first.get(&(1, 2)), &1);
+/// assert_eq!(first.set((1, 2), 3), (3, 2));
+/// ```
+pub fn lens<S, A, G, M>(get: G, get_mut: M) -> Lens<S, A, G, M>
+where
+    S: ?Sized,
+    A: ?Sized,
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    Lens {
+        get,
+        get_mut,
+        marker: PhantomData,
+    }
+}
+
+impl<S, A, G, M> Lens<S, A, G, M>
+where
+    S: ?Sized,
+    A: ?Sized,
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    /// Returns the focused part.
+    pub fn get<'s>(&self, source: &'s S) -> &'s A {
+        (self.get)(source)
+    }
+
+    /// Returns the focused part mutably.
+    pub fn get_mut<'s>(&self, source: &'s mut S) -> &'s mut A {
+        (self.get_mut)(source)
+    }
+}
+
+impl<S, A, G, M> Optic<S> for Lens<S, A, G, M>
+where
+    S: ?Sized,
+    A: ?Sized,
+    G: Fn(&S) -> &A,
+    M: Fn(&mut S) -> &mut A,
+{
+    type Focus = A;
+
+    fn preview<'s>(&self, source: &'s S) -> Option<&'s A>
+    where
+        Self: 's,
+    {
+        Some((self.get)(source))
+    }
+
+    fn preview_mut<'s>(&self, source: &'s mut S) -> Option<&'s mut A>
+    where
+        Self: 's,
+    {
+        Some((self.get_mut)(source))
+    }
+}
+
+impl<S: ?Sized, A: ?Sized, G: Clone, M: Clone> Clone for Lens<S, A, G, M> {
+    fn clone(&self) -> Self {
+        Self {
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S: ?Sized, A: ?Sized, G, M> fmt::Debug for Lens<S, A, G, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lens").finish_non_exhaustive()
+    }
+}
+
+/// An optic focusing on a part that may be missing, created by [`affine`].
+pub struct Affine<S: ?Sized, A: ?Sized, P, M> {
+    preview: P,
+    preview_mut: M,
+    marker: PhantomData<fn(&S) -> &A>,
+}
+
+/// Creates an optic from closures returning a part of `S` if it is there.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::optics::{Optic, affine};
+///
+/// let head = affine(|v: &Vec<i32>| v.first(), |v: &mut Vec<i32>| v.first_mut());
+///
+/// assert_eq!(head.preview(&vec![1, 2]), Some(&1));
+/// assert_eq!(head.modify(vec![1, 2], |x| *x *= 10), [10, 2]);
+/// assert_eq!(head.set(vec![], 5), Vec::<i32>::new());
+/// ```
+pub fn affine<S, A, P, M>(preview: P, preview_mut: M) -> Affine<S, A, P, M>
+where
+    S: ?Sized,
+    A: ?Sized,
+    P: Fn(&S) -> Option<&A>,
+    M: Fn(&mut S) -> Option<&mut A>,
+{
+    Affine {
+        preview,
+        preview_mut,
+        marker: PhantomData,
+    }
+}
+
+impl<S, A, P, M> Optic<S> for Affine<S, A, P, M>
+where
+    S: ?Sized,
+    A: ?Sized,
+    P: Fn(&S) -> Option<&A>,
+    M: Fn(&mut S) -> Option<&mut A>,
+{
+    type Focus = A;
+
+    fn preview<'s>(&self, source: &'s S) -> Option<&'s A>
+    where
+        Self: 's,
+    {
+        (self.preview)(source)
+    }
+
+    fn preview_mut<'s>(&self, source: &'s mut S) -> Option<&'s mut A>
+    where
+        Self: 's,
+    {
+        (self.preview_mut)(source)
+    }
+}
+
+impl<S: ?Sized, A: ?Sized, P: Clone, M: Clone> Clone for Affine<S, A, P, M> {
+    fn clone(&self) -> Self {
+        Self {
+            preview: self.preview.clone(),
+            preview_mut: self.preview_mut.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<S: ?Sized, A: ?Sized, P, M> fmt::Debug for Affine<S, A, P, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Affine").finish_non_exhaustive()
+    }
+}
+
+/// The prism focusing the `Some` case of a `Presence<T>`, created by [`some`].
+pub struct SomePrism<T>(PhantomData<fn(T) -> T>);
+
+/// Returns the prism focusing the `Some` case of a `Presence<T>`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::optics::{Optic, some};
+///
+/// assert_eq!(some().preview(&Presence::Some(1)), Some(&1));
+/// assert_eq!(some::<i32>().preview(&Presence::Null), None);
+/// assert_eq!(some().set(Presence::Some(1), 2), Presence::Some(2));
+/// assert_eq!(some().set(Presence::Absent, 2), Presence::Absent);
+/// assert_eq!(some().review(2), Presence::Some(2));
+/// ```
+pub const fn some<T>() -> SomePrism<T> {
+    SomePrism(PhantomData)
+}
+
+impl<T> SomePrism<T> {
+    /// Builds a `Presence<T>` from the focused case.
+    pub fn review(&self, value: T) -> Presence<T> {
+        Presence::Some(value)
+    }
+}
+
+impl<T> Optic<Presence<T>> for SomePrism<T> {
+    type Focus = T;
+
+    fn preview<'s>(&self, source: &'s Presence<T>) -> Option<&'s T>
+    where
+        Self: 's,
+    {
+        match source {
+            Presence::Some(value) => Some(value),
+            Presence::Null | Presence::Absent => None,
+        }
+    }
+
+    fn preview_mut<'s>(&self, source: &'s mut Presence<T>) -> Option<&'s mut T>
+    where
+        Self: 's,
+    {
+        match source {
+            Presence::Some(value) => Some(value),
+            Presence::Null | Presence::Absent => None,
+        }
+    }
+}
+
+impl<T> Clone for SomePrism<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SomePrism<T> {}
+
+impl<T> Default for SomePrism<T> {
+    fn default() -> Self {
+        some()
+    }
+}
+
+impl<T> fmt::Debug for SomePrism<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SomePrism")
+    }
+}
+
+/// Two optics applied one after the other, created by [`Optic::then`].
+#[derive(Clone, Copy, Debug)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<S, A, B> Optic<S> for Then<A, B>
+where
+    S: ?Sized,
+    A: Optic<S>,
+    B: Optic<A::Focus>,
+{
+    type Focus = B::Focus;
+
+    fn preview<'s>(&self, source: &'s S) -> Option<&'s B::Focus>
+    where
+        Self: 's,
+    {
+        self.first
+            .preview(source)
+            .and_then(|part| self.next.preview(part))
+    }
+
+    fn preview_mut<'s>(&self, source: &'s mut S) -> Option<&'s mut B::Focus>
+    where
+        Self: 's,
+    {
+        self.first
+            .preview_mut(source)
+            .and_then(|part| self.next.preview_mut(part))
+    }
+}
+
+/// Creates an optic focusing on the value of a `Presence<A>` field of `S`.
+///
+/// This is [`lens`] followed by [`some`]; the focus is missing when the field is `Null`
+/// or `Absent`.
+pub fn presence_field<S, A, G, M>(
+    get: G,
+    get_mut: M,
+) -> Then<Lens<S, Presence<A>, G, M>, SomePrism<A>>
+where
+    S: ?Sized,
+    G: Fn(&S) -> &Presence<A>,
+    M: Fn(&mut S) -> &mut Presence<A>,
+{
+    lens(get, get_mut).then(some())
+}
@@ -0,0 +1,195 @@
+//! Parquet columns of [`Presence<T>`] values, encoded with definition levels.
+//!
+//! Parquet encodes nullability with definition levels rather than bitmaps. A presence
+//! column is stored as an optional group around an optional value, as built by
+//! [`presence_group`], so each state has its own level and no side channel is needed:
+//!
+//! | State | Definition level |
+//! |-------|------------------|
+//! | `Absent` | `0` (the group is null) |
+//! | `Null` | `1` (the value is null) |
+//! | `Some(value)` | `2` ([`MAX_DEFINITION_LEVEL`]) |
+//!
+//! [`write_column`] and [`read_column`] move presence values through parquet's typed
+//! column writers and readers. [`encode_levels`] and [`decode_levels`] expose the mapping
+//! for other writers.
+//!
+//! # Examples
+//!
+//! ```
+//! use parquet::basic::Type as PhysicalType;
+//! use presence_rs::parquet::presence_group;
+//!
+//! let group = presence_group("age", PhysicalType::INT32).unwrap();
+//! assert!(group.is_group());
+//! assert_eq!(group.get_fields()[0].name(), "value");
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use parquet::basic::{Repetition, Type as PhysicalType};
+use parquet::column::reader::ColumnReaderImpl;
+use parquet::column::writer::ColumnWriterImpl;
+use parquet::data_type::DataType;
+use parquet::errors::{ParquetError, Result};
+use parquet::schema::types::Type;
+use std::fmt;
+use std::sync::Arc;
+
+/// The definition level of a `Some` slot in a [`presence_group`] column.
+pub const MAX_DEFINITION_LEVEL: i16 = 2;
+
+/// The number of records [`read_column`] asks the reader for at a time.
+const BATCH_SIZE: usize = 1024;
+
+/// Returns the schema of a presence column: an optional group `name` holding an optional
+/// `value` of the given physical type.
+///
+/// # Errors
+///
+/// Returns an error if parquet rejects the type, for example a `FIXED_LEN_BYTE_ARRAY`
+/// without a length.
+pub fn presence_group(name: &str, physical: PhysicalType) -> Result<Type> {
+    let value = Type::primitive_type_builder("value", physical)
+        .with_repetition(Repetition::OPTIONAL)
+        .build()?;
+    Type::group_type_builder(name)
+        .with_repetition(Repetition::OPTIONAL)
+        .with_fields(vec![Arc::new(value)])
+        .build()
+}
+
+/// Writes presence values to the `value` column of a [`presence_group`].
+///
+/// Returns the number of values written, which is the number of `Some` slots.
+///
+/// # Errors
+///
+/// Returns an error if the column writer fails.
+pub fn write_column<T: DataType>(
+    writer: &mut ColumnWriterImpl<'_, T>,
+    presences: &[Presence<T::T>],
+) -> Result<usize> {
+    let (values, levels) = encode_levels(presences);
+    writer.write_batch(&values, Some(&levels), None)
+}
+
+/// Reads every remaining value of the `value` column of a [`presence_group`].
+///
+/// # Errors
+///
+/// Returns an error if the column reader fails or reads a level outside the presence
+/// group's levels.
+pub fn read_column<T: DataType>(reader: &mut ColumnReaderImpl<T>) -> Result<Vec<Presence<T::T>>> {
+    let mut values = Vec::new();
+    let mut levels = Vec::new();
+    loop {
+        let (records, _, _) =
+            reader.read_records(BATCH_SIZE, Some(&mut levels), None, &mut values)?;
+        if records == 0 {
+            break;
+        }
+    }
+    decode_levels(values, &levels).map_err(|error| ParquetError::General(error.to_string()))
+}
+
+/// Splits presence values into the dense `Some` values and the definition levels of a
+/// [`presence_group`] column.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::parquet::encode_levels;
+///
+/// let (values, levels) = encode_levels(&[Presence::Some(7), Presence::Null, Presence::Absent]);
+/// assert_eq!(values, [7]);
+/// assert_eq!(levels, [2, 1, 0]);
+/// ```
+pub fn encode_levels<T: Clone>(presences: &[Presence<T>]) -> (Vec<T>, Vec<i16>) {
+    let mut values = Vec::new();
+    let levels = presences
+        .iter()
+        .map(|presence| match presence {
+            Presence::Some(value) => {
+                values.push(value.clone());
+                MAX_DEFINITION_LEVEL
+            }
+            Presence::Null => 1,
+            Presence::Absent => 0,
+        })
+        .collect();
+    (values, levels)
+}
+
+/// Rebuilds presence values from the dense values and definition levels of a
+/// [`presence_group`] column.
+///
+/// # Errors
+///
+/// Returns an error if a level is above [`MAX_DEFINITION_LEVEL`] or negative, or if the
+/// number of values differs from the number of `Some` levels.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::parquet::decode_levels;
+///
+/// assert_eq!(
+///     decode_levels(vec!["a"], &[0, 2, 1]).unwrap(),
+///     [Presence::Absent, Presence::Some("a"), Presence::Null]
+/// );
+/// assert!(decode_levels(vec!["a"], &[1]).is_err());
+/// ```
+pub fn decode_levels<T>(values: Vec<T>, levels: &[i16]) -> Result<Vec<Presence<T>>, LevelError> {
+    let expected = levels
+        .iter()
+        .filter(|&&level| level == MAX_DEFINITION_LEVEL)
+        .count();
+    if expected != values.len() {
+        return Err(LevelError::ValueCount {
+            expected,
+            actual: values.len(),
+        });
+    }
+
+    let mut values = values.into_iter();
+    levels
+        .iter()
+        .map(|&level| match level {
+            0 => Ok(Presence::Absent),
+            1 => Ok(Presence::Null),
+            MAX_DEFINITION_LEVEL => Ok(Presence::Some(values.next().unwrap())),
+            level => Err(LevelError::InvalidLevel(level)),
+        })
+        .collect()
+}
+
+/// Error returned by [`decode_levels`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LevelError {
+    /// A definition level is outside `0..=MAX_DEFINITION_LEVEL`.
+    InvalidLevel(i16),
+    /// The number of values differs from the number of `Some` levels.
+    ValueCount {
+        /// The number of `Some` levels.
+        expected: usize,
+        /// The number of values.
+        actual: usize,
+    },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelError::InvalidLevel(level) => write!(f, "invalid definition level {}", level),
+            LevelError::ValueCount { expected, actual } => {
+                write!(f, "expected {} values, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
@@ -0,0 +1,161 @@
+//! polars [`Series`] from and to sequences of [`Presence<T>`] values.
+//!
+//! A polars column has a single validity bitmap, so it can hold `Absent` in one of two
+//! ways:
+//!
+//! - A nullable column ([`to_series`]), where `Absent` becomes null as chosen by an
+//!   [`AbsentPolicy`] and reads back as `Null`
+//! - A struct column ([`to_struct_series`]) with a nullable `value` field and a boolean
+//!   `defined` field, which keeps all three states
+//!
+//! The reverse conversions read numeric columns with [`from_series`] and
+//! [`from_struct_series`], and string columns with [`from_str_series`] and
+//! [`from_str_struct_series`].
+//!
+//! # Examples
+//!
+//! ```
+//! use polars::prelude::*;
+//! use presence_rs::Presence;
+//! use presence_rs::polars::{from_struct_series, to_struct_series};
+//!
+//! let ages = [Presence::Some(30), Presence::Null, Presence::Absent];
+//! let series = to_struct_series("age", &ages).unwrap();
+//! let frame = DataFrame::new(vec![series.into()]).unwrap();
+//!
+//! let column = frame.column("age").unwrap().as_materialized_series();
+//! assert_eq!(from_struct_series::<Int32Type>(column).unwrap(), ages);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::columnar::{AbsentPolicy, PresenceColumn};
+use crate::presence::Presence;
+use polars::prelude::*;
+
+/// The name of the value field of a [`to_struct_series`] column.
+pub const VALUE_FIELD: &str = "value";
+
+/// The name of the definedness field of a [`to_struct_series`] column.
+pub const DEFINED_FIELD: &str = "defined";
+
+/// Builds a nullable column from presence values, handling `Absent` slots by `policy`.
+///
+/// # Errors
+///
+/// Returns a compute error for the first `Absent` slot with [`AbsentPolicy::Reject`].
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::columnar::AbsentPolicy;
+/// use presence_rs::polars::to_series;
+///
+/// let names = [Presence::Some("Ann".to_string()), Presence::Absent];
+/// let series = to_series("name", &names, AbsentPolicy::Null).unwrap();
+/// assert_eq!(series.null_count(), 1);
+/// assert!(to_series("name", &names, AbsentPolicy::Reject).is_err());
+/// ```
+pub fn to_series<T>(
+    name: &str,
+    presences: &[Presence<T>],
+    policy: AbsentPolicy,
+) -> PolarsResult<Series>
+where
+    T: Clone + Default,
+    Series: NamedFrom<Vec<Option<T>>, [Option<T>]>,
+{
+    let values = PresenceColumn::from(presences)
+        .to_options(policy)
+        .map_err(|error| polars_err!(ComputeError: "{}", error))?;
+    Ok(Series::new(name.into(), values))
+}
+
+/// Builds a struct column with a nullable [`VALUE_FIELD`] and a boolean
+/// [`DEFINED_FIELD`] from presence values.
+///
+/// # Errors
+///
+/// Returns an error if polars cannot build the struct column.
+pub fn to_struct_series<T>(name: &str, presences: &[Presence<T>]) -> PolarsResult<Series>
+where
+    T: Clone + Default,
+    Series: NamedFrom<Vec<Option<T>>, [Option<T>]>,
+{
+    let (values, defined) = PresenceColumn::from(presences).to_struct_fields();
+    let fields = [
+        Series::new(VALUE_FIELD.into(), values),
+        BooleanChunked::from_slice(DEFINED_FIELD.into(), &defined).into_series(),
+    ];
+    StructChunked::from_series(name.into(), presences.len(), fields.iter())
+        .map(IntoSeries::into_series)
+}
+
+/// Reads a nullable numeric column, with null slots as `Null`.
+///
+/// # Errors
+///
+/// Returns an error if `series` is not of type `T`.
+pub fn from_series<T: PolarsNumericType>(
+    series: &Series,
+) -> PolarsResult<Vec<Presence<T::Native>>> {
+    let values = series.unpack::<T>()?.iter().collect();
+    Ok(PresenceColumn::from_options(values).into_presences())
+}
+
+/// Reads a nullable string column, with null slots as `Null`.
+///
+/// # Errors
+///
+/// Returns an error if `series` is not a string column.
+pub fn from_str_series(series: &Series) -> PolarsResult<Vec<Presence<String>>> {
+    let values = series
+        .str()?
+        .iter()
+        .map(|value| value.map(str::to_string))
+        .collect();
+    Ok(PresenceColumn::from_options(values).into_presences())
+}
+
+/// Reads a numeric [`to_struct_series`] column.
+///
+/// # Errors
+///
+/// Returns an error if `series` is not a struct column with a [`VALUE_FIELD`] of type
+/// `T` and a non-null boolean [`DEFINED_FIELD`].
+pub fn from_struct_series<T: PolarsNumericType>(
+    series: &Series,
+) -> PolarsResult<Vec<Presence<T::Native>>> {
+    let (value, defined) = struct_fields(series)?;
+    let values = value.unpack::<T>()?.iter().collect();
+    Ok(PresenceColumn::from_struct_fields(values, defined).into_presences())
+}
+
+/// Reads a string [`to_struct_series`] column.
+///
+/// # Errors
+///
+/// Returns an error if `series` is not a struct column with a string [`VALUE_FIELD`] and
+/// a non-null boolean [`DEFINED_FIELD`].
+pub fn from_str_struct_series(series: &Series) -> PolarsResult<Vec<Presence<String>>> {
+    let (value, defined) = struct_fields(series)?;
+    let values = value
+        .str()?
+        .iter()
+        .map(|value| value.map(str::to_string))
+        .collect();
+    Ok(PresenceColumn::from_struct_fields(values, defined).into_presences())
+}
+
+fn struct_fields(series: &Series) -> PolarsResult<(Series, Vec<bool>)> {
+    let fields = series.struct_()?;
+    let value = fields.field_by_name(VALUE_FIELD)?;
+    let defined = fields
+        .field_by_name(DEFINED_FIELD)?
+        .bool()?
+        .iter()
+        .map(|defined| defined.ok_or_else(|| polars_err!(ComputeError: "null `defined` flag")))
+        .collect::<PolarsResult<_>>()?;
+    Ok((value, defined))
+}
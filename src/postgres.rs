@@ -0,0 +1,109 @@
+//! `ToSql`/`FromSql` for [`Presence<T>`] with tokio-postgres and postgres.
+//!
+//! `Presence<T>` behaves like `Option<T>` for a nullable column:
+//!
+//! - Reading: SQL `NULL` → `Null`, a value → `Some(value)`. Rows never produce `Absent`,
+//!   because every selected column has a value.
+//! - Writing: `Some(value)` → the value, `Null` → `NULL`.
+//!
+//! `Absent` is also written as `NULL`. A bound parameter always sets its column, so there
+//! is no way to "not write" a value from inside `ToSql`; leave `Absent` columns out of the
+//! statement instead of binding them. A [`SetClause`] does that for `UPDATE` statements,
+//! and [`params`] passes its binds to `execute` or `query`.
+//!
+//! # Examples
+//!
+//! ```
+//! use bytes::BytesMut;
+//! use postgres_types::{FromSql, IsNull, ToSql, Type};
+//! use presence_rs::Presence;
+//!
+//! let mut buf = BytesMut::new();
+//! assert!(matches!(Presence::<i32>::Null.to_sql(&Type::INT4, &mut buf).unwrap(), IsNull::Yes));
+//!
+//! let value = Presence::<i32>::from_sql_nullable(&Type::INT4, None).unwrap();
+//! assert_eq!(value, Presence::Null);
+//! ```
+//!
+//! Updating the defined fields of a patch:
+//!
+//! ```
+//! use postgres_types::ToSql;
+//! use presence_rs::Presence;
+//! use presence_rs::postgres::params;
+//! use presence_rs::sql::{Placeholder, SetClause};
+//!
+//! let mut set = SetClause::<String>::new(Placeholder::Dollar);
+//! set.set("name", Presence::Some("Bob".to_string()))
+//!     .set("bio", Presence::<String>::Null)
+//!     .set("email", Presence::<String>::Absent);
+//!
+//! let sql = format!("UPDATE users {} WHERE id = {}", set.sql(), set.next_placeholder());
+//! assert_eq!(sql, "UPDATE users SET name = $1, bio = $2 WHERE id = $3");
+//!
+//! let id = 7_i64;
+//! let mut params = params(&set);
+//! params.push(&id);
+//! assert_eq!(params.len(), 3);
+//! // client.execute(&sql, &params)?;
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`SetClause`]: crate::sql::SetClause
+
+use crate::presence::Presence;
+use crate::sql::SetClause;
+use bytes::BytesMut;
+use postgres_types::{Format, FromSql, IsNull, ToSql, Type, to_sql_checked};
+use std::error::Error;
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for Presence<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        T::from_sql(ty, raw).map(Presence::Some)
+    }
+
+    fn from_sql_null(_: &Type) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        Ok(Presence::Null)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        T::accepts(ty)
+    }
+}
+
+impl<T: ToSql> ToSql for Presence<T> {
+    fn to_sql(
+        &self,
+        ty: &Type,
+        out: &mut BytesMut,
+    ) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        match self {
+            Presence::Some(value) => value.to_sql(ty, out),
+            Presence::Null | Presence::Absent => Ok(IsNull::Yes),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        T::accepts(ty)
+    }
+
+    fn encode_format(&self, ty: &Type) -> Format {
+        match self {
+            Presence::Some(value) => value.encode_format(ty),
+            Presence::Null | Presence::Absent => Format::Binary,
+        }
+    }
+
+    to_sql_checked!();
+}
+
+/// Returns the binds of `set` as parameters for `execute` or `query`, in placeholder
+/// order.
+///
+/// The clause must use [`Placeholder::Dollar`](crate::sql::Placeholder::Dollar).
+pub fn params<V: ToSql + Sync>(set: &SetClause<V>) -> Vec<&(dyn ToSql + Sync)> {
+    set.binds()
+        .iter()
+        .map(|bind| bind as &(dyn ToSql + Sync))
+        .collect()
+}
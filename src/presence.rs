@@ -162,13 +162,20 @@
 //!
 //! - **Querying**: `is_absent()`, `is_null()`, `is_present()`, `is_defined()`, `is_nullish()`
 //! - **Extracting**: `expect()`, `unwrap()`, `unwrap_or()`, `unwrap_or_default()`
-//! - **Transforming**: `map()`, `filter()`, `and_then()`, `flatten()`
-//! - **Combining**: `and()`, `or()`, `xor()`, `zip()`, `zip_with()`
+//! - **Setting**: `set_value()`, `try_insert()`, `get_or_insert_with()`, `get_or_try_insert_with()`, `set_null()`, `set_absent()`, `clear()`, `replace()`, `replace_if()`, `take()`
+//! - **Transforming**: `map()`, `modify()`, `update()`, `filter()`, `and_then()`, `flatten()`
+//! - **Combining**: `and()`, `or()`, `xor()`, `zip()`, `zip_with()`, `unzip()`, `unzip3()`, `unzip4()`, `merge()`
 //! - **Converting**: `to_optional()`, `to_nullable()`, `from_optional()`, `from_nullable()`
 //! - **References**: `as_ref()`, `as_mut()`, `as_deref()`, `copied()`, `cloned()`
+//! - **Ordering**: `cmp_nulls_first()`, `cmp_nulls_last()`, `min_present()`, `max_present()`, `clamp_present()`
 //! - **Iterating**: `iter()`, `iter_mut()`, `into_iter()`
+//! - **Strings**: `as_str()`, `empty_to_null()`, `trim_to_null()`, `is_blank_or_nullish()`
+//! - **Lists**: `iter_flat()`, `is_empty_or_nullish()`, `empty_to_absent()`, `empty_to_null()`
+//! - **Borrowed data**: `into_owned()`, `into_static()` for `Presence<Cow<B>>`
+//! - **Boxes**: `boxed()`, `unbox()`
+//! - **Shared pointers**: `make_mut()`, `try_unwrap()` for `Presence<Arc<T>>` and `Presence<Rc<T>>`
 
-use std::{fmt, iter::FusedIterator};
+use std::{borrow::Cow, cmp::Ordering, fmt, iter::FusedIterator, rc::Rc, str::FromStr, sync::Arc};
 
 #[must_use = "`Presence` may contain a value that should be used"]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -192,6 +199,20 @@ pub enum Presence<T> {
     Some(T),
 }
 
+/// The state of a [`Presence<T>`] without its value.
+///
+/// Returned by [`Presence::state`], for code that inspects patches without caring about
+/// the field types.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PresenceState {
+    /// The presence is [`Absent`](Presence::Absent)
+    Absent,
+    /// The presence is [`Null`](Presence::Null)
+    Null,
+    /// The presence is [`Some`](Presence::Some)
+    Some,
+}
+
 /////////////////////////////////////////////////////////////////////////////
 // Type implementation
 /////////////////////////////////////////////////////////////////////////////
@@ -329,6 +350,26 @@ impl<T> Presence<T> {
         !matches!(self, Presence::Some(_))
     }
 
+    /// Returns the state of the presence, dropping the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::{Presence, PresenceState};
+    ///
+    /// assert_eq!(Presence::Some(42).state(), PresenceState::Some);
+    /// assert_eq!(Presence::<i32>::Null.state(), PresenceState::Null);
+    /// assert_eq!(Presence::<i32>::Absent.state(), PresenceState::Absent);
+    /// ```
+    #[inline]
+    pub const fn state(&self) -> PresenceState {
+        match self {
+            Presence::Absent => PresenceState::Absent,
+            Presence::Null => PresenceState::Null,
+            Presence::Some(_) => PresenceState::Some,
+        }
+    }
+
     /// Converts to `Option<T>`, treating both [`Null`] and [`Absent`] as `None`.
     ///
     /// This is the "optional" representation where only concrete values matter.
@@ -770,7 +811,7 @@ impl<T> Presence<T> {
     /// assert_eq!(y.as_mut_slice(), &mut []);
     ///
     /// let mut z: Presence<i32> = Presence::Absent;
-    /// assert_eq!(z.as_mut_slice(), &mut []);
+    /// assert_eq!(z.as_mut_slice(), &mut [] as &mut [i32]);
     /// ```
     #[inline]
     pub fn as_mut_slice(&mut self) -> &mut [T] {
@@ -1165,6 +1206,282 @@ impl<T> Presence<T> {
         std::mem::replace(self, Presence::Some(value))
     }
 
+    /// Replaces the presence with [`Some`] of `value` if `predicate` returns `true`,
+    /// returning the old presence in `Ok`.
+    ///
+    /// The predicate receives the current presence as a `Presence<&T>`, so it can accept
+    /// or reject `Null` and `Absent` as well as values. When it returns `false`, `self` is
+    /// left unchanged and `value` is handed back in `Err`, so a rejected replacement is
+    /// never mistaken for replacing an `Absent` presence.
+    ///
+    /// [`Some`]: Presence::Some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// // Only overwrite a version the caller has seen.
+    /// let mut version = Presence::Some(3);
+    /// assert_eq!(version.replace_if(|v| v == Presence::Some(&3), 4), Ok(Presence::Some(3)));
+    /// assert_eq!(version.replace_if(|v| v == Presence::Some(&3), 5), Err(5));
+    /// assert_eq!(version, Presence::Some(4));
+    ///
+    /// // Fill a field that was explicitly cleared, but not one left out.
+    /// let mut x: Presence<i32> = Presence::Null;
+    /// assert_eq!(x.replace_if(|v| v.is_null(), 1), Ok(Presence::Null));
+    /// assert_eq!(x, Presence::Some(1));
+    /// ```
+    #[inline]
+    pub fn replace_if<P>(&mut self, predicate: P, value: T) -> Result<Presence<T>, T>
+    where
+        P: FnOnce(Presence<&T>) -> bool,
+    {
+        if predicate(self.as_ref()) {
+            Ok(self.replace(value))
+        } else {
+            Err(value)
+        }
+    }
+
+    /// Sets the presence to [`Some`] with `value`, returning the previous state.
+    ///
+    /// This is [`replace`] under a name that pairs with [`set_null`] and [`set_absent`].
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`replace`]: Presence::replace
+    /// [`set_null`]: Presence::set_null
+    /// [`set_absent`]: Presence::set_absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut x = Presence::Null;
+    /// assert_eq!(x.set_value(3), Presence::Null);
+    /// assert_eq!(x, Presence::Some(3));
+    /// ```
+    #[inline]
+    pub fn set_value(&mut self, value: T) -> Presence<T> {
+        self.replace(value)
+    }
+
+    /// Sets the presence to [`Null`], returning the previous state.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut x = Presence::Some("bio");
+    /// assert_eq!(x.set_null(), Presence::Some("bio"));
+    /// assert_eq!(x, Presence::Null);
+    /// ```
+    #[inline]
+    pub const fn set_null(&mut self) -> Presence<T> {
+        let mut slot = Presence::Null;
+        std::mem::swap(self, &mut slot);
+        slot
+    }
+
+    /// Sets the presence to [`Absent`], returning the previous state.
+    ///
+    /// This is [`take`] under a name that pairs with [`set_value`] and [`set_null`].
+    ///
+    /// [`Absent`]: Presence::Absent
+    /// [`take`]: Presence::take
+    /// [`set_value`]: Presence::set_value
+    /// [`set_null`]: Presence::set_null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut x = Presence::<i32>::Null;
+    /// assert_eq!(x.set_absent(), Presence::Null);
+    /// assert_eq!(x, Presence::Absent);
+    /// ```
+    #[inline]
+    pub const fn set_absent(&mut self) -> Presence<T> {
+        self.take()
+    }
+
+    /// Clears the field by setting the presence to [`Null`], returning the previous state.
+    ///
+    /// In patch terms this is an explicit request to remove the stored value, as opposed
+    /// to [`set_absent`], which leaves the field out of the patch.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`set_absent`]: Presence::set_absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut nickname = Presence::Some("bobby");
+    /// assert_eq!(nickname.clear(), Presence::Some("bobby"));
+    /// assert_eq!(nickname, Presence::Null);
+    /// ```
+    #[inline]
+    pub const fn clear(&mut self) -> Presence<T> {
+        self.set_null()
+    }
+
+    /// Assigns anything that converts into a `Presence<T>`, returning the previous state.
+    ///
+    /// Accepts a `Presence<T>`, a `T` (as [`Some`]) or an `Option<Option<T>>` (as with
+    /// [`from_nullable`]).
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`from_nullable`]: Presence::from_nullable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut x = Presence::Some(1);
+    /// assert_eq!(x.assign(2), Presence::Some(1));
+    /// assert_eq!(x.assign(Some(None)), Presence::Some(2));
+    /// assert_eq!(x.assign(Presence::Absent), Presence::Null);
+    /// assert_eq!(x, Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn assign<U: Into<Presence<T>>>(&mut self, value: U) -> Presence<T> {
+        std::mem::replace(self, value.into())
+    }
+
+    /// Assigns an `Option<T>`, with `None` as [`Null`], returning the previous state.
+    ///
+    /// Use this when `None` means the value was cleared, as for an emptied form input.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut bio = Presence::Some("hi");
+    /// assert_eq!(bio.set_from_option_null(None), Presence::Some("hi"));
+    /// assert_eq!(bio, Presence::Null);
+    /// ```
+    #[inline]
+    pub fn set_from_option_null(&mut self, value: Option<T>) -> Presence<T> {
+        match value {
+            Some(value) => self.replace(value),
+            None => self.set_null(),
+        }
+    }
+
+    /// Assigns an `Option<T>`, with `None` as [`Absent`], returning the previous state.
+    ///
+    /// Use this when `None` means the value was not provided.
+    ///
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut bio = Presence::Some("hi");
+    /// assert_eq!(bio.set_from_option_absent(Some("hey")), Presence::Some("hi"));
+    /// assert_eq!(bio.set_from_option_absent(None), Presence::Some("hey"));
+    /// assert_eq!(bio, Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn set_from_option_absent(&mut self, value: Option<T>) -> Presence<T> {
+        std::mem::replace(self, Presence::from_optional(value))
+    }
+
+    /// Assigns an `Option<Option<T>>`, preserving all three states, and returns the
+    /// previous state.
+    ///
+    /// - `None` → [`Absent`]
+    /// - `Some(None)` → [`Null`]
+    /// - `Some(Some(v))` → [`Some(v)`]
+    ///
+    /// [`Absent`]: Presence::Absent
+    /// [`Null`]: Presence::Null
+    /// [`Some(v)`]: Presence::Some
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut age = Presence::Some(30);
+    /// assert_eq!(age.set_from_nullable(Some(None)), Presence::Some(30));
+    /// assert_eq!(age, Presence::Null);
+    /// assert_eq!(age.set_from_nullable(None), Presence::Null);
+    /// assert_eq!(age, Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn set_from_nullable(&mut self, value: Option<Option<T>>) -> Presence<T> {
+        std::mem::replace(self, Presence::from_nullable(value))
+    }
+
+    /// Calls `f` with a mutable reference to the contained value, if any.
+    ///
+    /// `Null` and `Absent` are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut tags = Presence::Some(vec!["a"]);
+    /// tags.modify(|tags| tags.push("b"));
+    /// assert_eq!(tags, Presence::Some(vec!["a", "b"]));
+    ///
+    /// let mut tags: Presence<Vec<&str>> = Presence::Null;
+    /// tags.modify(|tags| tags.push("b"));
+    /// assert_eq!(tags, Presence::Null);
+    /// ```
+    #[inline]
+    pub fn modify<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        if let Presence::Some(value) = self {
+            f(value);
+        }
+    }
+
+    /// Replaces the contained value, if any, with the result of `f`.
+    ///
+    /// `Null` and `Absent` are left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut name = Presence::Some(String::from("bob"));
+    /// name.update(|name| name.to_uppercase());
+    /// assert_eq!(name, Presence::Some(String::from("BOB")));
+    ///
+    /// let mut count: Presence<u32> = Presence::Absent;
+    /// count.update(|n| n + 1);
+    /// assert_eq!(count, Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(T) -> T,
+    {
+        // `take_if` leaves `Null` and `Absent` in place.
+        if let Presence::Some(value) = self.take_if(|_| true) {
+            *self = Presence::Some(f(value));
+        }
+    }
+
     /// Inserts `value` into the presence, then returns a mutable reference to it.
     ///
     /// If the presence already contained a value, the old value is dropped.
@@ -1199,6 +1516,36 @@ impl<T> Presence<T> {
         }
     }
 
+    /// Inserts `value` into the presence if it is [`Null`] or [`Absent`], then returns a
+    /// mutable reference to it.
+    ///
+    /// If the presence is already [`Some`], returns a [`TryInsertError`] holding a
+    /// reference to the existing value and the rejected `value`, so the first writer wins.
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut source = Presence::Null;
+    /// assert_eq!(source.try_insert("header"), Ok(&mut "header"));
+    ///
+    /// let err = source.try_insert("query").unwrap_err();
+    /// assert_eq!(*err.current, "header");
+    /// assert_eq!(err.value, "query");
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, value: T) -> Result<&mut T, TryInsertError<'_, T>> {
+        match self {
+            Presence::Some(current) => Err(TryInsertError { current, value }),
+            Presence::Null | Presence::Absent => Ok(self.insert(value)),
+        }
+    }
+
     /// Inserts `value` into the presence if it is [`Null`] or [`Absent`], then
     /// returns a mutable reference to the contained value.
     ///
@@ -1299,6 +1646,40 @@ impl<T> Presence<T> {
         }
     }
 
+    /// Inserts a value computed from the fallible `f` into the presence if it is [`Null`]
+    /// or [`Absent`], then returns a mutable reference to the contained value.
+    ///
+    /// If `f` fails, the presence is left unchanged and the error is returned.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut port: Presence<u16> = Presence::Absent;
+    /// assert!(port.get_or_try_insert_with(|| "http".parse()).is_err());
+    /// assert_eq!(port, Presence::Absent);
+    ///
+    /// assert_eq!(port.get_or_try_insert_with(|| "8080".parse()), Ok(&mut 8080));
+    /// assert_eq!(port.get_or_try_insert_with(|| "80".parse()), Ok(&mut 8080));
+    /// ```
+    #[inline]
+    pub fn get_or_try_insert_with<F, E>(&mut self, f: F) -> Result<&mut T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if matches!(self, Presence::Null | Presence::Absent) {
+            *self = Presence::Some(f()?);
+        }
+        match self {
+            Presence::Some(v) => Ok(v),
+            _ => unreachable!(),
+        }
+    }
+
     /// Returns the number of elements in the `Presence`.
     ///
     /// This returns `1` if the presence contains a [`Some`] value, and `0` for
@@ -1531,23 +1912,16 @@ impl<T> Presence<T> {
         }
     }
 
-    /////////////////////////////////////////////////////////////////////////
-    // Result conversions
-    /////////////////////////////////////////////////////////////////////////
-
-    /// Transforms the `Presence<T>` into a [`Result<T, E>`], mapping [`Some(v)`] to
-    /// [`Ok(v)`] and [`Null`] or [`Absent`] to [`Err(err)`].
+    /// Maps a `Presence<T>` to `U` by applying a function to a contained value,
+    /// with different defaults for [`Null`] and [`Absent`].
     ///
-    /// Arguments passed to `ok_or` are eagerly evaluated; if you are passing the
-    /// result of a function call, it is recommended to use [`ok_or_else`], which is
-    /// lazily evaluated.
+    /// Arguments passed to `map_or_null_default` are eagerly evaluated; if you are
+    /// passing the result of a function call, it is recommended to use
+    /// [`map_or_null_else`], which is lazily evaluated.
     ///
-    /// [`Some(v)`]: Presence::Some
-    /// [`Ok(v)`]: Ok
     /// [`Null`]: Presence::Null
     /// [`Absent`]: Presence::Absent
-    /// [`Err(err)`]: Err
-    /// [`ok_or_else`]: Presence::ok_or_else
+    /// [`map_or_null_else`]: Presence::map_or_null_else
     ///
     /// # Examples
     ///
@@ -1555,40 +1929,151 @@ impl<T> Presence<T> {
     /// use presence_rs::Presence;
     ///
     /// let x = Presence::Some("foo");
-    /// assert_eq!(x.ok_or(0), Ok("foo"));
+    /// assert_eq!(x.map_or_null_default("unchanged", "cleared", |v| v), "foo");
     ///
     /// let y: Presence<&str> = Presence::Null;
-    /// assert_eq!(y.ok_or(0), Err(0));
+    /// assert_eq!(y.map_or_null_default("unchanged", "cleared", |v| v), "cleared");
     ///
     /// let z: Presence<&str> = Presence::Absent;
-    /// assert_eq!(z.ok_or(0), Err(0));
+    /// assert_eq!(z.map_or_null_default("unchanged", "cleared", |v| v), "unchanged");
     /// ```
     #[inline]
-    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+    #[must_use = "Returns the mapped value or default"]
+    pub fn map_or_null_default<U, F>(self, absent_default: U, null_default: U, f: F) -> U
+    where
+        F: FnOnce(T) -> U,
+    {
         match self {
-            Presence::Some(val) => Ok(val),
-            Presence::Null | Presence::Absent => Err(err),
+            Presence::Some(val) => f(val),
+            Presence::Null => null_default,
+            Presence::Absent => absent_default,
         }
     }
 
-    /// Transforms the `Presence<T>` into a [`Result<T, E>`], mapping [`Some(v)`] to
-    /// [`Ok(v)`] and [`Null`] or [`Absent`] to [`Err(err())`].
+    /// Maps a `Presence<T>` to `U` by applying a function to a contained value,
+    /// or computes a default with `null` (if [`Null`]) or `absent` (if [`Absent`]).
     ///
-    /// [`Some(v)`]: Presence::Some
-    /// [`Ok(v)`]: Ok
     /// [`Null`]: Presence::Null
     /// [`Absent`]: Presence::Absent
-    /// [`Err(err())`]: Err
     ///
     /// # Examples
     ///
     /// ```
     /// use presence_rs::Presence;
     ///
-    /// let x = Presence::Some("foo");
-    /// assert_eq!(x.ok_or_else(|| 0), Ok("foo"));
+    /// let describe = |x: Presence<u32>| {
+    ///     x.map_or_null_else(|| "not sent".to_string(), || "reset".to_string(), |v| format!("set to {v}"))
+    /// };
     ///
-    /// let y: Presence<&str> = Presence::Null;
+    /// assert_eq!(describe(Presence::Some(3)), "set to 3");
+    /// assert_eq!(describe(Presence::Null), "reset");
+    /// assert_eq!(describe(Presence::Absent), "not sent");
+    /// ```
+    #[inline]
+    #[must_use = "Returns the mapped value or computed default"]
+    pub fn map_or_null_else<U, A, N, F>(self, absent: A, null: N, f: F) -> U
+    where
+        A: FnOnce() -> U,
+        N: FnOnce() -> U,
+        F: FnOnce(T) -> U,
+    {
+        match self {
+            Presence::Some(val) => f(val),
+            Presence::Null => null(),
+            Presence::Absent => absent(),
+        }
+    }
+
+    /// Consumes the presence, calling exactly one of the functions for its state and
+    /// returning the result.
+    ///
+    /// This is the exhaustive counterpart of a `match`, usable in method chains.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// fn to_sql(value: Presence<i64>) -> String {
+    ///     value.fold(|| "DEFAULT".into(), || "NULL".into(), |v| v.to_string())
+    /// }
+    ///
+    /// assert_eq!(to_sql(Presence::Some(7)), "7");
+    /// assert_eq!(to_sql(Presence::Null), "NULL");
+    /// assert_eq!(to_sql(Presence::Absent), "DEFAULT");
+    /// ```
+    #[inline]
+    pub fn fold<U, A, N, S>(self, on_absent: A, on_null: N, on_some: S) -> U
+    where
+        A: FnOnce() -> U,
+        N: FnOnce() -> U,
+        S: FnOnce(T) -> U,
+    {
+        match self {
+            Presence::Some(val) => on_some(val),
+            Presence::Null => on_null(),
+            Presence::Absent => on_absent(),
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Result conversions
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Transforms the `Presence<T>` into a [`Result<T, E>`], mapping [`Some(v)`] to
+    /// [`Ok(v)`] and [`Null`] or [`Absent`] to [`Err(err)`].
+    ///
+    /// Arguments passed to `ok_or` are eagerly evaluated; if you are passing the
+    /// result of a function call, it is recommended to use [`ok_or_else`], which is
+    /// lazily evaluated.
+    ///
+    /// [`Some(v)`]: Presence::Some
+    /// [`Ok(v)`]: Ok
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    /// [`Err(err)`]: Err
+    /// [`ok_or_else`]: Presence::ok_or_else
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let x = Presence::Some("foo");
+    /// assert_eq!(x.ok_or(0), Ok("foo"));
+    ///
+    /// let y: Presence<&str> = Presence::Null;
+    /// assert_eq!(y.ok_or(0), Err(0));
+    ///
+    /// let z: Presence<&str> = Presence::Absent;
+    /// assert_eq!(z.ok_or(0), Err(0));
+    /// ```
+    #[inline]
+    pub fn ok_or<E>(self, err: E) -> Result<T, E> {
+        match self {
+            Presence::Some(val) => Ok(val),
+            Presence::Null | Presence::Absent => Err(err),
+        }
+    }
+
+    /// Transforms the `Presence<T>` into a [`Result<T, E>`], mapping [`Some(v)`] to
+    /// [`Ok(v)`] and [`Null`] or [`Absent`] to [`Err(err())`].
+    ///
+    /// [`Some(v)`]: Presence::Some
+    /// [`Ok(v)`]: Ok
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    /// [`Err(err())`]: Err
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let x = Presence::Some("foo");
+    /// assert_eq!(x.ok_or_else(|| 0), Ok("foo"));
+    ///
+    /// let y: Presence<&str> = Presence::Null;
     /// assert_eq!(y.ok_or_else(|| 0), Err(0));
     ///
     /// let z: Presence<&str> = Presence::Absent;
@@ -1944,6 +2429,39 @@ impl<T> Presence<T> {
         self.zip_with(other, f)
     }
 
+    /// Applies a presence-wrapped function to `self`.
+    ///
+    /// If `f` is `Some(f)` and `self` is `Some(v)`, this method returns `Some(f(v))`.
+    /// Otherwise, returns `Absent` if either is `Absent`, or `Null` if either is `Null`,
+    /// the same precedence as [`zip_with`].
+    ///
+    /// [`zip_with`]: Presence::zip_with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let double = Presence::Some(|x: i32| x * 2);
+    /// assert_eq!(Presence::Some(4).apply(double), Presence::Some(8));
+    /// assert_eq!(Presence::Null.apply(double), Presence::Null);
+    ///
+    /// let missing: Presence<fn(i32) -> i32> = Presence::Absent;
+    /// assert_eq!(Presence::Null.apply(missing), Presence::Absent);
+    ///
+    /// // Curried functions apply one argument at a time.
+    /// let add = |a: i32| move |b: i32| a + b;
+    /// let sum = Presence::Some(2).apply(Presence::Some(1).map(add));
+    /// assert_eq!(sum, Presence::Some(3));
+    /// ```
+    #[inline]
+    pub fn apply<U, F>(self, f: Presence<F>) -> Presence<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        f.zip_with(self, |f, value| f(value))
+    }
+
     /// Unzips a presence containing a tuple of two values.
     ///
     /// If `self` is `Some((a, b))`, this method returns `(Some(a), Some(b))`.
@@ -1977,6 +2495,236 @@ impl<T> Presence<T> {
         }
     }
 
+    /// Unzips a presence containing a tuple of three values.
+    ///
+    /// If `self` is `Some((a, b, c))`, this method returns `(Some(a), Some(b), Some(c))`.
+    /// Otherwise, every element is `Null` or `Absent`, like `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let x = Presence::Some((1, "hi", true));
+    /// assert_eq!(
+    ///     x.unzip3(),
+    ///     (Presence::Some(1), Presence::Some("hi"), Presence::Some(true))
+    /// );
+    ///
+    /// let y: Presence<(i32, &str, bool)> = Presence::Null;
+    /// assert_eq!(y.unzip3(), (Presence::Null, Presence::Null, Presence::Null));
+    /// ```
+    #[inline]
+    pub fn unzip3<A, B, C>(self) -> (Presence<A>, Presence<B>, Presence<C>)
+    where
+        T: Into<(A, B, C)>,
+    {
+        match self {
+            Presence::Some(val) => {
+                let (a, b, c) = val.into();
+                (Presence::Some(a), Presence::Some(b), Presence::Some(c))
+            }
+            Presence::Null => (Presence::Null, Presence::Null, Presence::Null),
+            Presence::Absent => (Presence::Absent, Presence::Absent, Presence::Absent),
+        }
+    }
+
+    /// Unzips a presence containing a tuple of four values.
+    ///
+    /// If `self` is `Some((a, b, c, d))`, this method returns
+    /// `(Some(a), Some(b), Some(c), Some(d))`. Otherwise, every element is `Null` or
+    /// `Absent`, like `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let rgba: Presence<(u8, u8, u8, u8)> = Presence::Some((255, 128, 0, 255));
+    /// let (r, g, b, a) = rgba.unzip4();
+    /// assert_eq!(r, Presence::Some(255));
+    /// assert_eq!(g, Presence::Some(128));
+    /// assert_eq!(b, Presence::Some(0));
+    /// assert_eq!(a, Presence::Some(255));
+    ///
+    /// let rgba: Presence<(u8, u8, u8, u8)> = Presence::Absent;
+    /// assert_eq!(rgba.unzip4().3, Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn unzip4<A, B, C, D>(self) -> (Presence<A>, Presence<B>, Presence<C>, Presence<D>)
+    where
+        T: Into<(A, B, C, D)>,
+    {
+        match self {
+            Presence::Some(val) => {
+                let (a, b, c, d) = val.into();
+                (
+                    Presence::Some(a),
+                    Presence::Some(b),
+                    Presence::Some(c),
+                    Presence::Some(d),
+                )
+            }
+            Presence::Null => (
+                Presence::Null,
+                Presence::Null,
+                Presence::Null,
+                Presence::Null,
+            ),
+            Presence::Absent => (
+                Presence::Absent,
+                Presence::Absent,
+                Presence::Absent,
+                Presence::Absent,
+            ),
+        }
+    }
+
+    /////////////////////////////////////////////////////////////////////////
+    // Ordering
+    /////////////////////////////////////////////////////////////////////////
+
+    /// Compares `self` with `other`, ordering `Null` and `Absent` before all values.
+    ///
+    /// This is the derived `Ord`, which sorts `Absent` before `Null`. Use it with
+    /// `sort_by` when the intent should be explicit, as with SQL's `NULLS FIRST`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut values = vec![Presence::Some(2), Presence::Null, Presence::Some(1), Presence::Absent];
+    /// values.sort_by(Presence::cmp_nulls_first);
+    /// assert_eq!(
+    ///     values,
+    ///     [Presence::Absent, Presence::Null, Presence::Some(1), Presence::Some(2)]
+    /// );
+    /// ```
+    #[inline]
+    pub fn cmp_nulls_first(&self, other: &Self) -> Ordering
+    where
+        T: Ord,
+    {
+        self.cmp(other)
+    }
+
+    /// Compares `self` with `other`, ordering `Null` and `Absent` after all values.
+    ///
+    /// Values compare by `T`'s `Ord`, and `Absent` sorts before `Null`, as with SQL's
+    /// `NULLS LAST`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let mut values = vec![Presence::Some(2), Presence::Null, Presence::Some(1), Presence::Absent];
+    /// values.sort_by(Presence::cmp_nulls_last);
+    /// assert_eq!(
+    ///     values,
+    ///     [Presence::Some(1), Presence::Some(2), Presence::Absent, Presence::Null]
+    /// );
+    ///
+    /// // Descending values, still with nulls last.
+    /// use std::cmp::Reverse;
+    /// values.sort_by(|a, b| a.map(Reverse).cmp_nulls_last(&b.map(Reverse)));
+    /// assert_eq!(
+    ///     values,
+    ///     [Presence::Some(2), Presence::Some(1), Presence::Absent, Presence::Null]
+    /// );
+    /// ```
+    #[inline]
+    pub fn cmp_nulls_last(&self, other: &Self) -> Ordering
+    where
+        T: Ord,
+    {
+        match (self, other) {
+            (Presence::Some(a), Presence::Some(b)) => a.cmp(b),
+            (Presence::Some(_), _) => Ordering::Less,
+            (_, Presence::Some(_)) => Ordering::Greater,
+            (a, b) => a.state().cmp(&b.state()),
+        }
+    }
+
+    /// Returns the smaller of two present values.
+    ///
+    /// If both are `Some`, returns `Some` of the minimum, or `self` if they are equal.
+    /// Otherwise, like [`zip`], returns `Absent` if either is `Absent`, or `Null` if one is
+    /// `Null`. This differs from `Ord::min`, which orders `Absent < Null < Some` and so
+    /// returns an empty state whenever there is one.
+    ///
+    /// [`zip`]: Presence::zip
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(3).min_present(Presence::Some(5)), Presence::Some(3));
+    /// assert_eq!(Presence::Some(3).min_present(Presence::Null), Presence::Null);
+    /// assert_eq!(Presence::Null.min_present(Presence::<i32>::Absent), Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn min_present(self, other: Presence<T>) -> Presence<T>
+    where
+        T: Ord,
+    {
+        self.zip_with(other, Ord::min)
+    }
+
+    /// Returns the larger of two present values.
+    ///
+    /// If both are `Some`, returns `Some` of the maximum, or `other` if they are equal.
+    /// Otherwise, like [`zip`], returns `Absent` if either is `Absent`, or `Null` if one is
+    /// `Null`. `Ord::max` instead returns the `Some` value whenever there is one.
+    ///
+    /// [`zip`]: Presence::zip
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(3).max_present(Presence::Some(5)), Presence::Some(5));
+    /// assert_eq!(Presence::Null.max_present(Presence::Some(5)), Presence::Null);
+    /// assert_eq!(Presence::Absent.max_present(Presence::Some(5)), Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn max_present(self, other: Presence<T>) -> Presence<T>
+    where
+        T: Ord,
+    {
+        self.zip_with(other, Ord::max)
+    }
+
+    /// Restricts a present value to the interval `[min, max]`, leaving `Null` and `Absent`
+    /// unchanged.
+    ///
+    /// Unlike `Ord::clamp`, the bounds are plain values rather than presences.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min > max`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(120).clamp_present(0, 100), Presence::Some(100));
+    /// assert_eq!(Presence::Some(-4).clamp_present(0, 100), Presence::Some(0));
+    /// assert_eq!(Presence::<i32>::Null.clamp_present(0, 100), Presence::Null);
+    /// ```
+    #[inline]
+    pub fn clamp_present(self, min: T, max: T) -> Presence<T>
+    where
+        T: Ord,
+    {
+        assert!(min <= max, "clamp_present requires min <= max");
+        self.map(|value| value.clamp(min, max))
+    }
+
     /////////////////////////////////////////////////////////////////////////
     // Iterator constructors
     /////////////////////////////////////////////////////////////////////////
@@ -2048,6 +2796,42 @@ impl<T> Presence<T> {
         }
     }
 
+    /// Returns an iterator over the defined state.
+    ///
+    /// The iterator yields `Some(&value)` for [`Some`], `None` for [`Null`], and nothing
+    /// for [`Absent`], so explicit nulls are processed along with values.
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let x = Presence::Some(42);
+    /// assert_eq!(x.iter_defined().collect::<Vec<_>>(), [Some(&42)]);
+    ///
+    /// let y: Presence<i32> = Presence::Null;
+    /// assert_eq!(y.iter_defined().collect::<Vec<_>>(), [None]);
+    ///
+    /// let z: Presence<i32> = Presence::Absent;
+    /// assert_eq!(z.iter_defined().next(), None);
+    ///
+    /// let mentioned = [Presence::Some(1), Presence::Absent, Presence::Null]
+    ///     .iter()
+    ///     .flat_map(Presence::iter_defined)
+    ///     .count();
+    /// assert_eq!(mentioned, 2);
+    /// ```
+    #[inline]
+    pub fn iter_defined(&self) -> IterDefined<'_, T> {
+        IterDefined {
+            inner: self.as_ref().to_nullable(),
+        }
+    }
+
     /////////////////////////////////////////////////////////////////////////
     // Transforming contained values
     /////////////////////////////////////////////////////////////////////////
@@ -2333,13 +3117,66 @@ impl<'a, A> ExactSizeIterator for IterMut<'a, A> {
 
 impl<A> FusedIterator for IterMut<'_, A> {}
 
-/////////////////////////////////////////////////////////////////////////////
-// Trait implementations for Presence<&T>
-/////////////////////////////////////////////////////////////////////////////
+/// An iterator over the defined state of a `Presence`.
+///
+/// This struct is created by the [`iter_defined`] method on [`Presence`].
+///
+/// [`iter_defined`]: Presence::iter_defined
+/// [`Presence`]: Presence
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+///
+/// let x: Presence<i32> = Presence::Null;
+/// let mut iter = x.iter_defined();
+/// assert_eq!(iter.next(), Some(None));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct IterDefined<'a, A> {
+    inner: Option<Option<&'a A>>,
+}
 
-impl<T> Presence<&T> {
-    /// Maps a `Presence<&T>` to a `Presence<T>` by copying the contents of the
-    /// presence.
+impl<'a, A> Iterator for IterDefined<'a, A> {
+    type Item = Option<&'a A>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.take()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl<A> DoubleEndedIterator for IterDefined<'_, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.take()
+    }
+}
+
+impl<A> ExactSizeIterator for IterDefined<'_, A> {
+    #[inline]
+    fn len(&self) -> usize {
+        usize::from(self.inner.is_some())
+    }
+}
+
+impl<A> FusedIterator for IterDefined<'_, A> {}
+
+/////////////////////////////////////////////////////////////////////////////
+// Trait implementations for Presence<&T>
+/////////////////////////////////////////////////////////////////////////////
+
+impl<T> Presence<&T> {
+    /// Maps a `Presence<&T>` to a `Presence<T>` by copying the contents of the
+    /// presence.
     ///
     /// # Examples
     ///
@@ -2523,6 +3360,534 @@ impl<T> Presence<Presence<T>> {
     }
 }
 
+/////////////////////////////////////////////////////////////////////////////
+// Presence<String> and Presence<&str> implementation
+/////////////////////////////////////////////////////////////////////////////
+
+impl Presence<String> {
+    /// Converts from `&Presence<String>` to `Presence<&str>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let name = Presence::Some("Alice".to_string());
+    /// assert_eq!(name.as_str(), Presence::Some("Alice"));
+    /// assert_eq!(Presence::<String>::Null.as_str(), Presence::Null);
+    /// ```
+    #[inline]
+    pub fn as_str(&self) -> Presence<&str> {
+        match self {
+            Presence::Some(value) => Presence::Some(value.as_str()),
+            Presence::Null => Presence::Null,
+            Presence::Absent => Presence::Absent,
+        }
+    }
+
+    /// Converts an empty string to [`Null`], leaving other values unchanged.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(String::new()).empty_to_null(), Presence::Null);
+    /// assert_eq!(Presence::Some(" ".to_string()).empty_to_null(), Presence::Some(" ".to_string()));
+    /// assert_eq!(Presence::<String>::Absent.empty_to_null(), Presence::Absent);
+    /// ```
+    #[inline]
+    #[must_use = "Returns the normalized value"]
+    pub fn empty_to_null(self) -> Self {
+        match self {
+            Presence::Some(value) if value.is_empty() => Presence::Null,
+            other => other,
+        }
+    }
+
+    /// Trims whitespace from both ends, converting a string that is empty after trimming
+    /// to [`Null`].
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some("  Bob ".to_string()).trim_to_null(), Presence::Some("Bob".to_string()));
+    /// assert_eq!(Presence::Some(" \t".to_string()).trim_to_null(), Presence::Null);
+    /// ```
+    #[must_use = "Returns the normalized value"]
+    pub fn trim_to_null(self) -> Self {
+        match self {
+            Presence::Some(value) => match value.trim() {
+                "" => Presence::Null,
+                trimmed if trimmed.len() == value.len() => Presence::Some(value),
+                trimmed => Presence::Some(trimmed.to_string()),
+            },
+            other => other,
+        }
+    }
+
+    /// Returns `true` if the presence is [`Null`], [`Absent`], or a string of only
+    /// whitespace.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert!(Presence::Some("  ".to_string()).is_blank_or_nullish());
+    /// assert!(Presence::<String>::Null.is_blank_or_nullish());
+    /// assert!(!Presence::Some("x".to_string()).is_blank_or_nullish());
+    /// ```
+    #[inline]
+    pub fn is_blank_or_nullish(&self) -> bool {
+        self.as_str().is_blank_or_nullish()
+    }
+
+    /// Parses the string into another type, keeping [`Null`] and [`Absent`].
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error of `U` if the string cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some("42".to_string()).parse::<u8>(), Ok(Presence::Some(42)));
+    /// assert_eq!(Presence::<String>::Null.parse::<u8>(), Ok(Presence::Null));
+    /// assert!(Presence::Some("x".to_string()).parse::<u8>().is_err());
+    /// ```
+    #[inline]
+    pub fn parse<U: FromStr>(self) -> Result<Presence<U>, U::Err> {
+        self.as_str().parse()
+    }
+}
+
+impl Presence<&str> {
+    /// Converts an empty string to [`Null`], leaving other values unchanged.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some("").empty_to_null(), Presence::Null);
+    /// assert_eq!(Presence::Some("a").empty_to_null(), Presence::Some("a"));
+    /// ```
+    #[inline]
+    #[must_use = "Returns the normalized value"]
+    pub fn empty_to_null(self) -> Self {
+        match self {
+            Presence::Some("") => Presence::Null,
+            other => other,
+        }
+    }
+
+    /// Trims whitespace from both ends, converting a string that is empty after trimming
+    /// to [`Null`].
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(" a ").trim_to_null(), Presence::Some("a"));
+    /// assert_eq!(Presence::Some("   ").trim_to_null(), Presence::Null);
+    /// ```
+    #[inline]
+    #[must_use = "Returns the normalized value"]
+    pub fn trim_to_null(self) -> Self {
+        self.map(str::trim).empty_to_null()
+    }
+
+    /// Returns `true` if the presence is [`Null`], [`Absent`], or a string of only
+    /// whitespace.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert!(Presence::Some("").is_blank_or_nullish());
+    /// assert!(Presence::<&str>::Absent.is_blank_or_nullish());
+    /// assert!(!Presence::Some(" x ").is_blank_or_nullish());
+    /// ```
+    #[inline]
+    pub fn is_blank_or_nullish(&self) -> bool {
+        match self {
+            Presence::Some(value) => value.trim().is_empty(),
+            Presence::Null | Presence::Absent => true,
+        }
+    }
+
+    /// Parses the string into another type, keeping [`Null`] and [`Absent`].
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error of `U` if the string cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let limit: Presence<&str> = Presence::Some("10");
+    /// assert_eq!(limit.parse::<usize>(), Ok(Presence::Some(10)));
+    /// assert_eq!(Presence::<&str>::Absent.parse::<usize>(), Ok(Presence::Absent));
+    /// assert!(Presence::Some("-1").parse::<usize>().is_err());
+    /// ```
+    #[inline]
+    pub fn parse<U: FromStr>(self) -> Result<Presence<U>, U::Err> {
+        self.map(str::parse).transpose()
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Presence<Vec<T>> implementation
+/////////////////////////////////////////////////////////////////////////////
+
+impl<T> Presence<Vec<T>> {
+    /// Returns an iterator over the elements of the contained list, which is empty for
+    /// [`Null`] and [`Absent`].
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let tags = Presence::Some(vec!["a", "b"]);
+    /// assert_eq!(tags.iter_flat().collect::<Vec<_>>(), [&"a", &"b"]);
+    /// assert_eq!(Presence::<Vec<i32>>::Null.iter_flat().count(), 0);
+    /// ```
+    #[inline]
+    pub fn iter_flat(&self) -> std::slice::Iter<'_, T> {
+        match self {
+            Presence::Some(values) => values.iter(),
+            Presence::Null | Presence::Absent => [].iter(),
+        }
+    }
+
+    /// Returns `true` if the presence is [`Null`], [`Absent`], or an empty list.
+    ///
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert!(Presence::Some(Vec::<i32>::new()).is_empty_or_nullish());
+    /// assert!(Presence::<Vec<i32>>::Absent.is_empty_or_nullish());
+    /// assert!(!Presence::Some(vec![1]).is_empty_or_nullish());
+    /// ```
+    #[inline]
+    pub fn is_empty_or_nullish(&self) -> bool {
+        match self {
+            Presence::Some(values) => values.is_empty(),
+            Presence::Null | Presence::Absent => true,
+        }
+    }
+
+    /// Converts an empty list to [`Absent`], so that sending `[]` leaves the field alone.
+    ///
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(Vec::<i32>::new()).empty_to_absent(), Presence::Absent);
+    /// assert_eq!(Presence::Some(vec![1]).empty_to_absent(), Presence::Some(vec![1]));
+    /// assert_eq!(Presence::<Vec<i32>>::Null.empty_to_absent(), Presence::Null);
+    /// ```
+    #[inline]
+    #[must_use = "Returns the normalized value"]
+    pub fn empty_to_absent(self) -> Self {
+        match self {
+            Presence::Some(values) if values.is_empty() => Presence::Absent,
+            other => other,
+        }
+    }
+
+    /// Converts an empty list to [`Null`], so that sending `[]` clears the field.
+    ///
+    /// [`Null`]: Presence::Null
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(Vec::<i32>::new()).empty_to_null(), Presence::Null);
+    /// assert_eq!(Presence::<Vec<i32>>::Absent.empty_to_null(), Presence::Absent);
+    /// ```
+    #[inline]
+    #[must_use = "Returns the normalized value"]
+    pub fn empty_to_null(self) -> Self {
+        match self {
+            Presence::Some(values) if values.is_empty() => Presence::Null,
+            other => other,
+        }
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Presence<Cow<B>> implementation
+/////////////////////////////////////////////////////////////////////////////
+
+impl<B: ?Sized + ToOwned> Presence<Cow<'_, B>> {
+    /// Converts the contained value into its owned form, cloning borrowed data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::borrow::Cow;
+    ///
+    /// let name: Presence<Cow<str>> = Presence::Some(Cow::Borrowed("Bob"));
+    /// let owned: Presence<String> = name.into_owned();
+    /// assert_eq!(owned, Presence::Some("Bob".to_string()));
+    /// ```
+    #[inline]
+    pub fn into_owned(self) -> Presence<B::Owned> {
+        self.map(Cow::into_owned)
+    }
+
+    /// Converts into a `Presence<Cow<'static, B>>` by taking ownership of borrowed data,
+    /// so that a zero-copy patch can outlive its input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::borrow::Cow;
+    ///
+    /// fn queue(_patch: Presence<Cow<'static, [u8]>>) {}
+    ///
+    /// let input = vec![1u8, 2, 3];
+    /// let avatar: Presence<Cow<[u8]>> = Presence::Some(Cow::Borrowed(&input));
+    /// queue(avatar.into_static());
+    /// ```
+    #[inline]
+    pub fn into_static(self) -> Presence<Cow<'static, B>>
+    where
+        B: 'static,
+    {
+        self.map(|value| Cow::Owned(value.into_owned()))
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Presence<Box<T>> implementation
+/////////////////////////////////////////////////////////////////////////////
+
+impl<T> Presence<Box<T>> {
+    /// Moves the contained value out of its box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// let x: Presence<Box<u32>> = Presence::Some(Box::new(7));
+    /// assert_eq!(x.unbox(), Presence::Some(7));
+    ///
+    /// let x: Presence<Box<u32>> = Presence::Null;
+    /// assert_eq!(x.unbox(), Presence::Null);
+    /// ```
+    #[inline]
+    pub fn unbox(self) -> Presence<T> {
+        self.map(|value| *value)
+    }
+}
+
+impl<T> Presence<T> {
+    /// Moves the contained value into a `Box`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!(Presence::Some(7).boxed(), Presence::Some(Box::new(7)));
+    /// assert_eq!(Presence::<u32>::Absent.boxed(), Presence::Absent);
+    /// ```
+    #[inline]
+    pub fn boxed(self) -> Presence<Box<T>> {
+        self.map(Box::new)
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// Presence<Arc<T>> and Presence<Rc<T>> implementation
+/////////////////////////////////////////////////////////////////////////////
+
+impl<T: Clone> Presence<Arc<T>> {
+    /// Returns a mutable reference to the contained value, cloning it first if the `Arc`
+    /// is shared.
+    ///
+    /// See [`Arc::make_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::sync::Arc;
+    ///
+    /// let cached = Arc::new(vec![1, 2]);
+    /// let mut x = Presence::Some(Arc::clone(&cached));
+    /// if let Presence::Some(list) = x.make_mut() {
+    ///     list.push(3);
+    /// }
+    /// assert_eq!(*cached, vec![1, 2]);
+    /// assert_eq!(x.as_deref(), Presence::Some(&vec![1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn make_mut(&mut self) -> Presence<&mut T> {
+        self.as_mut().map(Arc::make_mut)
+    }
+}
+
+impl<T> Presence<Arc<T>> {
+    /// Moves the contained value out of its `Arc` if it is the only strong reference,
+    /// and returns `self` unchanged otherwise.
+    ///
+    /// `Null` and `Absent` always succeed. See [`Arc::try_unwrap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::sync::Arc;
+    ///
+    /// let x = Presence::Some(Arc::new(5));
+    /// assert_eq!(x.try_unwrap(), Ok(Presence::Some(5)));
+    ///
+    /// let shared = Arc::new(5);
+    /// let x = Presence::Some(Arc::clone(&shared));
+    /// assert!(x.try_unwrap().is_err());
+    ///
+    /// assert_eq!(Presence::<Arc<i32>>::Null.try_unwrap(), Ok(Presence::Null));
+    /// ```
+    #[inline]
+    pub fn try_unwrap(self) -> Result<Presence<T>, Self> {
+        match self {
+            Presence::Some(value) => Arc::try_unwrap(value)
+                .map(Presence::Some)
+                .map_err(Presence::Some),
+            Presence::Null => Ok(Presence::Null),
+            Presence::Absent => Ok(Presence::Absent),
+        }
+    }
+}
+
+impl<T: Clone> Presence<Rc<T>> {
+    /// Returns a mutable reference to the contained value, cloning it first if the `Rc`
+    /// is shared.
+    ///
+    /// See [`Rc::make_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::rc::Rc;
+    ///
+    /// let mut x = Presence::Some(Rc::new(1));
+    /// let other = x.clone();
+    /// if let Presence::Some(value) = x.make_mut() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(x, Presence::Some(Rc::new(2)));
+    /// assert_eq!(other, Presence::Some(Rc::new(1)));
+    /// ```
+    #[inline]
+    pub fn make_mut(&mut self) -> Presence<&mut T> {
+        self.as_mut().map(Rc::make_mut)
+    }
+}
+
+impl<T> Presence<Rc<T>> {
+    /// Moves the contained value out of its `Rc` if it is the only strong reference,
+    /// and returns `self` unchanged otherwise.
+    ///
+    /// `Null` and `Absent` always succeed. See [`Rc::try_unwrap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use std::rc::Rc;
+    ///
+    /// let x = Presence::Some(Rc::new("a"));
+    /// assert_eq!(x.try_unwrap(), Ok(Presence::Some("a")));
+    /// assert_eq!(Presence::<Rc<i32>>::Absent.try_unwrap(), Ok(Presence::Absent));
+    /// ```
+    #[inline]
+    pub fn try_unwrap(self) -> Result<Presence<T>, Self> {
+        match self {
+            Presence::Some(value) => Rc::try_unwrap(value)
+                .map(Presence::Some)
+                .map_err(Presence::Some),
+            Presence::Null => Ok(Presence::Null),
+            Presence::Absent => Ok(Presence::Absent),
+        }
+    }
+}
+
+/////////////////////////////////////////////////////////////////////////////
+// TryInsertError
+/////////////////////////////////////////////////////////////////////////////
+
+/// The error returned by [`Presence::try_insert`] when the presence already holds a value.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TryInsertError<'a, T> {
+    /// The value already in the presence.
+    pub current: &'a mut T,
+    /// The value that was not inserted.
+    pub value: T,
+}
+
+impl<T: fmt::Debug> fmt::Display for TryInsertError<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, presence already holds {:?}",
+            self.value, self.current
+        )
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for TryInsertError<'_, T> {}
+
 /////////////////////////////////////////////////////////////////////////////
 // FromIterator trait implementation
 /////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,165 @@
+//! Property testing support for [`Presence<T>`] via proptest.
+//!
+//! `Presence<T>` implements proptest's `Arbitrary`, so `any::<Presence<T>>()` works out of
+//! the box. For a custom inner strategy or a different mix of states, use
+//! [`presence_strategy`] with [`PresenceWeights`].
+//!
+//! Generated values shrink towards `Absent`, then `Null`, then a shrunk `Some`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::merge::PreferOtherIfDefined;
+//! use presence_rs::proptest::{PresenceWeights, presence_strategy};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn merging_absent_is_identity(value in any::<Presence<i32>>()) {
+//!         prop_assert_eq!(value.merge(Presence::Absent, PreferOtherIfDefined), value);
+//!     }
+//!
+//!     fn names_are_short(
+//!         name in presence_strategy("[a-z]{1,8}").weights(PresenceWeights::new(1, 1, 8)),
+//!     ) {
+//!         prop_assert!(name.map_or(0, |name| name.len()) <= 8);
+//!     }
+//! }
+//! # merging_absent_is_identity();
+//! # names_are_short();
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use proptest::arbitrary::{Arbitrary, any_with};
+use proptest::strategy::{BoxedStrategy, Just, NewTree, Strategy, Union, UnionValueTree};
+use proptest::test_runner::TestRunner;
+use std::fmt;
+use std::sync::Arc;
+
+/// The relative weights of the three states in generated values.
+///
+/// The default is `Absent` 1, `Null` 1, `Some` 2, so half of the values hold a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PresenceWeights {
+    /// The weight of `Absent`.
+    pub absent: u32,
+    /// The weight of `Null`.
+    pub null: u32,
+    /// The weight of `Some`.
+    pub some: u32,
+}
+
+impl PresenceWeights {
+    /// Creates weights for `Absent`, `Null` and `Some`. A weight of 0 disables a state.
+    pub const fn new(absent: u32, null: u32, some: u32) -> Self {
+        Self { absent, null, some }
+    }
+}
+
+impl Default for PresenceWeights {
+    fn default() -> Self {
+        Self::new(1, 1, 2)
+    }
+}
+
+/// A strategy generating `Presence` values from an inner strategy.
+///
+/// Created by [`presence_strategy`].
+pub struct PresenceStrategy<S> {
+    inner: Arc<S>,
+    weights: PresenceWeights,
+}
+
+/// Returns a strategy that generates `Absent`, `Null`, or `Some` with a value from
+/// `inner`, weighted by [`PresenceWeights::default`].
+pub fn presence_strategy<S: Strategy>(inner: S) -> PresenceStrategy<S> {
+    PresenceStrategy {
+        inner: Arc::new(inner),
+        weights: PresenceWeights::default(),
+    }
+}
+
+impl<S> PresenceStrategy<S> {
+    /// Sets the weights of the three states.
+    ///
+    /// # Panics
+    ///
+    /// Panics if all weights are 0.
+    pub fn weights(mut self, weights: PresenceWeights) -> Self {
+        assert!(
+            weights.absent > 0 || weights.null > 0 || weights.some > 0,
+            "at least one presence weight must be positive"
+        );
+        self.weights = weights;
+        self
+    }
+}
+
+impl<S: Strategy + 'static> PresenceStrategy<S>
+where
+    S::Value: Clone,
+{
+    fn union(&self) -> Union<BoxedStrategy<Presence<S::Value>>> {
+        let PresenceWeights { absent, null, some } = self.weights;
+        // Earlier options are simpler for shrinking.
+        let options = [
+            (absent, Just(Presence::Absent).boxed()),
+            (null, Just(Presence::Null).boxed()),
+            (
+                some,
+                Arc::clone(&self.inner).prop_map(Presence::Some).boxed(),
+            ),
+        ];
+        Union::new_weighted(
+            options
+                .into_iter()
+                .filter(|(weight, _)| *weight > 0)
+                .collect(),
+        )
+    }
+}
+
+impl<S> Clone for PresenceStrategy<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            weights: self.weights,
+        }
+    }
+}
+
+impl<S: fmt::Debug> fmt::Debug for PresenceStrategy<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PresenceStrategy")
+            .field("inner", &self.inner)
+            .field("weights", &self.weights)
+            .finish()
+    }
+}
+
+impl<S: Strategy + 'static> Strategy for PresenceStrategy<S>
+where
+    S::Value: Clone,
+{
+    type Tree = UnionValueTree<BoxedStrategy<Presence<S::Value>>>;
+    type Value = Presence<S::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        self.union().new_tree(runner)
+    }
+}
+
+impl<T> Arbitrary for Presence<T>
+where
+    T: Arbitrary + Clone + 'static,
+    T::Strategy: 'static,
+{
+    type Parameters = (PresenceWeights, T::Parameters);
+    type Strategy = PresenceStrategy<T::Strategy>;
+
+    fn arbitrary_with((weights, args): Self::Parameters) -> Self::Strategy {
+        presence_strategy(any_with::<T>(args)).weights(weights)
+    }
+}
@@ -0,0 +1,399 @@
+//! Protobuf integration for patch structs of [`Presence<T>`] fields.
+//!
+//! gRPC update RPCs accompany the message with a `google.protobuf.FieldMask` naming the
+//! fields to write. Deriving the mask from the patch's presence state keeps the two
+//! from drifting apart: every defined field (`Some` or `Null`) becomes a path, and
+//! `Absent` fields are left out.
+//!
+//! Field names come from serde, so `#[serde(rename)]` and `rename_all` apply. Presence
+//! fields must be annotated with `#[serde(skip_serializing_if = "Presence::is_absent")]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::prost::field_mask;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct UpdateUser {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     display_name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     avatar_url: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     age: Presence<u32>,
+//! }
+//!
+//! let patch = UpdateUser {
+//!     display_name: Presence::Some("Bob".into()),
+//!     avatar_url: Presence::Null,
+//!     age: Presence::Absent,
+//! };
+//!
+//! let mask = field_mask(&patch).unwrap();
+//! assert_eq!(mask.paths, ["display_name", "avatar_url"]);
+//! ```
+//!
+//! # Applying Updates
+//!
+//! On the server side of an `Update` RPC, [`apply_patch!`] writes a Presence patch onto
+//! an existing prost message, and [`apply_mask!`] copies the masked fields of a request
+//! message onto the stored one. Optional proto3 fields (`Option<T>` in prost) are set
+//! to `None` by `Null`; plain scalar, string, repeated and map fields are reset to their
+//! default, which is how proto3 represents a cleared field.
+//!
+//! ```
+//! use presence_rs::{Presence, apply_mask, apply_patch};
+//! use presence_rs::prost::field_mask;
+//! use serde::Serialize;
+//!
+//! // As generated by prost for `message User { string name = 1; optional string bio = 2; }`
+//! #[derive(Clone, Debug, Default, PartialEq)]
+//! struct User {
+//!     name: String,
+//!     bio: Option<String>,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct UserPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     name: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     bio: Presence<String>,
+//! }
+//!
+//! let mut stored = User { name: "Alice".into(), bio: Some("hi".into()) };
+//!
+//! // From a patch struct
+//! let patch = UserPatch { name: Presence::Absent, bio: Presence::Null };
+//! apply_patch!(stored, patch; name, bio);
+//! assert_eq!(stored, User { name: "Alice".into(), bio: None });
+//!
+//! // From a request message and its mask
+//! let request = User { name: "Bob".into(), bio: Some("ignored".into()) };
+//! let mask = field_mask(&UserPatch { name: Presence::Some("Bob".into()), bio: Presence::Absent }).unwrap();
+//! apply_mask!(stored, request, mask; name, bio);
+//! assert_eq!(stored, User { name: "Bob".into(), bio: None });
+//! ```
+//!
+//! # Dynamic Payloads
+//!
+//! With the `json` feature, presence values convert to and from the well-known
+//! `google.protobuf.Value` and `Struct` types. `Null` is a `NullValue` and `Absent` a
+//! missing struct field:
+//!
+//! ```
+//! # #[cfg(feature = "json")] {
+//! use presence_rs::Presence;
+//! use presence_rs::prost::to_struct;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct Labels {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     team: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     owner: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     tier: Presence<u8>,
+//! }
+//!
+//! let labels = Labels { team: Presence::Some("core".into()), owner: Presence::Null, tier: Presence::Absent };
+//! let fields = to_struct(&labels).unwrap().fields;
+//!
+//! assert_eq!(Presence::<String>::from_prost_value(fields.get("team")).unwrap(), Presence::Some("core".into()));
+//! assert_eq!(Presence::<String>::from_prost_value(fields.get("owner")).unwrap(), Presence::Null);
+//! assert_eq!(Presence::<u8>::from_prost_value(fields.get("tier")).unwrap(), Presence::Absent);
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`apply_patch!`]: crate::apply_patch
+//! [`apply_mask!`]: crate::apply_mask
+
+use crate::fields;
+#[cfg(feature = "json")]
+use crate::presence::Presence;
+use prost_types::FieldMask;
+#[cfg(feature = "json")]
+use prost_types::{ListValue, NullValue, Struct, Value, value::Kind};
+use serde::Serialize;
+#[cfg(feature = "json")]
+use serde::de::DeserializeOwned;
+
+/// Builds a `FieldMask` listing every defined field of `patch`.
+///
+/// # Errors
+///
+/// Returns [`fields::Error::NotAStruct`] if `patch` does not serialize as a struct.
+pub fn field_mask<T: Serialize + ?Sized>(patch: &T) -> Result<FieldMask, fields::Error> {
+    Ok(FieldMask {
+        paths: fields::defined_fields(patch)?,
+    })
+}
+
+/// Builds a `FieldMask` for a patch of a sub-message, prefixing every path with `prefix.`.
+///
+/// # Errors
+///
+/// Returns [`fields::Error::NotAStruct`] if `patch` does not serialize as a struct.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::Presence;
+/// use presence_rs::prost::field_mask_with_prefix;
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct AddressPatch {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     city: Presence<String>,
+/// }
+///
+/// let patch = AddressPatch { city: Presence::Some("Lviv".into()) };
+/// let mask = field_mask_with_prefix("address", &patch).unwrap();
+/// assert_eq!(mask.paths, ["address.city"]);
+/// ```
+pub fn field_mask_with_prefix<T: Serialize + ?Sized>(
+    prefix: &str,
+    patch: &T,
+) -> Result<FieldMask, fields::Error> {
+    Ok(FieldMask {
+        paths: fields::defined_fields(patch)?
+            .into_iter()
+            .map(|path| format!("{}.{}", prefix, path))
+            .collect(),
+    })
+}
+
+/// Returns `true` if `mask` contains exactly `path`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::prost::mask_contains;
+/// use prost_types::FieldMask;
+///
+/// let mask = FieldMask { paths: vec!["name".into(), "address.city".into()] };
+/// assert!(mask_contains(&mask, "name"));
+/// assert!(mask_contains(&mask, "address.city"));
+/// assert!(!mask_contains(&mask, "address"));
+/// ```
+pub fn mask_contains(mask: &FieldMask, path: &str) -> bool {
+    mask.paths.iter().any(|p| p == path)
+}
+
+/// Copies the listed fields of `$source` onto `$target` when their path is in `$mask`.
+///
+/// `$mask` is a [`FieldMask`]; only top-level paths are matched, so a nested path such
+/// as `address.city` does not select the `address` field. Fields are moved out of
+/// `$source`.
+///
+/// A field's path is its name without a raw identifier prefix, so `r#type` is matched by
+/// `type`. [`field_mask`] takes paths from serde instead, so when the patch struct renames
+/// a field, write the serialized name as `field = "path"`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, apply_mask};
+/// use presence_rs::prost::field_mask;
+/// use serde::Serialize;
+///
+/// #[derive(Debug, Default, PartialEq)]
+/// struct Book { title: String, page_count: u32 }
+///
+/// #[derive(Serialize)]
+/// #[serde(rename_all = "camelCase")]
+/// struct BookPatch {
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     title: Presence<String>,
+///     #[serde(skip_serializing_if = "Presence::is_absent")]
+///     page_count: Presence<u32>,
+/// }
+///
+/// let mut stored = Book { title: "Dune".into(), page_count: 100 };
+/// let request = Book { title: "ignored".into(), page_count: 412 };
+/// let mask = field_mask(&BookPatch { title: Presence::Absent, page_count: Presence::Some(412) }).unwrap();
+/// assert_eq!(mask.paths, ["pageCount"]);
+///
+/// apply_mask!(stored, request, mask; title, page_count = "pageCount");
+/// assert_eq!(stored, Book { title: "Dune".into(), page_count: 412 });
+/// ```
+#[macro_export]
+macro_rules! apply_mask {
+    (@path $field:ident) => {
+        stringify!($field).trim_start_matches("r#")
+    };
+    (@path $field:ident $path:literal) => {
+        $path
+    };
+    ($target:expr, $source:expr, $mask:expr; $($field:ident $(= $path:literal)?),+ $(,)?) => {{
+        let source = $source;
+        let mask = &$mask;
+        $(
+            if $crate::prost::mask_contains(mask, $crate::apply_mask!(@path $field $($path)?)) {
+                $target.$field = source.$field;
+            }
+        )+
+    }};
+}
+
+/// Converts `value` into a `google.protobuf.Struct`.
+///
+/// Fields skipped by serde, such as `Absent` presence fields, are missing from the struct.
+///
+/// # Errors
+///
+/// Returns an error if [`json::patch_object`](crate::json::patch_object) fails.
+#[cfg(feature = "json")]
+pub fn to_struct<T: Serialize + ?Sized>(value: &T) -> Result<Struct, serde_json::Error> {
+    let fields = crate::json::patch_object(value)?;
+    Ok(Struct {
+        fields: fields
+            .into_iter()
+            .map(|(key, value)| (key, to_prost(value)))
+            .collect(),
+    })
+}
+
+/// Converts a `google.protobuf.Struct` into `T`.
+///
+/// Missing fields are left to serde, so `#[serde(default)]` presence fields become
+/// `Absent`.
+///
+/// # Errors
+///
+/// Returns an error if the struct holds a NaN or infinite number, or does not
+/// deserialize into `T`.
+#[cfg(feature = "json")]
+pub fn from_struct<T: DeserializeOwned>(value: &Struct) -> Result<T, serde_json::Error> {
+    serde_json::from_value(from_prost_struct(value)?)
+}
+
+#[cfg(feature = "json")]
+impl<T> Presence<T> {
+    /// Converts the presence into a `google.protobuf.Value`, or `None` if it is
+    /// [`Absent`].
+    ///
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `T` fails to serialize.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use prost_types::value::Kind;
+    ///
+    /// let value = Presence::Some(true).to_prost_value().unwrap().unwrap();
+    /// assert_eq!(value.kind, Some(Kind::BoolValue(true)));
+    ///
+    /// let value = Presence::<bool>::Null.to_prost_value().unwrap().unwrap();
+    /// assert_eq!(value.kind, Some(Kind::NullValue(0)));
+    ///
+    /// assert_eq!(Presence::<bool>::Absent.to_prost_value().unwrap(), None);
+    /// ```
+    pub fn to_prost_value(&self) -> Result<Option<Value>, serde_json::Error>
+    where
+        T: Serialize,
+    {
+        Ok(self.to_json_value()?.map(to_prost))
+    }
+
+    /// Converts a `google.protobuf.Value` looked up in a struct into a presence.
+    ///
+    /// `None` becomes [`Absent`], a `NullValue` (or a value with no kind) becomes [`Null`],
+    /// and any other value is deserialized into [`Some`].
+    ///
+    /// [`Some`]: Presence::Some
+    /// [`Null`]: Presence::Null
+    /// [`Absent`]: Presence::Absent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value holds a NaN or infinite number, which JSON cannot
+    /// represent, or does not deserialize into `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use prost_types::Value;
+    /// use prost_types::value::Kind;
+    ///
+    /// let nan = Value { kind: Some(Kind::NumberValue(f64::NAN)) };
+    /// assert!(Presence::<f64>::from_prost_value(Some(&nan)).is_err());
+    /// ```
+    pub fn from_prost_value(value: Option<&Value>) -> Result<Self, serde_json::Error>
+    where
+        T: DeserializeOwned,
+    {
+        Presence::from_json_lookup(value.map(from_prost).transpose()?.as_ref())
+    }
+}
+
+#[cfg(feature = "json")]
+fn to_prost(value: serde_json::Value) -> Value {
+    let kind = match value {
+        serde_json::Value::Null => Kind::NullValue(NullValue::NullValue as i32),
+        serde_json::Value::Bool(value) => Kind::BoolValue(value),
+        serde_json::Value::Number(value) => Kind::NumberValue(value.as_f64().unwrap_or(f64::NAN)),
+        serde_json::Value::String(value) => Kind::StringValue(value),
+        serde_json::Value::Array(values) => Kind::ListValue(ListValue {
+            values: values.into_iter().map(to_prost).collect(),
+        }),
+        serde_json::Value::Object(fields) => Kind::StructValue(Struct {
+            fields: fields
+                .into_iter()
+                .map(|(key, value)| (key, to_prost(value)))
+                .collect(),
+        }),
+    };
+    Value { kind: Some(kind) }
+}
+
+#[cfg(feature = "json")]
+fn from_prost(value: &Value) -> Result<serde_json::Value, serde_json::Error> {
+    Ok(match &value.kind {
+        None | Some(Kind::NullValue(_)) => serde_json::Value::Null,
+        Some(Kind::BoolValue(value)) => serde_json::Value::Bool(*value),
+        // Protobuf numbers are doubles; integral ones convert back to JSON integers so
+        // they deserialize into integer types.
+        Some(Kind::NumberValue(value))
+            if value.fract() == 0.0 && value.abs() < (1u64 << 53) as f64 =>
+        {
+            serde_json::Value::from(*value as i64)
+        }
+        Some(Kind::NumberValue(value)) => match serde_json::Number::from_f64(*value) {
+            Some(number) => serde_json::Value::Number(number),
+            None => {
+                return Err(serde::de::Error::custom(format_args!(
+                    "number {} cannot be represented in JSON",
+                    value
+                )));
+            }
+        },
+        Some(Kind::StringValue(value)) => serde_json::Value::String(value.clone()),
+        Some(Kind::ListValue(list)) => list
+            .values
+            .iter()
+            .map(from_prost)
+            .collect::<Result<_, _>>()?,
+        Some(Kind::StructValue(value)) => from_prost_struct(value)?,
+    })
+}
+
+#[cfg(feature = "json")]
+fn from_prost_struct(value: &Struct) -> Result<serde_json::Value, serde_json::Error> {
+    value
+        .fields
+        .iter()
+        .map(|(key, value)| Ok((key.clone(), from_prost(value)?)))
+        .collect::<Result<_, _>>()
+        .map(serde_json::Value::Object)
+}
@@ -0,0 +1,179 @@
+//! Redis hash updates from [`Presence<T>`] patches.
+//!
+//! [`HashUpdate`] collects the fields of a patch and turns them into the minimal commands
+//! for a hash key:
+//!
+//! - `Some(value)` → `HSET key field value`
+//! - `Null` → `HDEL key field`
+//! - `Absent` → nothing
+//!
+//! All `Some` fields go into a single `HSET` and all `Null` fields into a single `HDEL`, so
+//! a patch needs at most two commands. [`hash_update!`] adds the listed fields of a patch
+//! struct, using each field name as the hash field.
+//!
+//! A hash field holds a single value, so a `Some` value must convert to exactly one Redis
+//! argument. Values such as `Vec<T>` or `None`, which convert to several or none, are
+//! rejected with a type error rather than shifting the field/value pairs of the `HSET`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::redis::HashUpdate;
+//!
+//! let mut update = HashUpdate::new("user:42");
+//! update
+//!     .field("name", Presence::Some("Bob"))?
+//!     .field("avatar", Presence::<&str>::Null)?
+//!     .field("age", Presence::<u32>::Absent)?;
+//!
+//! let commands = update.commands();
+//! assert_eq!(commands.len(), 2);
+//!
+//! // Send both commands in one round trip, applied atomically.
+//! let pipe = update.pipeline();
+//! assert_eq!(pipe.len(), 2);
+//! # Ok::<(), redis::RedisError>(())
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`hash_update!`]: crate::hash_update
+
+use crate::presence::Presence;
+use redis::{Cmd, ErrorKind, Pipeline, RedisError, RedisResult, ToRedisArgs};
+
+/// The `HSET`/`HDEL` commands for a partial update of one hash.
+#[derive(Clone)]
+pub struct HashUpdate {
+    key: String,
+    set: Cmd,
+    set_fields: usize,
+    del: Cmd,
+    del_fields: usize,
+}
+
+impl HashUpdate {
+    /// Starts an update of the hash stored at `key`.
+    pub fn new(key: impl Into<String>) -> Self {
+        let key = key.into();
+        let mut set = redis::cmd("HSET");
+        set.arg(&key);
+        let mut del = redis::cmd("HDEL");
+        del.arg(&key);
+        Self {
+            key,
+            set,
+            set_fields: 0,
+            del,
+            del_fields: 0,
+        }
+    }
+
+    /// Returns the key of the hash being updated.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Adds a hash field: `Some` sets it, `Null` deletes it and `Absent` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns a type error, leaving the update unchanged, if a `Some` value does not
+    /// convert to exactly one Redis argument.
+    pub fn field<T: ToRedisArgs>(
+        &mut self,
+        name: &str,
+        value: Presence<T>,
+    ) -> RedisResult<&mut Self> {
+        match value {
+            Presence::Some(value) => {
+                let [arg] = <[Vec<u8>; 1]>::try_from(value.to_redis_args()).map_err(|args| {
+                    RedisError::from((
+                        ErrorKind::TypeError,
+                        "hash field value must be a single argument",
+                        format!("`{}` converts to {} arguments", name, args.len()),
+                    ))
+                })?;
+                self.set.arg(name).arg(arg);
+                self.set_fields += 1;
+            }
+            Presence::Null => {
+                self.del.arg(name);
+                self.del_fields += 1;
+            }
+            Presence::Absent => {}
+        }
+        Ok(self)
+    }
+
+    /// Returns `true` if no field is set or deleted.
+    pub fn is_empty(&self) -> bool {
+        self.set_fields == 0 && self.del_fields == 0
+    }
+
+    /// Returns the commands to run: an `HSET` if any field is set and an `HDEL` if any
+    /// field is deleted.
+    pub fn commands(&self) -> Vec<Cmd> {
+        let mut commands = Vec::with_capacity(2);
+        if self.set_fields > 0 {
+            commands.push(self.set.clone());
+        }
+        if self.del_fields > 0 {
+            commands.push(self.del.clone());
+        }
+        commands
+    }
+
+    /// Adds the commands to an existing pipeline.
+    pub fn add_to(&self, pipe: &mut Pipeline) {
+        for command in self.commands() {
+            pipe.add_command(command).ignore();
+        }
+    }
+
+    /// Returns an atomic (`MULTI`/`EXEC`) pipeline with the commands.
+    ///
+    /// The replies of the commands are ignored, so the pipeline can be run with
+    /// `query::<()>`.
+    pub fn pipeline(&self) -> Pipeline {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.add_to(&mut pipe);
+        pipe
+    }
+}
+
+/// Adds the listed fields of a patch struct to a [`HashUpdate`].
+///
+/// Each field is passed to [`HashUpdate::field`] with its name as the hash field. The patch
+/// fields are moved out of `$patch`. Evaluates to a `RedisResult<()>` with the first error,
+/// after which the remaining fields are not added.
+///
+/// [`HashUpdate`]: crate::redis::HashUpdate
+/// [`HashUpdate::field`]: crate::redis::HashUpdate::field
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, hash_update};
+/// use presence_rs::redis::HashUpdate;
+///
+/// struct SessionPatch { token: Presence<String>, ttl: Presence<u64> }
+///
+/// let patch = SessionPatch { token: Presence::Null, ttl: Presence::Absent };
+///
+/// let mut update = HashUpdate::new("session:1");
+/// hash_update!(update, patch; token, ttl)?;
+/// assert_eq!(update.commands().len(), 1);
+/// # Ok::<(), redis::RedisError>(())
+/// ```
+#[macro_export]
+macro_rules! hash_update {
+    ($update:expr, $patch:expr; $($field:ident),+ $(,)?) => {{
+        let patch = $patch;
+        Ok(())
+        $(
+            .and_then(|()| $update.field(stringify!($field), patch.$field).map(drop))
+        )+
+    }};
+}
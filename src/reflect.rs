@@ -0,0 +1,344 @@
+//! Inspecting the fields of patch structs without knowing their type.
+//!
+//! Middleware such as audit logging, metrics or field-level authorization needs to know
+//! which fields a caller is trying to change. [`PresenceFields`] exposes the state of
+//! every field of a patch struct as a [`PresenceState`], so such code can be written once
+//! against `&dyn PresenceFields` or a generic `P: PresenceFields`.
+//!
+//! Unlike the serde-based `fields` module, this works on any struct and
+//! reports field names as written in the source.
+//!
+//! With the `derive` feature, `#[derive(PresenceFields)]` reports every field; fields
+//! that are not `Presence<T>` must be excluded with `#[presence(skip)]`.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::{Presence, PresenceState};
+//! use presence_rs::reflect::PresenceFields;
+//!
+//! #[derive(PresenceFields)]
+//! struct UserPatch {
+//!     #[presence(skip)]
+//!     id: u64,
+//!     name: Presence<String>,
+//!     role: Presence<String>,
+//! }
+//!
+//! fn touches_role(patch: &dyn PresenceFields) -> bool {
+//!     patch.defined_fields().contains(&"role")
+//! }
+//!
+//! let patch = UserPatch { id: 1, name: Presence::Some("Bob".into()), role: Presence::Null };
+//! assert_eq!(
+//!     patch.field_states(),
+//!     [("name", PresenceState::Some), ("role", PresenceState::Null)]
+//! );
+//! assert!(touches_role(&patch));
+//! # }
+//! ```
+//!
+//! # Walking Nested Patches
+//!
+//! [`VisitPresence`] walks every `Presence` field of a structure, including nested patch
+//! structs, and hands each one to a [`PresenceVisitor`] with its path, its state and its
+//! value as `&dyn Debug`. Audit, redaction and metrics layers can be written once on top
+//! of it.
+//!
+//! # Change Reports
+//!
+//! [`DescribePatch`] renders a patch as a human-readable summary for audit trails, such as
+//! `name: set to "Bob", avatar: cleared, email: unchanged`. Its derive keeps the summary in
+//! sync with the struct definition; `#[presence(redact)]` hides the value of sensitive
+//! fields and `#[presence(skip)]` leaves a field out.
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::reflect::DescribePatch;
+//!
+//! #[derive(DescribePatch)]
+//! struct AccountPatch {
+//!     name: Presence<String>,
+//!     avatar: Presence<String>,
+//!     #[presence(redact)]
+//!     password: Presence<String>,
+//! }
+//!
+//! let patch = AccountPatch {
+//!     name: Presence::Some("Bob".into()),
+//!     avatar: Presence::Null,
+//!     password: Presence::Some("hunter2".into()),
+//! };
+//! assert_eq!(
+//!     patch.describe(),
+//!     r#"name: set to "Bob", avatar: cleared, password: set to <redacted>"#
+//! );
+//! # }
+//! ```
+
+use crate::presence::{Presence, PresenceState};
+use std::fmt::{self, Debug, Write};
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::{DescribePatch, PresenceFields, VisitPresence};
+
+/// A patch struct that reports the state of its `Presence<T>` fields.
+///
+/// Usually derived; see the [module documentation](self).
+pub trait PresenceFields {
+    /// Returns the name and state of every field, in declaration order.
+    fn field_states(&self) -> Vec<(&'static str, PresenceState)>;
+
+    /// Returns the names of all defined (`Null` or `Some`) fields.
+    fn defined_fields(&self) -> Vec<&'static str> {
+        names_where(self, |state| state != PresenceState::Absent)
+    }
+
+    /// Returns the names of all `Null` fields.
+    fn null_fields(&self) -> Vec<&'static str> {
+        names_where(self, |state| state == PresenceState::Null)
+    }
+
+    /// Returns `true` if every field is `Absent`.
+    fn is_empty_patch(&self) -> bool {
+        self.field_states()
+            .iter()
+            .all(|&(_, state)| state == PresenceState::Absent)
+    }
+}
+
+fn names_where<P: PresenceFields + ?Sized>(
+    patch: &P,
+    f: impl Fn(PresenceState) -> bool,
+) -> Vec<&'static str> {
+    patch
+        .field_states()
+        .into_iter()
+        .filter(|&(_, state)| f(state))
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// A patch struct that can describe its changes in words.
+///
+/// Usually derived; see the [module documentation](self#change-reports).
+pub trait DescribePatch {
+    /// Writes the summary of every field, separated by `", "`.
+    fn describe_to(&self, f: &mut dyn Write) -> fmt::Result;
+
+    /// Returns the summary of every field, separated by `", "`.
+    fn describe(&self) -> String {
+        let mut summary = String::new();
+        self.describe_to(&mut summary)
+            .expect("writing to a String never fails");
+        summary
+    }
+}
+
+/// Writes `name: <change>` for one field, preceded by `", "` unless it is the first.
+///
+/// Used by the `DescribePatch` derive.
+#[doc(hidden)]
+pub fn __describe_field<T: Debug>(
+    f: &mut dyn Write,
+    first: bool,
+    name: &str,
+    value: &Presence<T>,
+    redact: bool,
+) -> fmt::Result {
+    if !first {
+        f.write_str(", ")?;
+    }
+    write!(f, "{}: ", name)?;
+    match value {
+        Presence::Absent => f.write_str("unchanged"),
+        Presence::Null => f.write_str("cleared"),
+        Presence::Some(_) if redact => f.write_str("set to <redacted>"),
+        Presence::Some(value) => write!(f, "set to {:?}", value),
+    }
+}
+
+/// Receives every `Presence` field found while walking a [`VisitPresence`] value.
+///
+/// `path` holds the field names from the root, and `value` holds the state of the field
+/// with its value erased to `&dyn Debug`. Closures taking the same arguments are visitors.
+pub trait PresenceVisitor {
+    /// Visits one field.
+    fn visit(&mut self, path: &[&'static str], value: Presence<&dyn Debug>);
+
+    /// Visits a `#[presence(redact)]` field that is `Some`.
+    ///
+    /// By default, visits the field with a value that formats as `<redacted>`.
+    fn visit_redacted(&mut self, path: &[&'static str]) {
+        self.visit(path, Presence::Some(&Redacted))
+    }
+}
+
+impl<F> PresenceVisitor for F
+where
+    F: FnMut(&[&'static str], Presence<&dyn Debug>),
+{
+    fn visit(&mut self, path: &[&'static str], value: Presence<&dyn Debug>) {
+        self(path, value)
+    }
+}
+
+/// A structure whose `Presence` fields can be walked with a [`PresenceVisitor`].
+///
+/// Usually derived: every field is visited, `#[presence(nested)]` fields are walked
+/// recursively, `#[presence(redact)]` hides the value and `#[presence(skip)]` leaves a
+/// field out. A nested `Presence<P>` field is visited as a whole when it is `Null` or
+/// `Absent`, and walked into when it is `Some`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use presence_rs::Presence;
+/// use presence_rs::reflect::VisitPresence;
+///
+/// #[derive(VisitPresence)]
+/// struct AddressPatch {
+///     city: Presence<String>,
+/// }
+///
+/// #[derive(VisitPresence)]
+/// struct UserPatch {
+///     name: Presence<String>,
+///     #[presence(redact)]
+///     password: Presence<String>,
+///     #[presence(nested)]
+///     address: AddressPatch,
+/// }
+///
+/// let patch = UserPatch {
+///     name: Presence::Null,
+///     password: Presence::Some("hunter2".into()),
+///     address: AddressPatch { city: Presence::Some("Lviv".into()) },
+/// };
+///
+/// let mut log = Vec::new();
+/// patch.visit_presence(&mut |path: &[&str], value: Presence<&dyn std::fmt::Debug>| {
+///     log.push(format!("{} = {:?}", path.join("."), value));
+/// });
+/// assert_eq!(
+///     log,
+///     ["name = Null", "password = Some(<redacted>)", r#"address.city = Some("Lviv")"#]
+/// );
+/// # }
+/// ```
+pub trait VisitPresence {
+    /// Visits every field below `path`, pushing each field name onto `path` while it is
+    /// visited.
+    fn visit_presence_at(&self, path: &mut Vec<&'static str>, visitor: &mut dyn PresenceVisitor);
+
+    /// Visits every field, starting from an empty path.
+    fn visit_presence(&self, visitor: &mut dyn PresenceVisitor) {
+        self.visit_presence_at(&mut Vec::new(), visitor)
+    }
+
+    /// Returns the state of the field at `path`, with field names separated by `/`.
+    ///
+    /// A field below a `Null` or `Absent` nested patch, and a path that names no field,
+    /// are `Absent`. A path naming a nested patch that is `Some` is `Some`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "derive")] {
+    /// use presence_rs::{Presence, PresenceState};
+    /// use presence_rs::reflect::VisitPresence;
+    ///
+    /// #[derive(VisitPresence)]
+    /// struct AddressPatch {
+    ///     city: Presence<String>,
+    ///     zip: Presence<String>,
+    /// }
+    ///
+    /// #[derive(VisitPresence)]
+    /// struct UserPatch {
+    ///     #[presence(nested)]
+    ///     address: Presence<AddressPatch>,
+    /// }
+    ///
+    /// let patch = UserPatch {
+    ///     address: Presence::Some(AddressPatch {
+    ///         city: Presence::Absent,
+    ///         zip: Presence::Null,
+    ///     }),
+    /// };
+    /// assert_eq!(patch.presence_at("address/zip"), PresenceState::Null);
+    /// assert_eq!(patch.presence_at("address/city"), PresenceState::Absent);
+    /// assert_eq!(patch.presence_at("address"), PresenceState::Some);
+    /// # }
+    /// ```
+    fn presence_at(&self, path: &str) -> PresenceState {
+        let target: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+        let mut state = PresenceState::Absent;
+        self.visit_presence(
+            &mut |visited: &[&'static str], value: Presence<&dyn Debug>| {
+                if visited == target.as_slice() {
+                    state = value.state();
+                } else if visited.starts_with(&target) {
+                    // The target is a nested patch that was walked into.
+                    state = PresenceState::Some;
+                }
+            },
+        );
+        state
+    }
+}
+
+impl<P: VisitPresence> VisitPresence for Presence<P> {
+    fn visit_presence_at(&self, path: &mut Vec<&'static str>, visitor: &mut dyn PresenceVisitor) {
+        match self {
+            Presence::Some(patch) => patch.visit_presence_at(path, visitor),
+            Presence::Null => visitor.visit(path, Presence::Null),
+            Presence::Absent => visitor.visit(path, Presence::Absent),
+        }
+    }
+}
+
+struct Redacted;
+
+impl Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// Visits one field at `path` + `name`.
+///
+/// Used by the `VisitPresence` derive.
+#[doc(hidden)]
+pub fn __visit_field<T: Debug>(
+    path: &mut Vec<&'static str>,
+    name: &'static str,
+    value: &Presence<T>,
+    redact: bool,
+    visitor: &mut dyn PresenceVisitor,
+) {
+    path.push(name);
+    match value {
+        Presence::Some(_) if redact => visitor.visit_redacted(path),
+        value => visitor.visit(path, value.as_ref().map(|value| value as &dyn Debug)),
+    }
+    path.pop();
+}
+
+/// Walks a nested field at `path` + `name`.
+///
+/// Used by the `VisitPresence` derive.
+#[doc(hidden)]
+pub fn __visit_nested<T: VisitPresence + ?Sized>(
+    path: &mut Vec<&'static str>,
+    name: &'static str,
+    value: &T,
+    visitor: &mut dyn PresenceVisitor,
+) {
+    path.push(name);
+    value.visit_presence_at(path, visitor);
+    path.pop();
+}
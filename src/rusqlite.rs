@@ -0,0 +1,69 @@
+//! rusqlite integration for [`Presence<T>`].
+//!
+//! `Presence<T>` implements `ToSql` and `FromSql` like `Option<T>`: SQL `NULL` is read as
+//! `Null`, and both `Null` and `Absent` are written as `NULL`. Leave `Absent` columns out of
+//! the statement when they should not be written.
+//!
+//! A result column is never `Absent` on its own, but a query may not select every column,
+//! for example when the projection is built dynamically. [`get_presence`] reads such a
+//! column as `Absent` when it is not part of the row.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::rusqlite::get_presence;
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! let (nickname, bio): (Presence<String>, Presence<String>) = conn
+//!     .query_row("SELECT NULL AS nickname", [], |row| {
+//!         Ok((get_presence(row, "nickname")?, get_presence(row, "bio")?))
+//!     })
+//!     .unwrap();
+//!
+//! assert_eq!(nickname, Presence::Null);
+//! assert_eq!(bio, Presence::Absent);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use rusqlite::types::{FromSql, FromSqlResult, Null, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{Error, Result, Row, RowIndex};
+
+impl<T: FromSql> FromSql for Presence<T> {
+    #[inline]
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        match value {
+            ValueRef::Null => Ok(Presence::Null),
+            _ => T::column_result(value).map(Presence::Some),
+        }
+    }
+}
+
+impl<T: ToSql> ToSql for Presence<T> {
+    #[inline]
+    fn to_sql(&self) -> Result<ToSqlOutput<'_>> {
+        match self {
+            Presence::Some(value) => value.to_sql(),
+            Presence::Null | Presence::Absent => Ok(ToSqlOutput::from(Null)),
+        }
+    }
+}
+
+/// Reads a column of `row`, returning `Absent` if the row has no such column.
+///
+/// `idx` is a column name or index, as for [`Row::get`].
+///
+/// # Errors
+///
+/// Returns the same errors as [`Row::get`], except that a missing column name or an
+/// out-of-range index yields `Ok(Presence::Absent)`.
+pub fn get_presence<I: RowIndex, T: FromSql>(row: &Row<'_>, idx: I) -> Result<Presence<T>> {
+    match row.get(idx) {
+        Ok(value) => Ok(value),
+        Err(Error::InvalidColumnName(_) | Error::InvalidColumnIndex(_)) => Ok(Presence::Absent),
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,264 @@
+//! Checking patch structs against the `required` and nullability rules of a JSON Schema.
+//!
+//! Schema-first APIs declare which properties must be sent and which may be `null`. This
+//! module applies those rules to a struct of [`Presence<T>`] fields:
+//!
+//! - a property listed in `required` must not be `Absent`
+//! - a property whose schema does not allow `null` must not be `Null`
+//!
+//! A schema allows `null` through `"type": "null"` (alone or in a list), `"nullable": true`
+//! (OpenAPI 3.0), a `null` in `enum` or `const`, or an `anyOf`/`oneOf` branch that does.
+//! Local `$ref`s such as `#/$defs/Address` are resolved, and nested objects are checked
+//! with dotted paths. Types and other keywords are not validated.
+//!
+//! The value is serialized with [`json::patch_object`], so an `Absent` field is only
+//! reported when it is skipped rather than serialized.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::schema::{SchemaViolation, violations};
+//! use presence_rs::validate::ViolationKind;
+//! use serde::Serialize;
+//! use serde_json::json;
+//!
+//! let schema = json!({
+//!     "type": "object",
+//!     "required": ["id"],
+//!     "properties": {
+//!         "id": { "type": "integer" },
+//!         "email": { "type": "string" },
+//!         "bio": { "type": ["string", "null"] },
+//!     },
+//! });
+//!
+//! #[derive(Serialize)]
+//! struct UserPatch {
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     id: Presence<u64>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     email: Presence<String>,
+//!     #[serde(skip_serializing_if = "Presence::is_absent")]
+//!     bio: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch { id: Presence::Absent, email: Presence::Null, bio: Presence::Null };
+//! assert_eq!(
+//!     violations(&patch, &schema).unwrap(),
+//!     [
+//!         SchemaViolation { path: "email".into(), kind: ViolationKind::Null },
+//!         SchemaViolation { path: "id".into(), kind: ViolationKind::Absent },
+//!     ]
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`json::patch_object`]: crate::json::patch_object
+
+use crate::json::patch_object;
+use crate::validate::ViolationKind;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// How deep `$ref`s and `anyOf`/`oneOf` branches are followed before giving up on a
+/// cyclic schema.
+const MAX_REF_DEPTH: usize = 32;
+
+/// A property whose state is not allowed by the schema.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SchemaViolation {
+    /// The dotted path of the property, such as `address.city`.
+    pub path: String,
+    /// The state of the property.
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ViolationKind::Absent => write!(f, "`{}` is required", self.path),
+            ViolationKind::Null => write!(f, "`{}` must not be null", self.path),
+        }
+    }
+}
+
+impl std::error::Error for SchemaViolation {}
+
+/// Checks a patch struct against `schema`, returning every violation.
+///
+/// For each object, its `Null` properties and nested objects come first, in property
+/// order, followed by its missing `required` properties. An empty list means the patch is
+/// valid.
+///
+/// # Errors
+///
+/// Returns an error if [`patch_object`] fails.
+pub fn violations<T: Serialize + ?Sized>(
+    value: &T,
+    schema: &Value,
+) -> Result<Vec<SchemaViolation>, serde_json::Error> {
+    let object = patch_object(value)?;
+    Ok(object_violations(&object, schema))
+}
+
+/// Checks a JSON object against `schema`, where a missing property is `Absent`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::schema::object_violations;
+/// use serde_json::json;
+///
+/// let schema = json!({ "properties": { "tags": { "type": "array" } } });
+/// let object = json!({ "tags": null });
+///
+/// let violations = object_violations(object.as_object().unwrap(), &schema);
+/// assert_eq!(violations[0].to_string(), "`tags` must not be null");
+/// ```
+pub fn object_violations(object: &Map<String, Value>, schema: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+    check_object(object, schema, schema, "", &mut violations);
+    violations
+}
+
+fn check_object(
+    object: &Map<String, Value>,
+    schema: &Value,
+    root: &Value,
+    prefix: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let Some(schema) = object_schema(schema, root, 0) else {
+        return;
+    };
+
+    if let Some(Value::Object(properties)) = schema.get("properties") {
+        for (name, property) in properties {
+            let Some(value) = object.get(name) else {
+                continue;
+            };
+            let path = format!("{}{}", prefix, name);
+            match value {
+                Value::Null if !is_nullable(property, root, 0) => {
+                    violations.push(SchemaViolation {
+                        path,
+                        kind: ViolationKind::Null,
+                    })
+                }
+                Value::Object(nested) => {
+                    check_object(nested, property, root, &format!("{}.", path), violations)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(Value::Array(required)) = schema.get("required") {
+        let mut missing: Vec<&str> = required
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|name| !object.contains_key(*name))
+            .collect();
+        missing.sort_unstable();
+        violations.extend(missing.into_iter().map(|name| SchemaViolation {
+            path: format!("{}{}", prefix, name),
+            kind: ViolationKind::Absent,
+        }));
+    }
+}
+
+/// Follows local `$ref`s, returning `None` for unresolvable or cyclic ones.
+fn resolve<'a>(mut schema: &'a Value, root: &'a Value) -> Option<&'a Value> {
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(reference) = schema.get("$ref").and_then(Value::as_str) else {
+            return Some(schema);
+        };
+        schema = root.pointer(reference.strip_prefix('#')?)?;
+    }
+    None
+}
+
+/// Returns the schema describing the properties of an object, looking through the
+/// non-null branch of an `anyOf` or `oneOf` as generated for optional structs.
+fn object_schema<'a>(schema: &'a Value, root: &'a Value, depth: usize) -> Option<&'a Value> {
+    if depth > MAX_REF_DEPTH {
+        return None;
+    }
+    let schema = resolve(schema, root)?;
+    if schema.get("properties").is_some() || schema.get("required").is_some() {
+        return Some(schema);
+    }
+    ["anyOf", "oneOf"]
+        .iter()
+        .filter_map(|key| schema.get(*key).and_then(Value::as_array))
+        .flatten()
+        .find_map(|branch| object_schema(branch, root, depth + 1))
+}
+
+fn is_nullable(schema: &Value, root: &Value, depth: usize) -> bool {
+    if depth > MAX_REF_DEPTH {
+        return true;
+    }
+    let Some(schema) = resolve(schema, root) else {
+        return true;
+    };
+    let Value::Object(schema) = schema else {
+        // `true` allows everything and `false` nothing.
+        return schema.as_bool().unwrap_or(true);
+    };
+
+    if schema.get("nullable") == Some(&Value::Bool(true)) {
+        return true;
+    }
+    if let Some(Value::Array(all)) = schema.get("allOf") {
+        if !all
+            .iter()
+            .all(|branch| is_nullable(branch, root, depth + 1))
+        {
+            return false;
+        }
+    }
+
+    let mut constrained = false;
+    match schema.get("type") {
+        Some(Value::String(kind)) => {
+            constrained = true;
+            if kind == "null" {
+                return true;
+            }
+        }
+        Some(Value::Array(kinds)) => {
+            constrained = true;
+            if kinds.iter().any(|kind| kind == "null") {
+                return true;
+            }
+        }
+        _ => {}
+    }
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        constrained = true;
+        if values.contains(&Value::Null) {
+            return true;
+        }
+    }
+    if let Some(value) = schema.get("const") {
+        constrained = true;
+        if value.is_null() {
+            return true;
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(branches)) = schema.get(key) {
+            constrained = true;
+            if branches
+                .iter()
+                .any(|branch| is_nullable(branch, root, depth + 1))
+            {
+                return true;
+            }
+        }
+    }
+    !constrained
+}
@@ -0,0 +1,55 @@
+//! JSON Schema support for [`Presence<T>`] via schemars.
+//!
+//! `Presence<T>` produces the same schema as `Option<T>`: the schema of `T` with `null`
+//! allowed. Whether a struct field is `required` follows its serde attributes, like any
+//! other field: `#[serde(default)]`, which reads a missing field as `Absent`, leaves it out
+//! of `required` when reading input. When generating schemas for serialized output, a
+//! field is optional only if it is also annotated with
+//! `#[serde(skip_serializing_if = "Presence::is_absent")]`, because otherwise `Absent`
+//! serializes as `null`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use schemars::{JsonSchema, schema_for};
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, JsonSchema)]
+//! struct UserPatch {
+//!     id: u64,
+//!     #[serde(default)]
+//!     nickname: Presence<String>,
+//! }
+//!
+//! let schema = schema_for!(UserPatch);
+//! assert_eq!(schema.get("required").unwrap(), &serde_json::json!(["id"]));
+//! assert_eq!(
+//!     schema.get("properties").unwrap()["nickname"]["type"],
+//!     serde_json::json!(["string", "null"]),
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use std::borrow::Cow;
+
+impl<T: JsonSchema> JsonSchema for Presence<T> {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <Option<T>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        format!("Presence<{}>", T::schema_id()).into()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Option<T>>::json_schema(generator)
+    }
+}
@@ -0,0 +1,126 @@
+//! sea-orm integration for [`Presence<T>`].
+//!
+//! A `Presence<V>` maps onto the `ActiveValue<Option<V>>` of a nullable column:
+//!
+//! - `Absent` ↔ `NotSet`
+//! - `Null` ↔ `Set(None)`
+//! - `Some(v)` ↔ `Set(Some(v))`
+//!
+//! Converting back, `Unchanged` values are read the same way as `Set` ones.
+//!
+//! `Presence<V>` also implements [`IntoActiveValue`], so a patch struct can derive sea-orm's
+//! `DeriveIntoActiveModel` and be turned into an `ActiveModel` where only the defined fields
+//! are set.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use sea_orm::entity::prelude::*;
+//! use sea_orm::{ActiveValue, IntoActiveModel};
+//!
+//! mod user {
+//!     use sea_orm::entity::prelude::*;
+//!
+//!     #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+//!     #[sea_orm(table_name = "users")]
+//!     pub struct Model {
+//!         #[sea_orm(primary_key)]
+//!         pub id: i32,
+//!         pub nickname: Option<String>,
+//!         pub bio: Option<String>,
+//!     }
+//!
+//!     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+//!     pub enum Relation {}
+//!
+//!     impl ActiveModelBehavior for ActiveModel {}
+//! }
+//!
+//! #[derive(DeriveIntoActiveModel)]
+//! #[sea_orm(active_model = "user::ActiveModel")]
+//! struct UserPatch {
+//!     nickname: Presence<String>,
+//!     bio: Presence<String>,
+//! }
+//!
+//! let patch = UserPatch { nickname: Presence::Some("bob".into()), bio: Presence::Absent };
+//! let model = patch.into_active_model();
+//! assert_eq!(model.nickname, ActiveValue::Set(Some("bob".to_string())));
+//! assert_eq!(model.bio, ActiveValue::NotSet);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`IntoActiveValue`]: sea_orm::IntoActiveValue
+
+use crate::presence::Presence;
+use sea_orm::sea_query::Nullable;
+use sea_orm::{ActiveValue, IntoActiveValue, Value};
+
+impl<V> From<Presence<V>> for ActiveValue<Option<V>>
+where
+    V: Into<Value> + Nullable,
+{
+    /// Converts a `Presence<V>` into the `ActiveValue` of a nullable column.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use sea_orm::ActiveValue;
+    ///
+    /// let value: ActiveValue<Option<i32>> = Presence::Null.into();
+    /// assert_eq!(value, ActiveValue::Set(None));
+    ///
+    /// let value: ActiveValue<Option<i32>> = Presence::Absent.into();
+    /// assert_eq!(value, ActiveValue::NotSet);
+    /// ```
+    #[inline]
+    fn from(presence: Presence<V>) -> Self {
+        match presence {
+            Presence::Absent => ActiveValue::NotSet,
+            Presence::Null => ActiveValue::Set(None),
+            Presence::Some(value) => ActiveValue::Set(Some(value)),
+        }
+    }
+}
+
+impl<V> From<ActiveValue<Option<V>>> for Presence<V>
+where
+    V: Into<Value> + Nullable,
+{
+    /// Converts the `ActiveValue` of a nullable column into `Presence<V>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use sea_orm::ActiveValue;
+    ///
+    /// let p: Presence<i32> = ActiveValue::Unchanged(Some(3)).into();
+    /// assert_eq!(p, Presence::Some(3));
+    ///
+    /// let p: Presence<i32> = ActiveValue::NotSet.into();
+    /// assert_eq!(p, Presence::Absent);
+    /// ```
+    #[inline]
+    fn from(value: ActiveValue<Option<V>>) -> Self {
+        match value {
+            ActiveValue::NotSet => Presence::Absent,
+            ActiveValue::Set(None) | ActiveValue::Unchanged(None) => Presence::Null,
+            ActiveValue::Set(Some(value)) | ActiveValue::Unchanged(Some(value)) => {
+                Presence::Some(value)
+            }
+        }
+    }
+}
+
+impl<V> IntoActiveValue<Option<V>> for Presence<V>
+where
+    V: Into<Value> + Nullable,
+{
+    #[inline]
+    fn into_active_value(self) -> ActiveValue<Option<V>> {
+        self.into()
+    }
+}
@@ -0,0 +1,122 @@
+//! sea-query integration for [`Presence<T>`].
+//!
+//! `Presence<T>` converts into a sea-query [`Value`] like `Option<T>` does: `Some(value)`
+//! becomes the value and `Null` becomes a typed SQL `NULL`. `Absent` also converts to
+//! `NULL`, since a `Value` has no "missing" state.
+//!
+//! For partial updates, [`UpdateStatementExt::presence_value`] appends a column to an
+//! `UPDATE` statement only when its value is defined, so `Absent` columns are left untouched.
+//! [`UpdateStatementExt::set_clause`] appends the columns of a driver-agnostic
+//! [`SetClause`] instead.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::sea_query::UpdateStatementExt;
+//! use sea_query::{Alias, Expr, PostgresQueryBuilder, Query};
+//!
+//! let query = Query::update()
+//!     .table(Alias::new("users"))
+//!     .presence_value(Alias::new("name"), Presence::Some("Bob".to_string()))
+//!     .presence_value(Alias::new("bio"), Presence::<String>::Null)
+//!     .presence_value(Alias::new("age"), Presence::<i32>::Absent)
+//!     .and_where(Expr::col(Alias::new("id")).eq(1))
+//!     .to_string(PostgresQueryBuilder);
+//!
+//! assert_eq!(
+//!     query,
+//!     r#"UPDATE "users" SET "name" = 'Bob', "bio" = NULL WHERE "id" = 1"#,
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`Value`]: sea_query::Value
+//! [`SetClause`]: crate::sql::SetClause
+
+use crate::presence::Presence;
+use crate::sql::SetClause;
+use sea_query::{Alias, IntoIden, Keyword, Nullable, SimpleExpr, UpdateStatement, Value};
+
+impl<T> From<Presence<T>> for Value
+where
+    T: Into<Value> + Nullable,
+{
+    /// Converts a `Presence<T>` into a sea-query `Value`, mapping `Null` and `Absent` to
+    /// a typed `NULL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use sea_query::Value;
+    ///
+    /// assert_eq!(Value::from(Presence::Some(3_i32)), Value::Int(Some(3)));
+    /// assert_eq!(Value::from(Presence::<i32>::Null), Value::Int(None));
+    /// ```
+    #[inline]
+    fn from(presence: Presence<T>) -> Self {
+        match presence {
+            Presence::Some(value) => value.into(),
+            Presence::Null | Presence::Absent => T::null(),
+        }
+    }
+}
+
+/// Presence-aware methods for sea-query's [`UpdateStatement`].
+pub trait UpdateStatementExt {
+    /// Appends `col = value` unless `value` is `Absent`; `Null` sets the column to `NULL`.
+    fn presence_value<C, T>(&mut self, col: C, value: Presence<T>) -> &mut Self
+    where
+        C: IntoIden,
+        T: Into<Value> + Nullable;
+
+    /// Appends every column of `set`, with `NULL` for its `Null` columns.
+    ///
+    /// sea-query writes its own placeholders, so the placeholder style of `set` is not
+    /// used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sea_query::UpdateStatementExt;
+    /// use presence_rs::sql::{Placeholder, SetClause};
+    /// use sea_query::{Alias, PostgresQueryBuilder, Query};
+    ///
+    /// let mut set = SetClause::<i32>::new(Placeholder::Dollar);
+    /// set.set("age", Presence::Some(31)).set("rank", Presence::<i32>::Null);
+    ///
+    /// let query = Query::update()
+    ///     .table(Alias::new("users"))
+    ///     .set_clause(set)
+    ///     .to_string(PostgresQueryBuilder);
+    /// assert_eq!(query, r#"UPDATE "users" SET "age" = 31, "rank" = NULL"#);
+    /// ```
+    fn set_clause<V: Into<Value>>(&mut self, set: SetClause<V>) -> &mut Self;
+}
+
+impl UpdateStatementExt for UpdateStatement {
+    fn presence_value<C, T>(&mut self, col: C, value: Presence<T>) -> &mut Self
+    where
+        C: IntoIden,
+        T: Into<Value> + Nullable,
+    {
+        if value.is_absent() {
+            self
+        } else {
+            self.value(col, Value::from(value))
+        }
+    }
+
+    fn set_clause<V: Into<Value>>(&mut self, set: SetClause<V>) -> &mut Self {
+        for (column, value) in set.into_assignments() {
+            let value = match value {
+                Some(value) => SimpleExpr::Value(value.into()),
+                None => SimpleExpr::Keyword(Keyword::Null),
+            };
+            self.value(Alias::new(column), value);
+        }
+        self
+    }
+}
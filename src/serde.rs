@@ -15,7 +15,8 @@
 //!
 //! - `Some(value)` → Serializes the value directly
 //! - `Null` → Serializes as `null`
-//! - `Absent` → Serializes as `null` (use `skip_serializing_if` to omit the field)
+//! - `Absent` → Serializes as `null` (use `skip_serializing_if` to omit the field), or
+//!   fails in [strict mode](crate::strict)
 //!
 //! # Deserialization Behavior
 //!
@@ -71,9 +72,84 @@
 //! assert_eq!(json, r#"{"name":"Charlie"}"#);
 //! # }
 //! ```
+//!
+//! ## Flattened Structs
+//!
+//! Presence fields keep all three states inside a `#[serde(flatten)]` struct, as long as
+//! they carry the same attributes. Without `#[serde(default)]`, a missing field in a
+//! flattened struct is `Null`, just like in a regular one.
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! use presence_rs::Presence;
+//! use serde::{Serialize, Deserialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Contact {
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     email: Presence<String>,
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     phone: Presence<String>,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct UserPatch {
+//!     id: u64,
+//!     #[serde(flatten)]
+//!     contact: Contact,
+//! }
+//!
+//! let patch: UserPatch = serde_json::from_str(r#"{"id":1,"email":null}"#).unwrap();
+//! assert_eq!(patch.contact.email, Presence::Null);
+//! assert_eq!(patch.contact.phone, Presence::Absent);
+//! assert_eq!(serde_json::to_string(&patch).unwrap(), r#"{"id":1,"email":null}"#);
+//! # }
+//! ```
+//!
+//! ## Untagged Enums
+//!
+//! Inside `#[serde(untagged)]` enums, `null` is `Null` and a missing field with
+//! `#[serde(default)]` is `Absent`, as in a plain struct. Beware of variants whose fields
+//! are all defaulted Presence fields: they match any object, including one meant for a
+//! later variant. Add `#[serde(deny_unknown_fields)]` to such variants so that probing
+//! moves on.
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! use presence_rs::Presence;
+//! use serde::Deserialize;
+//!
+//! #[derive(Debug, Deserialize)]
+//! #[serde(deny_unknown_fields)]
+//! struct ProfilePatch {
+//!     #[serde(default)]
+//!     name: Presence<String>,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! #[serde(deny_unknown_fields)]
+//! struct SettingsPatch {
+//!     #[serde(default)]
+//!     theme: Presence<String>,
+//! }
+//!
+//! #[derive(Debug, Deserialize)]
+//! #[serde(untagged)]
+//! enum Patch {
+//!     Profile(ProfilePatch),
+//!     Settings(SettingsPatch),
+//! }
+//!
+//! let patch: Patch = serde_json::from_str(r#"{"theme":null}"#).unwrap();
+//! assert!(matches!(patch, Patch::Settings(SettingsPatch { theme: Presence::Null })));
+//! # }
+//! ```
 
 use crate::presence::Presence;
+use serde::de::{DeserializeSeed, Visitor};
+use serde::ser;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 
 impl<T: Serialize> Serialize for Presence<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -83,6 +159,9 @@ impl<T: Serialize> Serialize for Presence<T> {
         match self {
             Presence::Some(value) => serializer.serialize_some(value),
             Presence::Null => serializer.serialize_none(),
+            Presence::Absent if crate::strict::is_strict::<S>() => {
+                Err(ser::Error::custom(crate::strict::ABSENT_ERROR))
+            }
             Presence::Absent => serializer.serialize_none(),
         }
     }
@@ -100,6 +179,152 @@ impl<'de, T: Deserialize<'de>> Deserialize<'de> for Presence<T> {
     }
 }
 
+/// A [`DeserializeSeed`] for a `Presence` of the value produced by another seed.
+///
+/// `null` is `Null`, and any other value goes through the inner seed. Like the
+/// `Deserialize` impl, it cannot see a missing field; callers driving a `MapAccess` use
+/// `Absent` for keys they never saw.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, PresenceSeed};
+/// use serde::de::DeserializeSeed;
+/// use std::marker::PhantomData;
+///
+/// let mut de = serde_json::Deserializer::from_str("7");
+/// let value = PresenceSeed(PhantomData::<u8>).deserialize(&mut de).unwrap();
+/// assert_eq!(value, Presence::Some(7));
+///
+/// let mut de = serde_json::Deserializer::from_str("null");
+/// let value = PresenceSeed(PhantomData::<u8>).deserialize(&mut de).unwrap();
+/// assert_eq!(value, Presence::Null);
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PresenceSeed<S>(pub S);
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for PresenceSeed<S> {
+    type Value = Presence<S::Value>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_option(PresenceSeedVisitor(self.0))
+    }
+}
+
+struct PresenceSeedVisitor<S>(S);
+
+impl<'de, S: DeserializeSeed<'de>> Visitor<'de> for PresenceSeedVisitor<S> {
+    type Value = Presence<S::Value>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a value or null")
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Presence::Null)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Presence::Null)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        self.0.deserialize(deserializer).map(Presence::Some)
+    }
+}
+
+/// Declares a serde `with` module for `Presence<T>` fields that uses an inner `with`
+/// module for the value.
+///
+/// `presence_serde_with!(mod name: Type = inner)` generates `name::serialize` and
+/// `name::deserialize`, where `inner` is any module usable as `#[serde(with = "inner")]`
+/// for `Type`, such as `chrono::serde::ts_seconds`. `Null` and `Absent` are handled as for
+/// a plain `Presence<T>` field, and only `Some` values go through `inner`. Names are
+/// resolved from the module the macro is called in.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, presence_serde_with};
+/// use serde::{Deserialize, Serialize};
+///
+/// mod upper {
+///     use serde::{Deserialize, Deserializer, Serializer};
+///
+///     pub fn serialize<S: Serializer>(value: &String, serializer: S) -> Result<S::Ok, S::Error> {
+///         serializer.serialize_str(&value.to_uppercase())
+///     }
+///
+///     pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+///         String::deserialize(deserializer).map(|value| value.to_lowercase())
+///     }
+/// }
+///
+/// presence_serde_with!(mod upper_presence: String = upper);
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Patch {
+///     #[serde(default, with = "upper_presence", skip_serializing_if = "Presence::is_absent")]
+///     code: Presence<String>,
+/// }
+///
+/// # fn main() {
+/// let patch = Patch { code: Presence::Some("abc".into()) };
+/// assert_eq!(serde_json::to_string(&patch).unwrap(), r#"{"code":"ABC"}"#);
+///
+/// let patch: Patch = serde_json::from_str(r#"{"code":null}"#).unwrap();
+/// assert_eq!(patch.code, Presence::Null);
+///
+/// let patch: Patch = serde_json::from_str("{}").unwrap();
+/// assert_eq!(patch.code, Presence::Absent);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! presence_serde_with {
+    ($vis:vis mod $name:ident: $ty:ty = $inner:path) => {
+        $vis mod $name {
+            #[allow(unused_imports)]
+            use super::*;
+            use $inner as inner;
+            use $crate::__serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            struct Inner<T>(T);
+
+            impl Serialize for Inner<&$ty> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    inner::serialize(self.0, serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for Inner<$ty> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    inner::deserialize(deserializer).map(Inner)
+                }
+            }
+
+            pub fn serialize<S: Serializer>(
+                value: &$crate::Presence<$ty>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                value.as_ref().map(Inner).serialize(serializer)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<$crate::Presence<$ty>, D::Error> {
+                $crate::Presence::<Inner<$ty>>::deserialize(deserializer)
+                    .map(|value| value.map(|Inner(value)| value))
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
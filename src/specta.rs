@@ -0,0 +1,36 @@
+//! specta type support for [`Presence<T>`].
+//!
+//! `Presence<T>` is described with the same [`DataType`] as `Option<T>`, a nullable `T`,
+//! which the TypeScript exporter renders as `T | null`. Mark the struct field with
+//! `#[specta(optional)]` so that it may also be left out, giving
+//! `field?: T | null` (`T | null | undefined`) for tauri commands and rspc procedures.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use specta::datatype::{DataType, PrimitiveType};
+//! use specta::{Generics, Type, TypeCollection};
+//!
+//! let mut types = TypeCollection::default();
+//! let ty = Presence::<String>::inline(&mut types, Generics::Definition);
+//! assert!(matches!(ty, DataType::Nullable(inner) if matches!(*inner, DataType::Primitive(PrimitiveType::String))));
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`DataType`]: specta::DataType
+
+use crate::presence::Presence;
+use specta::datatype::DataType;
+use specta::datatype::reference::Reference;
+use specta::{Generics, Type, TypeCollection};
+
+impl<T: Type> Type for Presence<T> {
+    fn inline(type_map: &mut TypeCollection, generics: Generics) -> DataType {
+        <Option<T>>::inline(type_map, generics)
+    }
+
+    fn reference(type_map: &mut TypeCollection, generics: &[DataType]) -> Reference {
+        <Option<T>>::reference(type_map, generics)
+    }
+}
@@ -0,0 +1,252 @@
+//! `SET` clauses for SQL `UPDATE` statements from [`Presence<T>`] patches, without a
+//! database driver.
+//!
+//! [`SetClause`] renders the defined fields of a patch as a `SET` fragment and collects
+//! the values to bind, in placeholder order:
+//!
+//! - `Some(value)` → `column = $n`, binding `Some(value)`
+//! - `Null` → `column = $n`, binding `None`, or `column = NULL` with [`NullStyle::Literal`]
+//! - `Absent` → left out of the clause
+//!
+//! The bind type `V` is chosen by the caller, usually the value enum of their driver.
+//! Values are never written into the SQL. Column names are written verbatim and must come
+//! from code, never from user input.
+//!
+//! The driver integrations take a finished clause, so the same patch code serves every
+//! backend: `params` in [`postgres`](crate::postgres), `UpdateBuilder::set_clause` in
+//! [`sqlx`](crate::sqlx), `sql_update` in [`diesel`](crate::diesel) and
+//! `UpdateStatementExt::set_clause` in [`sea_query`](crate::sea_query).
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::sql::{Placeholder, SetClause};
+//!
+//! #[derive(Debug, PartialEq)]
+//! enum Value {
+//!     Text(String),
+//!     Int(i64),
+//! }
+//!
+//! let mut set = SetClause::<Value>::new(Placeholder::Dollar);
+//! set.set("name", Presence::Some(Value::Text("Bob".into())))
+//!     .set("bio", Presence::<Value>::Null)
+//!     .set("age", Presence::<Value>::Absent);
+//!
+//! let sql = format!("UPDATE users {} WHERE id = {}", set.sql(), set.next_placeholder());
+//! assert_eq!(sql, "UPDATE users SET name = $1, bio = $2 WHERE id = $3");
+//!
+//! let mut binds = set.into_binds();
+//! binds.push(Some(Value::Int(7)));
+//! assert_eq!(binds, [Some(Value::Text("Bob".into())), None, Some(Value::Int(7))]);
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use std::fmt::Write;
+
+/// The placeholder syntax of a SQL dialect.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Placeholder {
+    /// `$1`, `$2`, ... (PostgreSQL).
+    Dollar,
+    /// `?` (MySQL, SQLite).
+    Question,
+    /// `?1`, `?2`, ... (SQLite).
+    NumberedQuestion,
+    /// `:1`, `:2`, ... (Oracle).
+    Colon,
+    /// `@p1`, `@p2`, ... (SQL Server).
+    AtP,
+}
+
+impl Placeholder {
+    /// Writes the placeholder for the bind at the 1-based `index`.
+    fn write(self, sql: &mut String, index: usize) {
+        match self {
+            Placeholder::Dollar => write!(sql, "${}", index),
+            Placeholder::Question => write!(sql, "?"),
+            Placeholder::NumberedQuestion => write!(sql, "?{}", index),
+            Placeholder::Colon => write!(sql, ":{}", index),
+            Placeholder::AtP => write!(sql, "@p{}", index),
+        }
+        .expect("writing to a String cannot fail");
+    }
+}
+
+/// How `Null` fields are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NullStyle {
+    /// Bind `None`, like any other value.
+    #[default]
+    Bind,
+    /// Write `NULL` into the SQL, without a bind.
+    Literal,
+}
+
+/// Builds the `SET` clause of an `UPDATE` statement from `Presence<T>` values.
+///
+/// Call [`set`](Self::set) for each column, then render the clause with
+/// [`sql`](Self::sql) and pass [`binds`](Self::binds) to the driver.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetClause<V> {
+    sql: String,
+    binds: Vec<Option<V>>,
+    placeholder: Placeholder,
+    nulls: NullStyle,
+    offset: usize,
+    /// The columns in order, with `false` for a `NULL` literal that has no bind.
+    columns: Vec<(String, bool)>,
+}
+
+impl<V> SetClause<V> {
+    /// Starts an empty `SET` clause with the given placeholder syntax.
+    pub fn new(placeholder: Placeholder) -> Self {
+        Self {
+            sql: String::from("SET "),
+            binds: Vec::new(),
+            placeholder,
+            nulls: NullStyle::default(),
+            offset: 0,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Sets how `Null` fields are written.
+    pub fn nulls(mut self, nulls: NullStyle) -> Self {
+        self.nulls = nulls;
+        self
+    }
+
+    /// Numbers the placeholders after `offset` binds that precede the clause.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sql::{Placeholder, SetClause};
+    ///
+    /// let mut set = SetClause::<&str>::new(Placeholder::Dollar).offset(2);
+    /// set.set("name", Presence::Some("Bob"));
+    /// assert_eq!(set.sql(), "SET name = $3");
+    /// ```
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Adds `column = value` unless `value` is `Absent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+    ///
+    /// let mut set = SetClause::<i32>::new(Placeholder::Question).nulls(NullStyle::Literal);
+    /// set.set("a", Presence::Some(1))
+    ///     .set("b", Presence::<i32>::Null)
+    ///     .set("c", Presence::<i32>::Absent);
+    /// assert_eq!(set.sql(), "SET a = ?, b = NULL");
+    /// assert_eq!(set.binds(), [Some(1)]);
+    /// ```
+    pub fn set<T: Into<V>>(&mut self, column: &str, value: Presence<T>) -> &mut Self {
+        let value = match value {
+            Presence::Absent => return self,
+            Presence::Null => None,
+            Presence::Some(value) => Some(value.into()),
+        };
+
+        if !self.columns.is_empty() {
+            self.sql.push_str(", ");
+        }
+        self.sql.push_str(column);
+        self.sql.push_str(" = ");
+        let bound = value.is_some() || self.nulls == NullStyle::Bind;
+        if bound {
+            self.binds.push(value);
+            self.placeholder
+                .write(&mut self.sql, self.offset + self.binds.len());
+        } else {
+            self.sql.push_str("NULL");
+        }
+        self.columns.push((column.to_string(), bound));
+        self
+    }
+
+    /// Returns the number of columns in the clause.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `true` if no column has been set.
+    ///
+    /// An `UPDATE` with an empty `SET` clause is invalid SQL, so an all-`Absent` patch
+    /// must be handled by the caller, usually by skipping the query.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the clause, starting with `SET `.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Returns the values to bind, in placeholder order, with `None` for SQL `NULL`.
+    pub fn binds(&self) -> &[Option<V>] {
+        &self.binds
+    }
+
+    /// Returns the placeholder for the next bind after the clause, for the `WHERE` clause.
+    pub fn next_placeholder(&self) -> String {
+        let mut placeholder = String::new();
+        self.placeholder
+            .write(&mut placeholder, self.offset + self.binds.len() + 1);
+        placeholder
+    }
+
+    /// Returns the values to bind.
+    pub fn into_binds(self) -> Vec<Option<V>> {
+        self.binds
+    }
+
+    /// Returns each column with its value, `None` for `NULL`, in clause order.
+    ///
+    /// This is how the driver integrations read the clause when they write their own
+    /// placeholders.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+    ///
+    /// let mut set = SetClause::<i32>::new(Placeholder::Question).nulls(NullStyle::Literal);
+    /// set.set("a", Presence::<i32>::Null).set("b", Presence::Some(2));
+    /// assert_eq!(
+    ///     set.into_assignments(),
+    ///     [("a".to_string(), None), ("b".to_string(), Some(2))]
+    /// );
+    /// ```
+    pub fn into_assignments(self) -> Vec<(String, Option<V>)> {
+        let mut binds = self.binds.into_iter();
+        self.columns
+            .into_iter()
+            .map(|(column, bound)| {
+                let value = if bound { binds.next().flatten() } else { None };
+                (column, value)
+            })
+            .collect()
+    }
+
+    /// Returns the clause and the values to bind, or `None` if no column has been set.
+    pub fn finish(self) -> Option<(String, Vec<Option<V>>)> {
+        if self.is_empty() {
+            None
+        } else {
+            Some((self.sql, self.binds))
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Dynamic `UPDATE` statements for sqlx from [`Presence<T>`] patches.
+//!
+//! [`UpdateBuilder`] writes an `UPDATE <table> SET ...` statement that contains only the
+//! defined fields of a patch:
+//!
+//! - `Some(value)` → `column = $n`, binding `value`
+//! - `Null` → `column = $n`, binding SQL `NULL`
+//! - `Absent` → left out of the statement
+//!
+//! [`update_set!`] adds the listed fields of a patch struct, using each field name as the
+//! column name, and [`UpdateBuilder::set_clause`] adds the columns of a driver-agnostic
+//! [`SetClause`].
+//!
+//! Table and column names are written into the SQL verbatim. They must come from code,
+//! never from user input.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::sqlx::UpdateBuilder;
+//! use sqlx::Any;
+//!
+//! let mut update = UpdateBuilder::<Any>::new("users");
+//! update
+//!     .set("name", Presence::Some("Bob".to_string()))
+//!     .set("bio", Presence::<String>::Null)
+//!     .set("age", Presence::<i32>::Absent);
+//!
+//! let mut query = update.finish().expect("at least one field is defined");
+//! query.push(" WHERE id = ").push_bind(7_i64);
+//! assert_eq!(query.sql(), "UPDATE users SET name = ?, bio = ? WHERE id = ?");
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+//! [`update_set!`]: crate::update_set
+//! [`SetClause`]: crate::sql::SetClause
+
+use crate::presence::Presence;
+use crate::sql::SetClause;
+use sqlx::{Database, Encode, QueryBuilder, Type};
+
+/// Builds an `UPDATE ... SET` statement from `Presence<T>` values.
+///
+/// Call [`set`](Self::set) for each column, then [`finish`](Self::finish) to get the
+/// underlying [`QueryBuilder`] and append the `WHERE` clause.
+pub struct UpdateBuilder<'args, DB: Database> {
+    builder: QueryBuilder<'args, DB>,
+    columns: usize,
+}
+
+impl<'args, DB: Database> UpdateBuilder<'args, DB> {
+    /// Starts an `UPDATE` statement for `table`.
+    pub fn new(table: &str) -> Self {
+        Self {
+            builder: QueryBuilder::new(format!("UPDATE {} SET ", table)),
+            columns: 0,
+        }
+    }
+
+    /// Adds `column = value` unless `value` is `Absent`; `Null` binds SQL `NULL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sqlx::UpdateBuilder;
+    /// use sqlx::Any;
+    ///
+    /// let mut update = UpdateBuilder::<Any>::new("users");
+    /// update.set("name", Presence::<String>::Absent);
+    /// assert!(update.is_empty());
+    ///
+    /// update.set("name", Presence::<String>::Null);
+    /// assert_eq!(update.len(), 1);
+    /// ```
+    pub fn set<T>(&mut self, column: &str, value: Presence<T>) -> &mut Self
+    where
+        Option<T>: 'args + Encode<'args, DB> + Type<DB>,
+    {
+        let value = match value {
+            Presence::Absent => return self,
+            Presence::Null => None,
+            Presence::Some(value) => Some(value),
+        };
+
+        if self.columns > 0 {
+            self.builder.push(", ");
+        }
+        self.builder.push(column).push(" = ").push_bind(value);
+        self.columns += 1;
+        self
+    }
+
+    /// Adds every column of `set`, binding SQL `NULL` for its `Null` columns.
+    ///
+    /// sqlx writes its own placeholders, so the placeholder style of `set` is not used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::sql::{Placeholder, SetClause};
+    /// use presence_rs::sqlx::UpdateBuilder;
+    /// use sqlx::Any;
+    ///
+    /// let mut set = SetClause::<String>::new(Placeholder::Question);
+    /// set.set("name", Presence::Some("Bob".to_string()))
+    ///     .set("bio", Presence::<String>::Null);
+    ///
+    /// let mut update = UpdateBuilder::<Any>::new("users");
+    /// update.set_clause(set);
+    /// assert_eq!(update.finish().unwrap().sql(), "UPDATE users SET name = ?, bio = ?");
+    /// ```
+    pub fn set_clause<V>(&mut self, set: SetClause<V>) -> &mut Self
+    where
+        Option<V>: 'args + Encode<'args, DB> + Type<DB>,
+    {
+        for (column, value) in set.into_assignments() {
+            self.set(&column, Presence::from_nullable(Some(value)));
+        }
+        self
+    }
+
+    /// Returns the number of columns in the `SET` clause.
+    pub fn len(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns `true` if no column has been set.
+    pub fn is_empty(&self) -> bool {
+        self.columns == 0
+    }
+
+    /// Returns the query builder, or `None` if no column has been set.
+    ///
+    /// An `UPDATE` with an empty `SET` clause is invalid SQL, so an all-`Absent` patch
+    /// must be handled by the caller, usually by skipping the query.
+    pub fn finish(self) -> Option<QueryBuilder<'args, DB>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.builder)
+        }
+    }
+}
+
+/// Adds the listed fields of a patch struct to an [`UpdateBuilder`].
+///
+/// Each field is passed to [`UpdateBuilder::set`] with its name as the column name. The
+/// patch fields are moved out of `$patch`.
+///
+/// [`UpdateBuilder`]: crate::sqlx::UpdateBuilder
+/// [`UpdateBuilder::set`]: crate::sqlx::UpdateBuilder::set
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::{Presence, update_set};
+/// use presence_rs::sqlx::UpdateBuilder;
+/// use sqlx::Any;
+///
+/// struct UserPatch { name: Presence<String>, email: Presence<String> }
+///
+/// let patch = UserPatch { name: Presence::Absent, email: Presence::Some("b@example.com".into()) };
+///
+/// let mut update = UpdateBuilder::<Any>::new("users");
+/// update_set!(update, patch; name, email);
+///
+/// let query = update.finish().unwrap();
+/// assert_eq!(query.sql(), "UPDATE users SET email = ?");
+/// ```
+#[macro_export]
+macro_rules! update_set {
+    ($update:expr, $patch:expr; $($field:ident),+ $(,)?) => {{
+        let patch = $patch;
+        $(
+            $update.set(stringify!($field), patch.$field);
+        )+
+    }};
+}
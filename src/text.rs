@@ -0,0 +1,226 @@
+//! Text representations of [`Presence<T>`] with configurable tokens.
+//!
+//! The `Display` implementation of `Presence<T>` writes `null` and `(absent)` for the two
+//! empty states. Reports, tables and CSV exports usually want something else, such as `-`,
+//! `NULL` or nothing at all. [`Tokens`] holds the text for each empty state and
+//! [`Presence::display_with`] formats a value with them.
+//!
+//! Parsing is the inverse: [`Presence::parse_with`] reads a token as its state and parses
+//! anything else as the value, and `FromStr` does the same with the default tokens. Tokens
+//! are not escaped, so a `Some` value whose text equals a token reads back as that token's
+//! state: `Some("null")` displays as `null`, which parses as `Null`.
+//!
+//! The same [`Tokens`] configure how [`csv`](crate::csv) cells and
+//! [`env`](crate::env) variables are read.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::text::Tokens;
+//!
+//! let sql = Tokens::new("NULL", "DEFAULT");
+//! assert_eq!(Presence::Some(3).display_with(&sql).to_string(), "3");
+//! assert_eq!(Presence::<i32>::Null.display_with(&sql).to_string(), "NULL");
+//! assert_eq!(Presence::<i32>::Absent.display_with(&sql).to_string(), "DEFAULT");
+//!
+//! let report = Tokens::default().null("∅").absent("");
+//! let row: Vec<String> = [Presence::Some(1.5), Presence::Null, Presence::Absent]
+//!     .iter()
+//!     .map(|cell| format!("{:>4}", cell.display_with(&report)))
+//!     .collect();
+//! assert_eq!(row.join("|"), " 1.5|   ∅|    ");
+//!
+//! // Reading CLI or CSV input where `unset` leaves a setting alone and `none` clears it.
+//! let input = Tokens::new("none", "unset");
+//! assert_eq!(Presence::<u16>::parse_with("8080", &input), Ok(Presence::Some(8080)));
+//! assert_eq!(Presence::<u16>::parse_with("none", &input), Ok(Presence::Null));
+//! assert_eq!(Presence::<u16>::parse_with("unset", &input), Ok(Presence::Absent));
+//! assert!(Presence::<u16>::parse_with("", &input).is_err());
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::{Presence, PresenceState};
+use std::fmt;
+use std::str::FromStr;
+
+/// The text of the two empty states.
+///
+/// Each state has a list of tokens. Formatting writes the first one, and parsing reads
+/// any of them; tokens match exactly and the `Null` tokens are checked first.
+///
+/// The default tokens are those of `Presence`'s `Display`: `null` and `(absent)`.
+///
+/// # Examples
+///
+/// ```
+/// use presence_rs::PresenceState;
+/// use presence_rs::text::Tokens;
+///
+/// let tokens = Tokens::none().null("").or_null("NULL").absent("-");
+/// assert_eq!(tokens.state(""), Some(PresenceState::Null));
+/// assert_eq!(tokens.state("NULL"), Some(PresenceState::Null));
+/// assert_eq!(tokens.state("-"), Some(PresenceState::Absent));
+/// assert_eq!(tokens.state("null"), None);
+/// assert_eq!(tokens.null_token(), "");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tokens {
+    null: Vec<String>,
+    absent: Vec<String>,
+}
+
+impl Tokens {
+    /// Creates tokens for `Null` and `Absent`.
+    pub fn new(null: impl Into<String>, absent: impl Into<String>) -> Self {
+        Self::none().null(null).absent(absent)
+    }
+
+    /// Creates a set without tokens, where every text is a value.
+    pub const fn none() -> Self {
+        Self {
+            null: Vec::new(),
+            absent: Vec::new(),
+        }
+    }
+
+    /// Sets the token for `Null`, replacing any others.
+    pub fn null(mut self, token: impl Into<String>) -> Self {
+        self.null = vec![token.into()];
+        self
+    }
+
+    /// Sets the token for `Absent`, replacing any others. An empty token renders nothing.
+    pub fn absent(mut self, token: impl Into<String>) -> Self {
+        self.absent = vec![token.into()];
+        self
+    }
+
+    /// Adds another token that is read as `Null`.
+    pub fn or_null(mut self, token: impl Into<String>) -> Self {
+        self.null.push(token.into());
+        self
+    }
+
+    /// Adds another token that is read as `Absent`.
+    pub fn or_absent(mut self, token: impl Into<String>) -> Self {
+        self.absent.push(token.into());
+        self
+    }
+
+    /// Returns the token written for `Null`, or `""` if there is none.
+    pub fn null_token(&self) -> &str {
+        self.null.first().map_or("", String::as_str)
+    }
+
+    /// Returns the token written for `Absent`, or `""` if there is none.
+    pub fn absent_token(&self) -> &str {
+        self.absent.first().map_or("", String::as_str)
+    }
+
+    /// Returns the state `text` is a token of, or `None` if it is a value.
+    pub fn state(&self, text: &str) -> Option<PresenceState> {
+        if self.null.iter().any(|token| token == text) {
+            Some(PresenceState::Null)
+        } else if self.absent.iter().any(|token| token == text) {
+            Some(PresenceState::Absent)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Tokens {
+    fn default() -> Self {
+        Self::new("null", "(absent)")
+    }
+}
+
+/// Formats a `Presence` with custom [`Tokens`].
+///
+/// Created by [`Presence::display_with`]. Width, alignment and other formatting options
+/// apply to the tokens as well as to the value.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayWith<'p, 't, T> {
+    presence: &'p Presence<T>,
+    tokens: &'t Tokens,
+}
+
+impl<T: fmt::Display> fmt::Display for DisplayWith<'_, '_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.presence {
+            Presence::Some(value) => value.fmt(f),
+            Presence::Null => f.pad(self.tokens.null_token()),
+            Presence::Absent => f.pad(self.tokens.absent_token()),
+        }
+    }
+}
+
+impl<T> Presence<T> {
+    /// Returns a value that formats `self` with `tokens` for `Null` and `Absent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::text::Tokens;
+    ///
+    /// let dash = Tokens::new("-", "");
+    /// assert_eq!(format!("[{}]", Presence::<u8>::Null.display_with(&dash)), "[-]");
+    /// assert_eq!(format!("[{}]", Presence::<u8>::Absent.display_with(&dash)), "[]");
+    /// ```
+    pub fn display_with<'t>(&self, tokens: &'t Tokens) -> DisplayWith<'_, 't, T> {
+        DisplayWith {
+            presence: self,
+            tokens,
+        }
+    }
+}
+
+impl<T: FromStr> Presence<T> {
+    /// Parses `s`, reading the tokens of `tokens` as `Null` and `Absent` and parsing
+    /// anything else as `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::text::Tokens;
+    ///
+    /// let csv = Tokens::new("NULL", "");
+    /// assert_eq!(Presence::<i64>::parse_with("", &csv), Ok(Presence::Absent));
+    /// assert_eq!(Presence::<i64>::parse_with("NULL", &csv), Ok(Presence::Null));
+    /// assert_eq!(Presence::<i64>::parse_with("-4", &csv), Ok(Presence::Some(-4)));
+    /// ```
+    pub fn parse_with(s: &str, tokens: &Tokens) -> Result<Self, T::Err> {
+        match tokens.state(s) {
+            Some(PresenceState::Null) => Ok(Presence::Null),
+            Some(_) => Ok(Presence::Absent),
+            None => s.parse().map(Presence::Some),
+        }
+    }
+}
+
+impl<T: FromStr> FromStr for Presence<T> {
+    type Err = T::Err;
+
+    /// Parses `null` as `Null`, `(absent)` as `Absent` and anything else as `T`.
+    ///
+    /// This inverts `Display` except for `Some` values that display as one of the two
+    /// tokens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    ///
+    /// assert_eq!("42".parse::<Presence<u8>>(), Ok(Presence::Some(42)));
+    /// assert_eq!("null".parse::<Presence<u8>>(), Ok(Presence::Null));
+    /// assert_eq!("(absent)".parse::<Presence<u8>>(), Ok(Presence::Absent));
+    /// assert!("x".parse::<Presence<u8>>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Tokens::default())
+    }
+}
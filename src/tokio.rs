@@ -0,0 +1,184 @@
+//! Watch channels publishing a [`Presence<T>`].
+//!
+//! Dynamic configuration often has three states that subscribers must tell apart:
+//!
+//! - `Absent`: never configured, so the built-in default applies
+//! - `Null`: explicitly disabled
+//! - `Some(value)`: the live value
+//!
+//! [`PresenceWatch`] wraps a `tokio::sync::watch` channel of `Presence<T>`, which starts
+//! `Absent`. Each [`PresenceReceiver`] sees the latest state and can wait for a value with
+//! [`wait_for_present`](PresenceReceiver::wait_for_present).
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::tokio::PresenceWatch;
+//!
+//! # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+//! let watch = PresenceWatch::new();
+//! let mut rate_limit = watch.subscribe();
+//! assert_eq!(rate_limit.get(), Presence::Absent);
+//!
+//! watch.set_null();
+//! assert_eq!(rate_limit.get(), Presence::Null);
+//!
+//! let task = tokio::spawn(async move { rate_limit.wait_for_present().await.unwrap() });
+//! watch.set(100);
+//! assert_eq!(task.await.unwrap(), 100);
+//! # });
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::{Presence, PresenceState};
+use tokio::sync::watch;
+
+pub use tokio::sync::watch::error::RecvError;
+
+/// The sending half of a watch channel of `Presence<T>`.
+#[derive(Debug)]
+pub struct PresenceWatch<T> {
+    sender: watch::Sender<Presence<T>>,
+}
+
+impl<T> PresenceWatch<T> {
+    /// Creates a channel whose value is `Absent`.
+    pub fn new() -> Self {
+        Self::with_initial(Presence::Absent)
+    }
+
+    /// Creates a channel with the given initial value.
+    pub fn with_initial(initial: Presence<T>) -> Self {
+        Self {
+            sender: watch::Sender::new(initial),
+        }
+    }
+
+    /// Publishes `value`, returning the previous state.
+    ///
+    /// The value is stored even when there are no receivers, so later subscribers see it.
+    pub fn publish(&self, value: Presence<T>) -> Presence<T> {
+        self.sender.send_replace(value)
+    }
+
+    /// Publishes `Some(value)`.
+    pub fn set(&self, value: T) {
+        self.sender
+            .send_modify(|current| *current = Presence::Some(value));
+    }
+
+    /// Publishes `Null`.
+    pub fn set_null(&self) {
+        self.sender.send_modify(|current| *current = Presence::Null);
+    }
+
+    /// Publishes `Absent`.
+    pub fn set_absent(&self) {
+        self.sender
+            .send_modify(|current| *current = Presence::Absent);
+    }
+
+    /// Returns the state of the current value.
+    pub fn state(&self) -> PresenceState {
+        self.sender.borrow().state()
+    }
+
+    /// Returns a receiver that sees the current value and every later one.
+    pub fn subscribe(&self) -> PresenceReceiver<T> {
+        PresenceReceiver {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+
+    /// Returns the underlying watch sender.
+    pub fn into_inner(self) -> watch::Sender<Presence<T>> {
+        self.sender
+    }
+}
+
+impl<T> Default for PresenceWatch<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<watch::Sender<Presence<T>>> for PresenceWatch<T> {
+    fn from(sender: watch::Sender<Presence<T>>) -> Self {
+        Self { sender }
+    }
+}
+
+/// The receiving half of a watch channel of `Presence<T>`.
+#[derive(Clone, Debug)]
+pub struct PresenceReceiver<T> {
+    receiver: watch::Receiver<Presence<T>>,
+}
+
+impl<T> PresenceReceiver<T> {
+    /// Returns the state of the current value.
+    pub fn state(&self) -> PresenceState {
+        self.receiver.borrow().state()
+    }
+
+    /// Returns a clone of the current value and marks it as seen.
+    pub fn get(&mut self) -> Presence<T>
+    where
+        T: Clone,
+    {
+        self.receiver.borrow_and_update().clone()
+    }
+
+    /// Waits for a value that has not been seen yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`PresenceWatch`] has been dropped.
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        self.receiver.changed().await
+    }
+
+    /// Waits until the value is `Some` and returns a clone of it, immediately if it
+    /// already is.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`PresenceWatch`] is dropped before a value is published.
+    pub async fn wait_for_present(&mut self) -> Result<T, RecvError>
+    where
+        T: Clone,
+    {
+        let value = self.receiver.wait_for(Presence::is_present).await?;
+        match &*value {
+            Presence::Some(value) => Ok(value.clone()),
+            _ => unreachable!("wait_for returned a value failing its predicate"),
+        }
+    }
+
+    /// Waits until the value is defined, `Some` or `Null`, and returns a clone of it.
+    ///
+    /// Use this to wait until configuration has been decided, whether it enables or
+    /// disables a feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the [`PresenceWatch`] is dropped before a value is published.
+    pub async fn wait_for_defined(&mut self) -> Result<Presence<T>, RecvError>
+    where
+        T: Clone,
+    {
+        let value = self.receiver.wait_for(Presence::is_defined).await?;
+        Ok(value.clone())
+    }
+
+    /// Returns the underlying watch receiver.
+    pub fn into_inner(self) -> watch::Receiver<Presence<T>> {
+        self.receiver
+    }
+}
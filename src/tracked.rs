@@ -0,0 +1,210 @@
+//! Recording mutations of a value as a [`Presence<T>`] patch.
+//!
+//! [`Tracked<T>`] wraps the current value of a field, loaded in full, and remembers whether
+//! it was changed. At the end, [`Tracked::into_patch`] turns the field into its part of a
+//! PATCH payload:
+//!
+//! - Never changed → `Absent`
+//! - Cleared → `Null`
+//! - Set or modified → `Some(value)`
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::tracked::Tracked;
+//!
+//! struct Form {
+//!     name: Tracked<String>,
+//!     bio: Tracked<String>,
+//!     tags: Tracked<Vec<String>>,
+//! }
+//!
+//! let mut form = Form {
+//!     name: Tracked::new("Alice".into()),
+//!     bio: Tracked::new("Hello".into()),
+//!     tags: Tracked::new(vec!["rust".into()]),
+//! };
+//!
+//! form.bio.clear();
+//! form.tags.modify(|tags| tags.push("wasm".into()));
+//!
+//! assert_eq!(form.name.into_patch(), Presence::Absent);
+//! assert_eq!(form.bio.into_patch(), Presence::Null);
+//! assert_eq!(form.tags.into_patch(), Presence::Some(vec!["rust".into(), "wasm".into()]));
+//! ```
+//!
+//! With the `derive` feature, `#[derive(IntoPatch)]` on a struct of `Tracked` fields
+//! generates the whole patch struct, with a `Presence<T>` for every `Tracked<T>`, and
+//! [`IntoPatch::into_patch`] converts the struct into it:
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::tracked::{IntoPatch, Tracked};
+//!
+//! #[derive(IntoPatch)]
+//! struct Form {
+//!     name: Tracked<String>,
+//!     bio: Tracked<String>,
+//! }
+//!
+//! let mut form = Form {
+//!     name: Tracked::new("Alice".into()),
+//!     bio: Tracked::new("Hello".into()),
+//! };
+//! form.name.set("Bob".into());
+//!
+//! assert_eq!(
+//!     form.into_patch(),
+//!     FormPatch { name: Presence::Some("Bob".into()), bio: Presence::Absent }
+//! );
+//! # }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::IntoPatch;
+
+/// A value that converts into a patch of the changes made to it.
+///
+/// Implemented by [`Tracked<T>`], with a `Presence<T>` patch, and usually derived for
+/// structs of `Tracked` fields; see the [module documentation](self).
+pub trait IntoPatch {
+    /// The patch type.
+    type Patch;
+
+    /// Converts into the patch, where unchanged parts are `Absent`.
+    fn into_patch(self) -> Self::Patch;
+
+    /// Returns `true` if any part was changed.
+    fn is_changed(&self) -> bool;
+}
+
+/// A value that records whether it was changed.
+///
+/// The value is either set or cleared; a cleared value becomes `Null` in the patch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Tracked<T> {
+    value: Option<T>,
+    changed: bool,
+}
+
+impl<T> Tracked<T> {
+    /// Starts tracking a set value.
+    pub const fn new(value: T) -> Self {
+        Self {
+            value: Some(value),
+            changed: false,
+        }
+    }
+
+    /// Starts tracking a nullable value, where `None` is cleared.
+    pub const fn from_option(value: Option<T>) -> Self {
+        Self {
+            value,
+            changed: false,
+        }
+    }
+
+    /// Returns the current value, or `None` if it is cleared.
+    pub fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Returns `true` if the value was set, modified or cleared.
+    pub fn is_changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Sets the value.
+    pub fn set(&mut self, value: T) {
+        self.value = Some(value);
+        self.changed = true;
+    }
+
+    /// Clears the value.
+    pub fn clear(&mut self) {
+        self.value = None;
+        self.changed = true;
+    }
+
+    /// Calls `f` with the current value and marks it changed. A cleared value is left
+    /// unchanged.
+    pub fn modify<F: FnOnce(&mut T)>(&mut self, f: F) {
+        if let Some(value) = &mut self.value {
+            f(value);
+            self.changed = true;
+        }
+    }
+
+    /// Sets the value if it differs from the current one, so that writing back an
+    /// unchanged value does not end up in the patch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::tracked::Tracked;
+    ///
+    /// let mut name = Tracked::new("Alice".to_string());
+    /// name.set_if_changed("Alice".to_string());
+    /// assert_eq!(name.into_patch(), Presence::Absent);
+    /// ```
+    pub fn set_if_changed(&mut self, value: T)
+    where
+        T: PartialEq,
+    {
+        if self.value.as_ref() != Some(&value) {
+            self.set(value);
+        }
+    }
+
+    /// Returns the current value as a `Presence`, `Null` if it is cleared.
+    pub fn current(&self) -> Presence<&T> {
+        match &self.value {
+            Some(value) => Presence::Some(value),
+            None => Presence::Null,
+        }
+    }
+
+    /// Converts into the patch for this value: `Absent` if it was never changed, and its
+    /// current state otherwise.
+    pub fn into_patch(self) -> Presence<T> {
+        match (self.changed, self.value) {
+            (false, _) => Presence::Absent,
+            (true, Some(value)) => Presence::Some(value),
+            (true, None) => Presence::Null,
+        }
+    }
+
+    /// Returns the patch for this value without consuming it.
+    pub fn patch(&self) -> Presence<&T> {
+        if self.changed {
+            self.current()
+        } else {
+            Presence::Absent
+        }
+    }
+}
+
+impl<T> From<T> for Tracked<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T> IntoPatch for Tracked<T> {
+    type Patch = Presence<T>;
+
+    fn into_patch(self) -> Presence<T> {
+        Tracked::into_patch(self)
+    }
+
+    fn is_changed(&self) -> bool {
+        Tracked::is_changed(self)
+    }
+}
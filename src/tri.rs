@@ -0,0 +1,216 @@
+//! Three-valued truth for rule engines.
+//!
+//! A `Presence<bool>` has four states, and its `Null` and `Absent` both mean "unknown" to a
+//! rule that only asks whether something holds. [`Tri`] is the dedicated truth type, with
+//! Kleene logic for the `!`, `&`, `|` and `^` operators:
+//!
+//! - `False & Unknown` is `False`, and `True | Unknown` is `True`
+//! - any other combination with `Unknown` is `Unknown`
+//!
+//! [`Tri::from_presence_lossy`] and `From<Presence<bool>>` fold the `Null` and `Absent` of
+//! a `Presence<bool>` into `Unknown`. Where the difference matters, [`Tri::from_defined`] keeps `Absent` apart, and
+//! [`Tri::into_presence_absent`] chooses which state `Unknown` converts back to.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use presence_rs::tri::Tri;
+//!
+//! let verified = Tri::from_presence_lossy(Presence::Some(true));
+//! let banned = Tri::from_presence_lossy(Presence::Null);
+//!
+//! assert_eq!(verified & !banned, Tri::Unknown);
+//! assert_eq!(verified | banned, Tri::True);
+//! assert_eq!((verified & !banned).into_presence_null(), Presence::Null);
+//! ```
+
+use crate::presence::Presence;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+/// A truth value that may be unknown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Tri {
+    /// Known to hold.
+    True,
+    /// Known not to hold.
+    False,
+    /// Not known.
+    #[default]
+    Unknown,
+}
+
+impl Tri {
+    /// Returns `true` if the value is [`Tri::True`].
+    pub fn is_true(self) -> bool {
+        self == Tri::True
+    }
+
+    /// Returns `true` if the value is [`Tri::False`].
+    pub fn is_false(self) -> bool {
+        self == Tri::False
+    }
+
+    /// Returns `true` if the value is [`Tri::Unknown`].
+    pub fn is_unknown(self) -> bool {
+        self == Tri::Unknown
+    }
+
+    /// Converts a `Presence<bool>`, with both `Null` and `Absent` as `Unknown`.
+    ///
+    /// The conversion loses the difference between `Null` and `Absent`; use
+    /// [`Tri::from_defined`] to keep it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::tri::Tri;
+    ///
+    /// assert_eq!(Tri::from_presence_lossy(Presence::Some(true)), Tri::True);
+    /// assert_eq!(Tri::from_presence_lossy(Presence::Null), Tri::Unknown);
+    /// assert_eq!(Tri::from_presence_lossy(Presence::Absent), Tri::Unknown);
+    /// ```
+    pub fn from_presence_lossy(presence: Presence<bool>) -> Self {
+        match presence {
+            Presence::Some(value) => value.into(),
+            Presence::Null | Presence::Absent => Tri::Unknown,
+        }
+    }
+
+    /// Converts a `Presence<bool>`, returning `None` if it is `Absent` so that a missing
+    /// input can be told apart from an unknown one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::tri::Tri;
+    ///
+    /// assert_eq!(Tri::from_defined(Presence::Some(false)), Some(Tri::False));
+    /// assert_eq!(Tri::from_defined(Presence::Null), Some(Tri::Unknown));
+    /// assert_eq!(Tri::from_defined(Presence::Absent), None);
+    /// ```
+    pub fn from_defined(presence: Presence<bool>) -> Option<Self> {
+        match presence {
+            Presence::Absent => None,
+            presence => Some(Tri::from_presence_lossy(presence)),
+        }
+    }
+
+    /// Converts into a `Presence<bool>`, with `Unknown` as `Null`.
+    ///
+    /// Equivalent to `Presence::<bool>::from(self)`.
+    pub fn into_presence_null(self) -> Presence<bool> {
+        self.into()
+    }
+
+    /// Converts into a `Presence<bool>`, with `Unknown` as `Absent`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use presence_rs::Presence;
+    /// use presence_rs::tri::Tri;
+    ///
+    /// assert_eq!(Tri::True.into_presence_absent(), Presence::Some(true));
+    /// assert_eq!(Tri::Unknown.into_presence_absent(), Presence::Absent);
+    /// ```
+    pub fn into_presence_absent(self) -> Presence<bool> {
+        match self {
+            Tri::Unknown => Presence::Absent,
+            known => known.into(),
+        }
+    }
+}
+
+impl From<bool> for Tri {
+    fn from(value: bool) -> Self {
+        if value { Tri::True } else { Tri::False }
+    }
+}
+
+impl From<Option<bool>> for Tri {
+    /// Converts an `Option<bool>`, with `None` as `Unknown`.
+    fn from(value: Option<bool>) -> Self {
+        value.map_or(Tri::Unknown, Tri::from)
+    }
+}
+
+impl From<Presence<bool>> for Tri {
+    /// Converts a `Presence<bool>`, with both `Null` and `Absent` as `Unknown`.
+    ///
+    /// Equivalent to [`Tri::from_presence_lossy`].
+    fn from(presence: Presence<bool>) -> Self {
+        Tri::from_presence_lossy(presence)
+    }
+}
+
+impl From<Tri> for Option<bool> {
+    /// Converts into an `Option<bool>`, with `Unknown` as `None`.
+    fn from(tri: Tri) -> Self {
+        match tri {
+            Tri::True => Some(true),
+            Tri::False => Some(false),
+            Tri::Unknown => None,
+        }
+    }
+}
+
+impl From<Tri> for Presence<bool> {
+    /// Converts into a `Presence<bool>`, with `Unknown` as `Null`.
+    fn from(tri: Tri) -> Self {
+        match tri {
+            Tri::True => Presence::Some(true),
+            Tri::False => Presence::Some(false),
+            Tri::Unknown => Presence::Null,
+        }
+    }
+}
+
+impl Not for Tri {
+    type Output = Tri;
+
+    fn not(self) -> Tri {
+        match self {
+            Tri::True => Tri::False,
+            Tri::False => Tri::True,
+            Tri::Unknown => Tri::Unknown,
+        }
+    }
+}
+
+impl BitAnd for Tri {
+    type Output = Tri;
+
+    fn bitand(self, rhs: Tri) -> Tri {
+        match (self, rhs) {
+            (Tri::False, _) | (_, Tri::False) => Tri::False,
+            (Tri::True, Tri::True) => Tri::True,
+            _ => Tri::Unknown,
+        }
+    }
+}
+
+impl BitOr for Tri {
+    type Output = Tri;
+
+    fn bitor(self, rhs: Tri) -> Tri {
+        match (self, rhs) {
+            (Tri::True, _) | (_, Tri::True) => Tri::True,
+            (Tri::False, Tri::False) => Tri::False,
+            _ => Tri::Unknown,
+        }
+    }
+}
+
+impl BitXor for Tri {
+    type Output = Tri;
+
+    fn bitxor(self, rhs: Tri) -> Tri {
+        match (self, rhs) {
+            (Tri::Unknown, _) | (_, Tri::Unknown) => Tri::Unknown,
+            (lhs, rhs) => Tri::from(lhs != rhs),
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! TypeScript type generation for [`Presence<T>`] via ts-rs.
+//!
+//! `Presence<T>` is exported as `T | null`. Whether the property may also be omitted is a
+//! property of the struct field rather than its type: ts-rs marks a field optional when
+//! it has both `#[serde(default)]` and `skip_serializing_if`, which is exactly how patch
+//! structs are annotated, so those fields become `field?: T | null`. For other fields,
+//! `#[ts(optional)]` can be used the same way as on an `Option<T>` field.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use serde::{Deserialize, Serialize};
+//! use ts_rs::TS;
+//!
+//! #[derive(Serialize, Deserialize, TS)]
+//! struct UserPatch {
+//!     id: u32,
+//!     #[serde(default, skip_serializing_if = "Presence::is_absent")]
+//!     nickname: Presence<String>,
+//! }
+//!
+//! assert_eq!(
+//!     UserPatch::decl(),
+//!     "type UserPatch = { id: number, nickname?: string | null, };",
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use ts_rs::{IsOption, TS, TypeVisitor};
+
+impl<T: TS> TS for Presence<T> {
+    type WithoutGenerics = Self;
+    // Unlike `Option`, `#[ts(optional)]` must not strip `null` from the type, since
+    // `Null` is a distinct state that still has to be sent.
+    type OptionInnerType = Self;
+
+    fn name() -> String {
+        format!("{} | null", T::name())
+    }
+
+    fn inline() -> String {
+        format!("{} | null", T::inline())
+    }
+
+    fn visit_dependencies(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_dependencies(v);
+    }
+
+    fn visit_generics(v: &mut impl TypeVisitor)
+    where
+        Self: 'static,
+    {
+        T::visit_generics(v);
+        v.visit::<T>();
+    }
+
+    fn decl() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+
+    fn decl_concrete() -> String {
+        panic!("{} cannot be declared", Self::name())
+    }
+
+    fn inline_flattened() -> String {
+        panic!("{} cannot be flattened", Self::name())
+    }
+}
+
+impl<T> IsOption for Presence<T> {}
@@ -0,0 +1,55 @@
+//! OpenAPI schema support for [`Presence<T>`] via utoipa.
+//!
+//! `Presence<T>` is described as `oneOf: [null, T]`. utoipa decides whether a property is
+//! `required` from its serde attributes, so annotate Presence fields with
+//! `#[serde(default)]` (which deserializing `Absent` needs anyway) or
+//! `skip_serializing_if = "Presence::is_absent"` to have them documented as optional.
+//!
+//! utoipa's derive builds the schemas of other generic types through its internal
+//! `ComposeSchema` trait, so point Presence fields at the [`PartialSchema`] impl with
+//! `#[schema(schema_with = Presence::<T>::schema)]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use presence_rs::Presence;
+//! use serde::Deserialize;
+//! use utoipa::{PartialSchema, ToSchema};
+//!
+//! #[derive(Deserialize, ToSchema)]
+//! struct UserPatch {
+//!     id: u64,
+//!     #[serde(default)]
+//!     #[schema(schema_with = Presence::<String>::schema)]
+//!     nickname: Presence<String>,
+//! }
+//!
+//! let schema = serde_json::to_value(UserPatch::schema()).unwrap();
+//! assert_eq!(schema["required"], serde_json::json!(["id"]));
+//! assert_eq!(
+//!     schema["properties"]["nickname"],
+//!     serde_json::json!({ "oneOf": [{ "type": "null" }, { "type": "string" }] }),
+//! );
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use utoipa::openapi::RefOr;
+use utoipa::openapi::schema::{ObjectBuilder, OneOfBuilder, Schema, Type};
+use utoipa::{PartialSchema, ToSchema};
+
+impl<T: PartialSchema> PartialSchema for Presence<T> {
+    fn schema() -> RefOr<Schema> {
+        OneOfBuilder::new()
+            .item(ObjectBuilder::new().schema_type(Type::Null))
+            .item(T::schema())
+            .into()
+    }
+}
+
+impl<T: ToSchema> ToSchema for Presence<T> {
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        T::schemas(schemas);
+    }
+}
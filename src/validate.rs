@@ -0,0 +1,86 @@
+//! Checking patch structs against field policies.
+//!
+//! API contracts often restrict which states a patch field may take: some fields must
+//! always be sent, and some may be left out but never cleared. [`ValidatePresence`]
+//! reports every field that breaks such a rule as a [`Violation`].
+//!
+//! With the `derive` feature, the rules are declared next to the fields:
+//!
+//! - `#[presence(required)]`: the field must not be `Absent`
+//! - `#[presence(non_null)]`: the field must not be `Null`
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use presence_rs::Presence;
+//! use presence_rs::validate::{ValidatePresence, Violation, ViolationKind};
+//!
+//! #[derive(ValidatePresence)]
+//! struct AccountPatch {
+//!     #[presence(required)]
+//!     etag: Presence<String>,
+//!     #[presence(non_null)]
+//!     email: Presence<String>,
+//!     phone: Presence<String>,
+//! }
+//!
+//! let patch = AccountPatch {
+//!     etag: Presence::Some("v7".into()),
+//!     email: Presence::Absent,
+//!     phone: Presence::Null,
+//! };
+//! assert!(patch.validate_presence().is_ok());
+//!
+//! let patch = AccountPatch {
+//!     etag: Presence::Absent,
+//!     email: Presence::Null,
+//!     phone: Presence::Null,
+//! };
+//! let violations = patch.validate_presence().unwrap_err();
+//! assert_eq!(violations[0], Violation { field: "etag", kind: ViolationKind::Absent });
+//! assert_eq!(violations[1].to_string(), "`email` must not be null");
+//! # }
+//! ```
+
+use std::fmt;
+
+#[cfg(feature = "derive")]
+pub use presence_rs_derive::ValidatePresence;
+
+/// A patch struct whose fields can be checked against a policy.
+///
+/// Usually derived; see the [module documentation](self).
+pub trait ValidatePresence {
+    /// Checks every field, returning all violations in field order.
+    fn validate_presence(&self) -> Result<(), Vec<Violation>>;
+}
+
+/// The state that broke a field's policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ViolationKind {
+    /// A required field is `Absent`.
+    Absent,
+    /// A non-nullable field is `Null`.
+    Null,
+}
+
+/// A field whose state is not allowed by its policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Violation {
+    /// The name of the field.
+    pub field: &'static str,
+    /// The state of the field.
+    pub kind: ViolationKind,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ViolationKind::Absent => write!(f, "`{}` is required", self.field),
+            ViolationKind::Null => write!(f, "`{}` must not be null", self.field),
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
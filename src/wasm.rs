@@ -0,0 +1,66 @@
+//! Conversions between [`Presence<T>`] and JavaScript values via wasm-bindgen.
+//!
+//! JavaScript already has the three states that `Presence<T>` models, so the mapping is
+//! direct:
+//!
+//! | JavaScript  | `Presence<T>`  |
+//! |-------------|----------------|
+//! | `undefined` | `Absent`       |
+//! | `null`      | `Null`         |
+//! | any value   | `Some(value)`  |
+//!
+//! `JsValue::from(presence)` converts to JavaScript, and [`Presence::from_js`] or
+//! [`Presence::try_from_js`] convert back.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use presence_rs::Presence;
+//! use wasm_bindgen::prelude::*;
+//!
+//! #[wasm_bindgen]
+//! pub fn rename(name: JsValue) -> Result<JsValue, JsValue> {
+//!     let name = Presence::<String>::try_from_js(name)?;
+//!     Ok(name.map(|name| name.to_uppercase()).into())
+//! }
+//! ```
+//!
+//! [`Presence<T>`]: crate::Presence
+
+use crate::presence::Presence;
+use wasm_bindgen::JsValue;
+
+impl<T: Into<JsValue>> From<Presence<T>> for JsValue {
+    /// Converts `Absent` to `undefined`, `Null` to `null` and `Some(value)` to `value`.
+    fn from(value: Presence<T>) -> Self {
+        match value {
+            Presence::Absent => JsValue::UNDEFINED,
+            Presence::Null => JsValue::NULL,
+            Presence::Some(value) => value.into(),
+        }
+    }
+}
+
+impl Presence<JsValue> {
+    /// Reads a JavaScript value: `undefined` is `Absent` and `null` is `Null`.
+    pub fn from_js(value: JsValue) -> Self {
+        if value.is_undefined() {
+            Presence::Absent
+        } else if value.is_null() {
+            Presence::Null
+        } else {
+            Presence::Some(value)
+        }
+    }
+}
+
+impl<T: TryFrom<JsValue>> Presence<T> {
+    /// Reads a JavaScript value and converts it to `T` when it is neither `undefined` nor
+    /// `null`.
+    ///
+    /// Fails with `T`'s conversion error, which for wasm-bindgen's own conversions is the
+    /// original value.
+    pub fn try_from_js(value: JsValue) -> Result<Self, T::Error> {
+        Presence::from_js(value).map(T::try_from).transpose()
+    }
+}
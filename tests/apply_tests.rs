@@ -0,0 +1,470 @@
+use presence_rs::apply::{ApplyPresence, DeepApply, DeepApplyInverse};
+use presence_rs::{Presence, apply_patch};
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn test_apply_to_option() {
+    let mut target = Some(1);
+    target.apply_presence(Presence::Absent);
+    assert_eq!(target, Some(1));
+
+    target.apply_presence(Presence::Some(2));
+    assert_eq!(target, Some(2));
+
+    target.apply_presence(Presence::Null);
+    assert_eq!(target, None);
+}
+
+#[test]
+fn test_apply_to_presence() {
+    let mut target = Presence::Some(1);
+    target.apply_presence(Presence::Absent);
+    assert_eq!(target, Presence::Some(1));
+
+    target.apply_presence(Presence::Null);
+    assert_eq!(target, Presence::Null);
+
+    target.apply_presence(Presence::Some(3));
+    assert_eq!(target, Presence::Some(3));
+}
+
+#[test]
+fn test_apply_to_plain_values_resets_on_null() {
+    let mut name = "Alice".to_string();
+    name.apply_presence(Presence::Null);
+    assert_eq!(name, "");
+
+    let mut flag = true;
+    flag.apply_presence(Presence::Null);
+    assert!(!flag);
+
+    let mut ratio = 0.5f64;
+    ratio.apply_presence(Presence::Absent);
+    assert_eq!(ratio, 0.5);
+}
+
+#[test]
+fn test_apply_to_collections() {
+    let mut list = vec![1, 2];
+    list.apply_presence(Presence::Some(vec![3]));
+    assert_eq!(list, [3]);
+    list.apply_presence(Presence::Null);
+    assert!(list.is_empty());
+
+    let mut map = HashMap::from([("a", 1)]);
+    map.apply_presence(Presence::Null);
+    assert!(map.is_empty());
+
+    let mut tree = BTreeMap::from([("a", 1)]);
+    tree.apply_presence(Presence::Absent);
+    assert_eq!(tree.len(), 1);
+}
+
+#[test]
+fn test_apply_patch_macro() {
+    #[derive(Debug, PartialEq)]
+    struct Profile {
+        name: String,
+        bio: Option<String>,
+        avatar: Presence<String>,
+        age: u32,
+    }
+
+    struct ProfilePatch {
+        name: Presence<String>,
+        bio: Presence<String>,
+        avatar: Presence<String>,
+        age: Presence<u32>,
+    }
+
+    let mut profile = Profile {
+        name: "Alice".into(),
+        bio: Some("hello".into()),
+        avatar: Presence::Some("a.png".into()),
+        age: 30,
+    };
+    let patch = ProfilePatch {
+        name: Presence::Some("Bob".into()),
+        bio: Presence::Null,
+        avatar: Presence::Null,
+        age: Presence::Absent,
+    };
+
+    apply_patch!(profile, patch; name, bio, avatar, age);
+
+    assert_eq!(
+        profile,
+        Profile {
+            name: "Bob".into(),
+            bio: None,
+            avatar: Presence::Null,
+            age: 30,
+        }
+    );
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Limits {
+    max: u32,
+    burst: Option<u32>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct LimitsPatch {
+    max: Presence<u32>,
+    burst: Presence<u32>,
+}
+
+impl From<Limits> for LimitsPatch {
+    fn from(limits: Limits) -> Self {
+        LimitsPatch {
+            max: Presence::Some(limits.max),
+            burst: limits.burst.map_or(Presence::Null, Presence::Some),
+        }
+    }
+}
+
+impl DeepApply<Limits> for LimitsPatch {
+    fn deep_apply(self, limits: &mut Limits) {
+        apply_patch!(limits, self; max, burst);
+    }
+}
+
+impl DeepApplyInverse<Limits> for LimitsPatch {
+    fn deep_apply_inverse(self, limits: &mut Limits) -> Self {
+        apply_patch!(limits, self => LimitsPatch; max, burst)
+    }
+}
+
+impl DeepApply<Option<Limits>> for LimitsPatch {
+    fn deep_apply(self, limits: &mut Option<Limits>) {
+        self.deep_apply(limits.get_or_insert_with(Default::default));
+    }
+}
+
+struct Plan {
+    name: String,
+    limits: Limits,
+    overrides: Option<Limits>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct PlanPatch {
+    name: Presence<String>,
+    limits: Presence<LimitsPatch>,
+    overrides: Presence<LimitsPatch>,
+}
+
+impl DeepApply<Plan> for PlanPatch {
+    fn deep_apply(self, plan: &mut Plan) {
+        apply_patch!(plan, self; name; nested limits, overrides);
+    }
+}
+
+fn plan() -> Plan {
+    Plan {
+        name: "pro".into(),
+        limits: Limits {
+            max: 10,
+            burst: Some(20),
+        },
+        overrides: None,
+    }
+}
+
+#[test]
+fn test_deep_apply_absent_leaves_subtree() {
+    let mut plan = plan();
+    PlanPatch {
+        name: Presence::Absent,
+        limits: Presence::Absent,
+        overrides: Presence::Absent,
+    }
+    .deep_apply(&mut plan);
+    assert_eq!(plan.name, "pro");
+    assert_eq!(
+        plan.limits,
+        Limits {
+            max: 10,
+            burst: Some(20)
+        }
+    );
+    assert_eq!(plan.overrides, None);
+}
+
+#[test]
+fn test_deep_apply_some_descends() {
+    let mut plan = plan();
+    PlanPatch {
+        name: Presence::Some("team".into()),
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Absent,
+            burst: Presence::Null,
+        }),
+        overrides: Presence::Some(LimitsPatch {
+            max: Presence::Some(5),
+            burst: Presence::Absent,
+        }),
+    }
+    .deep_apply(&mut plan);
+    assert_eq!(plan.name, "team");
+    assert_eq!(
+        plan.limits,
+        Limits {
+            max: 10,
+            burst: None
+        }
+    );
+    assert_eq!(
+        plan.overrides,
+        Some(Limits {
+            max: 5,
+            burst: None
+        })
+    );
+}
+
+#[test]
+fn test_deep_apply_null_clears_subtree() {
+    let mut plan = plan();
+    plan.overrides = Some(Limits {
+        max: 1,
+        burst: None,
+    });
+    PlanPatch {
+        name: Presence::Absent,
+        limits: Presence::Null,
+        overrides: Presence::Null,
+    }
+    .deep_apply(&mut plan);
+    assert_eq!(plan.limits, Limits::default());
+    assert_eq!(plan.overrides, None);
+}
+
+#[test]
+fn test_apply_patch_nested_only() {
+    let mut plan = plan();
+    let patch = PlanPatch {
+        name: Presence::Some("ignored".into()),
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Some(99),
+            burst: Presence::Absent,
+        }),
+        overrides: Presence::Absent,
+    };
+    apply_patch!(plan, patch; ; nested limits);
+    assert_eq!(plan.name, "pro");
+    assert_eq!(plan.limits.max, 99);
+}
+
+#[test]
+fn test_apply_presence_inverse() {
+    use presence_rs::apply::ApplyPresenceInverse;
+
+    let mut count = 3u32;
+    assert_eq!(
+        count.apply_presence_inverse(Presence::Absent),
+        Presence::Absent
+    );
+    assert_eq!(
+        count.apply_presence_inverse(Presence::Null),
+        Presence::Some(3)
+    );
+    assert_eq!(count, 0);
+    assert_eq!(
+        count.apply_presence_inverse(Presence::Null),
+        Presence::Absent
+    );
+    assert_eq!(
+        count.apply_presence_inverse(Presence::Some(0)),
+        Presence::Absent
+    );
+
+    let mut nickname: Option<String> = None;
+    assert_eq!(
+        nickname.apply_presence_inverse(Presence::Null),
+        Presence::Absent
+    );
+    assert_eq!(
+        nickname.apply_presence_inverse(Presence::Some("Al".into())),
+        Presence::Null
+    );
+    assert_eq!(nickname.as_deref(), Some("Al"));
+    assert_eq!(
+        nickname.apply_presence_inverse(Presence::Some("Al".into())),
+        Presence::Absent
+    );
+
+    let mut state: Presence<i32> = Presence::Absent;
+    assert_eq!(
+        state.apply_presence_inverse(Presence::Some(1)),
+        Presence::Null
+    );
+    assert_eq!(
+        state.apply_presence_inverse(Presence::Null),
+        Presence::Some(1)
+    );
+    assert_eq!(
+        state.apply_presence_inverse(Presence::Null),
+        Presence::Absent
+    );
+
+    let mut list = vec![1];
+    assert_eq!(
+        list.apply_presence_inverse(Presence::Null),
+        Presence::Some(vec![1])
+    );
+    assert!(list.is_empty());
+    assert_eq!(
+        list.apply_presence_inverse(Presence::Some(Vec::new())),
+        Presence::Absent
+    );
+
+    let mut counts = BTreeMap::from([("a", 1)]);
+    assert_eq!(
+        counts.apply_presence_inverse(Presence::Some(BTreeMap::from([("a", 1)]))),
+        Presence::Absent
+    );
+    assert_eq!(
+        counts.apply_presence_inverse(Presence::Null),
+        Presence::Some(BTreeMap::from([("a", 1)]))
+    );
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ProfilePatch {
+    name: Presence<String>,
+    nickname: Presence<String>,
+    age: Presence<u32>,
+}
+
+struct Profile {
+    name: String,
+    nickname: Option<String>,
+    age: u32,
+}
+
+#[test]
+fn test_apply_patch_returns_inverse() {
+    let mut profile = Profile {
+        name: "Alice".into(),
+        nickname: None,
+        age: 30,
+    };
+    let patch = ProfilePatch {
+        name: Presence::Absent,
+        nickname: Presence::Some("Al".into()),
+        age: Presence::Some(31),
+    };
+
+    let undo = apply_patch!(profile, patch => ProfilePatch; name, nickname);
+    assert_eq!(profile.nickname.as_deref(), Some("Al"));
+    assert_eq!(profile.age, 30);
+
+    let same = ProfilePatch {
+        name: Presence::Some("Alice".into()),
+        ..ProfilePatch::default()
+    };
+    assert_eq!(
+        apply_patch!(profile, same => ProfilePatch; name),
+        ProfilePatch::default()
+    );
+    assert_eq!(
+        undo,
+        ProfilePatch {
+            name: Presence::Absent,
+            nickname: Presence::Null,
+            age: Presence::Absent,
+        }
+    );
+
+    apply_patch!(profile, undo; name, nickname, age);
+    assert_eq!(profile.name, "Alice");
+    assert_eq!(profile.nickname, None);
+}
+
+#[test]
+fn test_apply_patch_returns_nested_inverse() {
+    let mut plan = plan();
+    let patch = PlanPatch {
+        name: Presence::Some("team".into()),
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Some(10),
+            burst: Presence::Null,
+        }),
+        ..PlanPatch::default()
+    };
+
+    let undo = apply_patch!(plan, patch => PlanPatch; name; nested limits);
+    assert_eq!(plan.name, "team");
+    assert_eq!(plan.limits.burst, None);
+    assert_eq!(
+        undo,
+        PlanPatch {
+            name: Presence::Some("pro".into()),
+            limits: Presence::Some(LimitsPatch {
+                max: Presence::Absent,
+                burst: Presence::Some(20),
+            }),
+            overrides: Presence::Absent,
+        }
+    );
+
+    undo.deep_apply(&mut plan);
+    assert_eq!(plan.name, "pro");
+    assert_eq!(plan.limits.burst, Some(20));
+}
+
+#[test]
+fn test_nested_inverse_restores_cleared_subtree() {
+    let mut plan = plan();
+    let clear = PlanPatch {
+        limits: Presence::Null,
+        ..PlanPatch::default()
+    };
+
+    let undo = apply_patch!(plan, clear => PlanPatch; ; nested limits);
+    assert_eq!(plan.limits, Limits::default());
+    assert_eq!(
+        undo.limits,
+        Presence::Some(LimitsPatch {
+            max: Presence::Some(10),
+            burst: Presence::Some(20),
+        })
+    );
+
+    undo.deep_apply(&mut plan);
+    assert_eq!(
+        plan.limits,
+        Limits {
+            max: 10,
+            burst: Some(20)
+        }
+    );
+}
+
+#[test]
+fn test_nested_inverse_is_absent_when_untouched() {
+    let mut plan = plan();
+    let same = PlanPatch {
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Some(10),
+            burst: Presence::Absent,
+        }),
+        ..PlanPatch::default()
+    };
+    assert_eq!(
+        apply_patch!(plan, same => PlanPatch; name; nested limits),
+        PlanPatch::default()
+    );
+
+    let mut limits = Limits::default();
+    assert_eq!(
+        Presence::<LimitsPatch>::Null.deep_apply_inverse(&mut limits),
+        Presence::Absent
+    );
+    assert_eq!(
+        Presence::<LimitsPatch>::Absent.deep_apply_inverse(&mut limits),
+        Presence::Absent
+    );
+}
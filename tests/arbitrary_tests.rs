@@ -0,0 +1,51 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+use presence_rs::Presence;
+
+#[test]
+fn test_states() {
+    let data = [0, 1, 2, 0x34, 0x12, 3, 0x78, 0x56];
+    let mut u = Unstructured::new(&data);
+
+    assert_eq!(
+        Presence::<u16>::arbitrary(&mut u).unwrap(),
+        Presence::Absent
+    );
+    assert_eq!(Presence::<u16>::arbitrary(&mut u).unwrap(), Presence::Null);
+    assert_eq!(
+        Presence::<u16>::arbitrary(&mut u).unwrap(),
+        Presence::Some(0x1234)
+    );
+    assert_eq!(
+        Presence::<u16>::arbitrary(&mut u).unwrap(),
+        Presence::Some(0x5678)
+    );
+}
+
+#[test]
+fn test_exhausted_input_is_absent() {
+    let mut u = Unstructured::new(&[]);
+    assert_eq!(
+        Presence::<u32>::arbitrary(&mut u).unwrap(),
+        Presence::Absent
+    );
+}
+
+#[test]
+fn test_all_states_are_generated() {
+    let data: Vec<u8> = (0..=255).collect();
+    let mut u = Unstructured::new(&data);
+    let values: Vec<Presence<u8>> = (0..64)
+        .map(|_| Presence::arbitrary(&mut u).unwrap())
+        .collect();
+
+    assert!(values.iter().any(Presence::is_absent));
+    assert!(values.iter().any(Presence::is_null));
+    assert!(values.iter().any(Presence::is_present));
+}
+
+#[test]
+fn test_size_hint() {
+    assert_eq!(<Presence<u32> as Arbitrary>::size_hint(0), (1, Some(5)));
+}
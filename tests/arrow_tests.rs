@@ -0,0 +1,44 @@
+#![cfg(feature = "arrow")]
+
+use arrow::array::{Array, BooleanArray, Float64Array, StringArray};
+use arrow::datatypes::{Float64Type, UInt8Type};
+use presence_rs::Presence;
+use presence_rs::arrow::{from_primitive, from_strings, to_primitive, to_strings};
+
+#[test]
+fn test_primitive_round_trip() {
+    let scores = vec![
+        Presence::Absent,
+        Presence::Some(1.5),
+        Presence::Null,
+        Presence::Some(-2.0),
+    ];
+    let (values, defined) = to_primitive::<Float64Type>(&scores);
+    assert_eq!(
+        values,
+        Float64Array::from(vec![None, Some(1.5), None, Some(-2.0)])
+    );
+    assert_eq!(values.null_count(), 2);
+    assert_eq!(defined, BooleanArray::from(vec![false, true, true, true]));
+    assert_eq!(from_primitive(&values, &defined).unwrap(), scores);
+}
+
+#[test]
+fn test_strings_round_trip() {
+    let names = vec![
+        Presence::Some("a".to_string()),
+        Presence::Null,
+        Presence::Absent,
+    ];
+    let (values, defined) = to_strings(&names);
+    assert_eq!(values, StringArray::from(vec![Some("a"), None, None]));
+    assert_eq!(defined, BooleanArray::from(vec![true, true, false]));
+    assert_eq!(from_strings(&values, &defined).unwrap(), names);
+}
+
+#[test]
+fn test_defined_flags_are_checked() {
+    let (values, _) = to_primitive::<UInt8Type>(&[Presence::Some(1), Presence::Null]);
+    assert!(from_primitive(&values, &BooleanArray::from(vec![true])).is_err());
+    assert!(from_primitive(&values, &BooleanArray::from(vec![Some(true), None])).is_err());
+}
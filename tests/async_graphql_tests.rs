@@ -0,0 +1,137 @@
+#![cfg(feature = "async-graphql")]
+
+use async_graphql::{
+    EmptyMutation, EmptySubscription, InputObject, MaybeUndefined, Object, Schema,
+};
+use presence_rs::Presence;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+#[derive(InputObject)]
+struct UserPatch {
+    name: Presence<String>,
+    age: Presence<i32>,
+}
+
+fn describe(p: &Presence<impl std::fmt::Debug>) -> String {
+    match p {
+        Presence::Absent => "absent".to_string(),
+        Presence::Null => "null".to_string(),
+        Presence::Some(v) => format!("{:?}", v),
+    }
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn patch(&self, input: UserPatch) -> String {
+        format!("{} {}", describe(&input.name), describe(&input.age))
+    }
+
+    async fn arg(&self, value: Presence<i32>) -> String {
+        describe(&value)
+    }
+
+    async fn echo(&self, value: Presence<i32>) -> Presence<i32> {
+        value
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn run(query: &str) -> serde_json::Value {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let res = block_on(schema.execute(query));
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    res.data.into_json().unwrap()
+}
+
+#[test]
+fn test_input_object_fields() {
+    let data = run(r#"{ patch(input: { name: "Bob", age: null }) }"#);
+    assert_eq!(data["patch"], r#""Bob" null"#);
+
+    let data = run(r#"{ patch(input: {}) }"#);
+    assert_eq!(data["patch"], "absent absent");
+}
+
+#[test]
+fn test_argument_states() {
+    let data = run(r#"{ a: arg(value: 1) b: arg(value: null) c: arg }"#);
+    assert_eq!(data["a"], "1");
+    assert_eq!(data["b"], "null");
+    assert_eq!(data["c"], "absent");
+}
+
+#[test]
+fn test_variables() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let query = "query($v: Int) { arg(value: $v) }";
+
+    let req = async_graphql::Request::new(query).variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "v": null }),
+    ));
+    let data = block_on(schema.execute(req)).data.into_json().unwrap();
+    assert_eq!(data["arg"], "null");
+
+    let req = async_graphql::Request::new(query).variables(async_graphql::Variables::from_json(
+        serde_json::json!({ "v": 3 }),
+    ));
+    let data = block_on(schema.execute(req)).data.into_json().unwrap();
+    assert_eq!(data["arg"], "3");
+}
+
+#[test]
+fn test_output() {
+    let data = run(r#"{ a: echo(value: 5) b: echo(value: null) c: echo }"#);
+    assert_eq!(data["a"], 5);
+    assert_eq!(data["b"], serde_json::Value::Null);
+    assert_eq!(data["c"], serde_json::Value::Null);
+}
+
+#[test]
+fn test_schema_types_are_nullable() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let sdl = schema.sdl();
+    assert!(sdl.contains("name: String\n"));
+    assert!(sdl.contains("echo(value: Int): Int\n"));
+}
+
+#[test]
+fn test_maybe_undefined_round_trip() {
+    for presence in [Presence::Absent, Presence::Null, Presence::Some(7)] {
+        let maybe: MaybeUndefined<i32> = presence.into();
+        assert_eq!(Presence::from(maybe), presence);
+    }
+
+    for maybe in [
+        MaybeUndefined::Undefined,
+        MaybeUndefined::Null,
+        MaybeUndefined::Value("x".to_string()),
+    ] {
+        let presence: Presence<String> = maybe.clone().into();
+        assert_eq!(MaybeUndefined::from(presence), maybe);
+    }
+}
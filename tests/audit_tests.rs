@@ -0,0 +1,122 @@
+#![cfg(all(feature = "derive", feature = "json"))]
+
+use presence_rs::Presence;
+use presence_rs::audit::{
+    AuditEntry, Operation, audit_entries, audit_entries_with_prior, audit_json,
+};
+use presence_rs::reflect::VisitPresence;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+#[derive(Default, Serialize, VisitPresence)]
+struct LimitsPatch {
+    max: Presence<u32>,
+}
+
+#[derive(Default, Serialize, VisitPresence)]
+struct AccountPatch {
+    name: Presence<String>,
+    #[presence(redact)]
+    password: Presence<String>,
+    #[presence(nested)]
+    limits: Presence<LimitsPatch>,
+}
+
+fn entry(path: &str, operation: Operation, old: Option<Value>, new: Option<Value>) -> AuditEntry {
+    AuditEntry {
+        path: path.into(),
+        operation,
+        old,
+        new,
+    }
+}
+
+#[test]
+fn test_audit_entries() {
+    let patch = AccountPatch {
+        name: Presence::Absent,
+        password: Presence::Some("hunter2".into()),
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Some(5),
+        }),
+    };
+    assert_eq!(
+        audit_entries(&patch).unwrap(),
+        [
+            entry("password", Operation::Set, None, Some(json!("<redacted>"))),
+            entry("limits/max", Operation::Set, None, Some(json!(5))),
+        ]
+    );
+
+    let cleared = AccountPatch {
+        limits: Presence::Null,
+        ..Default::default()
+    };
+    assert_eq!(
+        audit_entries(&cleared).unwrap(),
+        [entry("limits", Operation::Clear, None, None)]
+    );
+    assert!(audit_entries(&AccountPatch::default()).unwrap().is_empty());
+}
+
+#[test]
+fn test_audit_entries_with_prior() {
+    let patch = AccountPatch {
+        name: Presence::Null,
+        password: Presence::Absent,
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Some(10),
+        }),
+    };
+    let prior = AccountPatch {
+        name: Presence::Some("Bob".into()),
+        password: Presence::Absent,
+        limits: Presence::Some(LimitsPatch {
+            max: Presence::Null,
+        }),
+    };
+    assert_eq!(
+        audit_entries_with_prior(&patch, &prior).unwrap(),
+        [
+            entry("name", Operation::Clear, Some(json!("Bob")), None),
+            entry("limits/max", Operation::Set, None, Some(json!(10))),
+        ]
+    );
+}
+
+#[test]
+fn test_operation_display() {
+    assert_eq!(Operation::Set.to_string(), "set");
+    assert_eq!(Operation::Clear.as_str(), "clear");
+}
+
+#[test]
+fn test_audit_json() {
+    let patch = AccountPatch {
+        name: Presence::Some("Al".into()),
+        password: Presence::Null,
+        ..Default::default()
+    };
+    assert_eq!(
+        audit_json(&audit_entries(&patch).unwrap()),
+        json!([
+            { "path": "name", "op": "set", "old": null, "new": "Al" },
+            { "path": "password", "op": "clear", "old": null, "new": null },
+        ])
+    );
+}
+
+#[test]
+fn test_audit_renamed_field_errors() {
+    #[derive(Serialize, VisitPresence)]
+    struct RenamedPatch {
+        #[serde(rename = "displayName")]
+        display_name: Presence<String>,
+    }
+
+    let patch = RenamedPatch {
+        display_name: Presence::Some("Al".into()),
+    };
+    let error = audit_entries(&patch).unwrap_err();
+    assert!(error.to_string().contains("display_name"));
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "axum")]
+
+use axum::body::{Body, to_bytes};
+use axum::extract::FromRequest;
+use axum::http::{Request, StatusCode, header};
+use axum::response::IntoResponse;
+use presence_rs::Presence;
+use presence_rs::axum::{FieldError, PresenceJson, PresenceJsonRejection};
+use presence_rs::validate::{ValidatePresence, Violation, ViolationKind};
+use serde::Deserialize;
+use serde_json::json;
+use std::future::Future;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+#[derive(Debug, Deserialize)]
+struct UserPatch {
+    #[serde(default)]
+    id: Presence<u64>,
+    #[serde(default)]
+    name: Presence<String>,
+    #[serde(default)]
+    avatar: Presence<String>,
+}
+
+impl ValidatePresence for UserPatch {
+    fn validate_presence(&self) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+        if self.id.is_absent() {
+            violations.push(Violation {
+                field: "id",
+                kind: ViolationKind::Absent,
+            });
+        }
+        for (field, null) in [("id", self.id.is_null()), ("name", self.name.is_null())] {
+            if null {
+                violations.push(Violation {
+                    field,
+                    kind: ViolationKind::Null,
+                });
+            }
+        }
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenPatch {
+    #[serde(default)]
+    note: Presence<String>,
+}
+
+impl ValidatePresence for OpenPatch {
+    fn validate_presence(&self) -> Result<(), Vec<Violation>> {
+        Ok(())
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn extract<T>(body: &str) -> Result<PresenceJson<T>, PresenceJsonRejection>
+where
+    PresenceJson<T>: FromRequest<(), Rejection = PresenceJsonRejection>,
+{
+    let req = Request::builder()
+        .method("PATCH")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    block_on(PresenceJson::<T>::from_request(req, &()))
+}
+
+fn invalid<T: std::fmt::Debug>(
+    result: Result<PresenceJson<T>, PresenceJsonRejection>,
+) -> Vec<FieldError> {
+    match result {
+        Err(PresenceJsonRejection::Invalid(errors)) => errors,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_valid_patch() {
+    let PresenceJson(patch) = extract::<UserPatch>(r#"{"id": 1, "avatar": null}"#).unwrap();
+    assert_eq!(patch.id, Presence::Some(1));
+    assert_eq!(patch.name, Presence::Absent);
+    assert_eq!(patch.avatar, Presence::Null);
+}
+
+#[test]
+fn test_validation_violations() {
+    let errors = invalid(extract::<UserPatch>(r#"{"name": null}"#));
+    assert_eq!(
+        errors,
+        [
+            FieldError {
+                field: Some("id".into()),
+                message: "is required".into()
+            },
+            FieldError {
+                field: Some("name".into()),
+                message: "must not be null".into()
+            },
+        ]
+    );
+
+    let errors = invalid(extract::<UserPatch>(r#"{"id": null}"#));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message, "must not be null");
+}
+
+#[test]
+fn test_type_error_names_field() {
+    let errors = invalid(extract::<UserPatch>(r#"{"id": 1, "name": 5}"#));
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].field.as_deref(), Some("name"));
+}
+
+#[test]
+fn test_no_rules_allow_null() {
+    let PresenceJson(patch) = extract::<OpenPatch>(r#"{"note": null}"#).unwrap();
+    assert_eq!(patch.note, Presence::Null);
+}
+
+#[test]
+fn test_unprocessable_response() {
+    let rejection = extract::<UserPatch>(r#"{"id": 1, "name": null}"#).unwrap_err();
+    assert_eq!(rejection.to_string(), "name: must not be null");
+
+    let response = rejection.into_response();
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body = block_on(to_bytes(response.into_body(), usize::MAX)).unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(
+        body,
+        json!({ "errors": [{ "field": "name", "message": "must not be null" }] })
+    );
+}
+
+#[test]
+fn test_malformed_json_uses_axum_rejection() {
+    let rejection = extract::<UserPatch>("{").unwrap_err();
+    assert!(matches!(rejection, PresenceJsonRejection::Json(_)));
+    assert_eq!(rejection.into_response().status(), StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "bson")]
+
+use bson::doc;
+use presence_rs::Presence;
+use presence_rs::bson::{NullHandling, update_document, update_document_with};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Address {
+    city: String,
+}
+
+#[derive(Serialize)]
+struct UserPatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    age: Presence<i32>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    address: Presence<Address>,
+}
+
+#[test]
+fn test_some_fields_are_set() {
+    let patch = UserPatch {
+        name: Presence::Some("Alice".into()),
+        age: Presence::Some(30),
+        address: Presence::Some(Address {
+            city: "Kyiv".into(),
+        }),
+    };
+    assert_eq!(
+        update_document(&patch).unwrap(),
+        doc! { "$set": { "name": "Alice", "age": 30, "address": { "city": "Kyiv" } } }
+    );
+}
+
+#[test]
+fn test_absent_fields_are_omitted() {
+    let patch = UserPatch {
+        name: Presence::Absent,
+        age: Presence::Some(30),
+        address: Presence::Absent,
+    };
+    assert_eq!(
+        update_document(&patch).unwrap(),
+        doc! { "$set": { "age": 30 } }
+    );
+}
+
+#[test]
+fn test_all_absent_is_empty() {
+    let patch = UserPatch {
+        name: Presence::Absent,
+        age: Presence::Absent,
+        address: Presence::Absent,
+    };
+    assert!(update_document(&patch).unwrap().is_empty());
+}
+
+#[test]
+fn test_null_set_by_default() {
+    let patch = UserPatch {
+        name: Presence::Null,
+        age: Presence::Absent,
+        address: Presence::Null,
+    };
+    assert_eq!(
+        update_document(&patch).unwrap(),
+        doc! { "$set": { "name": null, "address": null } }
+    );
+}
+
+#[test]
+fn test_null_unset() {
+    let patch = UserPatch {
+        name: Presence::Some("Bob".into()),
+        age: Presence::Null,
+        address: Presence::Null,
+    };
+    assert_eq!(
+        update_document_with(&patch, NullHandling::Unset).unwrap(),
+        doc! { "$set": { "name": "Bob" }, "$unset": { "age": "", "address": "" } }
+    );
+}
+
+#[test]
+fn test_non_struct_is_error() {
+    assert!(update_document(&42).is_err());
+}
@@ -0,0 +1,71 @@
+use presence_rs::Presence;
+use presence_rs::builder::BuilderValue;
+
+// The shape of a typed-builder setter with `setter(transform = ...)`.
+#[derive(Default)]
+struct PatchBuilder {
+    name: Presence<String>,
+    nickname: Presence<Option<String>>,
+}
+
+impl PatchBuilder {
+    fn name(mut self, value: impl BuilderValue<String>) -> Self {
+        self.name = value.into_field();
+        self
+    }
+
+    fn nickname(mut self, value: impl BuilderValue<Option<String>>) -> Self {
+        self.nickname = value.into_field();
+        self
+    }
+}
+
+#[test]
+fn test_builder_value_from_value() {
+    let builder = PatchBuilder::default().name("Ann".to_string());
+    assert_eq!(builder.name, Presence::Some("Ann".to_string()));
+    assert_eq!(builder.nickname, Presence::Absent);
+}
+
+#[test]
+fn test_builder_value_from_option() {
+    let builder = PatchBuilder::default().name(Some("Ann".to_string()));
+    assert_eq!(builder.name, Presence::Some("Ann".to_string()));
+
+    let builder = PatchBuilder::default().name(None);
+    assert_eq!(builder.name, Presence::Null);
+}
+
+#[test]
+fn test_builder_value_nested_option() {
+    let builder = PatchBuilder::default().nickname(None::<String>);
+    assert_eq!(builder.nickname, Presence::Some(None));
+
+    let builder = PatchBuilder::default().nickname(None::<Option<String>>);
+    assert_eq!(builder.nickname, Presence::Null);
+}
+
+#[derive(typed_builder::TypedBuilder)]
+struct UserPatch {
+    #[builder(default, setter(transform = |value: impl BuilderValue<String>| value.into_field()))]
+    name: Presence<String>,
+    #[builder(default, setter(transform = |value: impl BuilderValue<u32>| value.into_field()))]
+    age: Presence<u32>,
+    #[builder(default, setter(transform = |value: impl BuilderValue<String>| value.into_field()))]
+    bio: Presence<String>,
+}
+
+#[test]
+fn test_typed_builder_setters() {
+    let patch = UserPatch::builder()
+        .name("Ann".to_string())
+        .age(None)
+        .build();
+    assert_eq!(patch.name, Presence::Some("Ann".to_string()));
+    assert_eq!(patch.age, Presence::Null);
+    assert_eq!(patch.bio, Presence::Absent);
+
+    let patch = UserPatch::builder().bio(Some("Hi".to_string())).build();
+    assert_eq!(patch.name, Presence::Absent);
+    assert_eq!(patch.bio, Presence::Some("Hi".to_string()));
+}
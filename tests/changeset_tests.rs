@@ -0,0 +1,105 @@
+#![cfg(feature = "json")]
+
+use presence_rs::Presence;
+use presence_rs::audit::Operation;
+use presence_rs::changeset::{Change, ChangeSet};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Customer {
+    name: String,
+    nickname: Option<String>,
+    address: Address,
+}
+
+fn customer() -> Customer {
+    Customer {
+        name: "Ann".into(),
+        nickname: Some("annie".into()),
+        address: Address {
+            city: "Lviv".into(),
+            zip: None,
+        },
+    }
+}
+
+#[test]
+fn test_diff_nested_struct() {
+    let old = customer();
+    let mut new = customer();
+    new.nickname = None;
+    new.address.city = "Kyiv".into();
+    new.address.zip = Some("01001".into());
+
+    let changes = ChangeSet::diff(&old, &new).unwrap();
+    assert_eq!(
+        changes.patch(),
+        &Presence::Some(json!({
+            "nickname": null,
+            "address": { "city": "Kyiv", "zip": "01001" },
+        }))
+    );
+    assert_eq!(
+        changes.changes(),
+        [
+            Change {
+                path: "address/city".into(),
+                operation: Operation::Set,
+                old: Presence::Some(json!("Lviv")),
+                new: Presence::Some(json!("Kyiv")),
+            },
+            Change {
+                path: "address/zip".into(),
+                operation: Operation::Set,
+                old: Presence::Null,
+                new: Presence::Some(json!("01001")),
+            },
+            Change {
+                path: "nickname".into(),
+                operation: Operation::Clear,
+                old: Presence::Some(json!("annie")),
+                new: Presence::Null,
+            },
+        ]
+    );
+    assert_eq!(changes.apply(&old).unwrap().address, new.address);
+}
+
+#[test]
+fn test_diff_unchanged() {
+    let changes = ChangeSet::diff(&customer(), &customer()).unwrap();
+    assert!(changes.is_empty());
+    assert_eq!(changes.into_patch(), Presence::Absent);
+}
+
+#[test]
+fn test_removed_and_escaped_keys() {
+    let old = json!({ "a/b": 1, "gone": [1], "was_null": null, "c~d": { "x": null } });
+    let new = json!({ "a/b": 2, "c~d": {} });
+
+    let changes = ChangeSet::<Value>::from_values(&old, &new);
+    assert_eq!(changes.paths().collect::<Vec<_>>(), ["a~1b", "gone"]);
+    assert_eq!(changes.changes()[1].new, Presence::Absent);
+    assert_eq!(
+        changes.patch(),
+        &Presence::Some(json!({ "a/b": 2, "gone": null }))
+    );
+}
+
+#[test]
+fn test_diff_replaced_root() {
+    let changes = ChangeSet::<Value>::from_values(&json!({ "a": 1 }), &json!([1]));
+    assert_eq!(changes.patch(), &Presence::Some(json!([1])));
+    assert_eq!(changes.paths().collect::<Vec<_>>(), [""]);
+
+    let changes = ChangeSet::<Value>::from_values(&json!(1), &Value::Null);
+    assert_eq!(changes.patch(), &Presence::Null);
+    assert_eq!(changes.changes()[0].operation, Operation::Clear);
+}
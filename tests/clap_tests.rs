@@ -0,0 +1,76 @@
+#![cfg(feature = "clap")]
+
+use clap::builder::{BoolValueParser, TypedValueParser};
+use clap::{Arg, ArgAction, Command, Parser, value_parser};
+use presence_rs::Presence;
+use presence_rs::clap::{PresenceValueParser, get_presence, presence_parser, with_clear_flag};
+
+#[derive(Parser, Debug)]
+struct Patch {
+    #[arg(long, value_parser = presence_parser::<String>())]
+    name: Option<Presence<String>>,
+    #[arg(long, value_parser = presence_parser::<u8>())]
+    level: Option<Presence<u8>>,
+    #[arg(long, value_parser = presence_parser::<String>())]
+    tag: Option<Presence<String>>,
+    #[arg(long, conflicts_with = "tag")]
+    no_tag: bool,
+}
+
+#[test]
+fn test_derive_three_states() {
+    let args = Patch::try_parse_from(["patch", "--name", "Bob", "--level", "null"]).unwrap();
+    assert_eq!(args.name.unwrap_or_default(), Presence::Some("Bob".into()));
+    assert_eq!(args.level.unwrap_or_default(), Presence::Null);
+    assert_eq!(with_clear_flag(args.tag, args.no_tag), Presence::Absent);
+}
+
+#[test]
+fn test_derive_clear_flag() {
+    let args = Patch::try_parse_from(["patch", "--no-tag"]).unwrap();
+    assert_eq!(with_clear_flag(args.tag, args.no_tag), Presence::Null);
+
+    assert!(Patch::try_parse_from(["patch", "--tag", "x", "--no-tag"]).is_err());
+}
+
+#[test]
+fn test_invalid_value() {
+    let err = Patch::try_parse_from(["patch", "--level", "high"]).unwrap_err();
+    assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+}
+
+#[test]
+fn test_builder_with_custom_token() {
+    let cmd = Command::new("app").arg(
+        Arg::new("mode")
+            .long("mode")
+            .action(ArgAction::Set)
+            .value_parser(PresenceValueParser::new(value_parser!(u16)).null_token("-")),
+    );
+
+    let matches = cmd
+        .clone()
+        .try_get_matches_from(["app", "--mode", "-"])
+        .unwrap();
+    assert_eq!(get_presence::<u16>(&matches, "mode"), Presence::Null);
+
+    let matches = cmd
+        .clone()
+        .try_get_matches_from(["app", "--mode", "8"])
+        .unwrap();
+    assert_eq!(get_presence::<u16>(&matches, "mode"), Presence::Some(8));
+
+    let matches = cmd.try_get_matches_from(["app"]).unwrap();
+    assert_eq!(get_presence::<u16>(&matches, "mode"), Presence::Absent);
+}
+
+#[test]
+fn test_possible_values_include_null_token() {
+    let parser = PresenceValueParser::new(BoolValueParser::new());
+    let values: Vec<String> = parser
+        .possible_values()
+        .unwrap()
+        .map(|value| value.get_name().to_string())
+        .collect();
+    assert_eq!(values, ["true", "false", "null"]);
+}
@@ -0,0 +1,75 @@
+// This file is @generated by presence_rs::codegen.
+
+#[derive(Clone, Debug, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct UserPatch {
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub name: ::presence_rs::Presence<::std::string::String>,
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub bio: ::presence_rs::Presence<::std::string::String>,
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub r#type: ::presence_rs::Presence<::std::string::String>,
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub tags: ::presence_rs::Presence<::std::vec::Vec<::std::string::String>>,
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub address: ::presence_rs::Presence<super::Address>,
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub scores: ::presence_rs::Presence<::std::collections::HashMap<::std::string::String, i32>>,
+}
+
+impl UserPatch {
+    /// Returns a `FieldMask` with the defined fields of the patch.
+    pub fn field_mask(&self) -> ::prost_types::FieldMask {
+        let mut paths = ::std::vec::Vec::new();
+        if self.name.is_defined() {
+            paths.push("name".to_string());
+        }
+        if self.bio.is_defined() {
+            paths.push("bio".to_string());
+        }
+        if self.r#type.is_defined() {
+            paths.push("type".to_string());
+        }
+        if self.tags.is_defined() {
+            paths.push("tags".to_string());
+        }
+        if self.address.is_defined() {
+            paths.push("address".to_string());
+        }
+        if self.scores.is_defined() {
+            paths.push("scores".to_string());
+        }
+        ::prost_types::FieldMask { paths }
+    }
+
+    /// Writes the defined fields of the patch onto `message`.
+    pub fn apply_to(self, message: &mut super::User) {
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.name, self.name);
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.bio, self.bio);
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.r#type, self.r#type);
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.tags, self.tags);
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.address, self.address);
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.scores, self.scores);
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+pub struct AddressPatch {
+    #[serde(default, skip_serializing_if = "::presence_rs::Presence::is_absent")]
+    pub city: ::presence_rs::Presence<::std::string::String>,
+}
+
+impl AddressPatch {
+    /// Returns a `FieldMask` with the defined fields of the patch.
+    pub fn field_mask(&self) -> ::prost_types::FieldMask {
+        let mut paths = ::std::vec::Vec::new();
+        if self.city.is_defined() {
+            paths.push("city".to_string());
+        }
+        ::prost_types::FieldMask { paths }
+    }
+
+    /// Writes the defined fields of the patch onto `message`.
+    pub fn apply_to(self, message: &mut super::Address) {
+        ::presence_rs::apply::ApplyPresence::apply_presence(&mut message.city, self.city);
+    }
+}
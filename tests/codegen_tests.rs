@@ -0,0 +1,241 @@
+#![cfg(feature = "codegen")]
+
+use presence_rs::codegen::{PatchStruct, PresencePatches};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet, MessageOptions,
+    OneofDescriptorProto,
+};
+use std::io::ErrorKind;
+
+fn field(name: &str, number: i32, ty: Type) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.into()),
+        number: Some(number),
+        label: Some(Label::Optional.into()),
+        r#type: Some(ty.into()),
+        ..Default::default()
+    }
+}
+
+fn message_field(name: &str, number: i32, type_name: &str) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        type_name: Some(type_name.into()),
+        ..field(name, number, Type::Message)
+    }
+}
+
+// syntax = "proto3";
+// package shop;
+//
+// message User {
+//   string name = 1;
+//   optional string bio = 2;
+//   string type = 3;
+//   repeated string tags = 4;
+//   Address address = 5;
+//   map<string, int32> scores = 6;
+// }
+//
+// message Address { string city = 1; }
+fn shop() -> FileDescriptorSet {
+    let user = DescriptorProto {
+        name: Some("User".into()),
+        field: vec![
+            field("name", 1, Type::String),
+            FieldDescriptorProto {
+                oneof_index: Some(0),
+                proto3_optional: Some(true),
+                ..field("bio", 2, Type::String)
+            },
+            field("type", 3, Type::String),
+            FieldDescriptorProto {
+                label: Some(Label::Repeated.into()),
+                ..field("tags", 4, Type::String)
+            },
+            message_field("address", 5, ".shop.Address"),
+            FieldDescriptorProto {
+                label: Some(Label::Repeated.into()),
+                ..message_field("scores", 6, ".shop.User.ScoresEntry")
+            },
+        ],
+        nested_type: vec![DescriptorProto {
+            name: Some("ScoresEntry".into()),
+            field: vec![
+                field("key", 1, Type::String),
+                field("value", 2, Type::Int32),
+            ],
+            options: Some(MessageOptions {
+                map_entry: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }],
+        oneof_decl: vec![OneofDescriptorProto {
+            name: Some("_bio".into()),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let address = DescriptorProto {
+        name: Some("Address".into()),
+        field: vec![field("city", 1, Type::String)],
+        ..Default::default()
+    };
+    FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            name: Some("shop.proto".into()),
+            package: Some("shop".into()),
+            syntax: Some("proto3".into()),
+            message_type: vec![user, address],
+            ..Default::default()
+        }],
+    }
+}
+
+#[test]
+fn test_generate_matches_fixture() {
+    let files = PresencePatches::new()
+        .message(".shop.User")
+        .message(".shop.Address")
+        .generate(&shop())
+        .unwrap();
+    assert_eq!(
+        files,
+        [(
+            "shop.patch.rs".to_string(),
+            include_str!("codegen/shop.patch.rs").to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_generate_rejects_unknown_messages_and_oneofs() {
+    let error = PresencePatches::new()
+        .message(".shop.Order")
+        .generate(&shop())
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    let mut fds = shop();
+    let user = &mut fds.file[0].message_type[0];
+    user.field[0].oneof_index = Some(1);
+    user.oneof_decl.push(OneofDescriptorProto {
+        name: Some("contact".into()),
+        ..Default::default()
+    });
+    let error = PresencePatches::new()
+        .message(".shop.User")
+        .generate(&fds)
+        .unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_compile_fds_writes_prost_and_patch_modules() {
+    let out_dir = std::env::temp_dir().join(format!("presence_rs_codegen_{}", std::process::id()));
+    std::fs::create_dir_all(&out_dir).unwrap();
+
+    let mut config = prost_build::Config::new();
+    config.out_dir(&out_dir);
+    PresencePatches::new()
+        .message(".shop.User")
+        .out_dir(&out_dir)
+        .compile_fds(&mut config, shop())
+        .unwrap();
+
+    let prost = std::fs::read_to_string(out_dir.join("shop.rs")).unwrap();
+    assert!(prost.contains("Updated by `patch::UserPatch`."));
+    assert!(prost.contains("pub r#type: ::prost::alloc::string::String,"));
+    let patch = std::fs::read_to_string(out_dir.join("shop.patch.rs")).unwrap();
+    assert!(patch.contains("pub struct UserPatch {"));
+    std::fs::remove_dir_all(&out_dir).unwrap();
+}
+
+#[test]
+fn test_raw_identifiers_use_plain_mask_paths() {
+    let code = PatchStruct::new("ItemPatch")
+        .message("Item")
+        .field("r#type", "String")
+        .render();
+    assert!(code.contains("pub r#type: ::presence_rs::Presence<String>,"));
+    assert!(code.contains("paths.push(\"type\".to_string());"));
+    assert!(code.contains("apply_presence(&mut message.r#type, self.r#type);"));
+}
+
+#[test]
+fn test_render_without_message_has_no_methods() {
+    let code = PatchStruct::new("Empty").render();
+    assert_eq!(
+        code,
+        "#[derive(Clone, Debug, Default, PartialEq, ::serde::Serialize, ::serde::Deserialize)]\npub struct Empty {\n}\n"
+    );
+}
+
+#[cfg(feature = "serde")]
+mod shop {
+    use std::collections::HashMap;
+
+    // As generated by prost for `shop.proto` above.
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct User {
+        pub name: String,
+        pub bio: Option<String>,
+        pub r#type: String,
+        pub tags: Vec<String>,
+        pub address: Option<Address>,
+        pub scores: HashMap<String, i32>,
+    }
+
+    // With `config.type_attribute(".shop.Address", "#[derive(serde::Serialize, serde::Deserialize)]")`.
+    #[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+    pub struct Address {
+        pub city: String,
+    }
+
+    pub mod patch {
+        include!("codegen/shop.patch.rs");
+    }
+
+    #[test]
+    fn test_generated_field_mask_and_apply() {
+        use presence_rs::Presence;
+
+        let patch = patch::UserPatch {
+            bio: Presence::Null,
+            r#type: Presence::Some("admin".into()),
+            address: Presence::Some(Address {
+                city: "Oslo".into(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(patch.field_mask().paths, ["bio", "type", "address"]);
+
+        let mut user = User {
+            name: "Ann".into(),
+            bio: Some("hi".into()),
+            ..Default::default()
+        };
+        patch.apply_to(&mut user);
+        assert_eq!(
+            user,
+            User {
+                name: "Ann".into(),
+                bio: None,
+                r#type: "admin".into(),
+                address: Some(Address {
+                    city: "Oslo".into()
+                }),
+                ..Default::default()
+            }
+        );
+
+        let patch = patch::AddressPatch {
+            city: Presence::Some("Bergen".into()),
+        };
+        assert_eq!(patch.field_mask().paths, ["city"]);
+        let mut address = user.address.unwrap();
+        patch.apply_to(&mut address);
+        assert_eq!(address.city, "Bergen");
+    }
+}
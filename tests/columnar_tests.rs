@@ -0,0 +1,90 @@
+use presence_rs::Presence;
+use presence_rs::columnar::PresenceColumn;
+
+#[test]
+fn test_column_layout() {
+    let names = [
+        Presence::Null,
+        Presence::Some("a".to_string()),
+        Presence::Absent,
+    ];
+    let column = PresenceColumn::from(&names[..]);
+    assert_eq!(column.len(), 3);
+    assert_eq!(column.values(), ["", "a", ""]);
+    assert_eq!(column.validity(), [false, true, false]);
+    assert_eq!(column.defined(), [true, true, false]);
+    assert_eq!(
+        column.iter().collect::<Vec<_>>(),
+        names.iter().map(Presence::as_ref).collect::<Vec<_>>()
+    );
+    assert_eq!(column.get(3), None);
+}
+
+#[test]
+fn test_column_round_trip_through_parts() {
+    let values: Vec<Presence<i64>> = vec![Presence::Absent, Presence::Some(-1), Presence::Null];
+    let (buffer, validity, defined) = PresenceColumn::from(values.clone()).into_parts();
+    let column = PresenceColumn::from_parts(buffer, validity, defined);
+    assert_eq!(column.into_presences(), values);
+}
+
+#[test]
+fn test_column_undefined_slot_is_absent() {
+    let column = PresenceColumn::from_parts(vec![5], vec![true], vec![false]);
+    assert_eq!(column.get(0), Some(Presence::Absent));
+    assert_eq!(column.into_presences(), [Presence::Absent]);
+}
+
+#[test]
+#[should_panic(expected = "column parts must have the same length")]
+fn test_column_from_parts_length_mismatch() {
+    let _ = PresenceColumn::from_parts(vec![1, 2], vec![true], vec![true, true]);
+}
+
+#[test]
+fn test_column_collect_and_extend() {
+    let mut column: PresenceColumn<u8> = [Presence::Some(1)].into_iter().collect();
+    column.extend([Presence::Null]);
+    column.push(Presence::Absent);
+    assert!(!column.is_empty());
+    assert_eq!(
+        column.into_presences(),
+        [Presence::Some(1), Presence::Null, Presence::Absent]
+    );
+    assert!(PresenceColumn::<u8>::new().is_empty());
+}
+
+#[test]
+fn test_column_to_options() {
+    use presence_rs::columnar::{AbsentError, AbsentPolicy};
+
+    let column = PresenceColumn::from(vec![Presence::Null, Presence::Absent, Presence::Some(3)]);
+    assert_eq!(
+        column.to_options(AbsentPolicy::default()),
+        Ok(vec![None, None, Some(3)])
+    );
+    assert_eq!(
+        column.to_options(AbsentPolicy::Reject),
+        Err(AbsentError { row: 1 })
+    );
+    assert_eq!(AbsentError { row: 1 }.to_string(), "absent value at row 1");
+
+    let column = PresenceColumn::from_options(vec![Some(1), None]);
+    assert_eq!(column.into_presences(), [Presence::Some(1), Presence::Null]);
+}
+
+#[test]
+fn test_column_struct_fields_round_trip() {
+    let presences = vec![
+        Presence::Some("x".to_string()),
+        Presence::Absent,
+        Presence::Null,
+    ];
+    let (values, defined) = PresenceColumn::from(presences.clone()).to_struct_fields();
+    assert_eq!(values, [Some("x".to_string()), None, None]);
+    assert_eq!(defined, [true, false, true]);
+    assert_eq!(
+        PresenceColumn::from_struct_fields(values, defined).into_presences(),
+        presences
+    );
+}
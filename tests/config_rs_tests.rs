@@ -0,0 +1,53 @@
+#![cfg(all(feature = "config-rs", feature = "derive"))]
+
+use config::{Config, Value, ValueKind};
+use presence_rs::Presence;
+use presence_rs::config::Layers;
+use presence_rs::merge::MergeFrom;
+use serde::Deserialize;
+
+#[derive(Debug, Default, PartialEq, Deserialize, MergeFrom)]
+struct Settings {
+    #[serde(default)]
+    host: Presence<String>,
+    #[serde(default)]
+    port: Presence<u16>,
+    #[serde(default)]
+    proxy: Presence<String>,
+}
+
+#[test]
+fn test_config_layers() {
+    let defaults = Config::builder()
+        .set_default("host", "localhost")
+        .unwrap()
+        .set_default("port", 8080)
+        .unwrap()
+        .set_default("proxy", "http://proxy:3128")
+        .unwrap()
+        .build()
+        .unwrap();
+    let overrides = Config::builder()
+        .set_override("port", 9000)
+        .unwrap()
+        .set_override("proxy", Value::new(None, ValueKind::Nil))
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let settings = Layers::new("empty", Settings::default())
+        .with_config("defaults", defaults)
+        .unwrap()
+        .with_config("overrides", overrides)
+        .unwrap()
+        .resolve();
+
+    assert_eq!(
+        settings,
+        Settings {
+            host: Presence::Some("localhost".into()),
+            port: Presence::Some(9000),
+            proxy: Presence::Null,
+        }
+    );
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::Presence;
+use presence_rs::config::Layers;
+use presence_rs::merge::{MergeFrom, PreferOtherIfPresent};
+use presence_rs::reflect::PresenceFields;
+
+#[derive(Debug, Default, PartialEq, MergeFrom, PresenceFields)]
+struct Settings {
+    host: Presence<String>,
+    port: Presence<u16>,
+    #[presence(merge = PreferOtherIfPresent)]
+    token: Presence<String>,
+}
+
+fn defaults() -> Settings {
+    Settings {
+        host: Presence::Some("localhost".into()),
+        port: Presence::Some(8080),
+        token: Presence::Some("dev".into()),
+    }
+}
+
+#[test]
+fn test_absent_inherits_null_unsets_some_overrides() {
+    let layers = Layers::new("defaults", defaults())
+        .with(
+            "file",
+            Settings {
+                port: Presence::Some(9000),
+                ..Default::default()
+            },
+        )
+        .with(
+            "env",
+            Settings {
+                host: Presence::Null,
+                token: Presence::Null,
+                ..Default::default()
+            },
+        );
+    assert_eq!(layers.len(), 3);
+    assert_eq!(
+        layers.names().collect::<Vec<_>>(),
+        ["defaults", "file", "env"]
+    );
+
+    assert_eq!(
+        layers.resolve(),
+        Settings {
+            host: Presence::Null,
+            port: Presence::Some(9000),
+            token: Presence::Some("dev".into()),
+        }
+    );
+}
+
+#[test]
+fn test_origin() {
+    let mut layers = Layers::new("defaults", Settings::default());
+    layers.push(
+        "flags",
+        Settings {
+            port: Presence::Null,
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(layers.origin("port"), Some("flags"));
+    assert_eq!(layers.origin("host"), None);
+    assert_eq!(layers.origin("missing"), None);
+}
+
+#[test]
+fn test_single_layer() {
+    let layers = Layers::new("defaults", defaults());
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers.resolve(), defaults());
+}
@@ -145,3 +145,35 @@ fn test_round_trip_conversions() {
     let back = Presence::from_nullable(nullable);
     assert_eq!(original, back);
 }
+
+#[test]
+fn test_option_ext_conversions() {
+    use presence_rs::ext::{NestedOptionExt, OptionExt};
+
+    assert_eq!(Some("a").into_presence_absent(), Presence::Some("a"));
+    assert_eq!(None::<&str>.into_presence_absent(), Presence::Absent);
+    assert_eq!(Some("a").into_presence_null(), Presence::Some("a"));
+    assert_eq!(None::<&str>.into_presence_null(), Presence::Null);
+
+    assert_eq!(Some(Some(1)).into_presence(), Presence::Some(1));
+    assert_eq!(Some(None::<i32>).into_presence(), Presence::Null);
+    assert_eq!(None::<Option<i32>>.into_presence(), Presence::Absent);
+}
+
+#[test]
+fn test_option_ext_in_chains() {
+    use presence_rs::ext::{NestedOptionExt, OptionExt};
+
+    let values = [Some(Some(2)), Some(None), None];
+    let doubled: Vec<Presence<i32>> = values
+        .into_iter()
+        .map(|value| value.into_presence().map(|v| v * 2))
+        .collect();
+    assert_eq!(
+        doubled,
+        [Presence::Some(4), Presence::Null, Presence::Absent]
+    );
+
+    let parsed = "x".parse::<u8>().ok().into_presence_null();
+    assert_eq!(parsed, Presence::Null);
+}
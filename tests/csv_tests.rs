@@ -0,0 +1,157 @@
+#![cfg(feature = "csv")]
+
+use csv::StringRecord;
+use presence_rs::Presence;
+use presence_rs::text::Tokens;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Row {
+    id: u32,
+    #[serde(default)]
+    name: Presence<String>,
+    #[serde(default)]
+    score: Presence<i64>,
+}
+
+fn read_rows(data: &str, tokens: &Tokens) -> Vec<Row> {
+    let mut reader = csv::Reader::from_reader(data.as_bytes());
+    let headers = reader.headers().unwrap().clone();
+    reader
+        .records()
+        .map(|record| tokens.deserialize(&record.unwrap(), &headers).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_missing_column_is_absent() {
+    let rows = read_rows("id,name\n1,Alice\n", &Tokens::csv());
+    assert_eq!(rows[0].name, Presence::Some("Alice".to_string()));
+    assert_eq!(rows[0].score, Presence::Absent);
+}
+
+#[test]
+fn test_empty_cell_is_null() {
+    let rows = read_rows("id,name,score\n1,,\n", &Tokens::csv());
+    assert_eq!(rows[0].name, Presence::Null);
+    assert_eq!(rows[0].score, Presence::Null);
+}
+
+#[test]
+fn test_default_null_tokens() {
+    let rows = read_rows("id,name,score\n1,NULL,\\N\n", &Tokens::csv());
+    assert_eq!(rows[0].name, Presence::Null);
+    assert_eq!(rows[0].score, Presence::Null);
+}
+
+#[test]
+fn test_custom_null_tokens() {
+    let tokens = Tokens::none().null("").or_null("n/a");
+    let rows = read_rows("id,name,score\n1,n/a,\n", &tokens);
+    assert_eq!(rows[0].name, Presence::Null);
+    assert_eq!(rows[0].score, Presence::Null);
+
+    let rows = read_rows("id,name\n1,NULL\n", &tokens);
+    assert_eq!(rows[0].name, Presence::Some("NULL".to_string()));
+}
+
+#[test]
+fn test_deserialize_error() {
+    let headers = StringRecord::from(vec!["id", "score"]);
+    let record = StringRecord::from(vec!["1", "high"]);
+    let result: Result<Row, _> = Tokens::csv().deserialize(&record, &headers);
+    assert!(result.is_err());
+
+    let record = StringRecord::from(vec!["", "1"]);
+    let result: Result<Row, _> = Tokens::csv().deserialize(&record, &headers);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_deserialize_empty_as_value() {
+    let tokens = Tokens::none().null("NULL");
+    let rows = read_rows(
+        "id,name,score
+1,,NULL
+",
+        &tokens,
+    );
+    assert_eq!(rows[0].name, Presence::Some(String::new()));
+    assert_eq!(rows[0].score, Presence::Null);
+
+    let headers = StringRecord::from(vec!["id", "score"]);
+    let record = StringRecord::from(vec!["1", ""]);
+    let result: Result<Row, _> = tokens.deserialize(&record, &headers);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_short_record_is_absent() {
+    let headers = StringRecord::from(vec!["id", "name", "score"]);
+    let record = StringRecord::from(vec!["1", "Ann"]);
+    let tokens = Tokens::csv();
+
+    let row: Row = tokens.deserialize(&record, &headers).unwrap();
+    assert_eq!(row.score, Presence::Absent);
+    assert_eq!(
+        tokens.field::<i64>(&record, &headers, "score"),
+        Ok(Presence::Absent)
+    );
+}
+
+#[test]
+fn test_field_reads_three_states() {
+    let headers = StringRecord::from(vec!["id", "score"]);
+    let tokens = Tokens::csv();
+
+    let record = StringRecord::from(vec!["1", "10"]);
+    assert_eq!(
+        tokens.field::<i64>(&record, &headers, "score"),
+        Ok(Presence::Some(10))
+    );
+
+    let record = StringRecord::from(vec!["1", ""]);
+    assert_eq!(
+        tokens.field::<i64>(&record, &headers, "score"),
+        Ok(Presence::Null)
+    );
+
+    assert_eq!(
+        tokens.field::<i64>(&record, &headers, "rank"),
+        Ok(Presence::Absent)
+    );
+}
+
+#[test]
+fn test_field_empty_as_value() {
+    let headers = StringRecord::from(vec!["name"]);
+    let record = StringRecord::from(vec![""]);
+    let tokens = Tokens::none().null("NULL");
+
+    assert_eq!(
+        tokens.field::<String>(&record, &headers, "name"),
+        Ok(Presence::Some(String::new()))
+    );
+}
+
+#[test]
+fn test_normalize() {
+    let record = StringRecord::from(vec!["1", "NULL", "\\N", "x"]);
+    let normalized = Tokens::csv().normalize(&record);
+    assert_eq!(normalized, StringRecord::from(vec!["1", "", "", "x"]));
+}
+
+#[test]
+fn test_absent_token_cells_are_absent() {
+    let tokens = Tokens::csv().absent("-");
+    let rows = read_rows("id,name,score\n1,-,\n", &tokens);
+    assert_eq!(rows[0].name, Presence::Absent);
+    assert_eq!(rows[0].score, Presence::Null);
+
+    let headers = StringRecord::from(vec!["id", "score"]);
+    let record = StringRecord::from(vec!["1", "-"]);
+    assert_eq!(
+        tokens.field::<i64>(&record, &headers, "score"),
+        Ok(Presence::Absent)
+    );
+}
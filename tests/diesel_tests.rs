@@ -0,0 +1,244 @@
+#![cfg(feature = "diesel")]
+
+use diesel::deserialize::{self, QueryableByName};
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable, Text};
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use presence_rs::diesel::{assign, get_presence, sql_update};
+use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+use presence_rs::{Presence, presence_changeset};
+
+diesel::table! {
+    users {
+        id -> Integer,
+        name -> Nullable<Text>,
+        bio -> Nullable<Text>,
+        age -> Nullable<Integer>,
+    }
+}
+
+struct UserPatch {
+    name: Presence<String>,
+    bio: Presence<String>,
+    age: Presence<i32>,
+}
+
+fn connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, bio TEXT, age INTEGER)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    diesel::sql_query("INSERT INTO users VALUES (1, 'Alice', 'hello', 30)")
+        .execute(&mut conn)
+        .unwrap();
+    conn
+}
+
+type Row = (i32, Option<String>, Option<String>, Option<i32>);
+
+#[test]
+fn test_changeset_applies_presence_semantics() {
+    let mut conn = connection();
+    let patch = UserPatch {
+        name: Presence::Absent,
+        bio: Presence::Null,
+        age: Presence::Some(31),
+    };
+
+    diesel::update(users::table.find(1))
+        .set(presence_changeset!(users, patch; name, bio, age))
+        .execute(&mut conn)
+        .unwrap();
+
+    let row: Row = users::table.find(1).first(&mut conn).unwrap();
+    assert_eq!(row, (1, Some("Alice".into()), None, Some(31)));
+}
+
+#[test]
+fn test_assign_absent_is_skipped() {
+    assert!(assign(users::name, Presence::<String>::Absent).is_none());
+    assert!(assign(users::name, Presence::<String>::Null).is_some());
+    assert!(assign(users::name, Presence::Some("x".to_string())).is_some());
+}
+
+#[test]
+fn test_all_absent_is_an_error() {
+    let mut conn = connection();
+    let patch = UserPatch {
+        name: Presence::Absent,
+        bio: Presence::Absent,
+        age: Presence::Absent,
+    };
+
+    let result = diesel::update(users::table.find(1))
+        .set(presence_changeset!(users, patch; name, bio, age))
+        .execute(&mut conn);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_sql_update_binds_set_clause() {
+    let mut conn = connection();
+    let mut set = SetClause::<String>::new(Placeholder::Question).nulls(NullStyle::Literal);
+    set.set("name", Presence::<String>::Absent)
+        .set("bio", Presence::<String>::Null)
+        .set("name", Presence::Some("Bob".to_string()));
+
+    sql_update::<Sqlite, Text, _>("users", set)
+        .unwrap()
+        .sql(" WHERE id = ?")
+        .bind::<Integer, _>(1)
+        .execute(&mut conn)
+        .unwrap();
+
+    let row: Row = users::table.find(1).first(&mut conn).unwrap();
+    assert_eq!(row, (1, Some("Bob".into()), None, Some(30)));
+}
+
+#[test]
+fn test_sql_update_empty_set_clause() {
+    let set = SetClause::<String>::new(Placeholder::Question);
+    assert!(sql_update::<Sqlite, Text, _>("users", set).is_none());
+}
+
+#[test]
+fn test_load_nullable_column() {
+    let mut conn = connection();
+    diesel::sql_query("INSERT INTO users VALUES (2, NULL, NULL, NULL)")
+        .execute(&mut conn)
+        .unwrap();
+
+    let names: Vec<Presence<String>> = users::table
+        .select(users::name)
+        .order(users::id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(names, [Presence::Some("Alice".into()), Presence::Null]);
+}
+
+#[test]
+fn test_bind_presence_values() {
+    let mut conn = connection();
+    diesel::insert_into(users::table)
+        .values((
+            users::id.eq(2),
+            users::name.eq(Presence::Some("Bob".to_string())),
+            users::bio.eq(Presence::<String>::Null),
+            users::age.eq(&Presence::<i32>::Null),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+
+    let row: Row = users::table.find(2).first(&mut conn).unwrap();
+    assert_eq!(row, (2, Some("Bob".into()), None, None));
+
+    let found: Vec<i32> = users::table
+        .select(users::id)
+        .filter(users::name.eq(Presence::Some("Bob".to_string())))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(found, [2]);
+}
+
+#[test]
+fn test_bind_absent_is_an_error() {
+    let mut conn = connection();
+    let result = diesel::update(users::table.find(1))
+        .set(users::bio.eq(Presence::<String>::Absent))
+        .execute(&mut conn);
+    assert!(result.is_err());
+
+    let row: Row = users::table.find(1).first(&mut conn).unwrap();
+    assert_eq!(row.2, Some("hello".into()));
+}
+
+#[derive(QueryableByName, Debug, PartialEq)]
+struct NamedRow {
+    #[diesel(sql_type = diesel::sql_types::Nullable<diesel::sql_types::Text>)]
+    bio: Presence<String>,
+}
+
+#[test]
+fn test_queryable_by_name() {
+    let mut conn = connection();
+    let rows: Vec<NamedRow> =
+        diesel::sql_query("SELECT bio FROM users UNION ALL SELECT NULL AS bio")
+            .load(&mut conn)
+            .unwrap();
+    assert_eq!(
+        rows,
+        [
+            NamedRow {
+                bio: Presence::Some("hello".into())
+            },
+            NamedRow {
+                bio: Presence::Null
+            },
+        ]
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Profile {
+    name: Presence<String>,
+    bio: Presence<String>,
+}
+
+impl QueryableByName<Sqlite> for Profile {
+    fn build<'a>(row: &impl diesel::row::NamedRow<'a, Sqlite>) -> deserialize::Result<Self> {
+        Ok(Profile {
+            name: get_presence::<Nullable<Text>, _, _, _>(row, "name")?,
+            bio: get_presence::<Nullable<Text>, _, _, _>(row, "bio")?,
+        })
+    }
+}
+
+#[test]
+fn test_get_presence_reads_missing_columns_as_absent() {
+    let mut conn = connection();
+    let profiles: Vec<Profile> = diesel::sql_query("SELECT NULL AS bio FROM users")
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(
+        profiles,
+        [Profile {
+            name: Presence::Absent,
+            bio: Presence::Null,
+        }]
+    );
+
+    let profiles: Vec<Profile> = diesel::sql_query("SELECT name, bio FROM users")
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(profiles[0].name, Presence::Some("Alice".into()));
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+struct User {
+    id: i32,
+    name: Presence<String>,
+    bio: Presence<String>,
+    age: Presence<i32>,
+}
+
+#[test]
+fn test_queryable_struct() {
+    let mut conn = connection();
+    diesel::update(users::table.find(1))
+        .set(users::bio.eq(None::<String>))
+        .execute(&mut conn)
+        .unwrap();
+
+    let user: User = users::table.find(1).first(&mut conn).unwrap();
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: Presence::Some("Alice".into()),
+            bio: Presence::Null,
+            age: Presence::Some(30),
+        }
+    );
+}
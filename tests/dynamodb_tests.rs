@@ -0,0 +1,112 @@
+#![cfg(feature = "dynamodb")]
+
+use presence_rs::Presence;
+use presence_rs::dynamodb::{AttributeValue, Item, from_item, to_item};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Profile {
+    id: u64,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    tags: Presence<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    active: Presence<bool>,
+}
+
+#[test]
+fn test_to_item_maps_presence_to_attributes() {
+    let profile = Profile {
+        id: 7,
+        name: Presence::Null,
+        tags: Presence::Some(vec!["a".into()]),
+        active: Presence::Absent,
+    };
+    let item: HashMap<String, AttributeValue> = to_item(&profile).unwrap();
+    assert_eq!(
+        item,
+        HashMap::from([
+            ("id".to_string(), AttributeValue::N("7".into())),
+            ("name".to_string(), AttributeValue::Null(true)),
+            (
+                "tags".to_string(),
+                AttributeValue::L(vec![AttributeValue::S("a".into())])
+            ),
+        ])
+    );
+}
+
+#[test]
+fn test_item_round_trip() {
+    for profile in [
+        Profile {
+            id: 1,
+            name: Presence::Some("Ann".into()),
+            tags: Presence::Null,
+            active: Presence::Some(false),
+        },
+        Profile {
+            id: 2,
+            name: Presence::Absent,
+            tags: Presence::Absent,
+            active: Presence::Null,
+        },
+    ] {
+        let item: Item = to_item(&profile).unwrap();
+        assert_eq!(from_item::<_, Profile>(item).unwrap(), profile);
+    }
+}
+
+#[test]
+fn test_from_item_missing_and_null_attributes() {
+    let item = HashMap::from([
+        ("id".to_string(), AttributeValue::N("3".into())),
+        ("active".to_string(), AttributeValue::Null(true)),
+    ]);
+    let profile: Profile = from_item(item).unwrap();
+    assert_eq!(profile.name, Presence::Absent);
+    assert_eq!(profile.active, Presence::Null);
+}
+
+#[test]
+fn test_to_item_rejects_non_struct_and_serialized_absent() {
+    assert!(to_item::<_, Item>(&42).is_err());
+
+    #[derive(Serialize)]
+    struct Forgetful {
+        name: Presence<String>,
+    }
+    assert!(
+        to_item::<_, Item>(&Forgetful {
+            name: Presence::Absent
+        })
+        .is_err()
+    );
+    assert!(
+        to_item::<_, Item>(&Forgetful {
+            name: Presence::Null
+        })
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_numbers_keep_their_precision() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Counter {
+        #[serde(default, skip_serializing_if = "Presence::is_absent")]
+        total: Presence<u64>,
+    }
+
+    let item = HashMap::from([(
+        "total".to_string(),
+        AttributeValue::N("18446744073709551615".into()),
+    )]);
+    let counter: Counter = from_item(item.clone()).unwrap();
+    assert_eq!(counter.total, Presence::Some(u64::MAX));
+
+    let written: HashMap<String, AttributeValue> = to_item(&counter).unwrap();
+    assert_eq!(written, item);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "elasticsearch")]
+
+use presence_rs::Presence;
+use presence_rs::elasticsearch::{NullHandling, UPDATE_SCRIPT, update_body, update_body_with};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct ProductPatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    price: Presence<f64>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    tags: Presence<Vec<String>>,
+}
+
+#[test]
+fn test_update_body_includes_nulls() {
+    let patch = ProductPatch {
+        name: Presence::Absent,
+        price: Presence::Some(9.5),
+        tags: Presence::Null,
+    };
+    assert_eq!(
+        update_body(&patch).unwrap(),
+        json!({ "doc": { "price": 9.5, "tags": null } })
+    );
+}
+
+#[test]
+fn test_update_body_remove_without_nulls_is_partial_doc() {
+    let patch = ProductPatch {
+        name: Presence::Some("Lamp".into()),
+        price: Presence::Absent,
+        tags: Presence::Absent,
+    };
+    assert_eq!(
+        update_body_with(&patch, NullHandling::Remove).unwrap(),
+        json!({ "doc": { "name": "Lamp" } })
+    );
+}
+
+#[test]
+fn test_update_body_remove_uses_script() {
+    let patch = ProductPatch {
+        name: Presence::Null,
+        price: Presence::Absent,
+        tags: Presence::Null,
+    };
+    assert_eq!(
+        update_body_with(&patch, NullHandling::Remove).unwrap(),
+        json!({
+            "script": {
+                "lang": "painless",
+                "source": UPDATE_SCRIPT,
+                "params": { "set": {}, "remove": ["name", "tags"] },
+            }
+        })
+    );
+}
+
+#[test]
+fn test_update_body_empty_patch() {
+    let patch = ProductPatch {
+        name: Presence::Absent,
+        price: Presence::Absent,
+        tags: Presence::Absent,
+    };
+    assert_eq!(
+        update_body_with(&patch, NullHandling::Remove).unwrap(),
+        json!({ "doc": {} })
+    );
+}
+
+#[test]
+fn test_update_body_rejects_non_object() {
+    assert!(update_body(&[1, 2]).is_err());
+}
+
+#[test]
+fn test_update_body_rejects_serialized_absent() {
+    #[derive(Serialize)]
+    struct Careless {
+        name: Presence<String>,
+    }
+    let patch = Careless {
+        name: Presence::Absent,
+    };
+    assert!(update_body(&patch).is_err());
+}
@@ -0,0 +1,166 @@
+use presence_rs::Presence;
+use presence_rs::env::EnvError;
+use presence_rs::text::Tokens;
+use std::ffi::OsString;
+
+fn parse<T>(value: Option<&str>, tokens: Tokens) -> Result<Presence<T>, EnvError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    Presence::parse_env("VAR", value.map(OsString::from), &tokens)
+}
+
+#[test]
+fn test_unset_empty_and_value() {
+    assert_eq!(
+        parse::<u8>(None, Tokens::none().null("")),
+        Ok(Presence::Absent)
+    );
+    assert_eq!(
+        parse::<u8>(Some(""), Tokens::none().null("")),
+        Ok(Presence::Null)
+    );
+    assert_eq!(
+        parse::<u8>(Some("7"), Tokens::none().null("")),
+        Ok(Presence::Some(7))
+    );
+}
+
+#[test]
+fn test_tokens() {
+    assert_eq!(
+        parse::<u8>(Some(""), Tokens::none().absent("")),
+        Ok(Presence::Absent)
+    );
+    assert_eq!(
+        parse::<String>(Some(""), Tokens::none()),
+        Ok(Presence::Some(String::new()))
+    );
+    assert!(parse::<u8>(Some(""), Tokens::none()).is_err());
+    assert_eq!(
+        parse::<u8>(Some("none"), Tokens::new("none", "unset")),
+        Ok(Presence::Null)
+    );
+    assert_eq!(
+        parse::<u8>(Some("unset"), Tokens::new("none", "unset")),
+        Ok(Presence::Absent)
+    );
+}
+
+#[test]
+fn test_parse_error() {
+    let err = parse::<u16>(Some("eighty"), Tokens::none().null("")).unwrap_err();
+    assert_eq!(
+        err,
+        EnvError::Invalid {
+            var: "VAR".into(),
+            value: "eighty".into(),
+            message: "invalid digit found in string".into(),
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "invalid value `eighty` for environment variable `VAR`: invalid digit found in string"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_not_unicode() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let value = OsString::from_vec(vec![0xff]);
+    let err =
+        Presence::<String>::parse_env("VAR", Some(value), &Tokens::none().null("")).unwrap_err();
+    assert_eq!(err, EnvError::NotUnicode { var: "VAR".into() });
+}
+
+#[test]
+fn test_from_process_env() {
+    let value = Presence::<String>::from_env("PRESENCE_RS_TEST_SURELY_UNSET").unwrap();
+    assert_eq!(value, Presence::Absent);
+
+    let path = Presence::<String>::from_env_with("PATH", &Tokens::none().absent("")).unwrap();
+    assert_eq!(
+        path.is_present(),
+        std::env::var("PATH").is_ok_and(|p| !p.is_empty())
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use presence_rs::Presence;
+    use presence_rs::env::{EnvError, FromEnv};
+    use std::collections::HashMap;
+    use std::ffi::OsString;
+
+    #[derive(Debug, Default, PartialEq, FromEnv)]
+    #[presence(env_prefix = "TLS_")]
+    struct Tls {
+        cert: Presence<String>,
+    }
+
+    #[derive(Debug, PartialEq, FromEnv)]
+    #[presence(env_prefix = "APP_")]
+    struct Settings {
+        port: Presence<u16>,
+        log_level: Presence<String>,
+        #[presence(env = "HTTPS_PROXY")]
+        proxy: Presence<String>,
+        #[presence(empty = absent)]
+        workers: Presence<u8>,
+        #[presence(empty = parse)]
+        banner: Presence<String>,
+        #[presence(nested)]
+        tls: Tls,
+        #[presence(skip)]
+        loaded: bool,
+    }
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<OsString> {
+        let vars: HashMap<String, OsString> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), OsString::from(v)))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_derive() {
+        let settings = Settings::from_env_lookup(lookup(&[
+            ("APP_PORT", "8080"),
+            ("APP_LOG_LEVEL", ""),
+            ("HTTPS_PROXY", "http://proxy"),
+            ("APP_WORKERS", ""),
+            ("APP_BANNER", ""),
+            ("TLS_CERT", "/etc/cert.pem"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            settings,
+            Settings {
+                port: Presence::Some(8080),
+                log_level: Presence::Null,
+                proxy: Presence::Some("http://proxy".into()),
+                workers: Presence::Absent,
+                banner: Presence::Some(String::new()),
+                tls: Tls {
+                    cert: Presence::Some("/etc/cert.pem".into()),
+                },
+                loaded: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_derive_unset_and_errors() {
+        let settings = Settings::from_env_lookup(lookup(&[])).unwrap();
+        assert_eq!(settings.port, Presence::Absent);
+        assert_eq!(settings.tls, Tls::default());
+
+        let err = Settings::from_env_lookup(lookup(&[("APP_PORT", "x")])).unwrap_err();
+        assert!(matches!(err, EnvError::Invalid { var, .. } if var == "APP_PORT"));
+    }
+}
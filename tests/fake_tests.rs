@@ -0,0 +1,59 @@
+#![cfg(feature = "fake")]
+
+use fake::faker::name::en::Name;
+use fake::{Dummy, Fake, Faker};
+use presence_rs::Presence;
+use presence_rs::fake::PresenceFaker;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+#[derive(Debug, Dummy)]
+struct ProfilePatch {
+    #[dummy(faker = "PresenceFaker::new(Name(), 0, 0)")]
+    name: Presence<String>,
+    #[dummy(faker = "PresenceFaker::new(Faker, 0, 100)")]
+    bio: Presence<String>,
+    #[dummy(faker = "PresenceFaker::new(18..65, 100, 0)")]
+    age: Presence<u8>,
+    score: Presence<u32>,
+}
+
+#[test]
+fn test_default_generates_all_states() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let values: Vec<Presence<u16>> = (0..200).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+
+    assert!(values.iter().any(Presence::is_absent));
+    assert!(values.iter().any(Presence::is_null));
+    assert!(values.iter().any(Presence::is_present));
+}
+
+#[test]
+fn test_rates() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let faker = PresenceFaker::new(1..10u8, 50, 0);
+    let values: Vec<Presence<u8>> = (0..200).map(|_| faker.fake_with_rng(&mut rng)).collect();
+
+    assert!(!values.iter().any(Presence::is_absent));
+    assert!(values.iter().any(Presence::is_null));
+    assert!(
+        values
+            .iter()
+            .all(|value| value.map_or(true, |v| (1..10).contains(&v)))
+    );
+}
+
+#[test]
+fn test_derive() {
+    let patch: ProfilePatch = Faker.fake();
+    assert!(patch.name.is_present());
+    assert!(patch.bio.is_absent());
+    assert!(patch.age.is_null());
+    let _ = patch.score;
+}
+
+#[test]
+#[should_panic(expected = "must not exceed 100 percent")]
+fn test_rates_over_100_panic() {
+    let _ = PresenceFaker::new(Faker, 60, 50);
+}
@@ -0,0 +1,61 @@
+use presence_rs::Presence;
+use presence_rs::ffi::{PresenceFfi, PresenceTag};
+use std::mem::{align_of, size_of};
+use std::rc::Rc;
+
+#[test]
+fn test_layout() {
+    assert_eq!(size_of::<PresenceTag>(), 1);
+    assert_eq!(size_of::<PresenceFfi<u32>>(), 8);
+    assert_eq!(align_of::<PresenceFfi<u64>>(), align_of::<u64>());
+    assert_eq!(size_of::<PresenceFfi<u8>>(), 2);
+    assert_eq!(size_of::<PresenceFfi<u64>>(), 16);
+}
+
+#[test]
+fn test_round_trip() {
+    for presence in [
+        Presence::Absent,
+        Presence::Null,
+        Presence::Some("a".to_string()),
+    ] {
+        let ffi = PresenceFfi::from(presence.clone());
+        assert_eq!(ffi.as_presence(), presence.as_ref());
+        assert_eq!(Presence::from(ffi), presence);
+    }
+}
+
+#[test]
+fn test_tags() {
+    assert_eq!(PresenceFfi::<i32>::absent().tag(), PresenceTag::Absent);
+    assert_eq!(PresenceFfi::<i32>::null().tag(), PresenceTag::Null);
+    assert_eq!(PresenceFfi::some(1).tag(), PresenceTag::Some);
+    assert_eq!(PresenceTag::Some as u8, 2);
+    assert_eq!(PresenceFfi::<i32>::default(), PresenceFfi::absent());
+}
+
+#[test]
+fn test_payload_is_dropped_once() {
+    let value = Rc::new(5);
+    let ffi = PresenceFfi::some(Rc::clone(&value));
+    let copy = ffi.clone();
+    assert_eq!(Rc::strong_count(&value), 3);
+    drop(ffi);
+    assert_eq!(Rc::strong_count(&value), 2);
+    let presence = copy.into_presence();
+    assert_eq!(Rc::strong_count(&value), 2);
+    drop(presence);
+    assert_eq!(Rc::strong_count(&value), 1);
+}
+
+#[test]
+fn test_debug() {
+    assert_eq!(
+        format!("{:?}", PresenceFfi::some(3)),
+        "PresenceFfi(Some(3))"
+    );
+    assert_eq!(
+        format!("{:?}", PresenceFfi::<i32>::null()),
+        "PresenceFfi(Null)"
+    );
+}
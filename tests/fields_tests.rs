@@ -0,0 +1,83 @@
+#![cfg(feature = "serde")]
+
+use presence_rs::Presence;
+use presence_rs::fields::{Error, defined_fields, null_fields};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Patch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    tags: Presence<Vec<String>>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    score: Presence<f64>,
+}
+
+#[test]
+fn test_defined_fields_skip_absent() {
+    let patch = Patch {
+        name: Presence::Absent,
+        tags: Presence::Some(vec!["a".into()]),
+        score: Presence::Null,
+    };
+    assert_eq!(defined_fields(&patch).unwrap(), ["tags", "score"]);
+    assert_eq!(null_fields(&patch).unwrap(), ["score"]);
+}
+
+#[test]
+fn test_renamed_fields() {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Renamed {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        display_name: Presence<String>,
+    }
+
+    let patch = Renamed {
+        display_name: Presence::Null,
+    };
+    assert_eq!(defined_fields(&patch).unwrap(), ["displayName"]);
+}
+
+#[test]
+fn test_flattened_fields() {
+    #[derive(Serialize)]
+    struct Inner {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        city: Presence<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        name: Presence<String>,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    let patch = Outer {
+        name: Presence::Some("x".into()),
+        inner: Inner {
+            city: Presence::Null,
+        },
+    };
+    assert_eq!(defined_fields(&patch).unwrap(), ["name", "city"]);
+    assert_eq!(null_fields(&patch).unwrap(), ["city"]);
+}
+
+#[test]
+fn test_map_fields() {
+    let mut map = BTreeMap::new();
+    map.insert("a", Presence::Some(1));
+    map.insert("b", Presence::Null);
+    assert_eq!(defined_fields(&map).unwrap(), ["a", "b"]);
+    assert_eq!(null_fields(&map).unwrap(), ["b"]);
+}
+
+#[test]
+fn test_not_a_struct() {
+    assert_eq!(defined_fields(&42), Err(Error::NotAStruct));
+    assert_eq!(defined_fields(&vec![1, 2]), Err(Error::NotAStruct));
+}
@@ -0,0 +1,54 @@
+#![cfg(all(feature = "figment", feature = "derive"))]
+
+use figment::Figment;
+use figment::providers::Serialized;
+use presence_rs::Presence;
+use presence_rs::config::Layers;
+use presence_rs::merge::MergeFrom;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize, MergeFrom)]
+struct Settings {
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    host: Presence<String>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    port: Presence<u16>,
+}
+
+#[test]
+fn test_figment_layers() {
+    let defaults = Settings {
+        host: Presence::Some("localhost".into()),
+        port: Presence::Some(8080),
+    };
+    let file = Figment::from(Serialized::defaults(serde_json::json!({ "port": 9000 })));
+    let overrides = Figment::from(Serialized::defaults(Settings {
+        host: Presence::Null,
+        port: Presence::Absent,
+    }));
+
+    let settings = Layers::new("defaults", defaults)
+        .with_figment("file", &file)
+        .unwrap()
+        .with_figment("overrides", &overrides)
+        .unwrap()
+        .resolve();
+
+    assert_eq!(
+        settings,
+        Settings {
+            host: Presence::Null,
+            port: Presence::Some(9000),
+        }
+    );
+}
+
+#[test]
+fn test_figment_error() {
+    let bad = Figment::from(Serialized::defaults(serde_json::json!({ "port": "high" })));
+    assert!(
+        Layers::new("defaults", Settings::default())
+            .with_figment("bad", &bad)
+            .is_err()
+    );
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "firestore")]
+
+use presence_rs::Presence;
+use presence_rs::firestore::{to_firestore_value, update};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct ProfilePatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    bio: Presence<String>,
+    #[serde(rename = "display-name", skip_serializing_if = "Presence::is_absent")]
+    display_name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    score: Presence<f64>,
+}
+
+#[test]
+fn test_update_masks_defined_fields() {
+    let patch = ProfilePatch {
+        bio: Presence::Null,
+        display_name: Presence::Some("Ann".into()),
+        score: Presence::Some(1.5),
+    };
+    let update = update(&patch).unwrap();
+    assert_eq!(update.field_paths, ["bio", "`display-name`", "score"]);
+    assert_eq!(
+        update.document,
+        json!({
+            "fields": {
+                "bio": { "nullValue": null },
+                "display-name": { "stringValue": "Ann" },
+                "score": { "doubleValue": 1.5 },
+            }
+        })
+    );
+    assert!(!update.is_empty());
+}
+
+#[test]
+fn test_update_skips_absent_fields() {
+    let patch = ProfilePatch {
+        bio: Presence::Absent,
+        display_name: Presence::Absent,
+        score: Presence::Absent,
+    };
+    let update = update(&patch).unwrap();
+    assert!(update.is_empty());
+    assert_eq!(update.document, json!({ "fields": {} }));
+}
+
+#[test]
+fn test_update_rejects_non_object() {
+    assert!(update(&"bio").is_err());
+}
+
+#[test]
+fn test_to_firestore_value_array() {
+    assert_eq!(
+        to_firestore_value(json!([-1, "a`b"])),
+        json!({ "arrayValue": { "values": [{ "integerValue": "-1" }, { "stringValue": "a`b" }] } })
+    );
+}
+
+#[test]
+fn test_field_path_quoting() {
+    #[derive(Serialize)]
+    struct Patch {
+        #[serde(rename = "a`b")]
+        quoted: Presence<u8>,
+        #[serde(rename = "_ok9")]
+        plain: Presence<u8>,
+        #[serde(rename = "9lives")]
+        digit: Presence<u8>,
+    }
+
+    let patch = Patch {
+        quoted: Presence::Null,
+        plain: Presence::Null,
+        digit: Presence::Null,
+    };
+    assert_eq!(
+        update(&patch).unwrap().field_paths,
+        ["`9lives`", "_ok9", "`a\\`b`"]
+    );
+}
+
+#[test]
+fn test_update_masks_nested_leaves() {
+    #[derive(Serialize)]
+    struct AddressPatch {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        city: Presence<String>,
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        zip: Presence<String>,
+    }
+
+    #[derive(Serialize)]
+    struct UserPatch {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        address: Presence<AddressPatch>,
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        prefs: Presence<serde_json::Value>,
+    }
+
+    let patch = UserPatch {
+        address: Presence::Some(AddressPatch {
+            city: Presence::Some("Lviv".into()),
+            zip: Presence::Absent,
+        }),
+        prefs: Presence::Some(json!({})),
+    };
+    let update = update(&patch).unwrap();
+    assert_eq!(update.field_paths, ["address.city", "prefs"]);
+    assert_eq!(
+        update.document,
+        json!({
+            "fields": {
+                "address": { "mapValue": { "fields": { "city": { "stringValue": "Lviv" } } } },
+                "prefs": { "mapValue": { "fields": {} } },
+            }
+        })
+    );
+}
+
+#[test]
+fn test_update_rejects_serialized_absent() {
+    #[derive(Serialize)]
+    struct Careless {
+        bio: Presence<String>,
+    }
+    let patch = Careless {
+        bio: Presence::Absent,
+    };
+    assert!(update(&patch).is_err());
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "form")]
+
+use presence_rs::Presence;
+use presence_rs::form::{Form, FormError};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Theme {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct SettingsPatch {
+    #[serde(default)]
+    name: Presence<String>,
+    #[serde(default)]
+    bio: Presence<String>,
+    #[serde(default)]
+    age: Presence<u8>,
+    #[serde(default)]
+    newsletter: Presence<bool>,
+    #[serde(default)]
+    theme: Presence<Theme>,
+    #[serde(default)]
+    score: Presence<f64>,
+}
+
+#[test]
+fn test_urlencoded_states() {
+    let form =
+        Form::from_urlencoded(b"name=Bob+Smith&bio=&newsletter=off&newsletter=on&theme=dark");
+    let patch: SettingsPatch = form.deserialize().unwrap();
+
+    assert_eq!(
+        patch,
+        SettingsPatch {
+            name: Presence::Some("Bob Smith".into()),
+            bio: Presence::Null,
+            age: Presence::Absent,
+            newsletter: Presence::Some(true),
+            theme: Presence::Some(Theme::Dark),
+            score: Presence::Absent,
+        }
+    );
+}
+
+#[test]
+fn test_clear_marker() {
+    let form = Form::from_urlencoded(b"age=__clear&score=1.5").with_clear_marker("__clear");
+    let patch: SettingsPatch = form.deserialize().unwrap();
+    assert_eq!(patch.age, Presence::Null);
+    assert_eq!(patch.score, Presence::Some(1.5));
+
+    assert_eq!(form.value("age"), Presence::Null);
+    assert_eq!(form.value("score"), Presence::Some("1.5"));
+}
+
+#[test]
+fn test_invalid_values() {
+    let err = Form::from_urlencoded(b"age=old")
+        .deserialize::<SettingsPatch>()
+        .unwrap_err();
+    assert!(matches!(err, FormError::Custom(msg) if msg.contains("old")));
+
+    let err = Form::from_urlencoded(b"newsletter=yes")
+        .deserialize::<SettingsPatch>()
+        .unwrap_err();
+    assert!(err.to_string().contains("checkbox"));
+
+    let err = Form::from_urlencoded(b"age=old")
+        .parse::<u8>("age")
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value for field `age`: invalid digit found in string"
+    );
+}
+
+fn multipart_body(avatar: Option<(&str, &str)>, extra: &str) -> String {
+    let mut body = String::from(
+        "preamble\r\n--b0undary\r\nContent-Disposition: form-data; name=\"name\"\r\n\r\nAlice\r\n",
+    );
+    if let Some((filename, content)) = avatar {
+        body.push_str(&format!(
+            "--b0undary\r\nContent-Disposition: form-data; name=\"avatar\"; filename=\"{}\"\r\nContent-Type: image/png\r\n\r\n{}\r\n",
+            filename, content
+        ));
+    }
+    body.push_str(extra);
+    body.push_str("--b0undary--\r\n");
+    body
+}
+
+#[test]
+fn test_multipart_file_states() {
+    let body = multipart_body(Some(("me.png", "PNG\r\ndata")), "");
+    let form = Form::from_multipart(body.as_bytes(), "b0undary").unwrap();
+    let Presence::Some(file) = form.file("avatar") else {
+        panic!("expected a file");
+    };
+    assert_eq!(file.filename, "me.png");
+    assert_eq!(file.content_type.as_deref(), Some("image/png"));
+    assert_eq!(file.data, b"PNG\r\ndata");
+    assert_eq!(form.files().count(), 1);
+    assert_eq!(form.value("name"), Presence::Some("Alice"));
+
+    let body = multipart_body(Some(("", "")), "");
+    let form = Form::from_multipart(body.as_bytes(), "b0undary").unwrap();
+    assert_eq!(form.file("avatar"), Presence::Absent);
+    assert_eq!(form.files().count(), 0);
+
+    let clear = "--b0undary\r\nContent-Disposition: form-data; name=\"avatar\"\r\n\r\nremove\r\n";
+    let body = multipart_body(Some(("", "")), clear);
+    let form = Form::from_multipart(body.as_bytes(), "b0undary")
+        .unwrap()
+        .with_clear_marker("remove");
+    assert_eq!(form.file("avatar"), Presence::Null);
+}
+
+#[test]
+fn test_multipart_fields_deserialize() {
+    let extra = "--b0undary\r\nContent-Disposition: form-data; name=\"bio\"\r\n\r\n\r\n";
+    let body = multipart_body(None, extra);
+    let form = Form::from_multipart(body.as_bytes(), "b0undary").unwrap();
+    assert_eq!(
+        form.fields().collect::<Vec<_>>(),
+        [("name", "Alice"), ("bio", "")]
+    );
+
+    let patch: SettingsPatch = form.deserialize().unwrap();
+    assert_eq!(patch.name, Presence::Some("Alice".into()));
+    assert_eq!(patch.bio, Presence::Null);
+}
+
+fn file_part(disposition: &str) -> Result<Form, FormError> {
+    let body = format!(
+        "--b0undary\r\nContent-Disposition: {}\r\n\r\ndata\r\n--b0undary--\r\n",
+        disposition
+    );
+    Form::from_multipart(body.as_bytes(), "b0undary")
+}
+
+fn filename(disposition: &str) -> String {
+    let form = file_part(disposition).unwrap();
+    form.files().next().unwrap().filename.clone()
+}
+
+#[test]
+fn test_multipart_disposition_params() {
+    assert_eq!(
+        filename(r#"form-data; name="avatar"; filename="a;b \"c\".png""#),
+        r#"a;b "c".png"#
+    );
+    assert_eq!(
+        filename("form-data; FILENAME=plain.png ;name=avatar"),
+        "plain.png"
+    );
+    assert_eq!(
+        filename(
+            r#"form-data; name="avatar"; filename="naive.txt"; filename*=UTF-8''na%C3%AFve.txt"#
+        ),
+        "na\u{ef}ve.txt"
+    );
+    assert_eq!(
+        filename("form-data; name=avatar; filename*=iso-8859-1'en'%E9t%E9.txt"),
+        "\u{e9}t\u{e9}.txt"
+    );
+
+    for disposition in [
+        r#"form-data; name="avatar"; filename="open"#,
+        r#"form-data; name="avatar" x; filename="a""#,
+        "form-data; name=avatar; filename*=UTF-8''%FF",
+        "form-data; name=avatar; filename*=koi8-r''abc",
+        "form-data; name",
+    ] {
+        assert!(
+            matches!(file_part(disposition), Err(FormError::Malformed(_))),
+            "{}",
+            disposition
+        );
+    }
+}
+
+#[test]
+fn test_multipart_malformed() {
+    assert!(matches!(
+        Form::from_multipart(b"no boundary here", "b0undary"),
+        Err(FormError::Malformed(_))
+    ));
+    let truncated = "--b0undary\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\nvalue";
+    assert!(Form::from_multipart(truncated.as_bytes(), "b0undary").is_err());
+    let nameless = "--b0undary\r\nContent-Type: text/plain\r\n\r\nvalue\r\n--b0undary--";
+    assert!(Form::from_multipart(nameless.as_bytes(), "b0undary").is_err());
+}
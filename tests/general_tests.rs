@@ -97,3 +97,159 @@ fn test_debug() {
     let debug_str = format!("{:?}", absent);
     assert!(debug_str.contains("Absent"));
 }
+
+#[test]
+fn test_cmp_nulls_first() {
+    use std::cmp::Ordering;
+
+    assert_eq!(
+        Presence::Null.cmp_nulls_first(&Presence::Some(0)),
+        Ordering::Less
+    );
+    assert_eq!(
+        Presence::<i32>::Absent.cmp_nulls_first(&Presence::Null),
+        Ordering::Less
+    );
+    assert_eq!(
+        Presence::Some(1).cmp_nulls_first(&Presence::Some(2)),
+        Ordering::Less
+    );
+}
+
+#[test]
+fn test_cmp_nulls_last() {
+    use std::cmp::Ordering;
+
+    assert_eq!(
+        Presence::Null.cmp_nulls_last(&Presence::Some(0)),
+        Ordering::Greater
+    );
+    assert_eq!(
+        Presence::Some(0).cmp_nulls_last(&Presence::Absent),
+        Ordering::Less
+    );
+    assert_eq!(
+        Presence::<i32>::Absent.cmp_nulls_last(&Presence::Null),
+        Ordering::Less
+    );
+    assert_eq!(
+        Presence::<i32>::Null.cmp_nulls_last(&Presence::Null),
+        Ordering::Equal
+    );
+    assert_eq!(
+        Presence::Some(3).cmp_nulls_last(&Presence::Some(2)),
+        Ordering::Greater
+    );
+
+    let mut names = vec![
+        Presence::Null,
+        Presence::Some("bob"),
+        Presence::Absent,
+        Presence::Some("alice"),
+    ];
+    names.sort_by(Presence::cmp_nulls_last);
+    assert_eq!(
+        names,
+        [
+            Presence::Some("alice"),
+            Presence::Some("bob"),
+            Presence::Absent,
+            Presence::Null
+        ]
+    );
+}
+
+#[test]
+fn test_state_setters_return_previous() {
+    let mut x = Presence::Absent;
+    assert_eq!(x.set_value(1), Presence::Absent);
+    assert_eq!(x.set_null(), Presence::Some(1));
+    assert_eq!(x.set_absent(), Presence::Null);
+    assert_eq!(x, Presence::Absent);
+
+    let mut y = Presence::Some(String::from("old"));
+    assert_eq!(y.clear(), Presence::Some(String::from("old")));
+    assert_eq!(y, Presence::Null);
+    assert_eq!(y.clear(), Presence::Null);
+}
+
+#[test]
+fn test_assign_from_other_representations() {
+    let mut x = Presence::Absent;
+    assert_eq!(x.set_from_option_null(Some(1)), Presence::Absent);
+    assert_eq!(x.set_from_option_null(None), Presence::Some(1));
+    assert_eq!(x, Presence::Null);
+    assert_eq!(x.set_from_option_absent(None), Presence::Null);
+    assert_eq!(x, Presence::Absent);
+    assert_eq!(x.set_from_option_absent(Some(2)), Presence::Absent);
+
+    assert_eq!(x.set_from_nullable(Some(None)), Presence::Some(2));
+    assert_eq!(x.set_from_nullable(Some(Some(3))), Presence::Null);
+    assert_eq!(x.set_from_nullable(None), Presence::Some(3));
+    assert_eq!(x, Presence::Absent);
+
+    assert_eq!(x.assign(4), Presence::Absent);
+    assert_eq!(x.assign(Presence::Null), Presence::Some(4));
+    assert_eq!(x.assign(None::<Option<i32>>), Presence::Null);
+    assert_eq!(x, Presence::Absent);
+}
+
+#[test]
+fn test_try_insert() {
+    let mut x: Presence<i32> = Presence::Absent;
+    *x.try_insert(1).unwrap() += 1;
+    assert_eq!(x, Presence::Some(2));
+
+    let err = x.try_insert(5).unwrap_err();
+    assert_eq!(*err.current, 2);
+    assert_eq!(err.value, 5);
+    assert_eq!(
+        err.to_string(),
+        "failed to insert 5, presence already holds 2"
+    );
+    *err.current = 3;
+    assert_eq!(x, Presence::Some(3));
+
+    let mut null: Presence<i32> = Presence::Null;
+    assert_eq!(null.try_insert(7), Ok(&mut 7));
+}
+
+#[test]
+fn test_get_or_try_insert_with() {
+    let mut x: Presence<i32> = Presence::Null;
+    assert_eq!(
+        x.get_or_try_insert_with(|| Err("lookup failed")),
+        Err("lookup failed")
+    );
+    assert_eq!(x, Presence::Null);
+
+    *x.get_or_try_insert_with(|| Ok::<_, ()>(1)).unwrap() += 1;
+    assert_eq!(x, Presence::Some(2));
+
+    let result: Result<&mut i32, ()> = x.get_or_try_insert_with(|| panic!("not called"));
+    assert_eq!(result, Ok(&mut 2));
+}
+
+#[test]
+fn test_replace_if() {
+    let mut x = Presence::Some(1);
+    assert_eq!(
+        x.replace_if(|v| v.is_some_and(|v| *v > 0), 2),
+        Ok(Presence::Some(1))
+    );
+    assert_eq!(x, Presence::Some(2));
+
+    assert_eq!(x.replace_if(|v| v.is_nullish(), 3), Err(3));
+    assert_eq!(x, Presence::Some(2));
+
+    let mut absent: Presence<i32> = Presence::Absent;
+    assert_eq!(
+        absent.replace_if(|v| v.is_absent(), 4),
+        Ok(Presence::Absent)
+    );
+    assert_eq!(absent, Presence::Some(4));
+
+    let mut null: Presence<i32> = Presence::Null;
+    assert_eq!(null.replace_if(|v| v.is_absent(), 5), Err(5));
+    assert_eq!(null, Presence::Null);
+}
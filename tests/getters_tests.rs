@@ -0,0 +1,60 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::validate::{Violation, ViolationKind};
+use presence_rs::{Presence, PresenceGetters};
+
+#[derive(PresenceGetters)]
+struct Shipment<T> {
+    carrier: Presence<String>,
+    r#ref: Presence<T>,
+    #[presence(nested)]
+    parcel: Presence<Parcel>,
+    #[presence(skip)]
+    attempts: u32,
+}
+
+#[derive(Debug, PartialEq, PresenceGetters)]
+struct Parcel {
+    weight: Presence<f64>,
+}
+
+fn shipment() -> Shipment<u32> {
+    Shipment {
+        carrier: Presence::Some("DHL".into()),
+        r#ref: Presence::Absent,
+        parcel: Presence::Some(Parcel {
+            weight: Presence::Null,
+        }),
+        attempts: 0,
+    }
+}
+
+#[test]
+fn test_getters_some() {
+    let shipment = shipment();
+    assert_eq!(shipment.carrier().map(String::as_str), Ok("DHL"));
+    assert_eq!(shipment.attempts, 0);
+}
+
+#[test]
+fn test_getters_violations() {
+    let shipment = shipment();
+    assert_eq!(
+        shipment.r#ref(),
+        Err(Violation {
+            field: "ref",
+            kind: ViolationKind::Absent
+        })
+    );
+    assert_eq!(
+        shipment.parcel().and_then(Parcel::weight),
+        Err(Violation {
+            field: "weight",
+            kind: ViolationKind::Null
+        })
+    );
+    assert_eq!(
+        shipment.r#ref().unwrap_err().to_string(),
+        "`ref` is required"
+    );
+}
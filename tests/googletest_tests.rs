@@ -0,0 +1,65 @@
+#![cfg(feature = "googletest")]
+
+use googletest::prelude::*;
+use presence_rs::Presence;
+use presence_rs::googletest::{is_absent, is_null, is_present};
+
+#[derive(Debug)]
+struct UserPatch {
+    name: Presence<String>,
+    age: Presence<u32>,
+}
+
+#[test]
+fn test_matchers_on_values() -> Result<()> {
+    verify_that!(Presence::Some(3), is_present(eq(3)))?;
+    verify_that!(Presence::<i32>::Null, is_null())?;
+    verify_that!(Presence::<i32>::Absent, is_absent())?;
+    verify_that!(Presence::Some(3), not(is_null()))?;
+    verify_that!(Presence::<i32>::Null, not(is_absent()))?;
+    verify_that!(Presence::<i32>::Absent, not(is_present(anything())))
+}
+
+#[test]
+fn test_matchers_on_references() -> Result<()> {
+    let patch = UserPatch {
+        name: Presence::Some("Ann".to_string()),
+        age: Presence::Null,
+    };
+    verify_that!(patch.name, is_present(eq("Ann")))?;
+    verify_that!(&patch.age, is_null())?;
+    verify_that!(
+        patch,
+        matches_pattern!(&UserPatch {
+            name: ref is_present(starts_with("A")),
+            age: ref is_null(),
+        })
+    )
+}
+
+#[test]
+fn test_failure_messages() -> Result<()> {
+    let result = verify_that!(Presence::Some(2), is_present(eq(1)));
+    verify_that!(
+        result,
+        err(displays_as(contains_substring(
+            "Expected: is present with a value which is equal to 1"
+        )))
+    )?;
+    verify_that!(
+        result,
+        err(displays_as(contains_substring("which isn't equal to 1")))
+    )?;
+
+    let result = verify_that!(Presence::<i32>::Null, is_present(eq(1)));
+    verify_that!(
+        result,
+        err(displays_as(contains_substring("which is null")))
+    )?;
+
+    let result = verify_that!(Presence::Some(1), is_absent());
+    verify_that!(
+        result,
+        err(displays_as(contains_substring("Expected: is absent")))
+    )
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "graphql")]
+
+use presence_rs::Presence;
+use presence_rs::graphql::{input_variables, variables};
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct AddressInput {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    city: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    zip: Presence<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateProfileInput {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    display_name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    address: Presence<AddressInput>,
+}
+
+#[derive(Serialize)]
+struct Careless {
+    name: Presence<String>,
+}
+
+#[test]
+fn test_variables() {
+    let input = UpdateProfileInput {
+        display_name: Presence::Null,
+        address: Presence::Some(AddressInput {
+            city: Presence::Some("Lviv".into()),
+            zip: Presence::Absent,
+        }),
+    };
+    assert_eq!(
+        variables(&input).unwrap(),
+        json!({ "displayName": null, "address": { "city": "Lviv" } })
+    );
+}
+
+#[test]
+fn test_input_variables() {
+    let input = UpdateProfileInput {
+        display_name: Presence::Absent,
+        address: Presence::Absent,
+    };
+    assert_eq!(
+        input_variables("input", &input).unwrap(),
+        json!({ "input": {} })
+    );
+}
+
+#[test]
+fn test_variables_errors() {
+    let careless = Careless {
+        name: Presence::Absent,
+    };
+    assert!(variables(&careless).is_err());
+    assert!(variables(&[1, 2]).is_err());
+
+    let careless = Careless {
+        name: Presence::Null,
+    };
+    assert_eq!(variables(&careless).unwrap(), json!({ "name": null }));
+}
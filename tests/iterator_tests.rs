@@ -171,3 +171,22 @@ fn test_size_hint() {
     let iter = absent.iter();
     assert_eq!(iter.size_hint(), (0, Some(0)));
 }
+
+#[test]
+fn test_iter_defined() {
+    let some = Presence::Some("a");
+    let mut iter = some.iter_defined();
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next(), Some(Some(&"a")));
+    assert_eq!(iter.next(), None);
+
+    let null: Presence<&str> = Presence::Null;
+    let mut iter = null.iter_defined();
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(iter.next_back(), Some(None));
+    assert_eq!(iter.next(), None);
+
+    let absent: Presence<&str> = Presence::Absent;
+    assert_eq!(absent.iter_defined().size_hint(), (0, Some(0)));
+    assert_eq!(absent.iter_defined().next(), None);
+}
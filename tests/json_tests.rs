@@ -0,0 +1,289 @@
+#![cfg(feature = "json")]
+
+use presence_rs::Presence;
+use serde_json::{Value, json};
+
+#[test]
+fn test_from_json_value() {
+    assert_eq!(
+        Presence::<String>::from_json_value(json!("x")).unwrap(),
+        Presence::Some("x".to_string())
+    );
+    assert_eq!(
+        Presence::<String>::from_json_value(Value::Null).unwrap(),
+        Presence::Null
+    );
+}
+
+#[test]
+fn test_from_json_value_type_mismatch() {
+    assert!(Presence::<bool>::from_json_value(json!(1)).is_err());
+}
+
+#[test]
+fn test_from_json_lookup() {
+    let value = json!({ "a": 1, "b": null });
+    assert_eq!(
+        Presence::<i32>::from_json_lookup(value.get("a")).unwrap(),
+        Presence::Some(1)
+    );
+    assert_eq!(
+        Presence::<i32>::from_json_lookup(value.get("b")).unwrap(),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::<i32>::from_json_lookup(value.get("c")).unwrap(),
+        Presence::Absent
+    );
+}
+
+#[test]
+fn test_try_from_ref() {
+    let value = json!({ "name": "Ann", "age": null, "tags": ["a"] });
+    assert_eq!(
+        Presence::<String>::try_from(&value["name"]).unwrap(),
+        Presence::Some("Ann".to_string())
+    );
+    assert_eq!(
+        Presence::<u8>::try_from(&value["age"]).unwrap(),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::<Value>::try_from(&value["tags"]).unwrap(),
+        Presence::Some(json!(["a"]))
+    );
+    assert!(Presence::<bool>::try_from(&value["name"]).is_err());
+
+    let age: Result<Presence<u8>, _> = (&value["name"]).try_into();
+    assert!(age.is_err());
+}
+
+#[test]
+fn test_try_from_nested_struct() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    let value = json!({ "x": 1, "y": 2 });
+    assert_eq!(
+        Presence::<Point>::from_json_ref(&value).unwrap(),
+        Presence::Some(Point { x: 1, y: 2 })
+    );
+}
+
+#[test]
+fn test_to_json_value_round_trip() {
+    for p in [Presence::Some(7), Presence::Null, Presence::Absent] {
+        let back = match p.to_json_value().unwrap() {
+            Some(value) => Presence::<i32>::from_json_value(value).unwrap(),
+            None => Presence::Absent,
+        };
+        assert_eq!(back, p);
+    }
+}
+
+#[test]
+fn test_into_value() {
+    let value: Value = Presence::Some(1.5).into();
+    assert_eq!(value, json!(1.5));
+
+    let value: Value = Presence::<bool>::Absent.into();
+    assert_eq!(value, Value::Null);
+
+    assert_eq!(Value::from(Presence::Some("hi")), json!("hi"));
+    assert_eq!(Value::from(Presence::Some(json!([1]))), json!([1]));
+    assert_eq!(Value::from(Presence::<String>::Null), Value::Null);
+}
+
+#[test]
+fn test_merge_missing_keys_untouched() {
+    let mut base = json!({ "a": 1, "b": 2 });
+    presence_rs::json::merge(&mut base, &json!({}));
+    assert_eq!(base, json!({ "a": 1, "b": 2 }));
+}
+
+#[test]
+fn test_merge_null_deletes() {
+    let mut base = json!({ "a": 1, "b": 2 });
+    presence_rs::json::merge(&mut base, &json!({ "b": null, "c": null }));
+    assert_eq!(base, json!({ "a": 1 }));
+}
+
+#[test]
+fn test_merge_recursive() {
+    let mut base = json!({ "outer": { "inner": { "x": 1, "y": 2 }, "z": 3 } });
+    let patch = json!({ "outer": { "inner": { "y": null, "w": 4 } } });
+    presence_rs::json::merge(&mut base, &patch);
+    assert_eq!(
+        base,
+        json!({ "outer": { "inner": { "x": 1, "w": 4 }, "z": 3 } })
+    );
+}
+
+#[test]
+fn test_merge_scalars_and_arrays_replace() {
+    let mut base = json!({ "list": [1, 2, 3], "obj": { "k": 1 } });
+    presence_rs::json::merge(&mut base, &json!({ "list": [4], "obj": "flat" }));
+    assert_eq!(base, json!({ "list": [4], "obj": "flat" }));
+}
+
+#[test]
+fn test_merge_into_states() {
+    let mut base = json!({ "a": 1 });
+    Presence::<Value>::Absent.merge_into(&mut base);
+    assert_eq!(base, json!({ "a": 1 }));
+
+    Presence::Some(json!({ "b": 2 })).merge_into(&mut base);
+    assert_eq!(base, json!({ "a": 1, "b": 2 }));
+
+    Presence::<Value>::Null.merge_into(&mut base);
+    assert_eq!(base, Value::Null);
+}
+
+#[derive(serde::Serialize)]
+struct ProfilePatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    avatar: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    prefs: Presence<PrefsPatch>,
+}
+
+#[derive(serde::Serialize)]
+struct PrefsPatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    theme: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent", rename = "a/b~c")]
+    escaped: Presence<u8>,
+}
+
+fn profile_patch() -> ProfilePatch {
+    ProfilePatch {
+        name: Presence::Some("Bob".into()),
+        avatar: Presence::Null,
+        prefs: Presence::Some(PrefsPatch {
+            theme: Presence::Null,
+            escaped: Presence::Some(1),
+        }),
+    }
+}
+
+#[test]
+fn test_patch_body_merge_patch() {
+    use presence_rs::json::{PatchBody, PatchFormat};
+
+    let body = PatchBody::merge_patch(&profile_patch()).unwrap();
+    assert_eq!(body.format(), PatchFormat::MergePatch);
+    assert_eq!(body.content_type(), "application/merge-patch+json");
+    assert_eq!(
+        body.value(),
+        &json!({ "name": "Bob", "avatar": null, "prefs": { "theme": null, "a/b~c": 1 } })
+    );
+}
+
+#[test]
+fn test_patch_body_json_patch() {
+    use presence_rs::json::PatchBody;
+
+    let body = PatchBody::json_patch(&profile_patch()).unwrap();
+    assert_eq!(body.content_type(), "application/json-patch+json");
+    assert_eq!(
+        body.into_value(),
+        json!([
+            { "op": "remove", "path": "/avatar" },
+            { "op": "add", "path": "/name", "value": "Bob" },
+            { "op": "add", "path": "/prefs/a~1b~0c", "value": 1 },
+            { "op": "remove", "path": "/prefs/theme" },
+        ])
+    );
+}
+
+#[test]
+fn test_patch_body_json_patch_empty_object() {
+    use presence_rs::json::PatchBody;
+
+    let patch = json!({ "prefs": {}, "meta": { "tags": {} } });
+    let body = PatchBody::json_patch(&patch).unwrap();
+    assert_eq!(
+        body.into_value(),
+        json!([
+            { "op": "add", "path": "/meta/tags", "value": {} },
+            { "op": "add", "path": "/prefs", "value": {} },
+        ])
+    );
+}
+
+#[test]
+fn test_patch_body_keeps_untouched_fields() {
+    use presence_rs::json::PatchBody;
+
+    let mut base =
+        json!({ "name": "Alice", "avatar": "a.png", "prefs": { "theme": "dark", "lang": "en" } });
+    let merge = PatchBody::merge_patch(&profile_patch()).unwrap();
+    presence_rs::json::merge(&mut base, merge.value());
+    assert_eq!(
+        base,
+        json!({ "name": "Bob", "prefs": { "lang": "en", "a/b~c": 1 } })
+    );
+}
+
+#[test]
+fn test_patch_body_empty_and_invalid() {
+    use presence_rs::json::{PatchBody, PatchFormat};
+
+    let patch = ProfilePatch {
+        name: Presence::Absent,
+        avatar: Presence::Absent,
+        prefs: Presence::Absent,
+    };
+    for format in [PatchFormat::MergePatch, PatchFormat::JsonPatch] {
+        assert!(PatchBody::new(&patch, format).unwrap().is_empty());
+    }
+    assert_eq!(PatchBody::merge_patch(&patch).unwrap().to_vec(), b"{}");
+
+    assert!(PatchBody::merge_patch(&[1, 2]).is_err());
+}
+
+#[test]
+fn test_presence_at() {
+    use presence_rs::json::presence_at;
+
+    let doc = json!({ "a": { "b": { "c": 1, "d": null } }, "list": [null, 2], "a~b": true });
+    assert_eq!(presence_at(&doc, "a/b/c"), Presence::Some(&json!(1)));
+    assert_eq!(presence_at(&doc, "/a/b/c"), Presence::Some(&json!(1)));
+    assert_eq!(presence_at(&doc, "a/b/d"), Presence::Null);
+    assert_eq!(presence_at(&doc, "a/b/e"), Presence::Absent);
+    assert_eq!(presence_at(&doc, "a/b/d/x"), Presence::Absent);
+    assert_eq!(presence_at(&doc, "list/0"), Presence::Null);
+    assert_eq!(presence_at(&doc, "list/1"), Presence::Some(&json!(2)));
+    assert_eq!(presence_at(&doc, "list/2"), Presence::Absent);
+    assert_eq!(presence_at(&doc, "a~0b"), Presence::Some(&json!(true)));
+    assert_eq!(presence_at(&doc, ""), Presence::Some(&doc));
+}
+
+#[test]
+fn test_json_string_round_trip() {
+    for value in [
+        Presence::Some("a".to_string()),
+        Presence::Null,
+        Presence::Absent,
+    ] {
+        let text = value.to_json_string().unwrap();
+        assert_eq!(Presence::<String>::from_json_str(&text).unwrap(), value);
+        let pretty = value.to_json_string_pretty().unwrap();
+        assert_eq!(Presence::<String>::from_json_str(&pretty).unwrap(), value);
+    }
+}
+
+#[test]
+fn test_from_json_str_errors() {
+    assert!(Presence::<u8>::from_json_str("300").is_err());
+    assert!(Presence::<u8>::from_json_str("1 2").is_err());
+    assert_eq!(
+        Presence::<Vec<u8>>::from_json_str("\n[1]\n").unwrap(),
+        Presence::Some(vec![1])
+    );
+}
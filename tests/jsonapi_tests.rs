@@ -0,0 +1,98 @@
+#![cfg(feature = "jsonapi")]
+
+use presence_rs::Presence;
+use presence_rs::jsonapi::Resource;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+struct PersonPatch {
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    age: Presence<u32>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    email: Presence<String>,
+}
+
+#[test]
+fn test_to_document() {
+    let resource = Resource::new(
+        "people",
+        "9",
+        PersonPatch {
+            name: Presence::Some("Dan".into()),
+            age: Presence::Null,
+            email: Presence::Absent,
+        },
+    );
+    assert_eq!(
+        resource.to_document().unwrap(),
+        json!({ "data": { "type": "people", "id": "9", "attributes": { "name": "Dan", "age": null } } })
+    );
+}
+
+#[test]
+fn test_to_document_without_id() {
+    let resource = Resource {
+        kind: "people".to_string(),
+        id: None,
+        attributes: PersonPatch::default(),
+    };
+    assert_eq!(
+        resource.to_document().unwrap(),
+        json!({ "data": { "type": "people", "attributes": {} } })
+    );
+}
+
+#[test]
+fn test_to_document_rejects_non_object_attributes() {
+    let resource = Resource::new("people", "9", 5);
+    assert!(resource.to_document().is_err());
+}
+
+#[test]
+fn test_from_document() {
+    let document = json!({
+        "data": {
+            "type": "people",
+            "id": "9",
+            "attributes": { "age": 40, "email": null },
+            "relationships": { "employer": { "data": null } }
+        },
+        "meta": { "trace": "abc" }
+    });
+    let resource = Resource::<PersonPatch>::from_document(document).unwrap();
+    assert_eq!(
+        resource,
+        Resource::new(
+            "people",
+            "9",
+            PersonPatch {
+                name: Presence::Absent,
+                age: Presence::Some(40),
+                email: Presence::Null,
+            }
+        )
+    );
+}
+
+#[test]
+fn test_from_document_without_attributes() {
+    let document = json!({ "data": { "type": "people", "id": "9" } });
+    let resource = Resource::<PersonPatch>::from_document(document).unwrap();
+    assert_eq!(resource.attributes, PersonPatch::default());
+}
+
+#[test]
+fn test_from_document_errors() {
+    for document in [
+        json!([]),
+        json!({ "data": null }),
+        json!({ "data": { "id": "9" } }),
+        json!({ "data": { "type": "people", "id": 9 } }),
+        json!({ "data": { "type": "people", "attributes": { "age": "old" } } }),
+    ] {
+        assert!(Resource::<PersonPatch>::from_document(document).is_err());
+    }
+}
@@ -168,3 +168,178 @@ fn test_presence_macro_method_chain() {
     let p = presence!("  hello  ".trim().to_uppercase());
     assert_eq!(p, Presence::Some("HELLO".to_string()));
 }
+
+#[test]
+fn test_assert_present_returns_value() {
+    let name = Presence::Some("Bob".to_string());
+    let borrowed: &String = presence_rs::assert_present!(&name);
+    assert_eq!(borrowed, "Bob");
+
+    let owned: String = presence_rs::assert_present!(name, "name of {}", "user");
+    assert_eq!(owned, "Bob");
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `missing` is present\n  actual: Absent")]
+fn test_assert_present_failure_message() {
+    let missing: Presence<u8> = Presence::Absent;
+    presence_rs::assert_present!(missing);
+}
+
+#[test]
+#[should_panic(expected = "is present: patch field `age`\n  actual: Null")]
+fn test_assert_present_custom_message() {
+    let age: Presence<u8> = Presence::Null;
+    presence_rs::assert_present!(age, "patch field `{}`", "age");
+}
+
+#[test]
+fn test_assert_null_and_absent_pass() {
+    let null: Presence<i32> = Presence::Null;
+    let absent: Presence<i32> = Presence::Absent;
+    presence_rs::assert_null!(&null);
+    presence_rs::assert_null!(null, "cleared");
+    presence_rs::assert_absent!(&absent);
+    presence_rs::assert_absent!(absent, "untouched");
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `value` is null\n  actual: Some(\"x\")")]
+fn test_assert_null_failure_message() {
+    let value = Presence::Some("x");
+    presence_rs::assert_null!(value);
+}
+
+#[test]
+#[should_panic(expected = "assertion failed: `&value` is absent: sent by mistake\n  actual: Null")]
+fn test_assert_absent_failure_message() {
+    let value: Presence<i32> = Presence::Null;
+    presence_rs::assert_absent!(&value, "sent by mistake");
+}
+
+#[test]
+fn test_match_presence_three_arms() {
+    let describe = |value: Presence<i32>| {
+        presence_rs::match_presence!(value,
+            some(v) => format!("some {}", v),
+            null => "null".to_string(),
+            absent => "absent".to_string(),
+        )
+    };
+    assert_eq!(describe(Presence::Some(1)), "some 1");
+    assert_eq!(describe(Presence::Null), "null");
+    assert_eq!(describe(Presence::Absent), "absent");
+}
+
+#[test]
+fn test_match_presence_shared_empty_arm() {
+    let value: Presence<String> = Presence::Some("abc".into());
+    let len = presence_rs::match_presence!(&value, some(s) => s.len(), null | absent => 0);
+    assert_eq!(len, 3);
+
+    let value: Presence<String> = Presence::Null;
+    let len = presence_rs::match_presence!(&value, some(s) => s.len(), null | absent => 0);
+    assert_eq!(len, 0);
+}
+
+#[test]
+fn test_match_presence_patterns_and_blocks() {
+    let point = Presence::Some((1, 2));
+    let sum = presence_rs::match_presence!(point,
+        some((x, y)) => {
+            let sum = x + y;
+            sum * 10
+        },
+        null => -1,
+        absent => 0
+    );
+    assert_eq!(sum, 30);
+}
+
+#[test]
+fn test_presence_macro_optional() {
+    let none: Option<i32> = None;
+    assert_eq!(presence!(?none), Presence::Absent);
+    assert_eq!(presence!(? Some(5)), Presence::Some(5));
+    assert_eq!(presence!(? "7".parse::<i32>().ok()), Presence::Some(7));
+}
+
+#[test]
+fn test_presence_macro_nullable() {
+    let values: [Option<Option<&str>>; 3] = [None, Some(None), Some(Some("x"))];
+    assert_eq!(presence!(??values[0]), Presence::Absent);
+    assert_eq!(presence!(?? values[1]), Presence::Null);
+    assert_eq!(presence!(?? values[2]), Presence::Some("x"));
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct ProfilePatch {
+    name: Presence<String>,
+    bio: Presence<String>,
+    age: Presence<u32>,
+    tags: Presence<Vec<String>>,
+}
+
+#[test]
+fn test_presence_patch_defaults_to_absent() {
+    let patch = presence_rs::presence_patch!(ProfilePatch {
+        name: "Bob",
+        bio: @null,
+        ..
+    });
+    assert_eq!(
+        patch,
+        ProfilePatch {
+            name: Presence::Some("Bob".to_string()),
+            bio: Presence::Null,
+            age: Presence::Absent,
+            tags: Presence::Absent,
+        }
+    );
+
+    let empty = presence_rs::presence_patch!(ProfilePatch { .. });
+    assert_eq!(empty, ProfilePatch::default());
+}
+
+#[test]
+fn test_presence_patch_all_fields() {
+    let age = 40;
+    let patch = presence_rs::presence_patch!(ProfilePatch {
+        name: format!("{}-{}", "a", 1),
+        bio: @absent,
+        age: age + 1,
+        tags: vec!["x".to_string()],
+    });
+    assert_eq!(patch.name, Presence::Some("a-1".to_string()));
+    assert_eq!(patch.bio, Presence::Absent);
+    assert_eq!(patch.age, Presence::Some(41));
+    assert_eq!(patch.tags, Presence::Some(vec!["x".to_string()]));
+}
+
+#[test]
+fn test_presence_patch_reads_locals_named_like_markers() {
+    let null = "none".to_string();
+    let absent = 7;
+    let patch = presence_rs::presence_patch!(ProfilePatch {
+        name: null,
+        age: absent,
+        ..
+    });
+    assert_eq!(patch.name, Presence::Some("none".to_string()));
+    assert_eq!(patch.age, Presence::Some(7));
+}
+
+mod nested {
+    #[derive(Debug, Default)]
+    pub struct Patch {
+        pub flag: presence_rs::Presence<bool>,
+    }
+}
+
+#[test]
+fn test_presence_patch_with_path() {
+    let patch = presence_rs::presence_patch!(nested::Patch { flag: true });
+    let aged = presence_rs::presence_patch!(ProfilePatch { age: 31, .. });
+    assert_eq!(aged.age, Presence::Some(31));
+    assert_eq!(patch.flag, Presence::Some(true));
+}
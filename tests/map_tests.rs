@@ -0,0 +1,162 @@
+use presence_rs::Presence;
+use presence_rs::map::{PresenceEntryExt, PresenceMap};
+use std::collections::{BTreeMap, HashMap};
+
+#[test]
+fn test_presence_entry_get() {
+    let mut map = HashMap::from([("a", Presence::Some(1)), ("b", Presence::Null)]);
+    assert_eq!(map.presence_entry("a").get(), Presence::Some(&1));
+    assert_eq!(map.presence_entry("b").get(), Presence::Null);
+    assert_eq!(map.presence_entry("c").get(), Presence::Absent);
+    assert_eq!(*map.presence_entry("c").key(), "c");
+    assert!(!map.contains_key("c"));
+}
+
+#[test]
+fn test_presence_entry_get_mut() {
+    let mut map = BTreeMap::from([("a", Presence::Some(1))]);
+    let mut entry = map.presence_entry("a");
+    if let Presence::Some(value) = entry.get_mut() {
+        *value = 2;
+    }
+    assert_eq!(map["a"], Presence::Some(2));
+}
+
+#[test]
+fn test_presence_entry_or_insert() {
+    let mut map: HashMap<&str, Presence<i32>> = HashMap::from([("null", Presence::Null)]);
+    *map.presence_entry("null").or_insert(1) += 10;
+    *map.presence_entry("missing").or_insert(2) += 10;
+    *map.presence_entry("missing").or_insert(3) += 10;
+    assert_eq!(map["null"], Presence::Some(11));
+    assert_eq!(map["missing"], Presence::Some(22));
+
+    assert_eq!(*map.presence_entry("default").or_default(), 0);
+}
+
+#[test]
+fn test_presence_entry_or_insert_null() {
+    let mut map = BTreeMap::from([("a", Presence::Some(1))]);
+    assert_eq!(*map.presence_entry("a").or_insert_null(), Presence::Some(1));
+    assert_eq!(*map.presence_entry("b").or_insert_null(), Presence::Null);
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_presence_entry_and_modify() {
+    let mut map = HashMap::from([("a", Presence::Some(1)), ("b", Presence::Null)]);
+    let value = map.presence_entry("a").and_modify(|v| *v += 1).or_insert(0);
+    assert_eq!(*value, 2);
+    map.presence_entry("b").and_modify(|_| panic!("not called"));
+    map.presence_entry("c").and_modify(|_| panic!("not called"));
+    assert_eq!(map.len(), 2);
+}
+
+#[test]
+fn test_presence_entry_set_and_remove() {
+    let mut map: BTreeMap<String, Presence<i32>> = BTreeMap::new();
+    assert_eq!(
+        map.presence_entry("a".into()).set(Presence::Some(1)),
+        Presence::Absent
+    );
+    assert_eq!(
+        map.presence_entry("a".into()).set(Presence::Null),
+        Presence::Some(1)
+    );
+    assert_eq!(map["a"], Presence::Null);
+    assert_eq!(
+        map.presence_entry("a".into()).set(Presence::Absent),
+        Presence::Null
+    );
+    assert!(map.is_empty());
+
+    map.insert("b".into(), Presence::Some(2));
+    assert_eq!(map.presence_entry("b".into()).remove(), Presence::Some(2));
+    assert_eq!(map.presence_entry("b".into()).remove(), Presence::Absent);
+}
+
+#[test]
+fn test_presence_map_get() {
+    let mut map = PresenceMap::new();
+    assert!(map.is_empty());
+    assert_eq!(map.insert("a".to_string(), 1), Presence::Absent);
+    assert_eq!(map.tombstone("b".to_string()), Presence::Absent);
+
+    assert_eq!(map.get("a"), Presence::Some(&1));
+    assert_eq!(map.get("b"), Presence::Null);
+    assert_eq!(map.get("c"), Presence::Absent);
+    assert!(map.contains_key("b"));
+    assert!(!map.contains_key("c"));
+    assert_eq!(map.len(), 2);
+
+    if let Presence::Some(value) = map.get_mut("a") {
+        *value += 1;
+    }
+    assert_eq!(map.get("a"), Presence::Some(&2));
+}
+
+#[test]
+fn test_presence_map_returns_previous_state() {
+    let mut map = PresenceMap::new();
+    let _ = map.insert("a", 1);
+    assert_eq!(map.tombstone("a"), Presence::Some(1));
+    assert_eq!(map.insert("a", 2), Presence::Null);
+    assert_eq!(map.set("a", Presence::Null), Presence::Some(2));
+    assert_eq!(map.set("a", Presence::Absent), Presence::Null);
+    assert_eq!(map.remove("a"), Presence::Absent);
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_presence_map_iteration() {
+    let map: PresenceMap<&str, i32> = [
+        ("a", Presence::Some(1)),
+        ("b", Presence::Null),
+        ("c", Presence::Absent),
+        ("d", Presence::Some(4)),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(map.len(), 3);
+
+    let mut entries: Vec<_> = map.entries().collect();
+    entries.sort();
+    assert_eq!(entries, [(&"a", &1), (&"d", &4)]);
+    assert_eq!(map.tombstones().collect::<Vec<_>>(), [&"b"]);
+
+    let mut all: Vec<_> = map.iter().collect();
+    all.sort();
+    assert_eq!(
+        all,
+        [
+            (&"a", Presence::Some(&1)),
+            (&"b", Presence::Null),
+            (&"d", Presence::Some(&4))
+        ]
+    );
+
+    let mut owned: Vec<_> = map.clone().into_iter().collect();
+    owned.sort();
+    assert_eq!(
+        owned,
+        [
+            ("a", Presence::Some(1)),
+            ("b", Presence::Null),
+            ("d", Presence::Some(4))
+        ]
+    );
+}
+
+#[test]
+fn test_presence_map_traits() {
+    let mut map = PresenceMap::default();
+    map.extend([("a", Presence::Some(1)), ("b", Presence::Null)]);
+    let mut other = PresenceMap::new();
+    let _ = other.tombstone("b");
+    let _ = other.insert("a", 1);
+    assert_eq!(map, other);
+
+    map.clear();
+    let _ = map.tombstone("x");
+    assert_eq!(format!("{:?}", map), r#"{"x": Null}"#);
+}
@@ -0,0 +1,166 @@
+use presence_rs::Presence;
+use presence_rs::merge::{MergeStrategy, PreferOtherIfDefined, PreferOtherIfPresent, PreferSelf};
+
+const STATES: [Presence<i32>; 3] = [Presence::Absent, Presence::Null, Presence::Some(1)];
+
+#[test]
+fn test_prefer_other_if_defined() {
+    for current in STATES {
+        assert_eq!(
+            current.merge(Presence::Absent, PreferOtherIfDefined),
+            current
+        );
+        assert_eq!(
+            current.merge(Presence::Null, PreferOtherIfDefined),
+            Presence::Null
+        );
+        assert_eq!(
+            current.merge(Presence::Some(2), PreferOtherIfDefined),
+            Presence::Some(2)
+        );
+    }
+}
+
+#[test]
+fn test_prefer_other_if_present() {
+    for current in STATES {
+        assert_eq!(
+            current.merge(Presence::Absent, PreferOtherIfPresent),
+            current
+        );
+        assert_eq!(current.merge(Presence::Null, PreferOtherIfPresent), current);
+        assert_eq!(
+            current.merge(Presence::Some(2), PreferOtherIfPresent),
+            Presence::Some(2)
+        );
+    }
+}
+
+#[test]
+fn test_prefer_self() {
+    for other in STATES {
+        assert_eq!(Presence::Absent.merge(other, PreferSelf), other);
+        assert_eq!(Presence::Null.merge(other, PreferSelf), Presence::Null);
+        assert_eq!(
+            Presence::Some(2).merge(other, PreferSelf),
+            Presence::Some(2)
+        );
+    }
+}
+
+#[test]
+fn test_layered_overrides() {
+    let layers = [
+        Presence::Some("defaults"),
+        Presence::Some("file"),
+        Presence::Absent,
+        Presence::Null,
+    ];
+
+    let mut value = Presence::Absent;
+    for layer in layers {
+        value.merge_with(layer, PreferOtherIfDefined);
+    }
+    assert_eq!(value, Presence::Null);
+
+    let value = layers.into_iter().fold(Presence::Absent, |acc, layer| {
+        acc.merge(layer, PreferOtherIfPresent)
+    });
+    assert_eq!(value, Presence::Some("file"));
+}
+
+#[test]
+fn test_strategy_as_trait_object_and_closure() {
+    let strategies: [&dyn MergeStrategy<i32>; 2] = [&PreferSelf, &PreferOtherIfDefined];
+    let merged: Vec<_> = strategies
+        .iter()
+        .map(|s| s.merge(Presence::Some(1), Presence::Some(2)))
+        .collect();
+    assert_eq!(merged, [Presence::Some(1), Presence::Some(2)]);
+
+    let max = |a: Presence<i32>, b: Presence<i32>| match (a, b) {
+        (Presence::Some(a), Presence::Some(b)) => Presence::Some(a.max(b)),
+        (a, b) => a.or(b),
+    };
+    assert_eq!(
+        Presence::Some(5).merge(Presence::Some(3), max),
+        Presence::Some(5)
+    );
+    assert_eq!(
+        Presence::Null.merge(Presence::Some(3), max),
+        Presence::Some(3)
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use presence_rs::Presence;
+    use presence_rs::merge::{MergeFrom, PreferOtherIfPresent, PreferSelf};
+
+    #[derive(Debug, PartialEq, MergeFrom)]
+    struct AddressPatch {
+        city: Presence<String>,
+        zip: Presence<String>,
+    }
+
+    #[derive(Debug, PartialEq, MergeFrom)]
+    struct UserPatch {
+        #[presence(skip)]
+        id: u64,
+        name: Presence<String>,
+        #[presence(merge = PreferOtherIfPresent)]
+        email: Presence<String>,
+        #[presence(merge = PreferSelf)]
+        created_by: Presence<String>,
+        #[presence(nested)]
+        address: AddressPatch,
+    }
+
+    fn patch(id: u64, name: Presence<&str>, city: Presence<&str>) -> UserPatch {
+        UserPatch {
+            id,
+            name: name.map(String::from),
+            email: Presence::Null,
+            created_by: Presence::Some(format!("user{}", id)),
+            address: AddressPatch {
+                city: city.map(String::from),
+                zip: Presence::Absent,
+            },
+        }
+    }
+
+    #[test]
+    fn test_later_patch_wins_where_defined() {
+        let mut merged = patch(1, Presence::Some("Al"), Presence::Some("Kyiv"));
+        merged.email = Presence::Some("al@example.com".into());
+        merged.merge_from(patch(2, Presence::Null, Presence::Absent));
+
+        assert_eq!(
+            merged,
+            UserPatch {
+                id: 1,
+                name: Presence::Null,
+                email: Presence::Some("al@example.com".into()),
+                created_by: Presence::Some("user1".into()),
+                address: AddressPatch {
+                    city: Presence::Some("Kyiv".into()),
+                    zip: Presence::Absent,
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_merging_many_patches() {
+        let patches = [
+            patch(1, Presence::Some("Al"), Presence::Absent),
+            patch(2, Presence::Absent, Presence::Some("Lviv")),
+            patch(3, Presence::Some("Bob"), Presence::Null),
+        ];
+
+        let merged = patches.into_iter().reduce(MergeFrom::merged).unwrap();
+        assert_eq!(merged.name, Presence::Some("Bob".into()));
+        assert_eq!(merged.address.city, Presence::Null);
+        assert_eq!(merged.created_by, Presence::Some("user1".into()));
+    }
+}
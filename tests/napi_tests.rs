@@ -0,0 +1,55 @@
+#![cfg(feature = "napi")]
+
+use napi::ValueType;
+use napi::bindgen_prelude::{FromNapiValue, ToNapiValue, TypeName, ValidateNapiValue};
+use presence_rs::Presence;
+use presence_rs::napi::NapiObject;
+
+#[derive(NapiObject)]
+pub struct UserPatch {
+    pub display_name: Presence<String>,
+    pub age: Presence<u32>,
+    #[presence(plain)]
+    pub id: u32,
+    #[presence(skip)]
+    pub revision: u64,
+}
+
+#[derive(NapiObject)]
+pub struct AccountPatch {
+    pub owner: Presence<UserPatch>,
+}
+
+fn assert_napi_value<T: FromNapiValue + ToNapiValue + ValidateNapiValue>() {}
+
+#[test]
+fn test_conversions_are_implemented() {
+    assert_napi_value::<Presence<String>>();
+    assert_napi_value::<Presence<u32>>();
+    assert_napi_value::<Presence<bool>>();
+    assert_napi_value::<UserPatch>();
+    assert_napi_value::<AccountPatch>();
+}
+
+#[test]
+fn test_type_name_is_inner_type() {
+    assert_eq!(
+        <Presence<String> as TypeName>::type_name(),
+        <String as TypeName>::type_name()
+    );
+    assert_eq!(<Presence<u32> as TypeName>::value_type(), ValueType::Number);
+    assert_eq!(
+        <Presence<bool> as TypeName>::value_type(),
+        ValueType::Boolean
+    );
+}
+
+#[test]
+fn test_napi_object_is_an_object() {
+    assert_eq!(<UserPatch as TypeName>::type_name(), "UserPatch");
+    assert_eq!(<UserPatch as TypeName>::value_type(), ValueType::Object);
+    assert_eq!(
+        <Presence<UserPatch> as TypeName>::value_type(),
+        ValueType::Object
+    );
+}
@@ -0,0 +1,92 @@
+use presence_rs::Presence;
+use presence_rs::optics::{Optic, affine, lens, presence_field, some};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Prefs {
+    theme: Presence<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct User {
+    name: String,
+    prefs: Presence<Prefs>,
+    tags: Vec<Presence<String>>,
+}
+
+fn user() -> User {
+    User {
+        name: "Ann".into(),
+        prefs: Presence::Some(Prefs {
+            theme: Presence::Null,
+        }),
+        tags: vec![Presence::Some("rust".into()), Presence::Absent],
+    }
+}
+
+#[test]
+fn test_lens_then_prism() {
+    let prefs = presence_field(|u: &User| &u.prefs, |u: &mut User| &mut u.prefs);
+    let theme = lens(|p: &Prefs| &p.theme, |p: &mut Prefs| &mut p.theme);
+    let optic = prefs.then(theme);
+
+    assert_eq!(optic.preview(&user()), Some(&Presence::Null));
+    let updated = optic.set(user(), Presence::Some("dark".into()));
+    assert_eq!(
+        updated.prefs,
+        Presence::Some(Prefs {
+            theme: Presence::Some("dark".into()),
+        })
+    );
+
+    // The theme itself is `Null`, so focusing its value finds nothing.
+    let value = optic.then(some());
+    assert!(!value.is_focused(&user()));
+    assert_eq!(value.set(user(), "dark".into()), user());
+}
+
+#[test]
+fn test_missing_focus() {
+    let theme = presence_field(|u: &User| &u.prefs, |u: &mut User| &mut u.prefs).then(
+        presence_field(|p: &Prefs| &p.theme, |p: &mut Prefs| &mut p.theme),
+    );
+
+    let mut absent = user();
+    absent.prefs = Presence::Absent;
+    assert_eq!(theme.preview(&absent), None);
+    assert_eq!(theme.modify(absent.clone(), |t| t.push('!')), absent);
+    assert_eq!(
+        theme.replace(&mut absent, "dark".into()),
+        Err("dark".to_string())
+    );
+}
+
+#[test]
+fn test_affine_over_collection() {
+    let tag = |index: usize| {
+        affine(
+            move |u: &User| u.tags.get(index),
+            move |u: &mut User| u.tags.get_mut(index),
+        )
+        .then(some())
+    };
+
+    let mut user = user();
+    assert_eq!(tag(0).preview(&user).map(String::as_str), Some("rust"));
+    assert_eq!(tag(1).preview(&user), None);
+    assert_eq!(tag(5).preview(&user), None);
+
+    assert_eq!(tag(0).replace(&mut user, "wasm".into()), Ok("rust".into()));
+    assert_eq!(user.tags[0], Presence::Some("wasm".into()));
+    assert_eq!(user.name, "Ann");
+}
+
+#[test]
+fn test_review() {
+    let prism = some::<u8>();
+    assert_eq!(prism.review(1), Presence::Some(1));
+    assert_eq!(prism.preview(&prism.review(1)), Some(&1));
+    assert_eq!(
+        lens(|u: &User| &u.name, |u: &mut User| &mut u.name).get(&user()),
+        "Ann"
+    );
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "parquet")]
+
+use bytes::Bytes;
+use parquet::basic::Type as PhysicalType;
+use parquet::column::reader::get_typed_column_reader;
+use parquet::data_type::Int32Type;
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::schema::types::Type;
+use presence_rs::Presence;
+use presence_rs::parquet::{presence_group, read_column, write_column};
+use std::sync::Arc;
+
+fn write_file(ages: &[Presence<i32>]) -> Bytes {
+    let schema = Type::group_type_builder("schema")
+        .with_fields(vec![Arc::new(
+            presence_group("age", PhysicalType::INT32).unwrap(),
+        )])
+        .build()
+        .unwrap();
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut buffer = Vec::new();
+    let mut writer = SerializedFileWriter::new(&mut buffer, Arc::new(schema), properties).unwrap();
+    let mut row_group = writer.next_row_group().unwrap();
+    let mut column = row_group.next_column().unwrap().unwrap();
+    write_column(column.typed::<Int32Type>(), ages).unwrap();
+    column.close().unwrap();
+    row_group.close().unwrap();
+    writer.close().unwrap();
+    Bytes::from(buffer)
+}
+
+#[test]
+fn test_file_round_trip() {
+    let ages = [
+        Presence::Some(30),
+        Presence::Absent,
+        Presence::Null,
+        Presence::Some(-1),
+    ];
+    let reader = SerializedFileReader::new(write_file(&ages)).unwrap();
+    let row_group = reader.get_row_group(0).unwrap();
+    let mut column = get_typed_column_reader::<Int32Type>(row_group.get_column_reader(0).unwrap());
+    assert_eq!(read_column(&mut column).unwrap(), ages);
+}
+
+#[test]
+fn test_rows_read_by_parquet() {
+    let ages = [Presence::Some(7), Presence::Null, Presence::Absent];
+    let reader = SerializedFileReader::new(write_file(&ages)).unwrap();
+    let rows: Vec<String> = reader
+        .get_row_iter(None)
+        .unwrap()
+        .map(|row| row.unwrap().to_string())
+        .collect();
+    assert_eq!(
+        rows,
+        ["{age: {value: 7}}", "{age: {value: null}}", "{age: null}"]
+    );
+}
+
+#[test]
+fn test_definition_levels_round_trip() {
+    use presence_rs::parquet::{MAX_DEFINITION_LEVEL, decode_levels, encode_levels};
+
+    let presences = [
+        Presence::Some(1.5),
+        Presence::Absent,
+        Presence::Null,
+        Presence::Some(-2.0),
+    ];
+    let (values, levels) = encode_levels(&presences);
+    assert_eq!(values, [1.5, -2.0]);
+    assert_eq!(levels, [MAX_DEFINITION_LEVEL, 0, 1, MAX_DEFINITION_LEVEL]);
+    assert_eq!(decode_levels(values, &levels).unwrap(), presences);
+}
+
+#[test]
+fn test_decode_levels_errors() {
+    use presence_rs::parquet::{LevelError, decode_levels};
+
+    assert_eq!(
+        decode_levels(Vec::<u8>::new(), &[3]),
+        Err(LevelError::InvalidLevel(3))
+    );
+    assert_eq!(
+        decode_levels(vec![1, 2], &[2, 0]),
+        Err(LevelError::ValueCount {
+            expected: 1,
+            actual: 2
+        })
+    );
+    assert_eq!(
+        LevelError::InvalidLevel(-1).to_string(),
+        "invalid definition level -1"
+    );
+}
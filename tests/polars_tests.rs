@@ -0,0 +1,51 @@
+#![cfg(feature = "polars")]
+
+use polars::prelude::*;
+use presence_rs::Presence;
+use presence_rs::columnar::AbsentPolicy;
+use presence_rs::polars::{
+    from_series, from_str_series, from_str_struct_series, from_struct_series, to_series,
+    to_struct_series,
+};
+
+#[test]
+fn test_nullable_series() {
+    let scores = [Presence::Some(1.5), Presence::Absent, Presence::Null];
+    let series = to_series("score", &scores, AbsentPolicy::Null).unwrap();
+    assert_eq!(series.name().as_str(), "score");
+    assert_eq!(series.dtype(), &DataType::Float64);
+    assert_eq!(series.null_count(), 2);
+    assert_eq!(
+        from_series::<Float64Type>(&series).unwrap(),
+        [Presence::Some(1.5), Presence::Null, Presence::Null]
+    );
+
+    let error = to_series("score", &scores, AbsentPolicy::Reject).unwrap_err();
+    assert!(error.to_string().contains("absent value at row 1"));
+}
+
+#[test]
+fn test_struct_series_round_trip() {
+    let ages = [Presence::Absent, Presence::Some(30u32), Presence::Null];
+    let series = to_struct_series("age", &ages).unwrap();
+    let frame = DataFrame::new(vec![series.into()]).unwrap();
+    let column = frame.column("age").unwrap().as_materialized_series();
+    assert_eq!(column.len(), 3);
+    assert_eq!(from_struct_series::<UInt32Type>(column).unwrap(), ages);
+    assert!(from_struct_series::<Int64Type>(column).is_err());
+}
+
+#[test]
+fn test_string_series() {
+    let names = [
+        Presence::Some("Ann".to_string()),
+        Presence::Null,
+        Presence::Absent,
+    ];
+    let series = to_struct_series("name", &names).unwrap();
+    assert_eq!(from_str_struct_series(&series).unwrap(), names);
+
+    let series = to_series("name", &names[..2], AbsentPolicy::Reject).unwrap();
+    assert_eq!(from_str_series(&series).unwrap(), names[..2]);
+    assert!(from_str_series(&to_struct_series("name", &names).unwrap()).is_err());
+}
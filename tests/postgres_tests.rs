@@ -0,0 +1,70 @@
+#![cfg(feature = "postgres")]
+
+use bytes::BytesMut;
+use postgres_types::{FromSql, IsNull, ToSql, Type};
+use presence_rs::Presence;
+use presence_rs::postgres::params;
+use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+
+fn encode<T: ToSql>(value: &T, ty: &Type) -> Option<Vec<u8>> {
+    let mut buf = BytesMut::new();
+    match value.to_sql_checked(ty, &mut buf).unwrap() {
+        IsNull::Yes => None,
+        IsNull::No => Some(buf.to_vec()),
+    }
+}
+
+#[test]
+fn test_to_sql_matches_option() {
+    assert_eq!(
+        encode(&Presence::Some(7_i32), &Type::INT4),
+        encode(&Some(7_i32), &Type::INT4)
+    );
+    assert_eq!(encode(&Presence::<i32>::Null, &Type::INT4), None);
+    assert_eq!(encode(&Presence::<i32>::Absent, &Type::INT4), None);
+}
+
+#[test]
+fn test_to_sql_checked_rejects_wrong_type() {
+    let mut buf = BytesMut::new();
+    assert!(
+        Presence::Some("text".to_string())
+            .to_sql_checked(&Type::INT4, &mut buf)
+            .is_err()
+    );
+}
+
+#[test]
+fn test_from_sql() {
+    let raw = encode(&42_i64, &Type::INT8).unwrap();
+    assert_eq!(
+        Presence::<i64>::from_sql_nullable(&Type::INT8, Some(&raw)).unwrap(),
+        Presence::Some(42)
+    );
+    assert_eq!(
+        Presence::<i64>::from_sql_nullable(&Type::INT8, None).unwrap(),
+        Presence::Null
+    );
+}
+
+#[test]
+fn test_accepts() {
+    assert!(<Presence<String> as FromSql>::accepts(&Type::TEXT));
+    assert!(!<Presence<String> as FromSql>::accepts(&Type::BOOL));
+    assert!(<Presence<bool> as ToSql>::accepts(&Type::BOOL));
+}
+
+#[test]
+fn test_params_follow_set_clause_binds() {
+    let mut set = SetClause::<i32>::new(Placeholder::Dollar).nulls(NullStyle::Literal);
+    set.set("age", Presence::Some(31))
+        .set("rank", Presence::<i32>::Null)
+        .set("score", Presence::Some(7));
+    assert_eq!(set.sql(), "SET age = $1, rank = NULL, score = $2");
+
+    let params = params(&set);
+    assert_eq!(params.len(), 2);
+    let mut buf = BytesMut::new();
+    params[1].to_sql_checked(&Type::INT4, &mut buf).unwrap();
+    assert_eq!(buf.to_vec(), 7_i32.to_be_bytes());
+}
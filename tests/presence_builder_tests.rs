@@ -0,0 +1,151 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::{Presence, PresenceBuilder};
+
+#[derive(Debug, PresenceBuilder)]
+#[presence(builder = "AccountDraft")]
+pub struct Account<T> {
+    #[presence(required, non_null)]
+    pub id: Presence<T>,
+    #[presence(non_null)]
+    pub email: Presence<String>,
+    pub nickname: Presence<String>,
+    #[presence(skip)]
+    pub revision: u64,
+}
+
+// The builder derive_builder generates with `#[builder(name = "AccountDraft")]`.
+#[derive(Default)]
+pub struct AccountDraft<T> {
+    id: Option<Presence<T>>,
+    email: Option<Presence<String>>,
+    nickname: Option<Presence<String>>,
+    revision: Option<u64>,
+}
+
+#[allow(dead_code)]
+#[derive(PresenceBuilder)]
+struct Unchecked {
+    note: Presence<String>,
+}
+
+#[derive(Default)]
+struct UncheckedBuilder {
+    note: Option<Presence<String>>,
+}
+
+#[test]
+fn test_null_setters() {
+    let mut draft = AccountDraft::<u32>::default();
+    draft.email_null().nickname_null();
+    assert_eq!(draft.email, Some(Presence::Null));
+    assert_eq!(draft.nickname, Some(Presence::Null));
+    assert_eq!(draft.id, None);
+    assert_eq!(draft.revision, None);
+}
+
+#[test]
+fn test_check_presence() {
+    let mut draft = AccountDraft::<u32>::default();
+    assert_eq!(draft.check_presence(), Err("`id` is required".to_string()));
+
+    draft.id_null().email_null();
+    assert_eq!(
+        draft.check_presence(),
+        Err("`id` must not be null; `email` must not be null".to_string())
+    );
+
+    draft.id = Some(Presence::Some(1));
+    draft.email = Some(Presence::Absent);
+    draft.nickname_null();
+    assert_eq!(draft.check_presence(), Ok(()));
+}
+
+#[test]
+fn test_check_presence_without_policies() {
+    let mut builder = UncheckedBuilder::default();
+    assert_eq!(builder.check_presence(), Ok(()));
+    builder.note_null();
+    assert_eq!(builder.note, Some(Presence::Null));
+}
+
+mod derive_builder_patterns {
+    use derive_builder::Builder;
+    use presence_rs::{Presence, PresenceBuilder};
+
+    #[derive(Builder, PresenceBuilder, Debug, Default, PartialEq)]
+    #[builder(default, setter(into), build_fn(validate = "Self::check_presence"))]
+    struct Mutable {
+        #[presence(required)]
+        name: Presence<String>,
+        bio: Presence<String>,
+    }
+
+    #[derive(Builder, PresenceBuilder, Debug, Default, PartialEq)]
+    #[builder(
+        name = "OwnedDraft",
+        pattern = "owned",
+        default,
+        setter(into),
+        build_fn(validate = "Self::check_presence")
+    )]
+    struct Owned {
+        #[presence(non_null)]
+        name: Presence<String>,
+        bio: Presence<String>,
+        #[builder(pattern = "immutable")]
+        email: Presence<String>,
+    }
+
+    #[derive(Builder, PresenceBuilder, Debug, Default, PartialEq)]
+    #[builder(pattern = "immutable", default, setter(into))]
+    struct Immutable {
+        bio: Presence<String>,
+    }
+
+    #[test]
+    fn test_mutable_pattern() {
+        let patch = MutableBuilder::default()
+            .name("Ann".to_string())
+            .bio_null()
+            .build()
+            .unwrap();
+        assert_eq!(patch.name, Presence::Some("Ann".to_string()));
+        assert_eq!(patch.bio, Presence::Null);
+
+        let error = MutableBuilder::default().bio_null().build().unwrap_err();
+        assert_eq!(error.to_string(), "`name` is required");
+    }
+
+    #[test]
+    fn test_owned_pattern_and_builder_name() {
+        let patch = OwnedDraft::default()
+            .bio_null()
+            .name("Ann".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            patch,
+            Owned {
+                name: Presence::Some("Ann".to_string()),
+                bio: Presence::Null,
+                email: Presence::Absent,
+            }
+        );
+
+        assert!(OwnedDraft::default().name_null().build().is_err());
+
+        let draft = OwnedDraft::default();
+        let email_null = draft.email_null();
+        assert_eq!(email_null.build().unwrap().email, Presence::Null);
+        assert_eq!(draft.build().unwrap().email, Presence::Absent);
+    }
+
+    #[test]
+    fn test_immutable_pattern() {
+        let draft = ImmutableBuilder::default();
+        let patch = draft.bio_null().build().unwrap();
+        assert_eq!(patch.bio, Presence::Null);
+        assert_eq!(draft.build().unwrap().bio, Presence::Absent);
+    }
+}
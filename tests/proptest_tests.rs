@@ -0,0 +1,66 @@
+#![cfg(feature = "proptest")]
+
+use presence_rs::Presence;
+use presence_rs::proptest::{PresenceWeights, presence_strategy};
+use proptest::prelude::*;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{TestCaseError, TestRunner};
+
+fn sample<S: Strategy>(strategy: &S, count: usize) -> Vec<S::Value> {
+    let mut runner = TestRunner::deterministic();
+    (0..count)
+        .map(|_| strategy.new_tree(&mut runner).unwrap().current())
+        .collect()
+}
+
+#[test]
+fn test_default_weights_generate_all_states() {
+    let values = sample(&any::<Presence<u8>>(), 200);
+    assert!(values.iter().any(Presence::is_absent));
+    assert!(values.iter().any(Presence::is_null));
+    assert!(values.iter().any(Presence::is_present));
+}
+
+#[test]
+fn test_zero_weights_disable_states() {
+    let only_some = presence_strategy(0..10u8).weights(PresenceWeights::new(0, 0, 1));
+    assert!(sample(&only_some, 50).iter().all(Presence::is_present));
+
+    let no_values = presence_strategy(0..10u8).weights(PresenceWeights::new(1, 1, 0));
+    assert!(!sample(&no_values, 50).iter().any(Presence::is_present));
+}
+
+#[test]
+fn test_arbitrary_with_weights() {
+    let strategy = any_with::<Presence<bool>>((PresenceWeights::new(0, 1, 0), ()));
+    assert!(sample(&strategy, 20).iter().all(Presence::is_null));
+}
+
+#[test]
+#[should_panic(expected = "at least one presence weight must be positive")]
+fn test_all_zero_weights_panic() {
+    let _ = presence_strategy(Just(1)).weights(PresenceWeights::new(0, 0, 0));
+}
+
+#[test]
+fn test_shrinks_towards_absent() {
+    let mut runner = TestRunner::deterministic();
+    let result = runner.run(&presence_strategy(0..100i32), |value| {
+        if value.is_absent() {
+            Ok(())
+        } else {
+            Err(TestCaseError::fail("defined"))
+        }
+    });
+    let Err(proptest::test_runner::TestError::Fail(_, minimal)) = result else {
+        panic!("expected a failure");
+    };
+    assert_eq!(minimal, Presence::Null);
+}
+
+proptest! {
+    #[test]
+    fn prop_some_values_come_from_inner(value in presence_strategy(10..20u32)) {
+        prop_assert!(value.map_or(true, |value| (10..20).contains(&value)));
+    }
+}
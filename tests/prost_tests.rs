@@ -0,0 +1,234 @@
+#![cfg(feature = "prost")]
+
+use presence_rs::Presence;
+use presence_rs::prost::{field_mask, field_mask_with_prefix};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct UpdateBook {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    title: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    subtitle: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    page_count: Presence<u32>,
+}
+
+#[test]
+fn test_field_mask_defined_fields() {
+    let patch = UpdateBook {
+        title: Presence::Some("Dune".into()),
+        subtitle: Presence::Null,
+        page_count: Presence::Absent,
+    };
+    assert_eq!(field_mask(&patch).unwrap().paths, ["title", "subtitle"]);
+}
+
+#[test]
+fn test_field_mask_empty() {
+    let patch = UpdateBook {
+        title: Presence::Absent,
+        subtitle: Presence::Absent,
+        page_count: Presence::Absent,
+    };
+    assert!(field_mask(&patch).unwrap().paths.is_empty());
+}
+
+#[test]
+fn test_field_mask_with_prefix() {
+    let patch = UpdateBook {
+        title: Presence::Absent,
+        subtitle: Presence::Absent,
+        page_count: Presence::Some(412),
+    };
+    assert_eq!(
+        field_mask_with_prefix("book", &patch).unwrap().paths,
+        ["book.page_count"]
+    );
+}
+
+/// Mirrors what prost generates for:
+/// `message Book { string title = 1; optional string subtitle = 2; uint32 page_count = 3; }`
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Book {
+    title: String,
+    subtitle: Option<String>,
+    page_count: u32,
+}
+
+fn stored_book() -> Book {
+    Book {
+        title: "Dune".into(),
+        subtitle: Some("A Novel".into()),
+        page_count: 100,
+    }
+}
+
+#[test]
+fn test_apply_patch_to_message() {
+    let mut book = stored_book();
+    let patch = UpdateBook {
+        title: Presence::Absent,
+        subtitle: Presence::Null,
+        page_count: Presence::Some(412),
+    };
+    presence_rs::apply_patch!(book, patch; title, subtitle, page_count);
+
+    assert_eq!(
+        book,
+        Book {
+            title: "Dune".into(),
+            subtitle: None,
+            page_count: 412,
+        }
+    );
+}
+
+#[test]
+fn test_apply_patch_null_resets_scalar() {
+    let mut book = stored_book();
+    let patch = UpdateBook {
+        title: Presence::Null,
+        subtitle: Presence::Absent,
+        page_count: Presence::Null,
+    };
+    presence_rs::apply_patch!(book, patch; title, subtitle, page_count);
+
+    assert_eq!(book.title, "");
+    assert_eq!(book.page_count, 0);
+    assert_eq!(book.subtitle.as_deref(), Some("A Novel"));
+}
+
+#[test]
+fn test_apply_mask_to_message() {
+    let mut book = stored_book();
+    let request = Book {
+        title: "Dune Messiah".into(),
+        subtitle: None,
+        page_count: 0,
+    };
+    let mask = prost_types::FieldMask {
+        paths: vec!["title".into(), "subtitle".into()],
+    };
+    presence_rs::apply_mask!(book, request, mask; title, subtitle, page_count);
+
+    assert_eq!(
+        book,
+        Book {
+            title: "Dune Messiah".into(),
+            subtitle: None,
+            page_count: 100,
+        }
+    );
+}
+
+#[test]
+fn test_apply_mask_with_renamed_and_raw_fields() {
+    #[derive(Debug, Default, PartialEq)]
+    struct Item {
+        r#type: String,
+        page_count: u32,
+    }
+
+    let mut item = Item::default();
+    let request = Item {
+        r#type: "book".into(),
+        page_count: 9,
+    };
+    let mask = prost_types::FieldMask {
+        paths: vec!["type".into(), "pageCount".into()],
+    };
+    presence_rs::apply_mask!(item, request, mask; r#type, page_count = "pageCount");
+    assert_eq!(
+        item,
+        Item {
+            r#type: "book".into(),
+            page_count: 9,
+        }
+    );
+}
+
+#[cfg(feature = "json")]
+mod well_known {
+    use presence_rs::Presence;
+    use presence_rs::prost::{from_struct, to_struct};
+    use prost_types::value::Kind;
+    use prost_types::{Struct, Value};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Payload {
+        #[serde(default, skip_serializing_if = "Presence::is_absent")]
+        count: Presence<u32>,
+        #[serde(default, skip_serializing_if = "Presence::is_absent")]
+        ratio: Presence<f64>,
+        #[serde(default, skip_serializing_if = "Presence::is_absent")]
+        tags: Presence<Vec<String>>,
+    }
+
+    #[test]
+    fn test_struct_round_trip() {
+        let payload = Payload {
+            count: Presence::Some(3),
+            ratio: Presence::Some(0.25),
+            tags: Presence::Null,
+        };
+        let value = to_struct(&payload).unwrap();
+        assert_eq!(value.fields.len(), 3);
+        assert_eq!(
+            value.fields["tags"].kind,
+            Some(Kind::NullValue(prost_types::NullValue::NullValue as i32))
+        );
+        assert_eq!(from_struct::<Payload>(&value).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_struct_missing_field_is_absent() {
+        let payload = Payload {
+            count: Presence::Absent,
+            ratio: Presence::Absent,
+            tags: Presence::Some(vec!["a".into()]),
+        };
+        let value = to_struct(&payload).unwrap();
+        assert!(!value.fields.contains_key("count"));
+        assert_eq!(from_struct::<Payload>(&value).unwrap(), payload);
+    }
+
+    #[test]
+    fn test_value_without_kind_is_null() {
+        let value = Value { kind: None };
+        assert_eq!(
+            Presence::<String>::from_prost_value(Some(&value)).unwrap(),
+            Presence::Null
+        );
+    }
+
+    #[test]
+    fn test_from_prost_value_type_mismatch() {
+        let value = Value {
+            kind: Some(Kind::StringValue("x".into())),
+        };
+        assert!(Presence::<u32>::from_prost_value(Some(&value)).is_err());
+        assert!(from_struct::<Payload>(&Struct::default()).is_ok());
+    }
+
+    #[test]
+    fn test_non_finite_numbers_are_errors() {
+        for number in [f64::NAN, f64::INFINITY] {
+            let value = Value {
+                kind: Some(Kind::NumberValue(number)),
+            };
+            assert!(Presence::<f64>::from_prost_value(Some(&value)).is_err());
+
+            let value = Struct {
+                fields: [("ratio".to_string(), value)].into(),
+            };
+            assert!(from_struct::<Payload>(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn test_to_struct_rejects_non_object() {
+        assert!(to_struct(&1).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "redis")]
+
+use presence_rs::redis::HashUpdate;
+use presence_rs::{Presence, hash_update};
+use redis::{Arg, Cmd};
+
+fn args(cmd: &Cmd) -> Vec<String> {
+    cmd.args_iter()
+        .map(|arg| match arg {
+            Arg::Simple(bytes) => String::from_utf8(bytes.to_vec()).unwrap(),
+            Arg::Cursor => "<cursor>".to_string(),
+        })
+        .collect()
+}
+
+struct UserPatch {
+    name: Presence<String>,
+    email: Presence<String>,
+    avatar: Presence<String>,
+    age: Presence<u32>,
+}
+
+#[test]
+fn test_minimal_commands() {
+    let patch = UserPatch {
+        name: Presence::Some("Alice".into()),
+        email: Presence::Null,
+        avatar: Presence::Null,
+        age: Presence::Some(30),
+    };
+
+    let mut update = HashUpdate::new("user:1");
+    hash_update!(update, patch; name, email, avatar, age).unwrap();
+
+    let commands = update.commands();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        args(&commands[0]),
+        ["HSET", "user:1", "name", "Alice", "age", "30"]
+    );
+    assert_eq!(args(&commands[1]), ["HDEL", "user:1", "email", "avatar"]);
+}
+
+#[test]
+fn test_only_set() {
+    let mut update = HashUpdate::new("user:2");
+    update
+        .field("name", Presence::Some("Bob"))
+        .unwrap()
+        .field("email", Presence::<&str>::Absent)
+        .unwrap();
+
+    let commands = update.commands();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(args(&commands[0]), ["HSET", "user:2", "name", "Bob"]);
+}
+
+#[test]
+fn test_all_absent() {
+    let patch = UserPatch {
+        name: Presence::Absent,
+        email: Presence::Absent,
+        avatar: Presence::Absent,
+        age: Presence::Absent,
+    };
+
+    let mut update = HashUpdate::new("user:3");
+    hash_update!(update, patch; name, email, avatar, age).unwrap();
+    assert!(update.is_empty());
+    assert!(update.commands().is_empty());
+    assert!(update.pipeline().is_empty());
+}
+
+#[test]
+fn test_pipeline() {
+    let mut update = HashUpdate::new("user:4");
+    update
+        .field("name", Presence::Some("Carol"))
+        .unwrap()
+        .field("bio", Presence::<&str>::Null)
+        .unwrap();
+    assert_eq!(update.key(), "user:4");
+
+    let pipe = update.pipeline();
+    assert_eq!(pipe.len(), 2);
+
+    let mut existing = redis::pipe();
+    existing.cmd("EXPIRE").arg("user:4").arg(60);
+    update.add_to(&mut existing);
+    assert_eq!(existing.len(), 3);
+}
+
+#[test]
+fn test_multi_arg_values_are_rejected() {
+    let mut update = HashUpdate::new("user:5");
+    update.field("name", Presence::Some("Dan")).unwrap();
+
+    let error = update
+        .field("tags", Presence::Some(vec!["a", "b"]))
+        .err()
+        .unwrap();
+    assert_eq!(error.kind(), redis::ErrorKind::TypeError);
+    assert!(update.field("bio", Presence::Some(None::<&str>)).is_err());
+
+    let commands = update.commands();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(args(&commands[0]), ["HSET", "user:5", "name", "Dan"]);
+}
@@ -0,0 +1,277 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::reflect::PresenceFields;
+use presence_rs::{Presence, PresenceState};
+
+#[derive(PresenceFields)]
+struct ProfilePatch {
+    #[presence(skip)]
+    id: u64,
+    name: Presence<String>,
+    avatar: Presence<String>,
+    r#type: Presence<String>,
+}
+
+#[derive(PresenceFields)]
+struct Empty {}
+
+#[derive(PresenceFields)]
+struct Wrapper<T> {
+    value: Presence<T>,
+}
+
+#[test]
+fn test_field_states() {
+    let patch = ProfilePatch {
+        id: 7,
+        name: Presence::Some("Bob".into()),
+        avatar: Presence::Null,
+        r#type: Presence::Absent,
+    };
+    assert_eq!(patch.id, 7);
+
+    assert_eq!(
+        patch.field_states(),
+        [
+            ("name", PresenceState::Some),
+            ("avatar", PresenceState::Null),
+            ("type", PresenceState::Absent),
+        ]
+    );
+    assert_eq!(patch.defined_fields(), ["name", "avatar"]);
+    assert_eq!(patch.null_fields(), ["avatar"]);
+    assert!(!patch.is_empty_patch());
+}
+
+#[test]
+fn test_empty_patch() {
+    let patch = ProfilePatch {
+        id: 1,
+        name: Presence::Absent,
+        avatar: Presence::Absent,
+        r#type: Presence::Absent,
+    };
+    assert!(patch.is_empty_patch());
+    assert!(patch.defined_fields().is_empty());
+    assert!(Empty {}.is_empty_patch());
+}
+
+#[test]
+fn test_dyn_and_generic() {
+    let patches: Vec<Box<dyn PresenceFields>> = vec![
+        Box::new(Wrapper {
+            value: Presence::Some(1),
+        }),
+        Box::new(Wrapper::<String> {
+            value: Presence::Null,
+        }),
+    ];
+    let defined: Vec<_> = patches.iter().map(|p| p.defined_fields()).collect();
+    assert_eq!(defined, [["value"], ["value"]]);
+    assert_eq!(patches[1].null_fields(), ["value"]);
+}
+
+mod describe {
+    use presence_rs::Presence;
+    use presence_rs::reflect::DescribePatch;
+
+    struct Secret;
+
+    #[derive(DescribePatch)]
+    struct UserPatch {
+        #[presence(skip)]
+        id: u64,
+        name: Presence<String>,
+        age: Presence<u32>,
+        tags: Presence<Vec<&'static str>>,
+        #[presence(redact)]
+        token: Presence<Secret>,
+        email: Presence<String>,
+    }
+
+    #[test]
+    fn test_describe() {
+        let patch = UserPatch {
+            id: 1,
+            name: Presence::Some("Bob".into()),
+            age: Presence::Null,
+            tags: Presence::Some(vec!["a", "b"]),
+            token: Presence::Some(Secret),
+            email: Presence::Absent,
+        };
+        assert_eq!(patch.id, 1);
+        assert_eq!(
+            patch.describe(),
+            r#"name: set to "Bob", age: cleared, tags: set to ["a", "b"], token: set to <redacted>, email: unchanged"#
+        );
+    }
+
+    #[test]
+    fn test_describe_redacted_states() {
+        let patch = UserPatch {
+            id: 2,
+            name: Presence::Absent,
+            age: Presence::Absent,
+            tags: Presence::Absent,
+            token: Presence::Null,
+            email: Presence::Absent,
+        };
+        assert!(patch.describe().contains("token: cleared"));
+    }
+
+    #[test]
+    fn test_describe_to_writer() {
+        #[derive(DescribePatch)]
+        struct Single {
+            value: Presence<i32>,
+        }
+
+        let mut out = String::from("changes: ");
+        Single {
+            value: Presence::Some(3),
+        }
+        .describe_to(&mut out)
+        .unwrap();
+        assert_eq!(out, "changes: value: set to 3");
+    }
+}
+
+mod visit {
+    use presence_rs::Presence;
+    use presence_rs::reflect::VisitPresence;
+    use std::fmt::Debug;
+
+    #[derive(VisitPresence)]
+    struct LimitsPatch {
+        max: Presence<u32>,
+        #[presence(skip)]
+        #[allow(dead_code)]
+        note: &'static str,
+    }
+
+    struct Secret;
+
+    #[derive(VisitPresence)]
+    struct PlanPatch {
+        name: Presence<String>,
+        #[presence(redact)]
+        token: Presence<Secret>,
+        #[presence(nested)]
+        limits: LimitsPatch,
+        #[presence(nested)]
+        overrides: Presence<LimitsPatch>,
+    }
+
+    fn collect(patch: &impl VisitPresence) -> Vec<String> {
+        let mut seen = Vec::new();
+        patch.visit_presence(&mut |path: &[&'static str], value: Presence<&dyn Debug>| {
+            seen.push(format!("{}={:?}", path.join("/"), value));
+        });
+        seen
+    }
+
+    #[test]
+    fn test_visit_presence_walks_nested_fields() {
+        let patch = PlanPatch {
+            name: Presence::Some("pro".into()),
+            token: Presence::Some(Secret),
+            limits: LimitsPatch {
+                max: Presence::Null,
+                note: "",
+            },
+            overrides: Presence::Some(LimitsPatch {
+                max: Presence::Some(3),
+                note: "",
+            }),
+        };
+        assert_eq!(
+            collect(&patch),
+            [
+                r#"name=Some("pro")"#,
+                "token=Some(<redacted>)",
+                "limits/max=Null",
+                "overrides/max=Some(3)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visit_presence_reports_empty_nested_presence() {
+        let patch = PlanPatch {
+            name: Presence::Absent,
+            token: Presence::Null,
+            limits: LimitsPatch {
+                max: Presence::Absent,
+                note: "",
+            },
+            overrides: Presence::Null,
+        };
+        assert_eq!(
+            collect(&patch),
+            [
+                "name=Absent",
+                "token=Null",
+                "limits/max=Absent",
+                "overrides=Null"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visit_presence_with_custom_visitor() {
+        use presence_rs::reflect::PresenceVisitor;
+
+        #[derive(Default)]
+        struct Counter {
+            defined: usize,
+        }
+
+        impl PresenceVisitor for Counter {
+            fn visit(&mut self, _path: &[&'static str], value: Presence<&dyn Debug>) {
+                if value.is_defined() {
+                    self.defined += 1;
+                }
+            }
+        }
+
+        let patch = LimitsPatch {
+            max: Presence::Some(1),
+            note: "",
+        };
+        let mut counter = Counter::default();
+        patch.visit_presence(&mut counter);
+        assert_eq!(counter.defined, 1);
+
+        let mut path = vec!["root"];
+        let mut paths = Vec::new();
+        patch.visit_presence_at(
+            &mut path,
+            &mut |path: &[&'static str], _: Presence<&dyn Debug>| paths.push(path.join(".")),
+        );
+        assert_eq!(paths, ["root.max"]);
+        assert_eq!(path, ["root"]);
+    }
+
+    #[test]
+    fn test_presence_at() {
+        use presence_rs::PresenceState;
+
+        let patch = PlanPatch {
+            name: Presence::Null,
+            token: Presence::Absent,
+            limits: LimitsPatch {
+                max: Presence::Some(1),
+                note: "",
+            },
+            overrides: Presence::Null,
+        };
+        assert_eq!(patch.presence_at("name"), PresenceState::Null);
+        assert_eq!(patch.presence_at("/token"), PresenceState::Absent);
+        assert_eq!(patch.presence_at("limits/max"), PresenceState::Some);
+        assert_eq!(patch.presence_at("limits"), PresenceState::Some);
+        assert_eq!(patch.presence_at("overrides"), PresenceState::Null);
+        assert_eq!(patch.presence_at("overrides/max"), PresenceState::Absent);
+        assert_eq!(patch.presence_at("missing"), PresenceState::Absent);
+        assert_eq!(patch.presence_at("note"), PresenceState::Absent);
+    }
+}
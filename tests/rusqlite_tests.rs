@@ -0,0 +1,82 @@
+#![cfg(feature = "rusqlite")]
+
+use presence_rs::Presence;
+use presence_rs::rusqlite::get_presence;
+use rusqlite::{Connection, params};
+
+fn connection() -> Connection {
+    let conn = Connection::open_in_memory().unwrap();
+    conn.execute_batch(
+        "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT, age INTEGER);
+         INSERT INTO users VALUES (1, 'Alice', 30), (2, NULL, NULL);",
+    )
+    .unwrap();
+    conn
+}
+
+#[test]
+fn test_from_sql() {
+    let conn = connection();
+    let mut stmt = conn.prepare("SELECT name FROM users ORDER BY id").unwrap();
+    let names: Vec<Presence<String>> = stmt
+        .query_map([], |row| row.get(0))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(names, [Presence::Some("Alice".into()), Presence::Null]);
+}
+
+#[test]
+fn test_to_sql() {
+    let conn = connection();
+    conn.execute(
+        "INSERT INTO users VALUES (?1, ?2, ?3)",
+        params![3, Presence::Some("Bob".to_string()), Presence::<i64>::Null],
+    )
+    .unwrap();
+    conn.execute(
+        "INSERT INTO users VALUES (?1, ?2, ?3)",
+        params![4, Presence::<String>::Absent, Presence::Some(5)],
+    )
+    .unwrap();
+
+    let row: (Option<String>, Option<i64>) = conn
+        .query_row("SELECT name, age FROM users WHERE id = 3", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(row, (Some("Bob".into()), None));
+
+    let row: (Option<String>, Option<i64>) = conn
+        .query_row("SELECT name, age FROM users WHERE id = 4", [], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })
+        .unwrap();
+    assert_eq!(row, (None, Some(5)));
+}
+
+#[test]
+fn test_get_presence_missing_column() {
+    let conn = connection();
+    let (name, age, missing): (Presence<String>, Presence<i64>, Presence<i64>) = conn
+        .query_row("SELECT name, age FROM users WHERE id = 2", [], |row| {
+            Ok((
+                get_presence(row, "name")?,
+                get_presence(row, 1)?,
+                get_presence(row, 2)?,
+            ))
+        })
+        .unwrap();
+    assert_eq!(name, Presence::Null);
+    assert_eq!(age, Presence::Null);
+    assert_eq!(missing, Presence::Absent);
+}
+
+#[test]
+fn test_get_presence_type_error() {
+    let conn = connection();
+    let result = conn.query_row("SELECT name FROM users WHERE id = 1", [], |row| {
+        get_presence::<_, i64>(row, "name")
+    });
+    assert!(result.is_err());
+}
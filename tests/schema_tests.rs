@@ -0,0 +1,142 @@
+#![cfg(feature = "json")]
+
+use presence_rs::Presence;
+use presence_rs::schema::{SchemaViolation, object_violations, violations};
+use presence_rs::validate::ViolationKind;
+use serde::Serialize;
+use serde_json::json;
+
+#[derive(Serialize)]
+struct AddressPatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    city: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    zip: Presence<String>,
+}
+
+#[derive(Serialize)]
+struct CustomerPatch {
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    name: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    nickname: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    tier: Presence<String>,
+    #[serde(skip_serializing_if = "Presence::is_absent")]
+    address: Presence<AddressPatch>,
+}
+
+fn schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "required": ["name", "address"],
+        "properties": {
+            "name": { "type": "string" },
+            "nickname": { "type": "string", "nullable": true },
+            "tier": { "enum": ["gold", "silver", null] },
+            "address": { "anyOf": [{ "$ref": "#/$defs/Address" }, { "type": "null" }] },
+        },
+        "$defs": {
+            "Address": {
+                "type": "object",
+                "required": ["city"],
+                "properties": {
+                    "city": { "type": "string" },
+                    "zip": { "oneOf": [{ "type": "string" }, { "const": null }] },
+                },
+            },
+        },
+    })
+}
+
+fn violation(path: &str, kind: ViolationKind) -> SchemaViolation {
+    SchemaViolation {
+        path: path.to_string(),
+        kind,
+    }
+}
+
+#[test]
+fn test_valid_patch() {
+    let patch = CustomerPatch {
+        name: Presence::Some("Ann".into()),
+        nickname: Presence::Null,
+        tier: Presence::Null,
+        address: Presence::Null,
+    };
+    assert_eq!(violations(&patch, &schema()).unwrap(), []);
+}
+
+#[test]
+fn test_nested_violations() {
+    let patch = CustomerPatch {
+        name: Presence::Null,
+        nickname: Presence::Absent,
+        tier: Presence::Absent,
+        address: Presence::Some(AddressPatch {
+            city: Presence::Absent,
+            zip: Presence::Null,
+        }),
+    };
+    assert_eq!(
+        violations(&patch, &schema()).unwrap(),
+        [
+            violation("address.city", ViolationKind::Absent),
+            violation("name", ViolationKind::Null),
+        ]
+    );
+}
+
+#[test]
+fn test_missing_required() {
+    let patch = CustomerPatch {
+        name: Presence::Absent,
+        nickname: Presence::Absent,
+        tier: Presence::Absent,
+        address: Presence::Absent,
+    };
+    let found = violations(&patch, &schema()).unwrap();
+    assert_eq!(
+        found,
+        [
+            violation("address", ViolationKind::Absent),
+            violation("name", ViolationKind::Absent),
+        ]
+    );
+    assert_eq!(found[0].to_string(), "`address` is required");
+}
+
+#[test]
+fn test_nullability_rules() {
+    let schema = json!({
+        "properties": {
+            "any": {},
+            "all": { "allOf": [{ "type": "string" }] },
+            "never": false,
+            "types": { "type": ["integer", "null"] },
+            "cyclic": { "$ref": "#/properties/cyclic" },
+            "unknown": { "type": "string" },
+        },
+    });
+    let object = json!({ "any": null, "all": null, "never": null, "types": null, "cyclic": null, "other": null });
+    assert_eq!(
+        object_violations(object.as_object().unwrap(), &schema),
+        [
+            violation("all", ViolationKind::Null),
+            violation("never", ViolationKind::Null),
+        ]
+    );
+}
+
+#[test]
+fn test_requires_skipped_absent_fields() {
+    #[derive(Serialize)]
+    struct Careless {
+        name: Presence<String>,
+    }
+    let careless = Careless {
+        name: Presence::Absent,
+    };
+    assert!(violations(&careless, &schema()).is_err());
+    assert!(violations(&[1], &schema()).is_err());
+}
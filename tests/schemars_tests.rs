@@ -0,0 +1,59 @@
+#![cfg(feature = "schemars")]
+
+use presence_rs::Presence;
+use schemars::{JsonSchema, SchemaGenerator, generate::SchemaSettings, schema_for};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Patch {
+    id: u64,
+    #[serde(default)]
+    name: Presence<String>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    age: Presence<u32>,
+}
+
+#[test]
+fn test_presence_schema_is_nullable() {
+    let schema = schema_for!(Presence<u32>);
+    assert_eq!(schema.get("type").unwrap(), &json!(["integer", "null"]));
+    assert_eq!(schema.get("format").unwrap(), "uint32");
+}
+
+#[test]
+fn test_matches_option_schema() {
+    assert_eq!(
+        schema_for!(Presence<String>).as_value(),
+        schema_for!(Option<String>).as_value()
+    );
+}
+
+#[test]
+fn test_fields_not_required_for_input() {
+    let schema = schema_for!(Patch);
+    assert_eq!(schema.get("required").unwrap(), &json!(["id"]));
+    assert_eq!(
+        schema.get("properties").unwrap()["name"]["type"],
+        json!(["string", "null"])
+    );
+}
+
+#[test]
+fn test_output_requires_fields_not_skipped() {
+    let settings = SchemaSettings::default().for_serialize();
+    let schema = SchemaGenerator::new(settings).into_root_schema_for::<Patch>();
+    assert_eq!(schema.get("required").unwrap(), &json!(["id", "name"]));
+}
+
+#[test]
+fn test_without_serde_default_is_required() {
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct Minimal {
+        value: Presence<bool>,
+    }
+
+    let schema = schema_for!(Minimal);
+    assert_eq!(schema.get("required").unwrap(), &json!(["value"]));
+}
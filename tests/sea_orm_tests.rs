@@ -0,0 +1,96 @@
+#![cfg(feature = "sea-orm")]
+
+use presence_rs::Presence;
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue, IntoActiveModel};
+
+mod user {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "users")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i32,
+        pub name: String,
+        pub nickname: Option<String>,
+        pub age: Option<i32>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[derive(DeriveIntoActiveModel)]
+#[sea_orm(active_model = "user::ActiveModel")]
+struct UserPatch {
+    nickname: Presence<String>,
+    age: Presence<i32>,
+}
+
+#[test]
+fn test_presence_to_active_value() {
+    assert_eq!(
+        ActiveValue::from(Presence::Some(1)),
+        ActiveValue::Set(Some(1))
+    );
+    assert_eq!(
+        ActiveValue::from(Presence::<i32>::Null),
+        ActiveValue::Set(None)
+    );
+    assert_eq!(
+        ActiveValue::from(Presence::<i32>::Absent),
+        ActiveValue::<Option<i32>>::NotSet
+    );
+}
+
+#[test]
+fn test_active_value_to_presence() {
+    assert_eq!(
+        Presence::from(ActiveValue::Set(Some("a".to_string()))),
+        Presence::Some("a".to_string())
+    );
+    assert_eq!(
+        Presence::<String>::from(ActiveValue::Unchanged(None)),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::<String>::from(ActiveValue::NotSet),
+        Presence::Absent
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    for presence in [Presence::Absent, Presence::Null, Presence::Some(5_i64)] {
+        let value: ActiveValue<Option<i64>> = presence.into();
+        assert_eq!(Presence::from(value), presence);
+    }
+}
+
+#[test]
+fn test_derive_into_active_model() {
+    let patch = UserPatch {
+        nickname: Presence::Null,
+        age: Presence::Some(42),
+    };
+
+    let model = patch.into_active_model();
+    assert_eq!(model.id, ActiveValue::NotSet);
+    assert_eq!(model.name, ActiveValue::NotSet);
+    assert_eq!(model.nickname, ActiveValue::Set(None));
+    assert_eq!(model.age, ActiveValue::Set(Some(42)));
+}
+
+#[test]
+fn test_all_absent_sets_nothing() {
+    let patch = UserPatch {
+        nickname: Presence::Absent,
+        age: Presence::Absent,
+    };
+
+    let model = patch.into_active_model();
+    assert!(!model.is_changed());
+}
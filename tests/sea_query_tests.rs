@@ -0,0 +1,72 @@
+#![cfg(feature = "sea-query")]
+
+use presence_rs::Presence;
+use presence_rs::sea_query::UpdateStatementExt;
+use presence_rs::sql::{Placeholder, SetClause};
+use sea_query::{Alias, Expr, PostgresQueryBuilder, Query, Value};
+
+#[test]
+fn test_value_conversion() {
+    assert_eq!(
+        Value::from(Presence::Some("a".to_string())),
+        Value::String(Some(Box::new("a".to_string())))
+    );
+    assert_eq!(Value::from(Presence::<bool>::Null), Value::Bool(None));
+    assert_eq!(Value::from(Presence::<i64>::Absent), Value::BigInt(None));
+}
+
+#[test]
+fn test_update_skips_absent() {
+    let mut update = Query::update();
+    update
+        .table(Alias::new("users"))
+        .presence_value(Alias::new("name"), Presence::<String>::Absent)
+        .presence_value(Alias::new("age"), Presence::Some(31))
+        .presence_value(Alias::new("bio"), Presence::<String>::Null);
+
+    assert_eq!(update.get_values().len(), 2);
+
+    let (sql, values) = update
+        .and_where(Expr::col(Alias::new("id")).eq(7))
+        .build(PostgresQueryBuilder);
+    assert_eq!(
+        sql,
+        r#"UPDATE "users" SET "age" = $1, "bio" = $2 WHERE "id" = $3"#
+    );
+    assert_eq!(
+        values.0,
+        [
+            Value::Int(Some(31)),
+            Value::String(None),
+            Value::Int(Some(7))
+        ]
+    );
+}
+
+#[test]
+fn test_all_absent_adds_nothing() {
+    let mut update = Query::update();
+    update
+        .table(Alias::new("users"))
+        .presence_value(Alias::new("name"), Presence::<String>::Absent);
+    assert!(update.get_values().is_empty());
+}
+
+#[test]
+fn test_set_clause_columns() {
+    let mut set = SetClause::<i32>::new(Placeholder::Question);
+    set.set("age", Presence::Some(31))
+        .set("name", Presence::<i32>::Absent)
+        .set("rank", Presence::<i32>::Null);
+
+    let (sql, values) = Query::update()
+        .table(Alias::new("users"))
+        .set_clause(set)
+        .and_where(Expr::col(Alias::new("id")).eq(7))
+        .build(PostgresQueryBuilder);
+    assert_eq!(
+        sql,
+        r#"UPDATE "users" SET "age" = $1, "rank" = NULL WHERE "id" = $2"#
+    );
+    assert_eq!(values.0, [Value::Int(Some(31)), Value::Int(Some(7))]);
+}
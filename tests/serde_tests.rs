@@ -220,3 +220,536 @@ fn test_option_of_presence() {
     let deserialized3: OptionalPresence = serde_json::from_str(&json3).unwrap();
     assert_eq!(data3, deserialized3);
 }
+
+#[test]
+fn test_known_keys_fills_missing_as_absent() {
+    use presence_rs::map::KnownKeys;
+    use serde::de::DeserializeSeed;
+
+    const KEYS: [&str; 3] = ["a", "b", "c"];
+    let mut input = serde_json::Deserializer::from_str(r#"{"a": 1, "b": null}"#);
+    let map = KnownKeys::<i32>::new(KEYS).deserialize(&mut input).unwrap();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["a"], Presence::Some(1));
+    assert_eq!(map["b"], Presence::Null);
+    assert_eq!(map["c"], Presence::Absent);
+
+    let mut input = serde_json::Deserializer::from_str("{}");
+    let map = KnownKeys::<i32>::new(KEYS).deserialize(&mut input).unwrap();
+    assert!(map.values().all(Presence::is_absent));
+}
+
+#[test]
+fn test_known_keys_rejects_unknown_and_duplicate_keys() {
+    use presence_rs::map::KnownKeys;
+    use serde::de::DeserializeSeed;
+
+    const KEYS: [&str; 1] = ["a"];
+    let mut input = serde_json::Deserializer::from_str(r#"{"z": 1}"#);
+    let err = KnownKeys::<i32>::new(KEYS)
+        .deserialize(&mut input)
+        .unwrap_err();
+    assert!(err.to_string().contains("unknown field `z`, expected `a`"));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"a": 1, "a": 2}"#);
+    let err = KnownKeys::<i32>::new(KEYS)
+        .deserialize(&mut input)
+        .unwrap_err();
+    assert!(err.to_string().contains("duplicate field `a`"));
+
+    let mut input = serde_json::Deserializer::from_str("[1]");
+    assert!(KnownKeys::<i32>::new(KEYS).deserialize(&mut input).is_err());
+}
+
+#[test]
+fn test_known_keys_from_runtime_keys() {
+    use presence_rs::map::KnownKeys;
+    use serde::de::DeserializeSeed;
+
+    let columns: Vec<String> = "id,name".split(',').map(String::from).collect();
+    let keys = KnownKeys::<String>::new(columns);
+    assert_eq!(keys.keys(), ["id", "name"]);
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"name": "Ann"}"#);
+    let map = keys.clone().deserialize(&mut input).unwrap();
+    assert_eq!(map["id"], Presence::Absent);
+    assert_eq!(map["name"], Presence::Some("Ann".to_string()));
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"age": 1}"#);
+    let err = keys.deserialize(&mut input).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unknown field `age`, expected one of `id`, `name`")
+    );
+
+    let mut input = serde_json::Deserializer::from_str(r#"{"age": 1}"#);
+    let err = KnownKeys::<String>::new(Vec::<String>::new())
+        .deserialize(&mut input)
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("unknown field `age`, there are no fields")
+    );
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Address {
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    city: Presence<String>,
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    zip: Presence<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Contact {
+    #[serde(default, skip_serializing_if = "Presence::is_absent")]
+    email: Presence<String>,
+    #[serde(flatten)]
+    address: Address,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct FlattenedPatch {
+    id: u32,
+    #[serde(flatten)]
+    contact: Contact,
+}
+
+#[test]
+fn test_flatten_distinguishes_missing_and_null() {
+    let patch: FlattenedPatch =
+        serde_json::from_str(r#"{"id":1,"email":null,"zip":"01001"}"#).unwrap();
+    assert_eq!(
+        patch,
+        FlattenedPatch {
+            id: 1,
+            contact: Contact {
+                email: Presence::Null,
+                address: Address {
+                    city: Presence::Absent,
+                    zip: Presence::Some("01001".into()),
+                },
+            },
+        }
+    );
+}
+
+#[test]
+fn test_flatten_round_trip() {
+    for json in [
+        r#"{"id":1}"#,
+        r#"{"id":2,"email":null,"city":null}"#,
+        r#"{"id":3,"email":"a@b.c","city":"Lviv","zip":null}"#,
+    ] {
+        let patch: FlattenedPatch = serde_json::from_str(json).unwrap();
+        assert_eq!(serde_json::to_string(&patch).unwrap(), json);
+    }
+}
+
+#[test]
+fn test_flatten_with_catch_all_map() {
+    #[derive(Debug, Deserialize)]
+    struct WithExtra {
+        #[serde(flatten)]
+        address: Address,
+        #[serde(flatten)]
+        extra: std::collections::BTreeMap<String, serde_json::Value>,
+    }
+
+    let value: WithExtra = serde_json::from_str(r#"{"city":null,"note":1}"#).unwrap();
+    assert_eq!(value.address.city, Presence::Null);
+    assert_eq!(value.address.zip, Presence::Absent);
+    assert_eq!(value.extra.len(), 1);
+}
+
+#[test]
+fn test_flatten_type_error() {
+    assert!(serde_json::from_str::<FlattenedPatch>(r#"{"id":1,"zip":5}"#).is_err());
+}
+
+mod seconds {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(value.as_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from_secs)
+    }
+}
+
+presence_rs::presence_serde_with!(mod seconds_presence: std::time::Duration = seconds);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TimeoutPatch {
+    #[serde(
+        default,
+        with = "seconds_presence",
+        skip_serializing_if = "Presence::is_absent"
+    )]
+    timeout: Presence<std::time::Duration>,
+}
+
+#[test]
+fn test_presence_serde_with_round_trip() {
+    use std::time::Duration;
+
+    for (patch, json) in [
+        (Presence::Some(Duration::from_secs(30)), r#"{"timeout":30}"#),
+        (Presence::Null, r#"{"timeout":null}"#),
+        (Presence::Absent, "{}"),
+    ] {
+        let patch = TimeoutPatch { timeout: patch };
+        assert_eq!(serde_json::to_string(&patch).unwrap(), json);
+        assert_eq!(serde_json::from_str::<TimeoutPatch>(json).unwrap(), patch);
+    }
+}
+
+#[test]
+fn test_presence_serde_with_inner_error() {
+    assert!(serde_json::from_str::<TimeoutPatch>(r#"{"timeout":"30s"}"#).is_err());
+}
+
+mod untagged {
+    use presence_rs::{Presence, PresenceSeed};
+    use serde::Deserialize;
+    use serde::de::DeserializeSeed;
+    use std::marker::PhantomData;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Scalar {
+        Number(Presence<i32>),
+        Text(String),
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct RenamePatch {
+        #[serde(default)]
+        name: Presence<String>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct MovePatch {
+        #[serde(default)]
+        parent: Presence<u64>,
+        #[serde(default)]
+        position: Presence<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum NodePatch {
+        Rename(RenamePatch),
+        Move(MovePatch),
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Greedy {
+        #[serde(default)]
+        name: Presence<String>,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum GreedyPatch {
+        Greedy(Greedy),
+        Move(MovePatch),
+    }
+
+    #[test]
+    fn test_untagged_scalar() {
+        let parse = |json| serde_json::from_str::<Scalar>(json).unwrap();
+        assert_eq!(parse("null"), Scalar::Number(Presence::Null));
+        assert_eq!(parse("3"), Scalar::Number(Presence::Some(3)));
+        assert_eq!(parse(r#""x""#), Scalar::Text("x".into()));
+    }
+
+    #[test]
+    fn test_untagged_struct_variants() {
+        let parse = |json| serde_json::from_str::<NodePatch>(json).unwrap();
+        assert_eq!(
+            parse(r#"{"name":null}"#),
+            NodePatch::Rename(RenamePatch {
+                name: Presence::Null
+            })
+        );
+        assert_eq!(
+            parse(r#"{"parent":null}"#),
+            NodePatch::Move(MovePatch {
+                parent: Presence::Null,
+                position: Presence::Absent,
+            })
+        );
+
+        let value = serde_json::json!({ "position": 2 });
+        assert_eq!(
+            serde_json::from_value::<NodePatch>(value).unwrap(),
+            NodePatch::Move(MovePatch {
+                parent: Presence::Absent,
+                position: Presence::Some(2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_untagged_variant_without_deny_unknown_fields_matches_anything() {
+        let patch: GreedyPatch = serde_json::from_str(r#"{"parent":null}"#).unwrap();
+        assert_eq!(
+            patch,
+            GreedyPatch::Greedy(Greedy {
+                name: Presence::Absent
+            })
+        );
+    }
+
+    #[test]
+    fn test_presence_seed() {
+        let seed = || PresenceSeed(PhantomData::<Vec<u8>>);
+        let mut de = serde_json::Deserializer::from_str("[1,2]");
+        assert_eq!(
+            seed().deserialize(&mut de).unwrap(),
+            Presence::Some(vec![1, 2])
+        );
+        let mut de = serde_json::Deserializer::from_str("null");
+        assert_eq!(seed().deserialize(&mut de).unwrap(), Presence::Null);
+        let mut de = serde_json::Deserializer::from_str("true");
+        assert!(seed().deserialize(&mut de).is_err());
+    }
+}
+
+mod strict {
+    use presence_rs::Presence;
+    use presence_rs::strict::Strict;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Inner {
+        value: Presence<i32>,
+    }
+
+    #[derive(Serialize)]
+    struct Outer {
+        #[serde(skip_serializing_if = "Presence::is_absent")]
+        skipped: Presence<i32>,
+        inner: Inner,
+    }
+
+    #[derive(Serialize)]
+    struct FieldLevel {
+        #[serde(serialize_with = "presence_rs::strict::serialize")]
+        strict: Presence<i32>,
+        lenient: Presence<i32>,
+    }
+
+    #[test]
+    fn test_strict_allows_defined_and_skipped() {
+        let outer = Outer {
+            skipped: Presence::Absent,
+            inner: Inner {
+                value: Presence::Null,
+            },
+        };
+        assert_eq!(
+            serde_json::to_string(&Strict(&outer)).unwrap(),
+            r#"{"inner":{"value":null}}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Strict(vec![Presence::Some(1), Presence::Null])).unwrap(),
+            "[1,null]"
+        );
+    }
+
+    #[test]
+    fn test_strict_rejects_nested_absent() {
+        let outer = Outer {
+            skipped: Presence::Some(1),
+            inner: Inner {
+                value: Presence::Absent,
+            },
+        };
+        let err = serde_json::to_string(&Strict(&outer)).unwrap_err();
+        assert!(err.to_string().contains("strict mode"));
+
+        // Strict mode ends with the wrapper.
+        assert_eq!(
+            serde_json::to_string(&outer).unwrap(),
+            r#"{"skipped":1,"inner":{"value":null}}"#
+        );
+    }
+
+    /// Serializes its field as a JSON string, outside of any strict wrapper.
+    struct Embedded(Inner);
+
+    impl Serialize for Embedded {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let json = serde_json::to_string(&self.0).map_err(serde::ser::Error::custom)?;
+            serializer.serialize_str(&json)
+        }
+    }
+
+    #[test]
+    fn test_strict_is_scoped_to_its_serializer() {
+        let embedded = Embedded(Inner {
+            value: Presence::Absent,
+        });
+        assert_eq!(
+            serde_json::to_string(&Strict(&embedded)).unwrap(),
+            r#""{\"value\":null}""#
+        );
+    }
+
+    #[test]
+    fn test_absent_serializes_as_none_outside_strict_mode() {
+        use serde_test::{Token, assert_ser_tokens};
+
+        assert_ser_tokens(&Presence::<i32>::Absent, &[Token::None]);
+        assert_ser_tokens(&Presence::<i32>::Null, &[Token::None]);
+        assert_ser_tokens(
+            &FieldLevel {
+                strict: Presence::Some(1),
+                lenient: Presence::Absent,
+            },
+            &[
+                Token::Struct {
+                    name: "FieldLevel",
+                    len: 2,
+                },
+                Token::Str("strict"),
+                Token::Some,
+                Token::I32(1),
+                Token::Str("lenient"),
+                Token::None,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_strict_field() {
+        let value = FieldLevel {
+            strict: Presence::Some(1),
+            lenient: Presence::Absent,
+        };
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"strict":1,"lenient":null}"#
+        );
+
+        let value = FieldLevel {
+            strict: Presence::Absent,
+            lenient: Presence::Absent,
+        };
+        assert!(serde_json::to_string(&value).is_err());
+    }
+}
+
+#[cfg(feature = "derive")]
+mod deserialize_presence {
+    use presence_rs::{DeserializePresence, Presence};
+
+    #[derive(Debug, Default, PartialEq, DeserializePresence)]
+    struct Limits {
+        max: Presence<u32>,
+    }
+
+    #[derive(Debug, PartialEq, DeserializePresence)]
+    #[serde(rename_all = "camelCase")]
+    struct AccountPatch {
+        display_name: Presence<String>,
+        #[serde(rename = "mail", alias = "email")]
+        email_address: Presence<String>,
+        #[presence(nested)]
+        limits: Limits,
+        #[presence(plain)]
+        id: u64,
+        #[presence(plain)]
+        #[serde(default)]
+        tags: Vec<String>,
+        #[presence(skip)]
+        loaded: bool,
+    }
+
+    #[derive(Debug, PartialEq, DeserializePresence)]
+    #[serde(deny_unknown_fields)]
+    struct StrictPatch<T> {
+        value: Presence<T>,
+    }
+
+    #[test]
+    fn test_unseen_fields_are_absent() {
+        let patch: AccountPatch = serde_json::from_str(r#"{"id":1}"#).unwrap();
+        assert_eq!(
+            patch,
+            AccountPatch {
+                display_name: Presence::Absent,
+                email_address: Presence::Absent,
+                limits: Limits::default(),
+                id: 1,
+                tags: Vec::new(),
+                loaded: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_skipped_fields_are_not_read() {
+        let patch: AccountPatch = serde_json::from_str(r#"{"id":1,"loaded":true}"#).unwrap();
+        assert!(!patch.loaded);
+        let patch: AccountPatch = serde_json::from_str(r#"[null,null,{},2,[]]"#).unwrap();
+        assert_eq!(patch.id, 2);
+    }
+
+    #[test]
+    fn test_container_rename() {
+        #[derive(Debug, DeserializePresence)]
+        #[serde(rename = "Account")]
+        struct Renamed {
+            #[allow(dead_code)]
+            name: Presence<String>,
+        }
+
+        let error = serde_json::from_str::<Renamed>("1").unwrap_err();
+        assert!(error.to_string().contains("expected struct Account"));
+    }
+
+    #[test]
+    fn test_renamed_fields() {
+        let patch: AccountPatch = serde_json::from_str(
+            r#"{"id":1,"displayName":null,"email":"a@b.c","limits":{"max":3},"tags":["x"],"other":0}"#,
+        )
+        .unwrap();
+        assert_eq!(patch.display_name, Presence::Null);
+        assert_eq!(patch.email_address, Presence::Some("a@b.c".to_string()));
+        assert_eq!(patch.limits.max, Presence::Some(3));
+        assert_eq!(patch.tags, ["x"]);
+    }
+
+    #[test]
+    fn test_errors() {
+        let missing = serde_json::from_str::<AccountPatch>("{}").unwrap_err();
+        assert!(missing.to_string().contains("missing field `id`"));
+
+        let duplicate =
+            serde_json::from_str::<AccountPatch>(r#"{"id":1,"mail":null,"email":null}"#)
+                .unwrap_err();
+        assert!(duplicate.to_string().contains("duplicate field `mail`"));
+
+        let unknown = serde_json::from_str::<StrictPatch<i32>>(r#"{"other":1}"#).unwrap_err();
+        assert!(unknown.to_string().contains("unknown field `other`"));
+    }
+
+    #[test]
+    fn test_generic_struct() {
+        let patch: StrictPatch<i32> = serde_json::from_str("{}").unwrap();
+        assert_eq!(patch.value, Presence::Absent);
+        let patch: StrictPatch<i32> = serde_json::from_str(r#"{"value":5}"#).unwrap();
+        assert_eq!(patch.value, Presence::Some(5));
+        let patch: StrictPatch<i32> = serde_json::from_str("[null]").unwrap();
+        assert_eq!(patch.value, Presence::Null);
+    }
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::{Presence, PresenceSetters};
+
+#[derive(Debug, Default, PartialEq, PresenceSetters)]
+struct LimitsPatch {
+    max: Presence<u32>,
+}
+
+#[derive(Debug, Default, PartialEq, PresenceSetters)]
+struct AccountPatch<T> {
+    name: Presence<String>,
+    r#type: presence_rs::Presence<T>,
+    #[presence(nested)]
+    limits: Presence<LimitsPatch>,
+    #[presence(nested)]
+    defaults: LimitsPatch,
+    #[presence(skip)]
+    revision: u64,
+}
+
+#[test]
+fn test_setters_chain() {
+    let mut patch = AccountPatch::<u8>::default();
+    patch
+        .set_name("main")
+        .set_type(3)
+        .null_limits()
+        .unset_name();
+    assert_eq!(patch.name, Presence::Absent);
+    assert_eq!(patch.r#type, Presence::Some(3));
+    assert_eq!(patch.limits, Presence::Null);
+}
+
+#[test]
+fn test_setters_nested() {
+    let mut limits = LimitsPatch::default();
+    limits.set_max(10u8);
+
+    let mut patch = AccountPatch::<u8>::default();
+    patch.set_limits(limits).null_type();
+    patch.defaults.null_max();
+
+    assert_eq!(
+        patch,
+        AccountPatch {
+            name: Presence::Absent,
+            r#type: Presence::Null,
+            limits: Presence::Some(LimitsPatch {
+                max: Presence::Some(10)
+            }),
+            defaults: LimitsPatch {
+                max: Presence::Null
+            },
+            revision: 0,
+        }
+    );
+}
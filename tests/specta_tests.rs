@@ -0,0 +1,41 @@
+#![cfg(feature = "specta")]
+
+use presence_rs::Presence;
+use specta::datatype::{DataType, PrimitiveType};
+use specta::{Generics, Type, TypeCollection};
+
+#[test]
+fn test_inline_is_nullable() {
+    let mut types = TypeCollection::default();
+    match Presence::<i32>::inline(&mut types, Generics::Definition) {
+        DataType::Nullable(inner) => {
+            assert!(matches!(*inner, DataType::Primitive(PrimitiveType::i32)))
+        }
+        other => panic!("expected a nullable type, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_matches_option() {
+    let mut types = TypeCollection::default();
+    assert_eq!(
+        Presence::<Vec<String>>::inline(&mut types, Generics::Definition),
+        Option::<Vec<String>>::inline(&mut types, Generics::Definition)
+    );
+    assert_eq!(
+        Presence::<bool>::reference(&mut types, &[]).inner,
+        Option::<bool>::reference(&mut types, &[]).inner
+    );
+}
+
+#[test]
+fn test_provided_generic_is_used() {
+    let mut types = TypeCollection::default();
+    let provided = [DataType::Primitive(PrimitiveType::String)];
+    match Presence::<i32>::inline(&mut types, Generics::Provided(&provided)) {
+        DataType::Nullable(inner) => {
+            assert!(matches!(*inner, DataType::Primitive(PrimitiveType::String)))
+        }
+        other => panic!("expected a nullable type, got {:?}", other),
+    }
+}
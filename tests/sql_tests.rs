@@ -0,0 +1,100 @@
+use presence_rs::Presence;
+use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+
+struct UserPatch {
+    name: Presence<String>,
+    email: Presence<String>,
+    age: Presence<i64>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bind {
+    Text(String),
+    Int(i64),
+}
+
+impl From<String> for Bind {
+    fn from(value: String) -> Self {
+        Bind::Text(value)
+    }
+}
+
+impl From<i64> for Bind {
+    fn from(value: i64) -> Self {
+        Bind::Int(value)
+    }
+}
+
+fn clause(patch: UserPatch, placeholder: Placeholder) -> SetClause<Bind> {
+    let mut set = SetClause::new(placeholder);
+    set.set("name", patch.name)
+        .set("email", patch.email)
+        .set("age", patch.age);
+    set
+}
+
+fn patch() -> UserPatch {
+    UserPatch {
+        name: Presence::Absent,
+        email: Presence::Null,
+        age: Presence::Some(30),
+    }
+}
+
+#[test]
+fn test_set_clause_binds() {
+    let set = clause(patch(), Placeholder::Dollar);
+    assert_eq!(set.sql(), "SET email = $1, age = $2");
+    assert_eq!(set.binds(), [None, Some(Bind::Int(30))]);
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.next_placeholder(), "$3");
+}
+
+#[test]
+fn test_set_clause_placeholders() {
+    for (placeholder, sql, next) in [
+        (Placeholder::Question, "SET email = ?, age = ?", "?"),
+        (
+            Placeholder::NumberedQuestion,
+            "SET email = ?1, age = ?2",
+            "?3",
+        ),
+        (Placeholder::Colon, "SET email = :1, age = :2", ":3"),
+        (Placeholder::AtP, "SET email = @p1, age = @p2", "@p3"),
+    ] {
+        let set = clause(patch(), placeholder);
+        assert_eq!(set.sql(), sql);
+        assert_eq!(set.next_placeholder(), next);
+    }
+}
+
+#[test]
+fn test_set_clause_null_literal_and_offset() {
+    let mut set = SetClause::<Bind>::new(Placeholder::Dollar)
+        .nulls(NullStyle::Literal)
+        .offset(1);
+    set.set("email", Presence::<String>::Null)
+        .set("name", Presence::Some("Ann".to_string()));
+    assert_eq!(set.sql(), "SET email = NULL, name = $2");
+    assert_eq!(
+        set.finish(),
+        Some((
+            "SET email = NULL, name = $2".to_string(),
+            vec![Some(Bind::Text("Ann".to_string()))]
+        ))
+    );
+}
+
+#[test]
+fn test_set_clause_empty() {
+    let set = clause(
+        UserPatch {
+            name: Presence::Absent,
+            email: Presence::Absent,
+            age: Presence::Absent,
+        },
+        Placeholder::Dollar,
+    );
+    assert!(set.is_empty());
+    assert_eq!(set.finish(), None);
+}
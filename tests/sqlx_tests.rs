@@ -0,0 +1,79 @@
+#![cfg(feature = "sqlx")]
+
+use presence_rs::Presence;
+use presence_rs::sql::{NullStyle, Placeholder, SetClause};
+use presence_rs::sqlx::UpdateBuilder;
+use presence_rs::update_set;
+use sqlx::Any;
+
+struct UserPatch {
+    name: Presence<String>,
+    bio: Presence<String>,
+    age: Presence<i32>,
+}
+
+#[test]
+fn test_only_defined_fields_are_set() {
+    let patch = UserPatch {
+        name: Presence::Some("Alice".into()),
+        bio: Presence::Null,
+        age: Presence::Absent,
+    };
+
+    let mut update = UpdateBuilder::<Any>::new("users");
+    update_set!(update, patch; name, bio, age);
+    assert_eq!(update.len(), 2);
+
+    let query = update.finish().unwrap();
+    assert_eq!(query.sql(), "UPDATE users SET name = ?, bio = ?");
+}
+
+#[test]
+fn test_all_absent_yields_none() {
+    let patch = UserPatch {
+        name: Presence::Absent,
+        bio: Presence::Absent,
+        age: Presence::Absent,
+    };
+
+    let mut update = UpdateBuilder::<Any>::new("users");
+    update_set!(update, patch; name, bio, age);
+    assert!(update.is_empty());
+    assert!(update.finish().is_none());
+}
+
+#[test]
+fn test_where_clause_after_finish() {
+    let mut update = UpdateBuilder::<Any>::new("users");
+    update.set("age", Presence::Some(31));
+
+    let mut query = update.finish().unwrap();
+    query.push(" WHERE id = ").push_bind(1_i64);
+    assert_eq!(query.sql(), "UPDATE users SET age = ? WHERE id = ?");
+}
+
+#[test]
+fn test_single_null_field() {
+    let mut update = UpdateBuilder::<Any>::new("accounts");
+    update.set("deleted_at", Presence::<String>::Null);
+    assert_eq!(
+        update.finish().unwrap().sql(),
+        "UPDATE accounts SET deleted_at = ?"
+    );
+}
+
+#[test]
+fn test_set_clause_columns() {
+    let mut set = SetClause::<i64>::new(Placeholder::Dollar).nulls(NullStyle::Literal);
+    set.set("age", Presence::Some(31_i64))
+        .set("name", Presence::<i64>::Absent)
+        .set("rank", Presence::<i64>::Null);
+
+    let mut update = UpdateBuilder::<Any>::new("users");
+    update.set_clause(set);
+    assert_eq!(update.len(), 2);
+    assert_eq!(
+        update.finish().unwrap().sql(),
+        "UPDATE users SET age = ?, rank = ?"
+    );
+}
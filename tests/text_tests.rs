@@ -0,0 +1,130 @@
+use presence_rs::text::Tokens;
+use presence_rs::{Presence, PresenceState};
+
+#[test]
+fn test_default_tokens_match_display() {
+    for value in [Presence::Some(7), Presence::Null, Presence::Absent] {
+        assert_eq!(
+            value.display_with(&Tokens::default()).to_string(),
+            value.to_string()
+        );
+    }
+}
+
+#[test]
+fn test_custom_tokens() {
+    let tokens = Tokens::new("NULL", "-");
+    assert_eq!(tokens.null_token(), "NULL");
+    assert_eq!(tokens.absent_token(), "-");
+
+    assert_eq!(Presence::Some("x").display_with(&tokens).to_string(), "x");
+    assert_eq!(
+        Presence::<&str>::Null.display_with(&tokens).to_string(),
+        "NULL"
+    );
+    assert_eq!(
+        Presence::<&str>::Absent.display_with(&tokens).to_string(),
+        "-"
+    );
+}
+
+#[test]
+fn test_builder_and_hidden_absent() {
+    let tokens = Tokens::default().absent("");
+    let cells: Vec<String> = [Presence::Some(1), Presence::Null, Presence::Absent]
+        .iter()
+        .map(|cell| cell.display_with(&tokens).to_string())
+        .collect();
+    assert_eq!(cells.join(","), "1,null,");
+}
+
+#[test]
+fn test_formatting_options_apply() {
+    let tokens = Tokens::new("∅", "?");
+    assert_eq!(
+        format!("{:>3}", Presence::<u8>::Null.display_with(&tokens)),
+        "  ∅"
+    );
+    assert_eq!(
+        format!("{:<3}|", Presence::<u8>::Absent.display_with(&tokens)),
+        "?  |"
+    );
+    assert_eq!(
+        format!("{:03}", Presence::Some(7).display_with(&tokens)),
+        "007"
+    );
+    assert_eq!(
+        format!("{:.1}", Presence::Some(2.25).display_with(&tokens)),
+        "2.2"
+    );
+}
+
+#[test]
+fn test_from_str_round_trips_display() {
+    for value in [Presence::Some(-3), Presence::Null, Presence::Absent] {
+        assert_eq!(value.to_string().parse::<Presence<i32>>(), Ok(value));
+    }
+    assert!("NULL".parse::<Presence<i32>>().is_err());
+}
+
+#[test]
+fn test_values_spelled_like_tokens_do_not_round_trip() {
+    let value = Presence::Some("null".to_string());
+    assert_eq!(value.to_string().parse(), Ok(Presence::<String>::Null));
+}
+
+#[test]
+fn test_multiple_tokens_per_state() {
+    let tokens = Tokens::none()
+        .null("NULL")
+        .or_null("\\N")
+        .absent("")
+        .or_absent("-");
+    assert_eq!(tokens.null_token(), "NULL");
+    assert_eq!(tokens.state("\\N"), Some(PresenceState::Null));
+    assert_eq!(tokens.state("-"), Some(PresenceState::Absent));
+    assert_eq!(tokens.state("x"), None);
+    assert_eq!(
+        Presence::<u8>::parse_with("-", &tokens),
+        Ok(Presence::Absent)
+    );
+
+    let none = Tokens::none();
+    assert_eq!(Presence::<u8>::Null.display_with(&none).to_string(), "");
+    assert_eq!(
+        Presence::<String>::parse_with("null", &none),
+        Ok(Presence::Some("null".to_string()))
+    );
+}
+
+#[test]
+fn test_parse_with_tokens() {
+    let tokens = Tokens::new("null", "unset");
+    assert_eq!(
+        Presence::<String>::parse_with("unset", &tokens),
+        Ok(Presence::Absent)
+    );
+    assert_eq!(
+        Presence::<String>::parse_with("null", &tokens),
+        Ok(Presence::Null)
+    );
+    assert_eq!(
+        Presence::<String>::parse_with("", &tokens),
+        Ok(Presence::Some(String::new()))
+    );
+}
+
+#[test]
+fn test_parse_with_round_trips_display_with() {
+    let tokens = Tokens::new("-", "");
+    for value in [Presence::Some(1.5), Presence::Null, Presence::Absent] {
+        let text = value.display_with(&tokens).to_string();
+        assert_eq!(Presence::parse_with(&text, &tokens), Ok(value));
+    }
+}
+
+#[test]
+fn test_parse_with_same_token_prefers_null() {
+    let tokens = Tokens::new("", "");
+    assert_eq!(Presence::<u8>::parse_with("", &tokens), Ok(Presence::Null));
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "tokio")]
+
+use presence_rs::tokio::PresenceWatch;
+use presence_rs::{Presence, PresenceState};
+use std::future::Future;
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn test_watch_states() {
+    let watch = PresenceWatch::new();
+    let mut receiver = watch.subscribe();
+    assert_eq!(watch.state(), PresenceState::Absent);
+    assert_eq!(watch.receiver_count(), 1);
+
+    watch.set("on");
+    assert_eq!(receiver.state(), PresenceState::Some);
+    assert_eq!(receiver.get(), Presence::Some("on"));
+
+    watch.set_null();
+    assert_eq!(receiver.get(), Presence::Null);
+    assert_eq!(watch.publish(Presence::Absent), Presence::Null);
+    assert_eq!(receiver.get(), Presence::Absent);
+}
+
+#[test]
+fn test_wait_for_present() {
+    block_on(async {
+        let watch = PresenceWatch::new();
+        let mut receiver = watch.subscribe();
+        let task = tokio::spawn(async move { receiver.wait_for_present().await });
+
+        tokio::task::yield_now().await;
+        watch.set_null();
+        tokio::task::yield_now().await;
+        watch.set(5);
+        assert_eq!(task.await.unwrap().unwrap(), 5);
+    });
+}
+
+#[test]
+fn test_wait_for_defined() {
+    block_on(async {
+        let watch = PresenceWatch::<u8>::with_initial(Presence::Null);
+        let mut receiver = watch.subscribe();
+        assert_eq!(receiver.wait_for_defined().await.unwrap(), Presence::Null);
+    });
+}
+
+#[test]
+fn test_wait_after_drop() {
+    block_on(async {
+        let watch = PresenceWatch::<u8>::default();
+        let mut receiver = watch.subscribe();
+        drop(watch);
+        assert!(receiver.wait_for_present().await.is_err());
+        assert!(receiver.changed().await.is_err());
+    });
+}
@@ -0,0 +1,127 @@
+use presence_rs::Presence;
+use presence_rs::tracked::Tracked;
+
+#[test]
+fn test_untouched_is_absent() {
+    let name = Tracked::new("Alice");
+    assert!(!name.is_changed());
+    assert_eq!(name.get(), Some(&"Alice"));
+    assert_eq!(name.current(), Presence::Some(&"Alice"));
+    assert_eq!(name.patch(), Presence::Absent);
+    assert_eq!(name.into_patch(), Presence::Absent);
+}
+
+#[test]
+fn test_set_and_clear() {
+    let mut bio = Tracked::from_option(None);
+    assert_eq!(bio.current(), Presence::Null);
+
+    bio.set(1);
+    assert!(bio.is_changed());
+    assert_eq!(bio.patch(), Presence::Some(&1));
+
+    bio.clear();
+    assert_eq!(bio.get(), None);
+    assert_eq!(bio.into_patch(), Presence::Null);
+}
+
+#[test]
+fn test_modify() {
+    let mut count = Tracked::from(1);
+    count.modify(|n| *n += 1);
+    assert_eq!(count.clone().into_patch(), Presence::Some(2));
+
+    let mut cleared: Tracked<i32> = Tracked::from_option(None);
+    cleared.modify(|_| panic!("not called"));
+    assert!(!cleared.is_changed());
+}
+
+#[test]
+fn test_set_if_changed() {
+    let mut role = Tracked::new("user");
+    role.set_if_changed("user");
+    assert_eq!(role.patch(), Presence::Absent);
+    role.set_if_changed("admin");
+    assert_eq!(role.into_patch(), Presence::Some("admin"));
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use presence_rs::Presence;
+    use presence_rs::tracked::{IntoPatch, Tracked};
+
+    #[derive(IntoPatch)]
+    struct Address {
+        city: Tracked<String>,
+    }
+
+    #[derive(IntoPatch)]
+    #[presence(patch = "UserChanges")]
+    pub struct User<T> {
+        pub name: Tracked<String>,
+        score: Tracked<T>,
+        address: Address,
+        #[presence(skip)]
+        #[allow(dead_code)]
+        id: u64,
+    }
+
+    fn user() -> User<f64> {
+        User {
+            name: Tracked::new("Ann".into()),
+            score: Tracked::new(1.5),
+            address: Address {
+                city: Tracked::new("Oslo".into()),
+            },
+            id: 1,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_struct_is_default_patch() {
+        let user = user();
+        assert!(!user.is_changed());
+        assert_eq!(user.into_patch(), UserChanges::default());
+    }
+
+    #[test]
+    fn test_nested_and_generic_fields() {
+        let mut user = user();
+        user.score.clear();
+        user.address.city.set("Bergen".into());
+        assert!(user.is_changed());
+
+        assert_eq!(
+            user.into_patch(),
+            UserChanges {
+                name: Presence::Absent,
+                score: Presence::Null,
+                address: AddressPatch {
+                    city: Presence::Some("Bergen".into()),
+                },
+            }
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_patch_derive_serde() {
+        #[derive(IntoPatch)]
+        #[presence(patch_derive(serde::Serialize, serde::Deserialize))]
+        struct Post {
+            title: Tracked<String>,
+            body: Tracked<String>,
+        }
+
+        let mut post = Post {
+            title: Tracked::new("Draft".into()),
+            body: Tracked::new("Text".into()),
+        };
+        post.body.clear();
+        let patch = post.into_patch();
+
+        let json = serde_json::to_string(&patch).unwrap();
+        assert_eq!(json, r#"{"body":null}"#);
+        assert_eq!(serde_json::from_str::<PostPatch>(&json).unwrap(), patch);
+    }
+}
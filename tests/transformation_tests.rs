@@ -36,6 +36,48 @@ fn test_map_or_else() {
     assert_eq!(absent.map_or_else(|| 42, |x| x * 2), 42);
 }
 
+#[test]
+fn test_map_or_null_default() {
+    let some = Presence::Some(5);
+    assert_eq!(some.map_or_null_default(-1, -2, |x| x * 2), 10);
+
+    let null: Presence<i32> = Presence::Null;
+    assert_eq!(null.map_or_null_default(-1, -2, |x| x * 2), -2);
+
+    let absent: Presence<i32> = Presence::Absent;
+    assert_eq!(absent.map_or_null_default(-1, -2, |x| x * 2), -1);
+}
+
+#[test]
+fn test_map_or_null_else() {
+    let some = Presence::Some(5);
+    assert_eq!(some.map_or_null_else(|| -1, || -2, |x| x * 2), 10);
+
+    let null: Presence<i32> = Presence::Null;
+    assert_eq!(
+        null.map_or_null_else(|| unreachable!(), || -2, |x| x * 2),
+        -2
+    );
+
+    let absent: Presence<i32> = Presence::Absent;
+    assert_eq!(
+        absent.map_or_null_else(|| -1, || unreachable!(), |x| x * 2),
+        -1
+    );
+}
+
+#[test]
+fn test_fold() {
+    let describe = |p: Presence<&str>| p.fold(|| 0, || 1, |s| s.len() + 10);
+    assert_eq!(describe(Presence::Some("abc")), 13);
+    assert_eq!(describe(Presence::Null), 1);
+    assert_eq!(describe(Presence::Absent), 0);
+
+    let owned = Presence::Some(vec![1, 2]);
+    let moved: Vec<i32> = owned.fold(Vec::new, Vec::new, |v| v);
+    assert_eq!(moved, [1, 2]);
+}
+
 #[test]
 fn test_and() {
     let some = Presence::Some(5);
@@ -228,6 +270,21 @@ fn test_zip() {
     assert_eq!(null1.zip(null2), Presence::Null);
 }
 
+#[test]
+fn test_apply() {
+    let len = Presence::Some(|s: &str| s.len());
+    assert_eq!(Presence::Some("four").apply(len), Presence::Some(4));
+    assert_eq!(Presence::<&str>::Null.apply(len), Presence::Null);
+    assert_eq!(Presence::<&str>::Absent.apply(len), Presence::Absent);
+
+    let null_fn: Presence<fn(&str) -> usize> = Presence::Null;
+    assert_eq!(Presence::Some("x").apply(null_fn), Presence::Null);
+    assert_eq!(Presence::<&str>::Absent.apply(null_fn), Presence::Absent);
+
+    let absent_fn: Presence<fn(&str) -> usize> = Presence::Absent;
+    assert_eq!(Presence::<&str>::Null.apply(absent_fn), Presence::Absent);
+}
+
 #[test]
 fn test_unzip() {
     let paired = Presence::Some((5, 10));
@@ -297,3 +354,300 @@ fn test_transpose_chaining() {
 
     assert_eq!(process(), Ok(Presence::Some(84)));
 }
+
+#[test]
+fn test_string_as_str() {
+    let name = Presence::Some("Ann".to_string());
+    assert_eq!(name.as_str(), Presence::Some("Ann"));
+    assert_eq!(Presence::<String>::Null.as_str(), Presence::Null);
+    assert_eq!(Presence::<String>::Absent.as_str(), Presence::Absent);
+}
+
+#[test]
+fn test_string_empty_to_null() {
+    assert_eq!(
+        Presence::Some(String::new()).empty_to_null(),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::Some("a".to_string()).empty_to_null(),
+        Presence::Some("a".to_string())
+    );
+    assert_eq!(Presence::<String>::Absent.empty_to_null(), Presence::Absent);
+    assert_eq!(Presence::Some("").empty_to_null(), Presence::Null);
+    assert_eq!(Presence::<&str>::Absent.empty_to_null(), Presence::Absent);
+}
+
+#[test]
+fn test_string_trim_to_null() {
+    assert_eq!(
+        Presence::Some(" Ann\n".to_string()).trim_to_null(),
+        Presence::Some("Ann".to_string())
+    );
+    assert_eq!(
+        Presence::Some("Ann".to_string()).trim_to_null(),
+        Presence::Some("Ann".to_string())
+    );
+    assert_eq!(
+        Presence::Some("\t ".to_string()).trim_to_null(),
+        Presence::Null
+    );
+    assert_eq!(Presence::<String>::Absent.trim_to_null(), Presence::Absent);
+    assert_eq!(Presence::Some(" x ").trim_to_null(), Presence::Some("x"));
+    assert_eq!(Presence::Some(" ").trim_to_null(), Presence::Null);
+}
+
+#[test]
+fn test_string_parse() {
+    assert_eq!(Presence::Some("7").parse::<i32>(), Ok(Presence::Some(7)));
+    assert_eq!(Presence::<&str>::Null.parse::<i32>(), Ok(Presence::Null));
+    assert_eq!(
+        Presence::<&str>::Absent.parse::<i32>(),
+        Ok(Presence::Absent)
+    );
+    assert!(Presence::Some("seven").parse::<i32>().is_err());
+    assert_eq!(
+        Presence::Some("true".to_string()).parse::<bool>(),
+        Ok(Presence::Some(true))
+    );
+    assert_eq!(
+        Presence::<String>::Absent.parse::<bool>(),
+        Ok(Presence::Absent)
+    );
+}
+
+#[test]
+fn test_string_is_blank_or_nullish() {
+    assert!(Presence::Some(" ".to_string()).is_blank_or_nullish());
+    assert!(Presence::Some(String::new()).is_blank_or_nullish());
+    assert!(Presence::<String>::Null.is_blank_or_nullish());
+    assert!(Presence::<String>::Absent.is_blank_or_nullish());
+    assert!(!Presence::Some(" a".to_string()).is_blank_or_nullish());
+    assert!(!Presence::Some("a").is_blank_or_nullish());
+}
+
+#[test]
+fn test_vec_iter_flat() {
+    let tags = Presence::Some(vec![1, 2, 3]);
+    assert_eq!(tags.iter_flat().sum::<i32>(), 6);
+    assert_eq!(Presence::<Vec<i32>>::Null.iter_flat().next(), None);
+    assert_eq!(Presence::<Vec<i32>>::Absent.iter_flat().len(), 0);
+}
+
+#[test]
+fn test_vec_is_empty_or_nullish() {
+    assert!(Presence::Some(Vec::<u8>::new()).is_empty_or_nullish());
+    assert!(Presence::<Vec<u8>>::Null.is_empty_or_nullish());
+    assert!(Presence::<Vec<u8>>::Absent.is_empty_or_nullish());
+    assert!(!Presence::Some(vec![0u8]).is_empty_or_nullish());
+}
+
+#[test]
+fn test_vec_empty_policies() {
+    let empty = || Presence::Some(Vec::<u8>::new());
+    assert_eq!(empty().empty_to_absent(), Presence::Absent);
+    assert_eq!(empty().empty_to_null(), Presence::Null);
+
+    // "Replace with a list", "clear" and "leave alone" stay distinct otherwise.
+    for value in [Presence::Some(vec![1u8]), Presence::Null, Presence::Absent] {
+        assert_eq!(value.clone().empty_to_absent(), value);
+        assert_eq!(value.clone().empty_to_null(), value);
+    }
+}
+
+#[test]
+fn test_cow_into_owned() {
+    use std::borrow::Cow;
+
+    let borrowed: Presence<Cow<str>> = Presence::Some(Cow::Borrowed("a"));
+    assert_eq!(borrowed.into_owned(), Presence::Some("a".to_string()));
+
+    let bytes: Presence<Cow<[u8]>> = Presence::Some(Cow::Owned(vec![1, 2]));
+    assert_eq!(bytes.into_owned(), Presence::Some(vec![1, 2]));
+
+    let null: Presence<Cow<str>> = Presence::Null;
+    assert_eq!(null.into_owned(), Presence::Null);
+}
+
+#[test]
+fn test_cow_into_static() {
+    use std::borrow::Cow;
+
+    fn promote(input: &str) -> Presence<Cow<'static, str>> {
+        Presence::Some(Cow::Borrowed(input)).into_static()
+    }
+
+    let input = String::from("queued");
+    let promoted = promote(&input);
+    drop(input);
+    assert!(matches!(promoted, Presence::Some(Cow::Owned(ref s)) if s == "queued"));
+
+    let absent: Presence<Cow<[u8]>> = Presence::Absent;
+    assert_eq!(absent.into_static(), Presence::Absent);
+}
+
+#[test]
+fn test_boxed_and_unbox() {
+    let boxed: Presence<Box<String>> = Presence::Some("large".to_string()).boxed();
+    assert_eq!(boxed, Presence::Some(Box::new("large".to_string())));
+    assert_eq!(boxed.unbox(), Presence::Some("large".to_string()));
+
+    assert_eq!(Presence::<i32>::Null.boxed().unbox(), Presence::Null);
+    assert_eq!(Presence::<i32>::Absent.boxed().unbox(), Presence::Absent);
+}
+
+#[test]
+fn test_arc_make_mut_is_copy_on_write() {
+    use std::sync::Arc;
+
+    let shared = Arc::new(String::from("a"));
+    let mut x = Presence::Some(Arc::clone(&shared));
+    if let Presence::Some(value) = x.make_mut() {
+        value.push('b');
+    }
+    assert_eq!(*shared, "a");
+    assert_eq!(x.as_deref(), Presence::Some(&"ab".to_string()));
+
+    let mut null: Presence<Arc<String>> = Presence::Null;
+    assert_eq!(null.make_mut(), Presence::Null);
+}
+
+#[test]
+fn test_arc_try_unwrap() {
+    use std::sync::Arc;
+
+    let shared = Arc::new(1);
+    let x = Presence::Some(Arc::clone(&shared));
+    let x = x.try_unwrap().unwrap_err();
+    drop(shared);
+    assert_eq!(x.try_unwrap(), Ok(Presence::Some(1)));
+    assert_eq!(
+        Presence::<Arc<i32>>::Absent.try_unwrap(),
+        Ok(Presence::Absent)
+    );
+}
+
+#[test]
+fn test_rc_make_mut_and_try_unwrap() {
+    use std::rc::Rc;
+
+    let mut x = Presence::Some(Rc::new(vec![1]));
+    let other = x.clone();
+    if let Presence::Some(list) = x.make_mut() {
+        list.push(2);
+    }
+    assert_eq!(other.try_unwrap(), Ok(Presence::Some(vec![1])));
+    assert_eq!(x.try_unwrap(), Ok(Presence::Some(vec![1, 2])));
+    assert_eq!(Presence::<Rc<i32>>::Null.try_unwrap(), Ok(Presence::Null));
+}
+
+#[test]
+fn test_min_max() {
+    let latencies = [Presence::Some(30), Presence::Some(12), Presence::Some(25)];
+    let fastest = latencies.into_iter().reduce(Presence::min_present).unwrap();
+    assert_eq!(fastest, Presence::Some(12));
+    let slowest = latencies.into_iter().reduce(Presence::max_present).unwrap();
+    assert_eq!(slowest, Presence::Some(30));
+
+    assert_eq!(
+        Presence::Some(1).min_present(Presence::Null),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::Null.max_present(Presence::Some(1)),
+        Presence::Null
+    );
+    assert_eq!(
+        Presence::Some(1).max_present(Presence::Absent),
+        Presence::Absent
+    );
+    assert_eq!(
+        Presence::<i32>::Absent.min_present(Presence::Null),
+        Presence::Absent
+    );
+    assert_eq!(
+        Presence::<i32>::Null.min_present(Presence::Null),
+        Presence::Null
+    );
+
+    // The derived `Ord` methods keep their meaning.
+    assert_eq!(Presence::Some(1).max(Presence::Null), Presence::Some(1));
+    assert_eq!(Presence::Some(1).min(Presence::Absent), Presence::Absent);
+}
+
+#[test]
+fn test_clamp() {
+    assert_eq!(Presence::Some(5).clamp_present(1, 10), Presence::Some(5));
+    assert_eq!(Presence::Some(50).clamp_present(1, 10), Presence::Some(10));
+    assert_eq!(
+        Presence::<i32>::Absent.clamp_present(1, 10),
+        Presence::Absent
+    );
+}
+
+#[test]
+#[should_panic(expected = "clamp_present requires min <= max")]
+fn test_clamp_panics_on_inverted_bounds() {
+    let _ = Presence::<i32>::Null.clamp_present(10, 1);
+}
+
+#[test]
+fn test_unzip3_and_unzip4() {
+    let point = Presence::Some((1.0, 2.0, 3.0));
+    assert_eq!(
+        point.unzip3(),
+        (
+            Presence::Some(1.0),
+            Presence::Some(2.0),
+            Presence::Some(3.0)
+        )
+    );
+    let absent: Presence<(i32, i32, i32)> = Presence::Absent;
+    assert_eq!(
+        absent.unzip3(),
+        (Presence::Absent, Presence::Absent, Presence::Absent)
+    );
+
+    let (a, b, c, d) = Presence::Some((1, 'b', "c", 4u8)).unzip4();
+    assert_eq!(a, Presence::Some(1));
+    assert_eq!(b, Presence::Some('b'));
+    assert_eq!(c, Presence::Some("c"));
+    assert_eq!(d, Presence::Some(4));
+
+    let null: Presence<(i32, i32, i32, i32)> = Presence::Null;
+    assert_eq!(
+        null.unzip4(),
+        (
+            Presence::Null,
+            Presence::Null,
+            Presence::Null,
+            Presence::Null
+        )
+    );
+}
+
+#[test]
+fn test_modify() {
+    let mut x = Presence::Some(1);
+    x.modify(|v| *v += 1);
+    assert_eq!(x, Presence::Some(2));
+
+    let mut null: Presence<i32> = Presence::Null;
+    null.modify(|_| panic!("not called"));
+    assert_eq!(null, Presence::Null);
+}
+
+#[test]
+fn test_update() {
+    let mut x = Presence::Some(String::from("a"));
+    x.update(|s| s + "b");
+    assert_eq!(x, Presence::Some(String::from("ab")));
+
+    let mut null: Presence<String> = Presence::Null;
+    null.update(|_| panic!("not called"));
+    assert_eq!(null, Presence::Null);
+
+    let mut absent: Presence<String> = Presence::Absent;
+    absent.update(|_| panic!("not called"));
+    assert_eq!(absent, Presence::Absent);
+}
@@ -0,0 +1,67 @@
+use presence_rs::Presence;
+use presence_rs::tri::Tri;
+
+const ALL: [Tri; 3] = [Tri::True, Tri::False, Tri::Unknown];
+
+#[test]
+fn test_tri_not() {
+    assert_eq!(!Tri::True, Tri::False);
+    assert_eq!(!Tri::False, Tri::True);
+    assert_eq!(!Tri::Unknown, Tri::Unknown);
+}
+
+#[test]
+fn test_tri_and_or_xor() {
+    for tri in ALL {
+        assert_eq!(Tri::False & tri, Tri::False);
+        assert_eq!(tri & Tri::True, tri);
+        assert_eq!(Tri::True | tri, Tri::True);
+        assert_eq!(tri | Tri::False, tri);
+        assert_eq!(Tri::Unknown ^ tri, Tri::Unknown);
+    }
+    assert_eq!(Tri::Unknown & Tri::Unknown, Tri::Unknown);
+    assert_eq!(Tri::Unknown | Tri::Unknown, Tri::Unknown);
+    assert_eq!(Tri::True ^ Tri::False, Tri::True);
+    assert_eq!(Tri::True ^ Tri::True, Tri::False);
+}
+
+#[test]
+fn test_tri_de_morgan() {
+    for a in ALL {
+        for b in ALL {
+            assert_eq!(!(a & b), !a | !b);
+            assert_eq!(!(a | b), !a & !b);
+        }
+    }
+}
+
+#[test]
+fn test_tri_from_presence() {
+    assert_eq!(Tri::from_presence_lossy(Presence::Some(true)), Tri::True);
+    assert_eq!(Tri::from_presence_lossy(Presence::Some(false)), Tri::False);
+    assert_eq!(Tri::from_presence_lossy(Presence::Null), Tri::Unknown);
+    assert_eq!(Tri::from_presence_lossy(Presence::Absent), Tri::Unknown);
+    assert_eq!(Tri::from(Presence::Some(true)), Tri::True);
+    assert_eq!(Tri::from(Presence::<bool>::Null), Tri::Unknown);
+    assert_eq!(Tri::from(Presence::<bool>::Absent), Tri::Unknown);
+    assert_eq!(Tri::from_defined(Presence::Absent), None);
+    assert_eq!(Tri::from_defined(Presence::Null), Some(Tri::Unknown));
+}
+
+#[test]
+fn test_tri_into_presence() {
+    assert_eq!(Presence::from(Tri::False), Presence::Some(false));
+    assert_eq!(Tri::Unknown.into_presence_null(), Presence::Null);
+    assert_eq!(Tri::Unknown.into_presence_absent(), Presence::Absent);
+    assert_eq!(Tri::True.into_presence_absent(), Presence::Some(true));
+}
+
+#[test]
+fn test_tri_option_and_bool() {
+    assert_eq!(Tri::from(true), Tri::True);
+    assert_eq!(Tri::from(None), Tri::Unknown);
+    assert_eq!(Option::<bool>::from(Tri::False), Some(false));
+    assert_eq!(Option::<bool>::from(Tri::Unknown), None);
+    assert_eq!(Tri::default(), Tri::Unknown);
+    assert!(Tri::True.is_true() && Tri::False.is_false() && Tri::Unknown.is_unknown());
+}
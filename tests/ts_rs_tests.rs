@@ -0,0 +1,43 @@
+#![cfg(feature = "ts-rs")]
+
+use presence_rs::Presence;
+use ts_rs::TS;
+
+#[derive(TS)]
+#[allow(dead_code)]
+struct Address {
+    city: String,
+}
+
+#[derive(TS)]
+#[allow(dead_code)]
+struct Patch {
+    id: u32,
+    #[ts(optional)]
+    name: Presence<String>,
+    #[ts(optional = nullable)]
+    age: Presence<u8>,
+    tags: Presence<Vec<String>>,
+    address: Presence<Address>,
+}
+
+#[test]
+fn test_presence_type_is_nullable() {
+    assert_eq!(Presence::<i32>::name(), "number | null");
+    assert_eq!(Presence::<Vec<bool>>::inline(), "Array<boolean> | null");
+}
+
+#[test]
+fn test_optional_fields_keep_null() {
+    assert_eq!(
+        Patch::decl(),
+        "type Patch = { id: number, name?: string | null, age?: number | null, \
+         tags: Array<string> | null, address: Address | null, };"
+    );
+}
+
+#[test]
+fn test_dependencies_include_inner_type() {
+    let deps = Patch::dependencies();
+    assert!(deps.iter().any(|dep| dep.ts_name == "Address"));
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "utoipa")]
+
+use presence_rs::Presence;
+use serde::Deserialize;
+use serde_json::json;
+use utoipa::{PartialSchema, ToSchema};
+
+#[derive(Deserialize, ToSchema)]
+#[allow(dead_code)]
+struct Owner {
+    name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+#[allow(dead_code)]
+struct Patch {
+    id: u64,
+    #[serde(default)]
+    #[schema(schema_with = Presence::<String>::schema)]
+    name: Presence<String>,
+    #[serde(default)]
+    #[schema(schema_with = Presence::<Owner>::schema)]
+    owner: Presence<Owner>,
+    #[schema(schema_with = Presence::<u32>::schema)]
+    age: Presence<u32>,
+}
+
+fn to_json<T: serde::Serialize>(value: T) -> serde_json::Value {
+    serde_json::to_value(value).unwrap()
+}
+
+#[test]
+fn test_presence_schema_is_nullable() {
+    assert_eq!(
+        to_json(Presence::<i32>::schema()),
+        json!({ "oneOf": [{ "type": "null" }, { "type": "integer", "format": "int32" }] })
+    );
+}
+
+#[test]
+fn test_fields_with_default_are_not_required() {
+    let schema = to_json(Patch::schema());
+    assert_eq!(schema["required"], json!(["id", "age"]));
+}
+
+#[test]
+fn test_schema_with_field() {
+    let schema = to_json(Patch::schema());
+    assert_eq!(
+        schema["properties"]["name"],
+        json!({ "oneOf": [{ "type": "null" }, { "type": "string" }] })
+    );
+
+    let owner = &schema["properties"]["owner"]["oneOf"];
+    assert_eq!(owner[0], json!({ "type": "null" }));
+    assert_eq!(owner[1]["properties"]["name"], json!({ "type": "string" }));
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "derive")]
+
+use presence_rs::Presence;
+use presence_rs::validate::{ValidatePresence, Violation, ViolationKind};
+
+#[derive(ValidatePresence)]
+struct ProfilePatch {
+    #[presence(required)]
+    id: Presence<u64>,
+    #[presence(non_null)]
+    name: Presence<String>,
+    #[presence(required, non_null)]
+    version: Presence<u32>,
+    #[presence(required)]
+    #[presence(non_null)]
+    r#type: Presence<String>,
+    bio: Presence<String>,
+}
+
+fn valid() -> ProfilePatch {
+    ProfilePatch {
+        id: Presence::Null,
+        name: Presence::Absent,
+        version: Presence::Some(2),
+        r#type: Presence::Some("user".into()),
+        bio: Presence::Null,
+    }
+}
+
+#[test]
+fn test_valid_patch() {
+    let patch = valid();
+    assert!(patch.bio.is_null());
+    assert_eq!(patch.validate_presence(), Ok(()));
+}
+
+#[test]
+fn test_all_violations_in_field_order() {
+    let patch = ProfilePatch {
+        id: Presence::Absent,
+        name: Presence::Null,
+        version: Presence::Null,
+        r#type: Presence::Absent,
+        bio: Presence::Absent,
+    };
+
+    assert_eq!(
+        patch.validate_presence().unwrap_err(),
+        [
+            Violation {
+                field: "id",
+                kind: ViolationKind::Absent
+            },
+            Violation {
+                field: "name",
+                kind: ViolationKind::Null
+            },
+            Violation {
+                field: "version",
+                kind: ViolationKind::Null
+            },
+            Violation {
+                field: "type",
+                kind: ViolationKind::Absent
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_required_and_non_null_requires_value() {
+    let mut patch = valid();
+    patch.version = Presence::Absent;
+    let violations = patch.validate_presence().unwrap_err();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].to_string(), "`version` is required");
+}
+
+#[derive(ValidatePresence)]
+struct Wrapper<T> {
+    #[presence(non_null)]
+    value: Presence<T>,
+}
+
+#[test]
+fn test_generic_struct() {
+    let wrapper = Wrapper::<i32> {
+        value: Presence::Null,
+    };
+    assert_eq!(
+        wrapper.validate_presence().unwrap_err()[0].to_string(),
+        "`value` must not be null"
+    );
+    assert!(
+        Wrapper {
+            value: Presence::Some(1)
+        }
+        .validate_presence()
+        .is_ok()
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use presence_rs::Presence;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_to_js() {
+    assert!(JsValue::from(Presence::<f64>::Absent).is_undefined());
+    assert!(JsValue::from(Presence::<f64>::Null).is_null());
+    assert_eq!(JsValue::from(Presence::Some(1.5)).as_f64(), Some(1.5));
+    assert_eq!(
+        JsValue::from(Presence::Some("Bob")).as_string(),
+        Some("Bob".to_string())
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_from_js() {
+    assert_eq!(Presence::from_js(JsValue::UNDEFINED), Presence::Absent);
+    assert_eq!(Presence::from_js(JsValue::NULL), Presence::Null);
+    assert!(Presence::from_js(JsValue::FALSE).is_present());
+}
+
+#[wasm_bindgen_test]
+fn test_try_from_js() {
+    assert_eq!(
+        Presence::<f64>::try_from_js(JsValue::UNDEFINED),
+        Ok(Presence::Absent)
+    );
+    assert_eq!(
+        Presence::<f64>::try_from_js(JsValue::NULL),
+        Ok(Presence::Null)
+    );
+    assert_eq!(
+        Presence::<f64>::try_from_js(JsValue::from(2.0)),
+        Ok(Presence::Some(2.0))
+    );
+    assert_eq!(
+        Presence::<String>::try_from_js(JsValue::from("Ann")),
+        Ok(Presence::Some("Ann".to_string()))
+    );
+    assert!(Presence::<f64>::try_from_js(JsValue::from("Ann")).is_err());
+}